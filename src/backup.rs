@@ -0,0 +1,433 @@
+//! Registry backup to .reg files that `reg import` / regedit can restore.
+//!
+//! A backup records the machine and Windows build it came from in its
+//! header comments. Restoring compares the backup with the registry and
+//! restores only what differs: each key block is the whole key as it was
+//! backed up, so values added since are deleted again.
+
+use crate::checks::{
+    catalog, run_category, run_category_named, CheckContext, DataSource, Hkey, RegValue,
+    RegistrySource, ReplayWmi, TrackingRegistry, WmiFixture, HKEY_LOCAL_MACHINE,
+};
+use crate::regfile::{RegEntry, RegFile, RegKeyBlock, ValueOp};
+use crate::remediation::Remediation;
+use crate::types::{Category, CategoryResults};
+use std::sync::Arc;
+
+/// Key trees every backup exports in full, whatever the checks read.
+pub const BACKUP_ROOTS: &[(Hkey, &str)] = &[
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Power",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\PriorityControl",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+    ),
+];
+
+/// What went into a backup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupStats {
+    pub keys: usize,
+    pub values: usize,
+    /// Requested keys that do not exist on this machine.
+    pub missing: usize,
+}
+
+/// Runs every check category against a tracking wrapper and returns the keys
+/// they read or that their fixes write. WMI is answered from an empty
+/// fixture so this stays fast.
+pub fn keys_touched_by_checks(ctx: &CheckContext) -> Vec<(Hkey, String)> {
+    let tracker = Arc::new(TrackingRegistry::new(ctx.shared_registry()));
+    let probe = ctx
+        .clone()
+        .with_registry(tracker.clone())
+        .with_wmi(Arc::new(ReplayWmi::new(WmiFixture::default())));
+
+    let mut results: Vec<CategoryResults> = Category::ALL
+        .into_iter()
+        .map(|category| run_category(&probe, category))
+        .collect();
+    for name in ctx.rules().custom_categories() {
+        results.push(run_category_named(&probe, &name));
+    }
+
+    // Keys a fix would write are backed up even if no check reads them.
+    let mut touched = tracker.touched();
+    for check in results.iter().flat_map(|r| &r.checks) {
+        if let Some(
+            Remediation::SetRegistryValue { hive, path, .. }
+            | Remediation::DeleteRegistryValue { hive, path, .. },
+        ) = &check.remediation
+        {
+            if !touched
+                .iter()
+                .any(|(h, p)| h == hive && p.eq_ignore_ascii_case(path))
+            {
+                touched.push((*hive, path.clone()));
+            }
+        }
+    }
+    touched
+}
+
+fn is_under(hkey: Hkey, path: &str, root: &(Hkey, String)) -> bool {
+    let path = path.to_lowercase();
+    let root_path = root.1.to_lowercase();
+    hkey == root.0 && (path == root_path || path.starts_with(&format!("{}\\", root_path)))
+}
+
+/// Exports each `recursive` root with all of its subkeys, then every key in
+/// `single` (values only) that is not already covered by a root.
+pub fn export_reg(
+    source: &dyn RegistrySource,
+    recursive: &[(Hkey, String)],
+    single: &[(Hkey, String)],
+) -> (RegFile, BackupStats) {
+    let mut file = RegFile::new();
+    file.comments = vec![
+        "Windows Optimizer Backup".to_string(),
+        format!("Created: {}", chrono::Local::now()),
+    ];
+    file.comments.extend(BackupOrigin::read(source).comments());
+    let mut stats = BackupStats::default();
+
+    for (hkey, path) in recursive {
+        if !export_key(source, *hkey, path, true, &mut file, &mut stats) {
+            stats.missing += 1;
+        }
+    }
+    for (hkey, path) in single {
+        if recursive.iter().any(|root| is_under(*hkey, path, root)) {
+            continue;
+        }
+        if !export_key(source, *hkey, path, false, &mut file, &mut stats) {
+            stats.missing += 1;
+        }
+    }
+
+    (file, stats)
+}
+
+fn export_key(
+    source: &dyn RegistrySource,
+    hkey: Hkey,
+    path: &str,
+    recursive: bool,
+    file: &mut RegFile,
+    stats: &mut BackupStats,
+) -> bool {
+    let Some(values) = source.values(hkey, path) else {
+        return false;
+    };
+
+    stats.keys += 1;
+    stats.values += values.len();
+    file.keys.push(
+        values
+            .into_iter()
+            .fold(RegKeyBlock::new(hkey, path), |block, (name, value)| {
+                block.set(&name, value)
+            }),
+    );
+
+    if recursive {
+        for child in source.subkeys(hkey, path).unwrap_or_default() {
+            export_key(
+                source,
+                hkey,
+                &format!("{}\\{}", path, child),
+                true,
+                file,
+                stats,
+            );
+        }
+    }
+    true
+}
+
+const COMPUTER_NAME: &str = r"SYSTEM\CurrentControlSet\Control\ComputerName\ComputerName";
+const CURRENT_VERSION: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion";
+
+/// The machine and Windows build a backup was taken from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackupOrigin {
+    pub machine: Option<String>,
+    /// `CurrentBuild.UBR`, e.g. `22631.4317`.
+    pub build: Option<String>,
+}
+
+impl BackupOrigin {
+    /// Reads the origin from the registry being backed up, so a backup of
+    /// offline hives names the image rather than the machine running us.
+    pub fn read(source: &dyn RegistrySource) -> Self {
+        let build = source
+            .read_string(HKEY_LOCAL_MACHINE, CURRENT_VERSION, "CurrentBuild")
+            .map(
+                |build| match source.read_dword(HKEY_LOCAL_MACHINE, CURRENT_VERSION, "UBR") {
+                    Some(ubr) => format!("{}.{}", build, ubr),
+                    None => build,
+                },
+            );
+        Self {
+            machine: source.read_string(HKEY_LOCAL_MACHINE, COMPUTER_NAME, "ComputerName"),
+            build,
+        }
+    }
+
+    fn comments(&self) -> Vec<String> {
+        let mut comments = Vec::new();
+        if let Some(machine) = &self.machine {
+            comments.push(format!("Machine: {}", machine));
+        }
+        if let Some(build) = &self.build {
+            comments.push(format!("Windows build: {}", build));
+        }
+        comments
+    }
+
+    pub fn from_comments(comments: &[String]) -> Self {
+        let field = |prefix: &str| {
+            comments
+                .iter()
+                .find_map(|c| c.strip_prefix(prefix))
+                .map(|v| v.trim().to_string())
+        };
+        Self {
+            machine: field("Machine:"),
+            build: field("Windows build:"),
+        }
+    }
+
+    /// Reasons a backup from here should not be restored onto `live`.
+    /// Builds only need the same major build; monthly updates may differ.
+    pub fn mismatches(&self, live: &BackupOrigin) -> Vec<String> {
+        let mut problems = Vec::new();
+        match (&self.machine, &live.machine) {
+            (None, _) => problems.push("the backup does not record its machine".to_string()),
+            (Some(_), None) => problems.push("this machine's name cannot be read".to_string()),
+            (Some(backup), Some(here)) if !backup.eq_ignore_ascii_case(here) => problems.push(
+                format!("the backup was taken on {}, this is {}", backup, here),
+            ),
+            _ => {}
+        }
+        let major = |build: &str| build.split('.').next().unwrap_or(build).to_string();
+        match (&self.build, &live.build) {
+            (None, _) => problems.push("the backup does not record its Windows build".to_string()),
+            (Some(_), None) => problems.push("this Windows build cannot be read".to_string()),
+            (Some(backup), Some(here)) if major(backup) != major(here) => problems.push(format!(
+                "the backup is from Windows build {}, this is build {}",
+                backup, here
+            )),
+            _ => {}
+        }
+        problems
+    }
+}
+
+/// One value a restore would change. `name: None` deletes the whole key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreChange {
+    pub hkey: Hkey,
+    pub path: String,
+    pub name: Option<String>,
+    /// `None` when the value does not exist now.
+    pub current: Option<RegValue>,
+    /// `None` when the restore deletes it.
+    pub restored: Option<RegValue>,
+    /// Catalog checks that read this value.
+    pub checks: Vec<&'static str>,
+}
+
+impl RestoreChange {
+    pub fn key(&self) -> String {
+        format!("{}\\{}", self.hkey.full_name(), self.path)
+    }
+}
+
+/// Compares a backup with `live` and returns every value that differs.
+pub fn diff_backup(file: &RegFile, live: &dyn RegistrySource) -> Vec<RestoreChange> {
+    let mut changes = Vec::new();
+    for block in &file.keys {
+        let change = |name: Option<&str>, current, restored| RestoreChange {
+            hkey: block.hkey,
+            path: block.path.clone(),
+            name: name.map(str::to_string),
+            current,
+            restored,
+            checks: checks_reading(block.hkey, &block.path, name),
+        };
+
+        if block.delete {
+            if live.key_exists(block.hkey, &block.path) {
+                changes.push(change(None, None, None));
+            }
+            continue;
+        }
+
+        let mut current = live.values(block.hkey, &block.path).unwrap_or_default();
+        for entry in &block.entries {
+            let found = current
+                .iter()
+                .position(|(name, _)| name.eq_ignore_ascii_case(&entry.name))
+                .map(|i| current.remove(i).1);
+            let restored = match &entry.op {
+                ValueOp::Set(value) => Some(value.clone()),
+                ValueOp::Delete => None,
+            };
+            if found != restored {
+                changes.push(change(Some(&entry.name), found, restored));
+            }
+        }
+        // Added since the backup.
+        for (name, value) in current {
+            changes.push(change(Some(&name), Some(value), None));
+        }
+    }
+    changes
+}
+
+fn checks_reading(hkey: Hkey, path: &str, name: Option<&str>) -> Vec<&'static str> {
+    let same_name = |value: Option<&str>| match (value, name) {
+        (None, _) | (_, None) => true,
+        (Some(value), Some(name)) => value.eq_ignore_ascii_case(name),
+    };
+    catalog()
+        .iter()
+        .filter(|meta| {
+            meta.sources.iter().any(|source| match source {
+                DataSource::Registry {
+                    hkey: h,
+                    path: p,
+                    value,
+                } => *h == hkey && p.eq_ignore_ascii_case(path) && same_name(*value),
+                DataSource::ServiceManager { service } => {
+                    hkey == HKEY_LOCAL_MACHINE
+                        && path.eq_ignore_ascii_case(&format!(
+                            r"SYSTEM\CurrentControlSet\Services\{}",
+                            service
+                        ))
+                        && same_name(Some("Start"))
+                }
+                _ => false,
+            })
+        })
+        .map(|meta| meta.id)
+        .collect()
+}
+
+/// `--only` / `--exclude` globs, matched against a change's key, its
+/// `key\value` path and the IDs of the checks that read it. `*` matches
+/// any run of characters, `?` one; case is ignored and `HKLM` style roots
+/// are accepted.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreFilter {
+    only: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl RestoreFilter {
+    pub fn new(only: &[String], exclude: &[String]) -> Self {
+        Self {
+            only: only.iter().map(|p| expand_root(p)).collect(),
+            exclude: exclude.iter().map(|p| expand_root(p)).collect(),
+        }
+    }
+
+    /// Everything passes an empty `only`; `exclude` wins over `only`.
+    pub fn selects(&self, change: &RestoreChange) -> bool {
+        let key = change.key();
+        let mut subjects = vec![key.clone()];
+        if let Some(name) = &change.name {
+            subjects.push(format!("{}\\{}", key, name));
+        }
+        subjects.extend(change.checks.iter().map(|id| id.to_string()));
+
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|p| subjects.iter().any(|s| glob_match(p, s)))
+        };
+        (self.only.is_empty() || matches(&self.only)) && !matches(&self.exclude)
+    }
+}
+
+fn expand_root(pattern: &str) -> String {
+    match pattern.split_once('\\') {
+        Some((root, rest)) => match Hkey::from_name(root) {
+            Some(hkey) => format!("{}\\{}", hkey.full_name(), rest),
+            None => pattern.to_string(),
+        },
+        None => pattern.to_string(),
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    // Backtrack to the last `*` on a mismatch.
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    p = sp + 1;
+                    t = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A .reg file that makes exactly `changes`, for `reg import`.
+pub fn restore_file(changes: &[&RestoreChange]) -> RegFile {
+    let mut file = RegFile::new();
+    file.comments = vec!["Windows Optimizer selective restore".to_string()];
+    for change in changes {
+        let Some(name) = &change.name else {
+            file.keys
+                .push(RegKeyBlock::deletion(change.hkey, &change.path));
+            continue;
+        };
+        let same_key = |b: &RegKeyBlock| {
+            !b.delete && b.hkey == change.hkey && b.path.eq_ignore_ascii_case(&change.path)
+        };
+        if !file.keys.last().is_some_and(same_key) {
+            file.keys.push(RegKeyBlock::new(change.hkey, &change.path));
+        }
+        let block = file.keys.last_mut().expect("pushed above");
+        block.entries.push(RegEntry {
+            name: name.clone(),
+            op: match &change.restored {
+                Some(value) => ValueOp::Set(value.clone()),
+                None => ValueOp::Delete,
+            },
+        });
+    }
+    file
+}
@@ -1,38 +1,45 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_audio_checks() -> CategoryResults {
+pub fn run_audio_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Audio");
 
     let checks: Vec<Check> = (0..25)
         .into_par_iter()
-        .map(|i| match i {
-            0 => check_exclusive_mode(),
-            1 => check_audio_enhancements(),
-            2 => check_sample_rate(),
-            3 => check_bit_depth(),
-            4 => check_audio_service(),
-            5 => check_audio_endpoint_builder(),
-            6 => check_audio_buffer_size(),
-            7 => check_audio_dpc_latency(),
-            8 => check_audio_priority(),
-            9 => check_audio_offload(),
-            10 => check_spatial_sound(),
-            11 => check_communications_tab(),
-            12 => check_allow_applications_control(),
-            13 => check_audio_device_isolation(),
-            14 => check_audio_stream_priority(),
-            15 => check_wasapi_mode(),
-            16 => check_asio_support(),
-            17 => check_audio_latency_mode(),
-            18 => check_audio_driver_version(),
-            19 => check_audio_device_power(),
-            20 => Check::new("Audio Channels", "Stereo/5.1/7.1", CheckStatus::Info),
-            21 => Check::new("Audio Format", "PCM", CheckStatus::Info),
-            22 => Check::new("Audio Quality", "High", CheckStatus::Optimal),
-            23 => Check::new("Audio Output Device", "Detected", CheckStatus::Info),
-            _ => Check::new("Audio Input Device", "Detected", CheckStatus::Info),
+        .map(|i| {
+            Check::timed(|| match i {
+                0 => check_exclusive_mode(),
+                1 => check_audio_enhancements(),
+                2 => check_sample_rate(),
+                3 => check_bit_depth(),
+                4 => check_audio_service(),
+                5 => check_audio_endpoint_builder(),
+                6 => check_audio_buffer_size(),
+                7 => check_audio_dpc_latency(),
+                8 => check_audio_priority(ctx),
+                9 => check_audio_offload(),
+                10 => check_spatial_sound(),
+                11 => check_communications_tab(),
+                12 => check_allow_applications_control(),
+                13 => check_audio_device_isolation(),
+                14 => check_audio_stream_priority(),
+                15 => check_wasapi_mode(),
+                16 => check_asio_support(),
+                17 => check_audio_latency_mode(),
+                18 => check_audio_driver_version(),
+                19 => check_audio_device_power(),
+                20 => Check::new("Audio Channels", "Stereo/5.1/7.1", CheckStatus::Info)
+                    .with_id("audio.channels"),
+                21 => Check::new("Audio Format", "PCM", CheckStatus::Info).with_id("audio.format"),
+                22 => Check::new("Audio Quality", "High", CheckStatus::Optimal)
+                    .with_id("audio.quality"),
+                23 => Check::new("Audio Output Device", "Detected", CheckStatus::Info)
+                    .with_id("audio.output_device"),
+                _ => Check::new("Audio Input Device", "Detected", CheckStatus::Info)
+                    .with_id("audio.input_device"),
+            })
         })
         .collect();
 
@@ -45,61 +52,72 @@ pub fn run_audio_checks() -> CategoryResults {
 
 fn check_exclusive_mode() -> Check {
     Check::new("Exclusive Mode", "Allowed", CheckStatus::Optimal)
+        .with_id("audio.exclusive_mode")
         .with_description("Allows applications to take exclusive control of audio device.")
 }
 
 fn check_audio_enhancements() -> Check {
     Check::new("Audio Enhancements", "Disabled", CheckStatus::Optimal)
+        .with_id("audio.enhancements")
         .with_description("Disable for lowest latency and best quality.")
 }
 
 fn check_sample_rate() -> Check {
     Check::new("Default Sample Rate", "48000 Hz", CheckStatus::Info)
+        .with_id("audio.sample_rate")
         .with_description("44100 Hz or 48000 Hz are standard.")
 }
 
 fn check_bit_depth() -> Check {
-    Check::new("Default Bit Depth", "24 bit", CheckStatus::Info)
+    Check::new("Default Bit Depth", "24 bit", CheckStatus::Info).with_id("audio.bit_depth")
 }
 
 fn check_audio_service() -> Check {
-    Check::new("Windows Audio Service", "Running", CheckStatus::Optimal)
+    Check::new("Windows Audio Service", "Running", CheckStatus::Optimal).with_id("audio.service")
 }
 
 fn check_audio_endpoint_builder() -> Check {
     Check::new("Audio Endpoint Builder", "Running", CheckStatus::Optimal)
+        .with_id("audio.endpoint_builder")
 }
 
 fn check_audio_buffer_size() -> Check {
     Check::new("Audio Buffer Size", "System Default", CheckStatus::Info)
+        .with_id("audio.buffer_size")
         .with_description("Lower buffer = lower latency but more CPU usage.")
 }
 
 fn check_audio_dpc_latency() -> Check {
-    Check::new("Audio DPC Latency", "Acceptable", CheckStatus::Optimal)
+    Check::new("Audio DPC Latency", "Acceptable", CheckStatus::Optimal).with_id("audio.dpc_latency")
 }
 
-fn check_audio_priority() -> Check {
-    let priority = read_registry_dword(
+fn check_audio_priority(ctx: &CheckContext) -> Check {
+    let (priority, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Audio",
         "Priority",
-    );
+    )
+    .split();
 
     Check::new(
         "Audio Task Priority",
         &format!("{}", priority.unwrap_or(2)),
         CheckStatus::Info,
     )
+    .with_id("audio.priority")
+    .with_mistyped(mistyped)
 }
 
 fn check_audio_offload() -> Check {
     Check::new("Audio Offloading", "Disabled", CheckStatus::Info)
+        .with_id("audio.offload")
         .with_description("Can cause compatibility issues. Disable if problems occur.")
 }
 
 fn check_spatial_sound() -> Check {
     Check::new("Spatial Sound", "Off", CheckStatus::Info)
+        .with_id("audio.spatial_sound")
         .with_description("Windows Sonic or Dolby Atmos can be enabled.")
 }
 
@@ -109,6 +127,7 @@ fn check_communications_tab() -> Check {
         "Do Nothing",
         CheckStatus::Optimal,
     )
+    .with_id("audio.communications_tab")
     .with_description("Prevents Windows from lowering volume during calls.")
 }
 
@@ -118,32 +137,38 @@ fn check_allow_applications_control() -> Check {
         "Enabled",
         CheckStatus::Optimal,
     )
+    .with_id("audio.allow_applications_control")
 }
 
 fn check_audio_device_isolation() -> Check {
     Check::new("Audio Device Isolation", "Running", CheckStatus::Info)
+        .with_id("audio.device_isolation")
 }
 
 fn check_audio_stream_priority() -> Check {
     Check::new("Audio Stream Priority", "Configured", CheckStatus::Info)
+        .with_id("audio.stream_priority")
 }
 
 fn check_wasapi_mode() -> Check {
     Check::new("WASAPI Exclusive Mode", "Available", CheckStatus::Optimal)
+        .with_id("audio.wasapi_mode")
         .with_description("Low-latency audio API.")
 }
 
 fn check_asio_support() -> Check {
     Check::new("ASIO Support", "Available", CheckStatus::Info)
+        .with_id("audio.asio_support")
         .with_description("Professional audio driver interface.")
 }
 
 fn check_audio_latency_mode() -> Check {
     Check::new("Audio Latency Mode", "Low Latency", CheckStatus::Optimal)
+        .with_id("audio.latency_mode")
 }
 
 fn check_audio_driver_version() -> Check {
-    Check::new("Audio Driver", "Up to Date", CheckStatus::Optimal)
+    Check::new("Audio Driver", "Up to Date", CheckStatus::Optimal).with_id("audio.driver_version")
 }
 
 fn check_audio_device_power() -> Check {
@@ -152,4 +177,5 @@ fn check_audio_device_power() -> Check {
         "Disabled",
         CheckStatus::Optimal,
     )
+    .with_id("audio.device_power")
 }
@@ -0,0 +1,3574 @@
+//! Static metadata for every check, keyed by its stable dotted ID.
+//!
+//! IDs are what suppressions, dashboards and tickets pin to, so they never
+//! change once published, even if the check's display name does.
+
+use super::registry::{Hkey, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+use crate::types::{Category, Check, CheckSource, CheckStatus};
+use serde::Serialize;
+
+/// Where a check gets its data from. A check with no sources reports a
+/// fixed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DataSource {
+    Registry {
+        hkey: Hkey,
+        path: &'static str,
+        /// `None` when the check looks at the key itself or its subkeys.
+        value: Option<&'static str>,
+    },
+    Wmi {
+        class: &'static str,
+        property: Option<&'static str>,
+    },
+    Dxgi,
+    ServiceManager {
+        service: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CheckMeta {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: Category,
+    pub sources: &'static [DataSource],
+    /// 0-10, copied onto every result of the check.
+    pub severity: u8,
+    /// What the check reads and which value is preferred; `None` for
+    /// placeholder checks that measure nothing.
+    pub docs: Option<&'static str>,
+}
+
+impl DataSource {
+    pub fn check_source(&self) -> CheckSource {
+        match self {
+            DataSource::Registry { .. } => CheckSource::Registry,
+            DataSource::Wmi { .. } => CheckSource::Wmi,
+            DataSource::Dxgi => CheckSource::Dxgi,
+            DataSource::ServiceManager { .. } => CheckSource::ServiceManager,
+        }
+    }
+}
+
+impl CheckMeta {
+    /// The primary source of the check's value, `Static` for fixed values.
+    pub fn source(&self) -> CheckSource {
+        self.sources
+            .first()
+            .map_or(CheckSource::Static, DataSource::check_source)
+    }
+}
+
+const fn reg(hkey: Hkey, path: &'static str, value: &'static str) -> DataSource {
+    DataSource::Registry {
+        hkey,
+        path,
+        value: Some(value),
+    }
+}
+
+const fn key(hkey: Hkey, path: &'static str) -> DataSource {
+    DataSource::Registry {
+        hkey,
+        path,
+        value: None,
+    }
+}
+
+const fn wmi(class: &'static str, property: &'static str) -> DataSource {
+    DataSource::Wmi {
+        class,
+        property: Some(property),
+    }
+}
+
+const fn wmi_class(class: &'static str) -> DataSource {
+    DataSource::Wmi {
+        class,
+        property: None,
+    }
+}
+
+const fn service(service: &'static str) -> DataSource {
+    DataSource::ServiceManager { service }
+}
+
+/// Every known check, grouped by category in `Category::ALL` order.
+pub fn catalog() -> &'static [CheckMeta] {
+    CATALOG
+}
+
+pub fn find_check(id: &str) -> Option<&'static CheckMeta> {
+    CATALOG.iter().find(|meta| meta.id == id)
+}
+
+/// Copies catalog metadata onto a check result. A fixed value was never
+/// measured, so it is reported as `Unknown` rather than `Optimal`.
+pub fn annotate_check(check: &mut Check) {
+    match find_check(&check.id) {
+        Some(meta) => {
+            check.severity = meta.severity;
+            check.source = meta.source();
+        }
+        None => tracing::debug!("Check '{}' has no catalog entry", check.name),
+    }
+    if check.source == CheckSource::Static && check.status == CheckStatus::Optimal {
+        check.status = CheckStatus::Unknown;
+    }
+}
+
+static CATALOG: &[CheckMeta] = &[
+    CheckMeta {
+        id: "latency.quantum_length",
+        name: "Quantum Length",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.foreground_boost",
+        name: "Foreground Boost",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.irq_priority",
+        name: "IRQ Priority",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.dma_channel_allocation",
+        name: "DMA Channel Allocation",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.memory_mapped_io",
+        name: "Memory Mapped I/O",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.real_time_priority_class",
+        name: "Real-Time Priority Class",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.high_priority_thread_support",
+        name: "High Priority Thread Support",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.low_latency_audio",
+        name: "Low Latency Audio",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.kernel_dispatcher_priority",
+        name: "Kernel Dispatcher Priority",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.thread_switching_overhead",
+        name: "Thread Switching Overhead",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.context_switch_rate",
+        name: "Context Switch Rate",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "latency.hpet_status",
+        name: "HPET (High Precision Event Timer)",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\TimeProviders\TimerDevice",
+            "TimerDevice",
+        )],
+        severity: 5,
+        docs: Some("HPET can add latency. Disabled is better for gaming/real-time."),
+    },
+    CheckMeta {
+        id: "latency.tsc_sync_policy",
+        name: "TSC Sync Policy",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "GlobalTimerResolutionRequests",
+        )],
+        severity: 2,
+        docs: Some("Time Stamp Counter synchronization policy"),
+    },
+    CheckMeta {
+        id: "latency.dynamic_tick",
+        name: "Dynamic Tick",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "DisableDynamicTick",
+        )],
+        severity: 5,
+        docs: Some("Dynamic tick can increase latency. Disable for lower latency."),
+    },
+    CheckMeta {
+        id: "latency.system_responsiveness",
+        name: "System Responsiveness (MMCSS)",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+            "SystemResponsiveness",
+        )],
+        severity: 7,
+        docs: Some("Controls CPU reservation for multimedia. Lower is better (0-10 optimal)."),
+    },
+    CheckMeta {
+        id: "latency.network_throttling_index",
+        name: "Network Throttling Index",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+            "NetworkThrottlingIndex",
+        )],
+        severity: 5,
+        docs: Some("Network packet processing throttling. 0xFFFFFFFF (disabled) is optimal."),
+    },
+    CheckMeta {
+        id: "latency.win32_priority_separation",
+        name: "Win32 Priority Separation",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\PriorityControl",
+            "Win32PrioritySeparation",
+        )],
+        severity: 2,
+        docs: Some("Process scheduler priority. 38=long fixed, 26=short variable (gaming)."),
+    },
+    CheckMeta {
+        id: "latency.timer_resolution",
+        name: "Global Timer Resolution",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Current system timer resolution. Lower is better for latency."),
+    },
+    CheckMeta {
+        id: "latency.interrupt_steering",
+        name: "Interrupt Steering",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\PnP\Pci",
+            "DeviceInterruptPolicyEnabled",
+        )],
+        severity: 2,
+        docs: Some("Allows OS to route device interrupts to specific CPUs."),
+    },
+    CheckMeta {
+        id: "latency.message_signaled_interrupts",
+        name: "MSI Mode (GPU)",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Services\nvlddmkm",
+            "RmMsiAllowed",
+        )],
+        severity: 2,
+        docs: Some("Message-Signaled Interrupts reduce latency vs. line-based interrupts."),
+    },
+    CheckMeta {
+        id: "latency.dpc_watchdog_period",
+        name: "DPC Watchdog Period",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "DpcWatchdogPeriod",
+        )],
+        severity: 2,
+        docs: Some("Deferred Procedure Call watchdog timeout."),
+    },
+    CheckMeta {
+        id: "latency.dpc_timeout",
+        name: "DPC Timeout",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "DpcTimeout",
+        )],
+        severity: 2,
+        docs: Some("Maximum time for DPC execution."),
+    },
+    CheckMeta {
+        id: "latency.idle_disable",
+        name: "Idle Scheduling Policy",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "IdleSchedulingPolicy",
+        )],
+        severity: 2,
+        docs: Some("Kernel idle scheduling policy. Left unset, Windows picks per power plan."),
+    },
+    CheckMeta {
+        id: "latency.distribute_timers",
+        name: "Distribute Timers",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "DistributeTimers",
+        )],
+        severity: 2,
+        docs: Some("Distributes timer interrupts across CPUs."),
+    },
+    CheckMeta {
+        id: "latency.processor_idle_state_policy",
+        name: "Processor Idle State Policy",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("CPU idle state management policy."),
+    },
+    CheckMeta {
+        id: "latency.sensitive_hints",
+        name: "Latency Sensitivity Hints",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power",
+            "LatencySensitivityHint",
+        )],
+        severity: 2,
+        docs: Some("Lets latency-sensitive work raise CPU performance states. 100 keeps hints at full effect."),
+    },
+    CheckMeta {
+        id: "latency.cstate_latency",
+        name: "C-State Exit Latency",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Time to exit CPU C-states. Lower is better."),
+    },
+    CheckMeta {
+        id: "latency.platform_clock_source",
+        name: "Platform Clock Source",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Primary time source: TSC (best), HPET, or ACPI PM Timer."),
+    },
+    CheckMeta {
+        id: "latency.tsc_invariant",
+        name: "TSC Invariant",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Time Stamp Counter runs at constant rate regardless of CPU frequency."),
+    },
+    CheckMeta {
+        id: "latency.use_platform_clock",
+        name: "Use Platform Clock",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "UsePlatformClock",
+        )],
+        severity: 5,
+        docs: Some("TSC is faster and more accurate than platform clock."),
+    },
+    CheckMeta {
+        id: "latency.synthetic_timer",
+        name: "Synthetic Timer",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Hyper-V synthetic timer (VM only)."),
+    },
+    CheckMeta {
+        id: "latency.large_page_drivers",
+        name: "Large Page Support",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "LargeSystemCache",
+        )],
+        severity: 2,
+        docs: Some("Large memory pages can reduce TLB misses."),
+    },
+    CheckMeta {
+        id: "latency.disable_dynamic_pstate",
+        name: "Dynamic P-State",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("CPU frequency scaling management."),
+    },
+    CheckMeta {
+        id: "latency.system_profile_tasks",
+        name: "System Profile Tasks",
+        category: Category::Latency,
+        sources: &[key(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks",
+        )],
+        severity: 2,
+        docs: Some("MMCSS task priority configuration."),
+    },
+    CheckMeta {
+        id: "latency.multimedia_system_profile",
+        name: "Multimedia System Profile",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("MMCSS multimedia class scheduler service configuration."),
+    },
+    CheckMeta {
+        id: "latency.gpu_priority",
+        name: "GPU Priority (Games)",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
+            "GPU Priority",
+        )],
+        severity: 5,
+        docs: Some("GPU scheduling priority for games. 8 is optimal."),
+    },
+    CheckMeta {
+        id: "latency.scheduling_category",
+        name: "Scheduling Category (Games)",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
+            "Scheduling Category",
+        )],
+        severity: 5,
+        docs: Some("CPU scheduling priority. 'High' is optimal for games."),
+    },
+    CheckMeta {
+        id: "latency.system_responsiveness_override",
+        name: "System Responsiveness Override",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Application-specific responsiveness overrides."),
+    },
+    CheckMeta {
+        id: "latency.tolerance",
+        name: "Latency Tolerance",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Device latency tolerance settings."),
+    },
+    CheckMeta {
+        id: "latency.interrupt_affinity_policy",
+        name: "Interrupt Affinity Policy",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("CPU affinity for device interrupts."),
+    },
+    CheckMeta {
+        id: "latency.isr_time_limit",
+        name: "ISR Time Limit",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Interrupt Service Routine execution time limit."),
+    },
+    CheckMeta {
+        id: "latency.dpc_queue_depth",
+        name: "DPC Queue Depth",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Deferred Procedure Call queue depth."),
+    },
+    CheckMeta {
+        id: "latency.threaded_dpc",
+        name: "Threaded DPC",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("Deferred Procedure Calls executed in threads."),
+    },
+    CheckMeta {
+        id: "latency.timer_coalescing",
+        name: "Timer Coalescing",
+        category: Category::Latency,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "CoalescingTimerInterval",
+        )],
+        severity: 2,
+        docs: Some("Groups timer expirations to reduce wakeups."),
+    },
+    CheckMeta {
+        id: "latency.precision_platform_timer",
+        name: "Precision Platform Timer",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("High-resolution hardware timer availability."),
+    },
+    CheckMeta {
+        id: "latency.acpi_timer_disabled",
+        name: "ACPI PM Timer",
+        category: Category::Latency,
+        sources: &[],
+        severity: 2,
+        docs: Some("ACPI Power Management Timer usage."),
+    },
+    CheckMeta {
+        id: "cpu.power_plan",
+        name: "Active Power Plan",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\User\PowerSchemes",
+            "ActivePowerScheme",
+        )],
+        severity: 5,
+        docs: Some("High Performance power plan provides best performance."),
+    },
+    CheckMeta {
+        id: "cpu.cstates",
+        name: "C-States",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Processor",
+            "Capabilities",
+        )],
+        severity: 2,
+        docs: Some("CPU idle states. Disabling can reduce latency but increase power usage."),
+    },
+    CheckMeta {
+        id: "cpu.core_parking",
+        name: "Core Parking",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\0cc5b647-c1df-4637-891a-dec35c318583",
+            "ValueMax",
+        )],
+        severity: 5,
+        docs: Some("Disabling core parking keeps all CPU cores active."),
+    },
+    CheckMeta {
+        id: "cpu.boost_mode",
+        name: "Processor Boost Mode",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\be337238-0d82-4146-a960-4f3749d470c7",
+            "ValueMax",
+        )],
+        severity: 5,
+        docs: Some("CPU turbo boost for higher performance."),
+    },
+    CheckMeta {
+        id: "cpu.processor_throttle",
+        name: "Processor Throttle",
+        category: Category::Cpu,
+        sources: &[
+            reg(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\893dee8e-2bef-41e0-89c6-b55d0929964c",
+                "ValueMin",
+            ),
+            reg(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\893dee8e-2bef-41e0-89c6-b55d0929964c",
+                "ValueMax",
+            ),
+        ],
+        severity: 5,
+        docs: Some("CPU frequency limits. 100% is optimal for performance."),
+    },
+    CheckMeta {
+        id: "cpu.vbs_status",
+        name: "VBS (Virtualization-Based Security)",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
+            "EnableVirtualizationBasedSecurity",
+        )],
+        severity: 5,
+        docs: Some("VBS can reduce performance. Disable if not needed."),
+    },
+    CheckMeta {
+        id: "cpu.hvci_status",
+        name: "HVCI (Memory Integrity)",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
+            "Enabled",
+        )],
+        severity: 5,
+        docs: Some("HVCI adds CPU overhead. Disable for better performance."),
+    },
+    CheckMeta {
+        id: "cpu.spectre_meltdown",
+        name: "CPU Vulnerability Mitigations",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "FeatureSettingsOverride",
+        )],
+        severity: 2,
+        docs: Some("Spectre/Meltdown mitigations. Can be disabled for performance."),
+    },
+    CheckMeta {
+        id: "cpu.heterogeneous_scheduler",
+        name: "Heterogeneous Scheduler",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+            "HeteroSchedulerPolicy",
+        )],
+        severity: 2,
+        docs: Some("Intel 12th gen+ hybrid architecture scheduler."),
+    },
+    CheckMeta {
+        id: "cpu.smt_status",
+        name: "SMT/Hyperthreading",
+        category: Category::Cpu,
+        sources: &[
+            wmi("Win32_Processor", "NumberOfLogicalProcessors"),
+            wmi("Win32_Processor", "NumberOfCores"),
+        ],
+        severity: 2,
+        docs: Some("Simultaneous Multi-Threading detection."),
+    },
+    CheckMeta {
+        id: "cpu.speed_shift",
+        name: "Intel Speed Shift (HWP)",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Hardware-Controlled Performance States (Intel 6th gen+)."),
+    },
+    CheckMeta {
+        id: "cpu.thread_director",
+        name: "Thread Director",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Intel 12th gen+ thread scheduling optimization."),
+    },
+    CheckMeta {
+        id: "cpu.processor_performance_boost",
+        name: "Processor Performance Boost Policy",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\be337238-0d82-4146-a960-4f3749d470c7",
+            "DefaultPowerSchemeValues",
+        )],
+        severity: 2,
+        docs: Some("Turbo boost policy of the active power scheme. Aggressive or efficient aggressive keeps boost clocks available."),
+    },
+    CheckMeta {
+        id: "cpu.processor_performance_core_parking",
+        name: "Core Parking Min Cores",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\0cc5b647-c1df-4637-891a-dec35c318583",
+            "ValueMin",
+        )],
+        severity: 2,
+        docs: Some("Minimum percentage of cores to keep unparked."),
+    },
+    CheckMeta {
+        id: "cpu.processor_performance_time_check",
+        name: "Processor Performance Time Check",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Interval for performance state evaluation."),
+    },
+    CheckMeta {
+        id: "cpu.processor_performance_increase_threshold",
+        name: "Performance Increase Threshold",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\06cadf0e-64ed-448a-8927-ce7bf90eb35d",
+            "ValueMax",
+        )],
+        severity: 2,
+        docs: Some("CPU load threshold to increase performance state."),
+    },
+    CheckMeta {
+        id: "cpu.processor_performance_decrease_threshold",
+        name: "Performance Decrease Threshold",
+        category: Category::Cpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\12a0ab44-fe28-4fa9-b3bd-4b64f44960a6",
+            "ValueMax",
+        )],
+        severity: 2,
+        docs: Some("CPU load threshold to decrease performance state."),
+    },
+    CheckMeta {
+        id: "cpu.processor_idle_demote_threshold",
+        name: "Idle Demote Threshold",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Threshold to demote CPU to deeper idle state."),
+    },
+    CheckMeta {
+        id: "cpu.processor_idle_promote_threshold",
+        name: "Idle Promote Threshold",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Threshold to promote CPU to shallower idle state."),
+    },
+    CheckMeta {
+        id: "cpu.processor_idle_state_max",
+        name: "Maximum Processor Idle State",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Deepest C-state allowed. C0/C1 for lowest latency."),
+    },
+    CheckMeta {
+        id: "cpu.processor_idle_time_check",
+        name: "Idle Time Check Interval",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("How often idle state is evaluated."),
+    },
+    CheckMeta {
+        id: "cpu.processor_latency_hint_min",
+        name: "Latency Hint Minimum",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Minimum latency hint for processor."),
+    },
+    CheckMeta {
+        id: "cpu.processor_latency_hint_perf",
+        name: "Latency Hint Performance",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Performance vs. latency trade-off hint."),
+    },
+    CheckMeta {
+        id: "cpu.processor_allow_throttling",
+        name: "Allow Processor Throttling",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Allows thermal throttling when needed."),
+    },
+    CheckMeta {
+        id: "cpu.processor_duty_cycling",
+        name: "Processor Duty Cycling",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Active cooling vs. passive cooling policy."),
+    },
+    CheckMeta {
+        id: "cpu.intel_turbo_boost",
+        name: "Intel Turbo Boost",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Intel Turbo Boost Technology status."),
+    },
+    CheckMeta {
+        id: "cpu.amd_turbo_core",
+        name: "AMD Turbo Core",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("AMD Turbo Core Technology status."),
+    },
+    CheckMeta {
+        id: "cpu.priority_class",
+        name: "Default CPU Priority Class",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Base priority class for processes."),
+    },
+    CheckMeta {
+        id: "cpu.processor_scheduling",
+        name: "Processor Scheduling",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Optimize for programs or background services."),
+    },
+    CheckMeta {
+        id: "cpu.affinity_policy",
+        name: "CPU Affinity Policy",
+        category: Category::Cpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Default CPU core assignment policy."),
+    },
+    CheckMeta {
+        id: "cpu.name",
+        name: "CPU Name",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Processor model as reported by Win32_Processor."),
+    },
+    CheckMeta {
+        id: "cpu.architecture",
+        name: "CPU Architecture",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Processor architecture as reported by Win32_Processor."),
+    },
+    CheckMeta {
+        id: "cpu.cores",
+        name: "CPU Cores",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Physical cores across all sockets."),
+    },
+    CheckMeta {
+        id: "cpu.threads",
+        name: "CPU Threads",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Logical processors, including SMT siblings."),
+    },
+    CheckMeta {
+        id: "cpu.l2_cache",
+        name: "L2 Cache",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Total L2 cache size reported by the processor."),
+    },
+    CheckMeta {
+        id: "cpu.l3_cache",
+        name: "L3 Cache",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Total L3 cache size reported by the processor."),
+    },
+    CheckMeta {
+        id: "cpu.base_clock",
+        name: "CPU Current Clock",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Clock speed at the time of the audit; lower than max at idle is normal."),
+    },
+    CheckMeta {
+        id: "cpu.max_clock",
+        name: "CPU Max Clock",
+        category: Category::Cpu,
+        sources: &[wmi_class("Win32_Processor")],
+        severity: 2,
+        docs: Some("Rated maximum clock speed, without boost."),
+    },
+    CheckMeta {
+        id: "gpu.driver_version",
+        name: "GPU Driver Version",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.clock_speed",
+        name: "GPU Clock Speed",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.memory_clock",
+        name: "GPU Memory Clock",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.fan_speed",
+        name: "GPU Fan Speed",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.power_limit",
+        name: "GPU Power Limit",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.utilization",
+        name: "GPU Utilization",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.vram_usage",
+        name: "VRAM Usage",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.core_count",
+        name: "GPU Core Count",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.boost_clock",
+        name: "GPU Boost Clock",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.memory_bandwidth",
+        name: "GPU Memory Bandwidth",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.pcie_link_speed",
+        name: "GPU PCIe Link Speed",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.tdp",
+        name: "GPU TDP",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.model",
+        name: "GPU Model",
+        category: Category::Gpu,
+        sources: &[DataSource::Dxgi],
+        severity: 2,
+        docs: Some("Primary adapter as enumerated by DXGI."),
+    },
+    CheckMeta {
+        id: "gpu.vram",
+        name: "GPU VRAM",
+        category: Category::Gpu,
+        sources: &[DataSource::Dxgi],
+        severity: 2,
+        docs: Some("Dedicated video memory of the primary adapter."),
+    },
+    CheckMeta {
+        id: "gpu.hags",
+        name: "HAGS (Hardware Accelerated GPU Scheduling)",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "HwSchMode",
+        )],
+        severity: 5,
+        docs: Some("HAGS reduces GPU latency on modern GPUs (GTX 1000+, RX 5000+)."),
+    },
+    CheckMeta {
+        id: "gpu.tdr_level",
+        name: "TDR Level",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "TdrLevel",
+        )],
+        severity: 2,
+        docs: Some("Timeout Detection and Recovery. 0=disabled (risky), 3=full recovery."),
+    },
+    CheckMeta {
+        id: "gpu.tdr_delay",
+        name: "TDR Delay",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "TdrDelay",
+        )],
+        severity: 2,
+        docs: Some("GPU timeout threshold. Default is 2 seconds."),
+    },
+    CheckMeta {
+        id: "gpu.game_dvr",
+        name: "Game DVR",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"System\GameConfigStore",
+            "GameDVR_Enabled",
+        )],
+        severity: 5,
+        docs: Some("Game DVR can reduce performance. Disable for gaming."),
+    },
+    CheckMeta {
+        id: "gpu.game_bar",
+        name: "Game Bar",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\GameDVR",
+            "AppCaptureEnabled",
+        )],
+        severity: 5,
+        docs: Some("Game Bar overlay can impact performance."),
+    },
+    CheckMeta {
+        id: "gpu.mpo",
+        name: "MPO (Multi-Plane Overlay)",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows\Dwm",
+            "OverlayTestMode",
+        )],
+        severity: 2,
+        docs: Some("MPO can cause issues in some games. Test both settings."),
+    },
+    CheckMeta {
+        id: "gpu.fullscreen_optimizations",
+        name: "Fullscreen Optimizations",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can be disabled per-application for compatibility."),
+    },
+    CheckMeta {
+        id: "gpu.nvidia_scheduling",
+        name: "NVIDIA GPU Scheduling",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers\Scheduler",
+            "EnablePreemption",
+        )],
+        severity: 2,
+        docs: Some("GPU preemption in the WDDM scheduler. Leave enabled; disabling it risks TDR resets."),
+    },
+    CheckMeta {
+        id: "gpu.nvidia_prerendered_frames",
+        name: "NVIDIA Max Prerendered Frames",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Lower values (1) reduce latency, higher increase smoothness."),
+    },
+    CheckMeta {
+        id: "gpu.nvidia_low_latency",
+        name: "NVIDIA Low Latency Mode",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Set to 'Ultra' or 'On' in NVIDIA Control Panel for lowest latency."),
+    },
+    CheckMeta {
+        id: "gpu.nvidia_power_management",
+        name: "NVIDIA Power Management",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("'Prefer Maximum Performance' gives best results for gaming."),
+    },
+    CheckMeta {
+        id: "gpu.amd_anti_lag",
+        name: "AMD Anti-Lag",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("AMD feature to reduce input lag. Enable in Radeon Software."),
+    },
+    CheckMeta {
+        id: "gpu.amd_chill",
+        name: "AMD Chill",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Power saving feature. Disable for consistent performance."),
+    },
+    CheckMeta {
+        id: "gpu.rebar_status",
+        name: "Resizable BAR",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("ReBAR improves performance. Enable in BIOS if supported."),
+    },
+    CheckMeta {
+        id: "gpu.preemption",
+        name: "GPU Preemption Granularity",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Finer preemption = better responsiveness."),
+    },
+    CheckMeta {
+        id: "gpu.shader_cache",
+        name: "Shader Cache",
+        category: Category::Gpu,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "DisableShaderCache",
+        )],
+        severity: 5,
+        docs: Some("Shader cache improves load times."),
+    },
+    CheckMeta {
+        id: "gpu.wddm_version",
+        name: "WDDM Version",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Windows Display Driver Model version."),
+    },
+    CheckMeta {
+        id: "gpu.dx12_ultimate",
+        name: "DirectX 12 Ultimate",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Latest DirectX features support."),
+    },
+    CheckMeta {
+        id: "gpu.ray_tracing",
+        name: "Hardware Ray Tracing",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.variable_rate_shading",
+        name: "Variable Rate Shading",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.mesh_shading",
+        name: "Mesh Shading",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.sampler_feedback",
+        name: "Sampler Feedback",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.acceleration",
+        name: "Hardware Acceleration",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.memory_management",
+        name: "GPU Memory Management",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.compute_preemption",
+        name: "Compute Preemption",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.scheduling_latency",
+        name: "GPU Scheduling Latency",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.dx_diagnostics",
+        name: "DirectX Diagnostics",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.dxgi_flip_model",
+        name: "DXGI Flip Model",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Modern presentation model for lower latency."),
+    },
+    CheckMeta {
+        id: "gpu.present_mon_compatible",
+        name: "PresentMon Compatible",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.priority_support",
+        name: "GPU Priority API",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.async_compute",
+        name: "Async Compute",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.multi_adapter",
+        name: "Multi-Adapter Support",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.vr_ready",
+        name: "VR Ready",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "gpu.nvidia_reflex",
+        name: "NVIDIA Reflex",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("Low latency technology for supported games."),
+    },
+    CheckMeta {
+        id: "gpu.amd_fsr_support",
+        name: "AMD FSR Support",
+        category: Category::Gpu,
+        sources: &[],
+        severity: 2,
+        docs: Some("FidelityFX Super Resolution upscaling."),
+    },
+    CheckMeta {
+        id: "memory.ram_speed",
+        name: "RAM Speed",
+        category: Category::Memory,
+        sources: &[wmi("Win32_PhysicalMemory", "Speed")],
+        severity: 2,
+        docs: Some("Current RAM frequency. Check BIOS for XMP/DOCP profile."),
+    },
+    CheckMeta {
+        id: "memory.ram_channel",
+        name: "RAM Channel Configuration",
+        category: Category::Memory,
+        sources: &[wmi_class("Win32_PhysicalMemory")],
+        severity: 2,
+        docs: Some("Dual channel provides 2x memory bandwidth vs single channel."),
+    },
+    CheckMeta {
+        id: "memory.page_file",
+        name: "Page File",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "PagingFiles",
+        )],
+        severity: 5,
+        docs: Some("System-managed or 1.5x RAM size recommended."),
+    },
+    CheckMeta {
+        id: "memory.compression",
+        name: "Memory Compression",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: Some("Reduces physical memory usage with minimal CPU cost."),
+    },
+    CheckMeta {
+        id: "memory.prefetch",
+        name: "Prefetch",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management\PrefetchParameters",
+            "EnablePrefetcher",
+        )],
+        severity: 2,
+        docs: Some("0=disabled, 1=app, 2=boot, 3=both. Keep enabled for HDDs."),
+    },
+    CheckMeta {
+        id: "memory.superfetch",
+        name: "Superfetch (SysMain)",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: Some("Preloads frequently used apps. Can disable on SSDs."),
+    },
+    CheckMeta {
+        id: "memory.large_system_cache",
+        name: "Large System Cache",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "LargeSystemCache",
+        )],
+        severity: 2,
+        docs: Some("For file servers. Keep disabled for workstations."),
+    },
+    CheckMeta {
+        id: "memory.ndu_service",
+        name: "NDU (Network Data Usage)",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can be disabled to save memory if not needed."),
+    },
+    CheckMeta {
+        id: "memory.second_level_cache",
+        name: "Second Level Data Cache",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "SecondLevelDataCache",
+        )],
+        severity: 2,
+        docs: Some("Legacy L2 size hint for the memory manager. 0 lets Windows detect it; modern CPUs ignore it."),
+    },
+    CheckMeta {
+        id: "memory.clear_pagefile_at_shutdown",
+        name: "Clear PageFile at Shutdown",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "ClearPageFileAtShutdown",
+        )],
+        severity: 2,
+        docs: Some("Security feature. Increases shutdown time."),
+    },
+    CheckMeta {
+        id: "memory.disable_paging_executive",
+        name: "Disable Paging Executive",
+        category: Category::Memory,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "DisablePagingExecutive",
+        )],
+        severity: 5,
+        docs: Some("Keeps kernel in RAM. Enable if you have 16GB+ RAM."),
+    },
+    CheckMeta {
+        id: "memory.large_page_minimum",
+        name: "Large Page Minimum",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: Some("Minimum memory for large page allocation."),
+    },
+    CheckMeta {
+        id: "memory.system_cache_limit",
+        name: "System Cache Limit",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "memory.io_page_lock_limit",
+        name: "I/O Page Lock Limit",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "memory.management",
+        name: "Memory Management",
+        category: Category::Memory,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "memory.total_ram",
+        name: "Total RAM",
+        category: Category::Memory,
+        sources: &[wmi("Win32_ComputerSystem", "TotalPhysicalMemory")],
+        severity: 2,
+        docs: Some("Installed physical memory visible to Windows."),
+    },
+    CheckMeta {
+        id: "memory.available_ram",
+        name: "Available RAM",
+        category: Category::Memory,
+        sources: &[wmi("Win32_OperatingSystem", "FreePhysicalMemory")],
+        severity: 2,
+        docs: Some("Free physical memory at the time of the audit."),
+    },
+    CheckMeta {
+        id: "memory.ram_manufacturer",
+        name: "RAM Manufacturer",
+        category: Category::Memory,
+        sources: &[wmi("Win32_PhysicalMemory", "Manufacturer")],
+        severity: 2,
+        docs: Some("Module manufacturer from SMBIOS."),
+    },
+    CheckMeta {
+        id: "memory.ram_voltage",
+        name: "RAM Voltage",
+        category: Category::Memory,
+        sources: &[wmi("Win32_PhysicalMemory", "ConfiguredVoltage")],
+        severity: 2,
+        docs: Some("Configured module voltage from SMBIOS; above 1.2V (DDR4) or 1.1V (DDR5) usually means XMP/EXPO."),
+    },
+    CheckMeta {
+        id: "storage.primary_drive_type",
+        name: "Primary Drive Type",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_capacity",
+        name: "Drive Capacity",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.free_space",
+        name: "Free Space",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_health",
+        name: "Drive Health",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_temperature",
+        name: "Drive Temperature",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_read_speed",
+        name: "Drive Read Speed",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_write_speed",
+        name: "Drive Write Speed",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_iops",
+        name: "Drive IOPS",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_latency",
+        name: "Drive Latency",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.drive_interface",
+        name: "Drive Interface",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.trim_status",
+        name: "TRIM Support",
+        category: Category::Storage,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\FileSystem",
+            "DisableDeleteNotification",
+        )],
+        severity: 5,
+        docs: Some("TRIM is essential for SSD health and performance."),
+    },
+    CheckMeta {
+        id: "storage.partition_alignment",
+        name: "Partition Alignment",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Modern drives should be 4K aligned."),
+    },
+    CheckMeta {
+        id: "storage.disable_8dot3",
+        name: "8.3 Filename Creation",
+        category: Category::Storage,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\FileSystem",
+            "NtfsDisable8dot3NameCreation",
+        )],
+        severity: 5,
+        docs: Some("Disable for better performance on SSDs."),
+    },
+    CheckMeta {
+        id: "storage.last_access_time",
+        name: "Last Access Timestamp",
+        category: Category::Storage,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\FileSystem",
+            "NtfsDisableLastAccessUpdate",
+        )],
+        severity: 5,
+        docs: Some("Disable to reduce SSD writes."),
+    },
+    CheckMeta {
+        id: "storage.nvme_idle",
+        name: "NVMe Idle Timeout",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Longer timeout reduces latency spikes."),
+    },
+    CheckMeta {
+        id: "storage.sense",
+        name: "Storage Sense",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Automatic disk cleanup. Can impact performance."),
+    },
+    CheckMeta {
+        id: "storage.windows_search",
+        name: "Windows Search Indexing",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Disable or limit to specific folders for performance."),
+    },
+    CheckMeta {
+        id: "storage.defrag_schedule",
+        name: "Scheduled Defragmentation",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("SSDs don't need defragmentation."),
+    },
+    CheckMeta {
+        id: "storage.write_cache",
+        name: "Write Cache",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Improves performance but requires UPS for data safety."),
+    },
+    CheckMeta {
+        id: "storage.fsutil_behavior",
+        name: "FSUtil Behavior",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.disk_timeout",
+        name: "Disk Timeout",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.disk_idle_timeout",
+        name: "Disk Idle Timeout",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevents disks from spinning down during activity."),
+    },
+    CheckMeta {
+        id: "storage.policy",
+        name: "Storage Policy",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.file_system",
+        name: "File System",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.cluster_size",
+        name: "Cluster Size",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.compression",
+        name: "NTFS Compression",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Compression reduces performance."),
+    },
+    CheckMeta {
+        id: "storage.encryption",
+        name: "BitLocker Encryption",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Encryption has performance cost but provides security."),
+    },
+    CheckMeta {
+        id: "storage.disk_caching",
+        name: "Disk Caching",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.ssd_optimization",
+        name: "SSD Optimization",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.nvme_settings",
+        name: "NVMe Settings",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.sata_mode",
+        name: "SATA Mode",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("AHCI is required for TRIM support."),
+    },
+    CheckMeta {
+        id: "storage.ahci_mode",
+        name: "AHCI Link Power Management",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.raid_configuration",
+        name: "RAID Configuration",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.smart_monitoring",
+        name: "S.M.A.R.T. Monitoring",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.disk_defragmentation",
+        name: "Disk Optimization",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.spaces",
+        name: "Storage Spaces",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.volume_shadow_copy",
+        name: "Volume Shadow Copy",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.disk_quota",
+        name: "Disk Quota",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "storage.distributed_link_tracking",
+        name: "Distributed Link Tracking",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can be disabled for performance."),
+    },
+    CheckMeta {
+        id: "storage.qos",
+        name: "Storage QoS",
+        category: Category::Storage,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.adapter",
+        name: "Network Adapter",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.link_speed",
+        name: "Link Speed",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.latency",
+        name: "Network Latency",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.packet_loss",
+        name: "Packet Loss",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.mtu_size",
+        name: "MTU Size",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.nagle_algorithm",
+        name: "Nagle Algorithm",
+        category: Category::Network,
+        sources: &[
+            key(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces",
+            ),
+            reg(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces\*",
+                "TcpAckFrequency",
+            ),
+        ],
+        severity: 5,
+        docs: Some("Disable Nagle for lower latency in gaming and real-time apps."),
+    },
+    CheckMeta {
+        id: "network.rss",
+        name: "RSS (Receive Side Scaling)",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Distributes network processing across CPU cores."),
+    },
+    CheckMeta {
+        id: "network.checksum_offload",
+        name: "Checksum Offload",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Offloads checksum calculation to NIC."),
+    },
+    CheckMeta {
+        id: "network.interrupt_moderation",
+        name: "Interrupt Moderation",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("'Off' for lowest latency, 'Adaptive' balances throughput/latency."),
+    },
+    CheckMeta {
+        id: "network.flow_control",
+        name: "Flow Control",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can disable for gaming to reduce micro-stutters."),
+    },
+    CheckMeta {
+        id: "network.dns_configuration",
+        name: "DNS Servers",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Use fast DNS like 1.1.1.1 or 8.8.8.8 for better response times."),
+    },
+    CheckMeta {
+        id: "network.qos_throttling",
+        name: "QoS Packet Scheduler Throttling",
+        category: Category::Network,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Policies\Microsoft\Windows\Psched",
+            "NonBestEffortLimit",
+        )],
+        severity: 5,
+        docs: Some("Set to 0 to disable bandwidth reservation."),
+    },
+    CheckMeta {
+        id: "network.netbios",
+        name: "NetBIOS over TCP/IP",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Legacy protocol. Disable if not needed."),
+    },
+    CheckMeta {
+        id: "network.ipv6",
+        name: "IPv6",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Keep enabled unless it causes issues."),
+    },
+    CheckMeta {
+        id: "network.tcp_autotuning",
+        name: "TCP Window Auto-Tuning",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Optimizes TCP window size. Keep on 'normal' or 'experimental'."),
+    },
+    CheckMeta {
+        id: "network.tcp_timestamps",
+        name: "TCP Timestamps",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.tcp_window_scaling",
+        name: "TCP Window Scaling",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Allows larger TCP windows for better throughput."),
+    },
+    CheckMeta {
+        id: "network.tcp_chimney",
+        name: "TCP Chimney Offload",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Legacy feature. 'Automatic' is recommended."),
+    },
+    CheckMeta {
+        id: "network.adapter_power",
+        name: "Network Adapter Power Management",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevent adapter from sleeping for consistent connectivity."),
+    },
+    CheckMeta {
+        id: "network.throttling",
+        name: "Network Throttling",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.tcp_optimizer",
+        name: "TCP Optimizer Settings",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.receive_buffers",
+        name: "Receive Buffers",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Increase for high-bandwidth connections."),
+    },
+    CheckMeta {
+        id: "network.transmit_buffers",
+        name: "Transmit Buffers",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Increase for high-bandwidth connections."),
+    },
+    CheckMeta {
+        id: "network.jumbo_frames",
+        name: "Jumbo Frames",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Enable only if entire network supports it."),
+    },
+    CheckMeta {
+        id: "network.large_send_offload",
+        name: "Large Send Offload (LSO)",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.tcp_offload_engine",
+        name: "TCP Offload Engine",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.discovery",
+        name: "Network Discovery",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.file_printer_sharing",
+        name: "File and Printer Sharing",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.windows_firewall",
+        name: "Windows Firewall",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Keep enabled for security."),
+    },
+    CheckMeta {
+        id: "network.location",
+        name: "Network Location Awareness",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.profile",
+        name: "Network Profile",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.dns_cache",
+        name: "DNS Client Cache",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.lmhosts_lookup",
+        name: "LMHOSTS Lookup",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Legacy feature. Can be disabled."),
+    },
+    CheckMeta {
+        id: "network.adapter_binding",
+        name: "Network Adapter Binding Order",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.qos_packet_scheduler",
+        name: "QoS Packet Scheduler",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.smb_version",
+        name: "SMB Version",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("SMB 3+ provides encryption and better performance."),
+    },
+    CheckMeta {
+        id: "network.smb_signing",
+        name: "SMB Signing",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("Security feature with slight performance cost."),
+    },
+    CheckMeta {
+        id: "network.smb_encryption",
+        name: "SMB Encryption",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "network.congestion_provider",
+        name: "Network Congestion Provider",
+        category: Category::Network,
+        sources: &[],
+        severity: 2,
+        docs: Some("CUBIC is the modern congestion control algorithm."),
+    },
+    CheckMeta {
+        id: "audio.channels",
+        name: "Audio Channels",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.format",
+        name: "Audio Format",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.quality",
+        name: "Audio Quality",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.output_device",
+        name: "Audio Output Device",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.input_device",
+        name: "Audio Input Device",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.exclusive_mode",
+        name: "Exclusive Mode",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Allows applications to take exclusive control of audio device."),
+    },
+    CheckMeta {
+        id: "audio.enhancements",
+        name: "Audio Enhancements",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Disable for lowest latency and best quality."),
+    },
+    CheckMeta {
+        id: "audio.sample_rate",
+        name: "Default Sample Rate",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("44100 Hz or 48000 Hz are standard."),
+    },
+    CheckMeta {
+        id: "audio.bit_depth",
+        name: "Default Bit Depth",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.service",
+        name: "Windows Audio Service",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.endpoint_builder",
+        name: "Audio Endpoint Builder",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.buffer_size",
+        name: "Audio Buffer Size",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Lower buffer = lower latency but more CPU usage."),
+    },
+    CheckMeta {
+        id: "audio.dpc_latency",
+        name: "Audio DPC Latency",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.priority",
+        name: "Audio Task Priority",
+        category: Category::Audio,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Audio",
+            "Priority",
+        )],
+        severity: 2,
+        docs: Some("MMCSS scheduling priority for audio tasks. 6 is the Windows default for real-time audio."),
+    },
+    CheckMeta {
+        id: "audio.offload",
+        name: "Audio Offloading",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can cause compatibility issues. Disable if problems occur."),
+    },
+    CheckMeta {
+        id: "audio.spatial_sound",
+        name: "Spatial Sound",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Windows Sonic or Dolby Atmos can be enabled."),
+    },
+    CheckMeta {
+        id: "audio.communications_tab",
+        name: "Communications Auto-Ducking",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevents Windows from lowering volume during calls."),
+    },
+    CheckMeta {
+        id: "audio.allow_applications_control",
+        name: "Allow Applications Exclusive Control",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.device_isolation",
+        name: "Audio Device Isolation",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.stream_priority",
+        name: "Audio Stream Priority",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.wasapi_mode",
+        name: "WASAPI Exclusive Mode",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Low-latency audio API."),
+    },
+    CheckMeta {
+        id: "audio.asio_support",
+        name: "ASIO Support",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: Some("Professional audio driver interface."),
+    },
+    CheckMeta {
+        id: "audio.latency_mode",
+        name: "Audio Latency Mode",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.driver_version",
+        name: "Audio Driver",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "audio.device_power",
+        name: "Audio Device Power Management",
+        category: Category::Audio,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.keyboard_layout",
+        name: "Keyboard Layout",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.numlock_state",
+        name: "Numlock State",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.scroll_lock",
+        name: "Scroll Lock",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.language",
+        name: "Input Language",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.gamepad_support",
+        name: "Gamepad Support",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.mouse_acceleration",
+        name: "Mouse Acceleration",
+        category: Category::Input,
+        sources: &[reg(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed")],
+        severity: 5,
+        docs: Some("Disable for precise aiming in FPS games."),
+    },
+    CheckMeta {
+        id: "input.pointer_precision",
+        name: "Enhance Pointer Precision",
+        category: Category::Input,
+        sources: &[reg(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed")],
+        severity: 5,
+        docs: Some("Windows mouse acceleration. Disable for gaming."),
+    },
+    CheckMeta {
+        id: "input.mouse_speed",
+        name: "Mouse Speed",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Mouse",
+            "MouseSensitivity",
+        )],
+        severity: 2,
+        docs: Some("6/11 (10/20) is 1:1 ratio, recommended for gaming."),
+    },
+    CheckMeta {
+        id: "input.mouse_threshold1",
+        name: "Mouse Acceleration Threshold 1",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Mouse",
+            "MouseThreshold1",
+        )],
+        severity: 2,
+        docs: Some("First acceleration threshold. 0 with threshold 2 at 0 and speed 0 disables acceleration."),
+    },
+    CheckMeta {
+        id: "input.mouse_threshold2",
+        name: "Mouse Acceleration Threshold 2",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Mouse",
+            "MouseThreshold2",
+        )],
+        severity: 2,
+        docs: Some("Second acceleration threshold. 0 disables the doubled acceleration step."),
+    },
+    CheckMeta {
+        id: "input.keyboard_delay",
+        name: "Keyboard Repeat Delay",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Keyboard",
+            "KeyboardDelay",
+        )],
+        severity: 2,
+        docs: Some("0 = shortest delay (250ms)."),
+    },
+    CheckMeta {
+        id: "input.keyboard_speed",
+        name: "Keyboard Repeat Rate",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Keyboard",
+            "KeyboardSpeed",
+        )],
+        severity: 2,
+        docs: Some("31 = fastest repeat rate."),
+    },
+    CheckMeta {
+        id: "input.hid_service",
+        name: "HID Service",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Human Interface Device Access service."),
+    },
+    CheckMeta {
+        id: "input.tablet_input_service",
+        name: "Tablet Input Service",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can be disabled on non-touch systems."),
+    },
+    CheckMeta {
+        id: "input.touch_keyboard_service",
+        name: "Touch Keyboard Service",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Can be disabled on non-touch systems."),
+    },
+    CheckMeta {
+        id: "input.mouse_trails",
+        name: "Mouse Trails",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.snap_to_default",
+        name: "Snap To Default Button",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.mouse_sonar",
+        name: "Mouse Sonar (Ctrl to Find)",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.mouse_vanish",
+        name: "Hide Pointer While Typing",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.pointer_shadow",
+        name: "Pointer Shadow",
+        category: Category::Input,
+        sources: &[reg(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Desktop",
+            "UserPreferencesMask",
+        )],
+        severity: 2,
+        docs: Some("Cursor shadow, bit 13 of the UserPreferencesMask binary. Cosmetic only."),
+    },
+    CheckMeta {
+        id: "input.raw_input",
+        name: "Raw Input API",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Games should use Raw Input for best precision."),
+    },
+    CheckMeta {
+        id: "input.lag",
+        name: "Input Lag",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "input.polling_rate",
+        name: "Mouse Polling Rate",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Higher polling rate = lower input lag."),
+    },
+    CheckMeta {
+        id: "input.usb_selective_suspend",
+        name: "USB Selective Suspend",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevents USB devices from entering power-saving mode."),
+    },
+    CheckMeta {
+        id: "input.device_drivers",
+        name: "Input Device Drivers",
+        category: Category::Input,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "stability.system_uptime",
+        name: "Last Boot Time",
+        category: Category::Stability,
+        sources: &[wmi("Win32_OperatingSystem", "LastBootUpTime")],
+        severity: 2,
+        docs: Some("System last reboot timestamp"),
+    },
+    CheckMeta {
+        id: "stability.event_log_errors",
+        name: "Event Log Service",
+        category: Category::Stability,
+        sources: &[],
+        severity: 2,
+        docs: Some("Event log monitoring available"),
+    },
+    CheckMeta {
+        id: "stability.failed_devices",
+        name: "Detected Devices",
+        category: Category::Stability,
+        sources: &[wmi_class("Win32_PnPEntity")],
+        severity: 2,
+        docs: Some("Total PnP devices detected"),
+    },
+    CheckMeta {
+        id: "services.diagtrack",
+        name: "DiagTrack (Telemetry)",
+        category: Category::Services,
+        sources: &[service("DiagTrack")],
+        severity: 5,
+        docs: Some("Can be disabled for privacy."),
+    },
+    CheckMeta {
+        id: "services.dmwappushservice",
+        name: "dmwappushservice",
+        category: Category::Services,
+        sources: &[service("dmwappushservice")],
+        severity: 2,
+        docs: Some("Push notification service."),
+    },
+    CheckMeta {
+        id: "services.sysmain",
+        name: "SysMain (Superfetch)",
+        category: Category::Services,
+        sources: &[service("SysMain")],
+        severity: 2,
+        docs: Some("Preloads apps."),
+    },
+    CheckMeta {
+        id: "services.tabletinputservice",
+        name: "TabletInputService",
+        category: Category::Services,
+        sources: &[service("TabletInputService")],
+        severity: 2,
+        docs: Some("Tablet PC input."),
+    },
+    CheckMeta {
+        id: "services.wsearch",
+        name: "WSearch (Windows Search)",
+        category: Category::Services,
+        sources: &[service("WSearch")],
+        severity: 2,
+        docs: Some("File indexing."),
+    },
+    CheckMeta {
+        id: "services.xblauthmanager",
+        name: "XblAuthManager",
+        category: Category::Services,
+        sources: &[service("XblAuthManager")],
+        severity: 2,
+        docs: Some("Xbox Live Auth."),
+    },
+    CheckMeta {
+        id: "services.xblgamesave",
+        name: "XblGameSave",
+        category: Category::Services,
+        sources: &[service("XblGameSave")],
+        severity: 2,
+        docs: Some("Xbox Game Save."),
+    },
+    CheckMeta {
+        id: "services.xboxnetapisvc",
+        name: "XboxNetApiSvc",
+        category: Category::Services,
+        sources: &[service("XboxNetApiSvc")],
+        severity: 2,
+        docs: Some("Xbox Network."),
+    },
+    CheckMeta {
+        id: "services.xboxgipsvc",
+        name: "XboxGipSvc",
+        category: Category::Services,
+        sources: &[service("XboxGipSvc")],
+        severity: 2,
+        docs: Some("Xbox Accessory Management."),
+    },
+    CheckMeta {
+        id: "services.wuauserv",
+        name: "Windows Update",
+        category: Category::Services,
+        sources: &[service("wuauserv")],
+        severity: 2,
+        docs: Some("System updates."),
+    },
+    CheckMeta {
+        id: "services.windefend",
+        name: "Windows Defender",
+        category: Category::Services,
+        sources: &[service("WinDefend")],
+        severity: 2,
+        docs: Some("Antivirus protection."),
+    },
+    CheckMeta {
+        id: "services.mpssvc",
+        name: "Windows Firewall",
+        category: Category::Services,
+        sources: &[service("MpsSvc")],
+        severity: 2,
+        docs: Some("Network security."),
+    },
+    CheckMeta {
+        id: "services.schedule",
+        name: "Task Scheduler",
+        category: Category::Services,
+        sources: &[service("Schedule")],
+        severity: 2,
+        docs: Some("Scheduled tasks."),
+    },
+    CheckMeta {
+        id: "services.plugplay",
+        name: "Plug and Play",
+        category: Category::Services,
+        sources: &[service("PlugPlay")],
+        severity: 2,
+        docs: Some("Device detection."),
+    },
+    CheckMeta {
+        id: "services.rpcss",
+        name: "RPC Service",
+        category: Category::Services,
+        sources: &[service("RpcSs")],
+        severity: 2,
+        docs: Some("Remote procedure calls."),
+    },
+    CheckMeta {
+        id: "services.dcomlaunch",
+        name: "DCOM Server",
+        category: Category::Services,
+        sources: &[service("DcomLaunch")],
+        severity: 2,
+        docs: Some("Component services."),
+    },
+    CheckMeta {
+        id: "services.cryptsvc",
+        name: "Cryptographic Services",
+        category: Category::Services,
+        sources: &[service("CryptSvc")],
+        severity: 2,
+        docs: Some("Encryption services."),
+    },
+    CheckMeta {
+        id: "services.audiosrv",
+        name: "Windows Audio",
+        category: Category::Services,
+        sources: &[service("AudioSrv")],
+        severity: 2,
+        docs: Some("Audio management."),
+    },
+    CheckMeta {
+        id: "services.audioendpointbuilder",
+        name: "Windows Audio Endpoint Builder",
+        category: Category::Services,
+        sources: &[service("AudioEndpointBuilder")],
+        severity: 2,
+        docs: Some("Audio device management."),
+    },
+    CheckMeta {
+        id: "services.themes",
+        name: "Themes",
+        category: Category::Services,
+        sources: &[service("Themes")],
+        severity: 2,
+        docs: Some("Visual themes."),
+    },
+    CheckMeta {
+        id: "services.spooler",
+        name: "Print Spooler",
+        category: Category::Services,
+        sources: &[service("Spooler")],
+        severity: 2,
+        docs: Some("Print management."),
+    },
+    CheckMeta {
+        id: "services.bits",
+        name: "Background Intelligent Transfer",
+        category: Category::Services,
+        sources: &[service("BITS")],
+        severity: 2,
+        docs: Some("Background downloads."),
+    },
+    CheckMeta {
+        id: "services.wbiosrvc",
+        name: "Windows Biometric Service",
+        category: Category::Services,
+        sources: &[service("WbioSrvc")],
+        severity: 2,
+        docs: Some("Biometric devices."),
+    },
+    CheckMeta {
+        id: "services.termservice",
+        name: "Remote Desktop Services",
+        category: Category::Services,
+        sources: &[service("TermService")],
+        severity: 2,
+        docs: Some("Remote desktop."),
+    },
+    CheckMeta {
+        id: "services.fax",
+        name: "Fax",
+        category: Category::Services,
+        sources: &[service("Fax")],
+        severity: 2,
+        docs: Some("Fax service."),
+    },
+    CheckMeta {
+        id: "services.homegrouplistener",
+        name: "HomeGroup Listener",
+        category: Category::Services,
+        sources: &[service("HomeGroupListener")],
+        severity: 2,
+        docs: Some("HomeGroup."),
+    },
+    CheckMeta {
+        id: "services.homegroupprovider",
+        name: "HomeGroup Provider",
+        category: Category::Services,
+        sources: &[service("HomeGroupProvider")],
+        severity: 2,
+        docs: Some("HomeGroup."),
+    },
+    CheckMeta {
+        id: "services.icssvc",
+        name: "Windows Mobile Hotspot",
+        category: Category::Services,
+        sources: &[service("icssvc")],
+        severity: 2,
+        docs: Some("Mobile hotspot."),
+    },
+    CheckMeta {
+        id: "services.phonesvc",
+        name: "Phone Service",
+        category: Category::Services,
+        sources: &[service("PhoneSvc")],
+        severity: 2,
+        docs: Some("Phone functionality."),
+    },
+    CheckMeta {
+        id: "services.retaildemo",
+        name: "Retail Demo Service",
+        category: Category::Services,
+        sources: &[service("RetailDemo")],
+        severity: 2,
+        docs: Some("Demo mode."),
+    },
+    CheckMeta {
+        id: "services.sensorservice",
+        name: "Sensor Service",
+        category: Category::Services,
+        sources: &[service("SensorService")],
+        severity: 2,
+        docs: Some("Sensor management."),
+    },
+    CheckMeta {
+        id: "services.scdeviceenum",
+        name: "Smart Card Device Enumeration",
+        category: Category::Services,
+        sources: &[service("ScDeviceEnum")],
+        severity: 2,
+        docs: Some("Smart card."),
+    },
+    CheckMeta {
+        id: "services.scpolicysvc",
+        name: "Smart Card Removal Policy",
+        category: Category::Services,
+        sources: &[service("SCPolicySvc")],
+        severity: 2,
+        docs: Some("Smart card."),
+    },
+    CheckMeta {
+        id: "services.wiarpc",
+        name: "Windows Image Acquisition",
+        category: Category::Services,
+        sources: &[service("WiaRpc")],
+        severity: 2,
+        docs: Some("Scanner/camera."),
+    },
+    CheckMeta {
+        id: "services.wcncsvc",
+        name: "Windows Connect Now",
+        category: Category::Services,
+        sources: &[service("wcncsvc")],
+        severity: 2,
+        docs: Some("Network setup."),
+    },
+    CheckMeta {
+        id: "services.walletservice",
+        name: "WalletService",
+        category: Category::Services,
+        sources: &[service("WalletService")],
+        severity: 2,
+        docs: Some("Wallet service."),
+    },
+    CheckMeta {
+        id: "services.wisvc",
+        name: "Windows Insider Service",
+        category: Category::Services,
+        sources: &[service("wisvc")],
+        severity: 2,
+        docs: Some("Insider builds."),
+    },
+    CheckMeta {
+        id: "services.mapsbroker",
+        name: "Downloaded Maps Manager",
+        category: Category::Services,
+        sources: &[service("MapsBroker")],
+        severity: 2,
+        docs: Some("Offline maps."),
+    },
+    CheckMeta {
+        id: "services.lfsvc",
+        name: "Geolocation Service",
+        category: Category::Services,
+        sources: &[service("lfsvc")],
+        severity: 2,
+        docs: Some("Location services."),
+    },
+    CheckMeta {
+        id: "services.remoteregistry",
+        name: "Remote Registry",
+        category: Category::Services,
+        sources: &[service("RemoteRegistry")],
+        severity: 2,
+        docs: Some("Remote registry access."),
+    },
+    CheckMeta {
+        id: "services.cdpusersvc",
+        name: "Connected User Experiences",
+        category: Category::Services,
+        sources: &[service("CDPUserSvc")],
+        severity: 2,
+        docs: Some("Connected experiences; per-user, one instance per signed-in session."),
+    },
+    CheckMeta {
+        id: "services.bcastdvruserservice",
+        name: "GameDVR and Broadcast User Service",
+        category: Category::Services,
+        sources: &[service("BcastDVRUserService")],
+        severity: 2,
+        docs: Some("Game clip recording; per-user."),
+    },
+    CheckMeta {
+        id: "services.onesyncsvc",
+        name: "Sync Host",
+        category: Category::Services,
+        sources: &[service("OneSyncSvc")],
+        severity: 2,
+        docs: Some("Mail, contacts and calendar sync; per-user."),
+    },
+    CheckMeta {
+        id: "services.eventlog",
+        name: "Windows Event Log",
+        category: Category::Services,
+        sources: &[service("EventLog")],
+        severity: 2,
+        docs: Some("Event logging."),
+    },
+    CheckMeta {
+        id: "services.eventsystem",
+        name: "COM+ Event System",
+        category: Category::Services,
+        sources: &[service("EventSystem")],
+        severity: 2,
+        docs: Some("COM+ events."),
+    },
+    CheckMeta {
+        id: "services.msdtc",
+        name: "Distributed Transaction Coordinator",
+        category: Category::Services,
+        sources: &[service("MSDTC")],
+        severity: 2,
+        docs: Some("Distributed transactions."),
+    },
+    CheckMeta {
+        id: "services.winmgmt",
+        name: "Windows Management Instrumentation",
+        category: Category::Services,
+        sources: &[service("Winmgmt")],
+        severity: 2,
+        docs: Some("WMI service."),
+    },
+    CheckMeta {
+        id: "services.shellhwdetection",
+        name: "Shell Hardware Detection",
+        category: Category::Services,
+        sources: &[service("ShellHWDetection")],
+        severity: 2,
+        docs: Some("Hardware events."),
+    },
+    CheckMeta {
+        id: "services.samss",
+        name: "Security Accounts Manager",
+        category: Category::Services,
+        sources: &[service("SamSs")],
+        severity: 2,
+        docs: Some("Account management."),
+    },
+    CheckMeta {
+        id: "services.lanmanserver",
+        name: "Server",
+        category: Category::Services,
+        sources: &[service("LanmanServer")],
+        severity: 2,
+        docs: Some("File sharing."),
+    },
+    CheckMeta {
+        id: "services.lanmanworkstation",
+        name: "Workstation",
+        category: Category::Services,
+        sources: &[service("LanmanWorkstation")],
+        severity: 2,
+        docs: Some("Network connections."),
+    },
+    CheckMeta {
+        id: "services.netprofm",
+        name: "Network List Service",
+        category: Category::Services,
+        sources: &[service("netprofm")],
+        severity: 2,
+        docs: Some("Network identification."),
+    },
+    CheckMeta {
+        id: "services.dnscache",
+        name: "DNS Client",
+        category: Category::Services,
+        sources: &[service("Dnscache")],
+        severity: 2,
+        docs: Some("DNS resolution."),
+    },
+    CheckMeta {
+        id: "security.windows_defender_antivirus",
+        name: "Windows Defender Antivirus",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.real_time_protection",
+        name: "Real-Time Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.cloud_delivered_protection",
+        name: "Cloud-Delivered Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.automatic_sample_submission",
+        name: "Automatic Sample Submission",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.tamper_protection",
+        name: "Tamper Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.vbs",
+        name: "VBS (Virtualization-Based Security)",
+        category: Category::Security,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
+            "EnableVirtualizationBasedSecurity",
+        )],
+        severity: 2,
+        docs: Some("Provides security but reduces performance."),
+    },
+    CheckMeta {
+        id: "security.core_isolation",
+        name: "Core Isolation (HVCI)",
+        category: Category::Security,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
+            "Enabled",
+        )],
+        severity: 2,
+        docs: Some("Memory integrity protection."),
+    },
+    CheckMeta {
+        id: "security.credential_guard",
+        name: "Credential Guard",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: Some("Enterprise security feature."),
+    },
+    CheckMeta {
+        id: "security.defender_status",
+        name: "Windows Defender",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.firewall_status",
+        name: "Windows Firewall",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.secure_boot",
+        name: "Secure Boot",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.tpm_status",
+        name: "TPM (Trusted Platform Module)",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.exploit_protection",
+        name: "Exploit Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.controlled_folder_access",
+        name: "Controlled Folder Access",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: Some("Ransomware protection. Can cause compatibility issues."),
+    },
+    CheckMeta {
+        id: "security.ransomware_protection",
+        name: "Ransomware Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.smartscreen",
+        name: "SmartScreen",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.windows_update",
+        name: "Windows Update",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.user_account_control",
+        name: "User Account Control (UAC)",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.bitlocker",
+        name: "BitLocker",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "security.network_protection",
+        name: "Network Protection",
+        category: Category::Security,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "platform.windows_version",
+        name: "Windows Version",
+        category: Category::Platform,
+        sources: &[wmi("Win32_OperatingSystem", "Caption")],
+        severity: 2,
+        docs: Some("Operating system name as reported by Win32_OperatingSystem."),
+    },
+    CheckMeta {
+        id: "platform.windows_build",
+        name: "Windows Build",
+        category: Category::Platform,
+        sources: &[wmi("Win32_OperatingSystem", "BuildNumber")],
+        severity: 2,
+        docs: Some("OS build number; several checks only apply from a given build."),
+    },
+    CheckMeta {
+        id: "platform.windows_edition",
+        name: "Windows Edition",
+        category: Category::Platform,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+            "EditionID",
+        )],
+        severity: 2,
+        docs: Some("Edition ID. Home lacks Group Policy and some security features."),
+    },
+    CheckMeta {
+        id: "platform.install_date",
+        name: "Windows Install Date",
+        category: Category::Platform,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+            "InstallTime",
+        )],
+        severity: 2,
+        docs: Some("When Windows was installed or last feature-upgraded, from the InstallTime FILETIME."),
+    },
+    CheckMeta {
+        id: "platform.system_type",
+        name: "System Architecture",
+        category: Category::Platform,
+        sources: &[wmi("Win32_OperatingSystem", "OSArchitecture")],
+        severity: 2,
+        docs: Some("32- or 64-bit operating system."),
+    },
+    CheckMeta {
+        id: "platform.bios_mode",
+        name: "BIOS Mode",
+        category: Category::Platform,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "platform.manufacturer",
+        name: "System Manufacturer",
+        category: Category::Platform,
+        sources: &[wmi("Win32_ComputerSystem", "Manufacturer")],
+        severity: 2,
+        docs: Some("System manufacturer from SMBIOS."),
+    },
+    CheckMeta {
+        id: "platform.model",
+        name: "System Model",
+        category: Category::Platform,
+        sources: &[wmi("Win32_ComputerSystem", "Model")],
+        severity: 2,
+        docs: Some("System model from SMBIOS."),
+    },
+    CheckMeta {
+        id: "platform.system_uptime",
+        name: "Last Boot Time",
+        category: Category::Platform,
+        sources: &[wmi("Win32_OperatingSystem", "LastBootUpTime")],
+        severity: 2,
+        docs: Some("Time since the last boot; long uptimes can hide pending updates and leaks."),
+    },
+    CheckMeta {
+        id: "thermal.cpu_temp",
+        name: "CPU Temperature",
+        category: Category::Thermal,
+        sources: &[wmi("Win32_TemperatureProbe", "CurrentReading")],
+        severity: 2,
+        docs: Some("Most systems require vendor-specific tools for accurate temps"),
+    },
+    CheckMeta {
+        id: "thermal.zone",
+        name: "Thermal Sensors",
+        category: Category::Thermal,
+        sources: &[wmi_class("Win32_TemperatureProbe")],
+        severity: 2,
+        docs: Some("Temperature probes exposed through WMI; many boards report none."),
+    },
+    CheckMeta {
+        id: "power.plan_guid",
+        name: "Power Plan GUID",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.active_power_scheme",
+        name: "Active Power Scheme",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.options",
+        name: "Power Options",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.processor_performance_state",
+        name: "Processor Performance State",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.device_power_states",
+        name: "Device Power States",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.pcie_aspm",
+        name: "PCIe ASPM (Active State Power Management)",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: Some("Disable for GPU/NVMe to prevent latency spikes."),
+    },
+    CheckMeta {
+        id: "power.usb_selective_suspend",
+        name: "USB Selective Suspend",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevents USB devices from entering power-saving mode."),
+    },
+    CheckMeta {
+        id: "power.fast_startup",
+        name: "Fast Startup (Hiberboot)",
+        category: Category::Power,
+        sources: &[reg(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Power",
+            "HiberbootEnabled",
+        )],
+        severity: 2,
+        docs: Some("Hybrid boot mode. Can cause issues, test both settings."),
+    },
+    CheckMeta {
+        id: "power.hybrid_sleep",
+        name: "Hybrid Sleep",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.hibernate",
+        name: "Hibernate",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.monitor_timeout",
+        name: "Monitor Timeout",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.disk_timeout",
+        name: "Hard Disk Timeout",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.sleep_timeout",
+        name: "Sleep Timeout",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.pci_link_state",
+        name: "PCI Express Link State Power Management",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.ahci_link_power",
+        name: "AHCI Link Power Management",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.wifi_power_saving",
+        name: "Wireless Adapter Power Saving",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.throttling",
+        name: "Power Throttling",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: Some("Windows 10+ feature to reduce background app power usage."),
+    },
+    CheckMeta {
+        id: "power.cpu_power_management",
+        name: "Processor Power Management",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.display_power_saving",
+        name: "Display Power Saving",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.hard_disk_power",
+        name: "Hard Disk Power Saving",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.sleep_state",
+        name: "Sleep State (S3 vs S0)",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.away_mode",
+        name: "Away Mode",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.display_brightness",
+        name: "Display Brightness",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.adaptive_brightness",
+        name: "Adaptive Brightness",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.video_playback_quality",
+        name: "Video Playback Quality Bias",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.battery_saver",
+        name: "Battery Saver",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.button_action",
+        name: "Power Button Action",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.sleep_button_action",
+        name: "Sleep Button Action",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.lid_close_action",
+        name: "Lid Close Action",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: None,
+    },
+    CheckMeta {
+        id: "power.wake_timers",
+        name: "Wake Timers",
+        category: Category::Power,
+        sources: &[],
+        severity: 2,
+        docs: Some("Prevents system from waking for scheduled tasks."),
+    },
+];
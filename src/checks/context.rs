@@ -0,0 +1,76 @@
+use super::registry::{LiveRegistry, RegistrySource};
+use super::rules::RuleSet;
+use super::wmi_source::{LiveWmi, WmiSource};
+use std::sync::Arc;
+
+/// Data sources shared by every check module during a run.
+#[derive(Clone)]
+pub struct CheckContext {
+    registry: Arc<dyn RegistrySource>,
+    wmi: Arc<dyn WmiSource>,
+    rules: Arc<RuleSet>,
+    /// The registry is an offline image rather than this machine's.
+    offline: bool,
+}
+
+impl Default for CheckContext {
+    fn default() -> Self {
+        Self::live()
+    }
+}
+
+impl CheckContext {
+    /// Reads from the machine the auditor is running on.
+    pub fn live() -> Self {
+        Self {
+            registry: Arc::new(LiveRegistry),
+            wmi: Arc::new(LiveWmi),
+            rules: Arc::new(RuleSet::new()),
+            offline: false,
+        }
+    }
+
+    pub fn with_registry(mut self, registry: Arc<dyn RegistrySource>) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Marks the registry as an offline image. Live-only sources such as
+    /// the service manager describe the host, so checks skip them.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn registry(&self) -> &dyn RegistrySource {
+        self.registry.as_ref()
+    }
+
+    /// A handle to the registry source, for wrapping it in another source.
+    pub fn shared_registry(&self) -> Arc<dyn RegistrySource> {
+        self.registry.clone()
+    }
+
+    pub fn with_wmi(mut self, wmi: Arc<dyn WmiSource>) -> Self {
+        self.wmi = wmi;
+        self
+    }
+
+    pub fn wmi(&self) -> &dyn WmiSource {
+        self.wmi.as_ref()
+    }
+
+    /// User-defined rules evaluated alongside the built-in checks.
+    pub fn with_rules(mut self, rules: Arc<RuleSet>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        self.rules.as_ref()
+    }
+}
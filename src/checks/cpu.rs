@@ -1,3 +1,4 @@
+use super::context::CheckContext;
 use super::utils::*;
 use super::wmi_helper::*;
 use crate::types::*;
@@ -9,27 +10,27 @@ const CORE_PARKING_GUID: &str = r"\0cc5b647-c1df-4637-891a-dec35c318583";
 const BOOST_MODE_GUID: &str = r"\be337238-0d82-4146-a960-4f3749d470c7";
 const PROCESSOR_THROTTLE_GUID: &str = r"\893dee8e-2bef-41e0-89c6-b55d0929964c";
 
-pub fn run_cpu_checks() -> CategoryResults {
+pub fn run_cpu_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("CPU");
 
-    let checks: Vec<Check> = vec![
-        check_power_plan(),
-        check_cstates(),
-        check_core_parking(),
-        check_boost_mode(),
-        check_processor_throttle(),
-        check_vbs_status(),
-        check_hvci_status(),
-        check_spectre_meltdown(),
-        check_heterogeneous_scheduler(),
-        check_smt_status(),
+    let checks: Vec<Check> = timed![
+        check_power_plan(ctx),
+        check_cstates(ctx),
+        check_core_parking(ctx),
+        check_boost_mode(ctx),
+        check_processor_throttle(ctx),
+        check_vbs_status(ctx),
+        check_hvci_status(ctx),
+        check_spectre_meltdown(ctx),
+        check_heterogeneous_scheduler(ctx),
+        check_smt_status(ctx),
         check_speed_shift(),
         check_thread_director(),
-        check_processor_performance_boost(),
-        check_processor_performance_core_parking(),
+        check_processor_performance_boost(ctx),
+        check_processor_performance_core_parking(ctx),
         check_processor_performance_time_check(),
-        check_processor_performance_increase_threshold(),
-        check_processor_performance_decrease_threshold(),
+        check_processor_performance_increase_threshold(ctx),
+        check_processor_performance_decrease_threshold(ctx),
         check_processor_idle_demote_threshold(),
         check_processor_idle_promote_threshold(),
         check_processor_idle_state_max(),
@@ -43,14 +44,14 @@ pub fn run_cpu_checks() -> CategoryResults {
         check_cpu_priority_class(),
         check_processor_scheduling(),
         check_cpu_affinity_policy(),
-        check_cpu_architecture(),
-        check_cpu_name(),
-        check_cpu_cores(),
-        check_cpu_threads(),
-        check_l2_cache(),
-        check_l3_cache(),
-        check_cpu_base_clock(),
-        check_cpu_max_clock(),
+        check_cpu_architecture(ctx),
+        check_cpu_name(ctx),
+        check_cpu_cores(ctx),
+        check_cpu_threads(ctx),
+        check_l2_cache(ctx),
+        check_l3_cache(ctx),
+        check_cpu_base_clock(ctx),
+        check_cpu_max_clock(ctx),
     ]
     .into_par_iter()
     .collect();
@@ -62,12 +63,14 @@ pub fn run_cpu_checks() -> CategoryResults {
     results
 }
 
-fn check_power_plan() -> Check {
-    let g = read_registry_string(
+fn check_power_plan(ctx: &CheckContext) -> Check {
+    let (g, mistyped) = lookup_registry_string(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power\User\PowerSchemes",
         "ActivePowerScheme",
-    );
+    )
+    .split();
     let hp = g
         .as_ref()
         .map(|x| x.to_lowercase().contains("8c5e7fda"))
@@ -85,26 +88,32 @@ fn check_power_plan() -> Check {
             CheckStatus::Warning
         },
     )
+    .with_id("cpu.power_plan")
     .with_description("High Performance power plan provides best performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_cstates() -> Check {
-    let v = read_registry_dword(
+fn check_cstates(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Processor",
         "Capabilities",
-    );
+    )
+    .split();
     Check::new(
         "C-States",
         if v.is_some() { "Configured" } else { "Default" },
         CheckStatus::Info,
     )
+    .with_id("cpu.cstates")
     .with_description("CPU idle states. Disabling can reduce latency but increase power usage.")
+    .with_mistyped(mistyped)
 }
 
-fn check_core_parking() -> Check {
+fn check_core_parking(ctx: &CheckContext) -> Check {
     let path = format!("{}{}", POWER_SETTINGS_BASE, CORE_PARKING_GUID);
-    let v = read_registry_dword(HKEY_LOCAL_MACHINE, &path, "ValueMax");
+    let (v, mistyped) = lookup_registry_dword(ctx, HKEY_LOCAL_MACHINE, &path, "ValueMax").split();
     Check::new(
         "Core Parking",
         if v == Some(0) { "Disabled" } else { "Enabled" },
@@ -114,12 +123,20 @@ fn check_core_parking() -> Check {
             CheckStatus::Warning
         },
     )
+    .with_id("cpu.core_parking")
+    .with_remediation(Remediation::power_setting(
+        "SUB_PROCESSOR",
+        "CPMINCORES",
+        100,
+        100,
+    ))
     .with_description("Disabling core parking keeps all CPU cores active.")
+    .with_mistyped(mistyped)
 }
 
-fn check_boost_mode() -> Check {
+fn check_boost_mode(ctx: &CheckContext) -> Check {
     let path = format!("{}{}", POWER_SETTINGS_BASE, BOOST_MODE_GUID);
-    let v = read_registry_dword(HKEY_LOCAL_MACHINE, &path, "ValueMax");
+    let (v, mistyped) = lookup_registry_dword(ctx, HKEY_LOCAL_MACHINE, &path, "ValueMax").split();
     let st = if v == Some(1) || v.is_none() {
         CheckStatus::Optimal
     } else {
@@ -130,13 +147,24 @@ fn check_boost_mode() -> Check {
         if v == Some(1) { "Enabled" } else { "Disabled" },
         st,
     )
+    .with_id("cpu.boost_mode")
+    .with_remediation(Remediation::power_setting(
+        "SUB_PROCESSOR",
+        "PERFBOOSTMODE",
+        1,
+        1,
+    ))
     .with_description("CPU turbo boost for higher performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_processor_throttle() -> Check {
+fn check_processor_throttle(ctx: &CheckContext) -> Check {
     let path = format!("{}{}", POWER_SETTINGS_BASE, PROCESSOR_THROTTLE_GUID);
-    let min = read_registry_dword(HKEY_LOCAL_MACHINE, &path, "ValueMin").unwrap_or(5);
-    let max = read_registry_dword(HKEY_LOCAL_MACHINE, &path, "ValueMax").unwrap_or(100);
+    let (min, min_mistyped) =
+        lookup_registry_dword(ctx, HKEY_LOCAL_MACHINE, &path, "ValueMin").split();
+    let (max, max_mistyped) =
+        lookup_registry_dword(ctx, HKEY_LOCAL_MACHINE, &path, "ValueMax").split();
+    let (min, max) = (min.unwrap_or(5), max.unwrap_or(100));
     let st = if min >= 100 && max >= 100 {
         CheckStatus::Optimal
     } else {
@@ -147,15 +175,25 @@ fn check_processor_throttle() -> Check {
         &format!("Min: {}%, Max: {}%", min, max),
         st,
     )
+    .with_id("cpu.processor_throttle")
+    .with_remediation(Remediation::power_setting(
+        "SUB_PROCESSOR",
+        "PROCTHROTTLEMIN",
+        100,
+        100,
+    ))
     .with_description("CPU frequency limits. 100% is optimal for performance.")
+    .with_mistyped(min_mistyped.or(max_mistyped))
 }
 
-fn check_vbs_status() -> Check {
-    let vbs_enabled = read_registry_dword(
+fn check_vbs_status(ctx: &CheckContext) -> Check {
+    let (vbs_enabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
         "EnableVirtualizationBasedSecurity",
-    );
+    )
+    .split();
 
     let status = if vbs_enabled == Some(1) {
         CheckStatus::Warning
@@ -172,15 +210,25 @@ fn check_vbs_status() -> Check {
         },
         status,
     )
+    .with_id("cpu.vbs_status")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
+        "EnableVirtualizationBasedSecurity",
+        0,
+    ))
     .with_description("VBS can reduce performance. Disable if not needed.")
+    .with_mistyped(mistyped)
 }
 
-fn check_hvci_status() -> Check {
-    let hvci_enabled = read_registry_dword(
+fn check_hvci_status(ctx: &CheckContext) -> Check {
+    let (hvci_enabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
         "Enabled",
-    );
+    )
+    .split();
 
     let status = if hvci_enabled == Some(1) {
         CheckStatus::Warning
@@ -197,15 +245,25 @@ fn check_hvci_status() -> Check {
         },
         status,
     )
+    .with_id("cpu.hvci_status")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
+        "Enabled",
+        0,
+    ))
     .with_description("HVCI adds CPU overhead. Disable for better performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_spectre_meltdown() -> Check {
-    let mitigations = read_registry_dword(
+fn check_spectre_meltdown(ctx: &CheckContext) -> Check {
+    let (mitigations, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "FeatureSettingsOverride",
-    );
+    )
+    .split();
 
     Check::new(
         "CPU Vulnerability Mitigations",
@@ -216,15 +274,19 @@ fn check_spectre_meltdown() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("cpu.spectre_meltdown")
     .with_description("Spectre/Meltdown mitigations. Can be disabled for performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_heterogeneous_scheduler() -> Check {
-    let het_policy = read_registry_dword(
+fn check_heterogeneous_scheduler(ctx: &CheckContext) -> Check {
+    let (het_policy, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "HeteroSchedulerPolicy",
-    );
+    )
+    .split();
 
     Check::new(
         "Heterogeneous Scheduler",
@@ -235,12 +297,20 @@ fn check_heterogeneous_scheduler() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("cpu.heterogeneous_scheduler")
     .with_description("Intel 12th gen+ hybrid architecture scheduler.")
+    .with_mistyped(mistyped)
 }
 
-fn check_smt_status() -> Check {
-    let logical = query_wmi_u32("Win32_Processor", "NumberOfLogicalProcessors").unwrap_or(0);
-    let cores = query_wmi_u32("Win32_Processor", "NumberOfCores").unwrap_or(0);
+fn check_smt_status(ctx: &CheckContext) -> Check {
+    let (logical, failed) = split_failure(query_wmi_u32(
+        ctx,
+        "Win32_Processor",
+        "NumberOfLogicalProcessors",
+    ));
+    let (cores, cores_failed) =
+        split_failure(query_wmi_u32(ctx, "Win32_Processor", "NumberOfCores"));
+    let (logical, cores) = (logical.unwrap_or(0), cores.unwrap_or(0));
 
     let status = if logical > cores && cores > 0 {
         "Enabled"
@@ -251,46 +321,56 @@ fn check_smt_status() -> Check {
     };
 
     Check::new("SMT/Hyperthreading", status, CheckStatus::Info)
+        .with_id("cpu.smt_status")
         .with_description("Simultaneous Multi-Threading detection.")
+        .with_error(failed.or(cores_failed))
 }
 
 fn check_speed_shift() -> Check {
     Check::new("Intel Speed Shift (HWP)", "Supported", CheckStatus::Info)
+        .with_id("cpu.speed_shift")
         .with_description("Hardware-Controlled Performance States (Intel 6th gen+).")
 }
 
 fn check_thread_director() -> Check {
     Check::new("Thread Director", "Not Applicable", CheckStatus::Info)
+        .with_id("cpu.thread_director")
         .with_description("Intel 12th gen+ thread scheduling optimization.")
 }
 
-fn check_processor_performance_boost() -> Check {
-    let boost_policy = read_registry_dword(
+fn check_processor_performance_boost(ctx: &CheckContext) -> Check {
+    let (boost_policy, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\be337238-0d82-4146-a960-4f3749d470c7",
         "DefaultPowerSchemeValues",
-    );
+    ).split();
 
     Check::new(
         "Processor Performance Boost Policy",
         &format!("{:?}", boost_policy.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_performance_boost")
+    .with_mistyped(mistyped)
 }
 
-fn check_processor_performance_core_parking() -> Check {
-    let parking_min = read_registry_dword(
+fn check_processor_performance_core_parking(ctx: &CheckContext) -> Check {
+    let (parking_min, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\0cc5b647-c1df-4637-891a-dec35c318583",
         "ValueMin",
-    );
+    ).split();
 
     Check::new(
         "Core Parking Min Cores",
         &format!("{}%", parking_min.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_performance_core_parking")
     .with_description("Minimum percentage of cores to keep unparked.")
+    .with_mistyped(mistyped)
 }
 
 fn check_processor_performance_time_check() -> Check {
@@ -299,41 +379,49 @@ fn check_processor_performance_time_check() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_performance_time_check")
     .with_description("Interval for performance state evaluation.")
 }
 
-fn check_processor_performance_increase_threshold() -> Check {
-    let threshold = read_registry_dword(
+fn check_processor_performance_increase_threshold(ctx: &CheckContext) -> Check {
+    let (threshold, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\06cadf0e-64ed-448a-8927-ce7bf90eb35d",
         "ValueMax",
-    );
+    ).split();
 
     Check::new(
         "Performance Increase Threshold",
         &format!("{}%", threshold.unwrap_or(60)),
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_performance_increase_threshold")
     .with_description("CPU load threshold to increase performance state.")
+    .with_mistyped(mistyped)
 }
 
-fn check_processor_performance_decrease_threshold() -> Check {
-    let threshold = read_registry_dword(
+fn check_processor_performance_decrease_threshold(ctx: &CheckContext) -> Check {
+    let (threshold, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power\PowerSettings\54533251-82be-4824-96c1-47b60b740d00\12a0ab44-fe28-4fa9-b3bd-4b64f44960a6",
         "ValueMax",
-    );
+    ).split();
 
     Check::new(
         "Performance Decrease Threshold",
         &format!("{}%", threshold.unwrap_or(40)),
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_performance_decrease_threshold")
     .with_description("CPU load threshold to decrease performance state.")
+    .with_mistyped(mistyped)
 }
 
 fn check_processor_idle_demote_threshold() -> Check {
     Check::new("Idle Demote Threshold", "System Default", CheckStatus::Info)
+        .with_id("cpu.processor_idle_demote_threshold")
         .with_description("Threshold to demote CPU to deeper idle state.")
 }
 
@@ -343,11 +431,13 @@ fn check_processor_idle_promote_threshold() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_idle_promote_threshold")
     .with_description("Threshold to promote CPU to shallower idle state.")
 }
 
 fn check_processor_idle_state_max() -> Check {
     Check::new("Maximum Processor Idle State", "C2", CheckStatus::Info)
+        .with_id("cpu.processor_idle_state_max")
         .with_description("Deepest C-state allowed. C0/C1 for lowest latency.")
 }
 
@@ -357,11 +447,13 @@ fn check_processor_idle_time_check() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_idle_time_check")
     .with_description("How often idle state is evaluated.")
 }
 
 fn check_processor_latency_hint_min() -> Check {
     Check::new("Latency Hint Minimum", "System Default", CheckStatus::Info)
+        .with_id("cpu.processor_latency_hint_min")
         .with_description("Minimum latency hint for processor.")
 }
 
@@ -371,53 +463,65 @@ fn check_processor_latency_hint_perf() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("cpu.processor_latency_hint_perf")
     .with_description("Performance vs. latency trade-off hint.")
 }
 
 fn check_processor_allow_throttling() -> Check {
     Check::new("Allow Processor Throttling", "Enabled", CheckStatus::Info)
+        .with_id("cpu.processor_allow_throttling")
         .with_description("Allows thermal throttling when needed.")
 }
 
 fn check_processor_duty_cycling() -> Check {
     Check::new("Processor Duty Cycling", "Disabled", CheckStatus::Info)
+        .with_id("cpu.processor_duty_cycling")
         .with_description("Active cooling vs. passive cooling policy.")
 }
 
 fn check_intel_turbo_boost() -> Check {
     Check::new("Intel Turbo Boost", "System Detected", CheckStatus::Info)
+        .with_id("cpu.intel_turbo_boost")
         .with_description("Intel Turbo Boost Technology status.")
 }
 
 fn check_amd_turbo_core() -> Check {
     Check::new("AMD Turbo Core", "System Detected", CheckStatus::Info)
+        .with_id("cpu.amd_turbo_core")
         .with_description("AMD Turbo Core Technology status.")
 }
 
 fn check_cpu_priority_class() -> Check {
     Check::new("Default CPU Priority Class", "Normal", CheckStatus::Info)
+        .with_id("cpu.priority_class")
         .with_description("Base priority class for processes.")
 }
 
 fn check_processor_scheduling() -> Check {
     Check::new("Processor Scheduling", "Programs", CheckStatus::Info)
+        .with_id("cpu.processor_scheduling")
         .with_description("Optimize for programs or background services.")
 }
 
 fn check_cpu_affinity_policy() -> Check {
     Check::new("CPU Affinity Policy", "System Managed", CheckStatus::Info)
+        .with_id("cpu.affinity_policy")
         .with_description("Default CPU core assignment policy.")
 }
 
-fn check_cpu_name() -> Check {
-    let name = query_cpu_info()
+fn check_cpu_name(ctx: &CheckContext) -> Check {
+    let (name, failed) = split_failure(query_cpu_info(ctx));
+    let name = name
         .map(|info| info.name.trim().to_string())
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("CPU Name", &name, CheckStatus::Info)
+        .with_id("cpu.name")
+        .with_error(failed)
 }
 
-fn check_cpu_architecture() -> Check {
-    let arch = query_cpu_info()
+fn check_cpu_architecture(ctx: &CheckContext) -> Check {
+    let (arch, failed) = split_failure(query_cpu_info(ctx));
+    let arch = arch
         .map(|info| match info.architecture {
             0 => "x86",
             1 => "MIPS",
@@ -429,24 +533,33 @@ fn check_cpu_architecture() -> Check {
         })
         .unwrap_or_else(|| "Unknown");
     Check::new("CPU Architecture", arch, CheckStatus::Info)
+        .with_id("cpu.architecture")
+        .with_error(failed)
 }
 
-fn check_cpu_cores() -> Check {
-    let cores = query_cpu_info()
+fn check_cpu_cores(ctx: &CheckContext) -> Check {
+    let (cores, failed) = split_failure(query_cpu_info(ctx));
+    let cores = cores
         .map(|info| format!("{} cores", info.cores))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("CPU Cores", &cores, CheckStatus::Info)
+        .with_id("cpu.cores")
+        .with_error(failed)
 }
 
-fn check_cpu_threads() -> Check {
-    let threads = query_cpu_info()
+fn check_cpu_threads(ctx: &CheckContext) -> Check {
+    let (threads, failed) = split_failure(query_cpu_info(ctx));
+    let threads = threads
         .map(|info| format!("{} threads", info.logical))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("CPU Threads", &threads, CheckStatus::Info)
+        .with_id("cpu.threads")
+        .with_error(failed)
 }
 
-fn check_l2_cache() -> Check {
-    let cache = query_cpu_info()
+fn check_l2_cache(ctx: &CheckContext) -> Check {
+    let (cache, failed) = split_failure(query_cpu_info(ctx));
+    let cache = cache
         .map(|info| {
             if info.l2_cache > 0 {
                 format!("{} KB", info.l2_cache)
@@ -456,10 +569,13 @@ fn check_l2_cache() -> Check {
         })
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("L2 Cache", &cache, CheckStatus::Info)
+        .with_id("cpu.l2_cache")
+        .with_error(failed)
 }
 
-fn check_l3_cache() -> Check {
-    let cache = query_cpu_info()
+fn check_l3_cache(ctx: &CheckContext) -> Check {
+    let (cache, failed) = split_failure(query_cpu_info(ctx));
+    let cache = cache
         .map(|info| {
             if info.l3_cache > 0 {
                 format!("{} KB", info.l3_cache)
@@ -469,18 +585,26 @@ fn check_l3_cache() -> Check {
         })
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("L3 Cache", &cache, CheckStatus::Info)
+        .with_id("cpu.l3_cache")
+        .with_error(failed)
 }
 
-fn check_cpu_base_clock() -> Check {
-    let clock = query_cpu_info()
+fn check_cpu_base_clock(ctx: &CheckContext) -> Check {
+    let (clock, failed) = split_failure(query_cpu_info(ctx));
+    let clock = clock
         .map(|info| format!("{} MHz", info.current_clock))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("CPU Current Clock", &clock, CheckStatus::Info)
+        .with_id("cpu.base_clock")
+        .with_error(failed)
 }
 
-fn check_cpu_max_clock() -> Check {
-    let clock = query_cpu_info()
+fn check_cpu_max_clock(ctx: &CheckContext) -> Check {
+    let (clock, failed) = split_failure(query_cpu_info(ctx));
+    let clock = clock
         .map(|info| format!("{} MHz", info.max_clock))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("CPU Max Clock", &clock, CheckStatus::Info)
+        .with_id("cpu.max_clock")
+        .with_error(failed)
 }
@@ -1,20 +1,21 @@
+use super::context::CheckContext;
 use super::gpu_helper::*;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_gpu_checks() -> CategoryResults {
+pub fn run_gpu_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("GPU");
 
-    let checks: Vec<Check> = vec![
-        check_hags(),
-        check_tdr_level(),
-        check_tdr_delay(),
-        check_game_dvr(),
-        check_game_bar(),
-        check_mpo(),
+    let checks: Vec<Check> = timed![
+        check_hags(ctx),
+        check_tdr_level(ctx),
+        check_tdr_delay(ctx),
+        check_game_dvr(ctx),
+        check_game_bar(ctx),
+        check_mpo(ctx),
         check_fullscreen_optimizations(),
-        check_nvidia_scheduling(),
+        check_nvidia_scheduling(ctx),
         check_nvidia_prerendered_frames(),
         check_nvidia_low_latency(),
         check_nvidia_power_management(),
@@ -22,7 +23,7 @@ pub fn run_gpu_checks() -> CategoryResults {
         check_amd_chill(),
         check_rebar_status(),
         check_gpu_preemption(),
-        check_shader_cache(),
+        check_shader_cache(ctx),
         check_wddm_version(),
         check_dx12_ultimate(),
         check_ray_tracing(),
@@ -44,18 +45,22 @@ pub fn run_gpu_checks() -> CategoryResults {
         check_amd_fsr_support(),
         check_gpu_model(),
         check_gpu_vram(),
-        Check::new("GPU Driver Version", "Detected", CheckStatus::Info),
-        Check::new("GPU Clock Speed", "Normal", CheckStatus::Optimal),
-        Check::new("GPU Memory Clock", "Normal", CheckStatus::Optimal),
-        Check::new("GPU Fan Speed", "Automatic", CheckStatus::Info),
-        Check::new("GPU Power Limit", "Default", CheckStatus::Info),
-        Check::new("GPU Utilization", "Low at Idle", CheckStatus::Optimal),
-        Check::new("VRAM Usage", "Available", CheckStatus::Optimal),
-        Check::new("GPU Core Count", "Detected", CheckStatus::Info),
-        Check::new("GPU Boost Clock", "Active", CheckStatus::Optimal),
-        Check::new("GPU Memory Bandwidth", "Optimal", CheckStatus::Optimal),
-        Check::new("GPU PCIe Link Speed", "x16 Gen4", CheckStatus::Optimal),
-        Check::new("GPU TDP", "Within Limits", CheckStatus::Optimal),
+        Check::new("GPU Driver Version", "Detected", CheckStatus::Info)
+            .with_id("gpu.driver_version"),
+        Check::new("GPU Clock Speed", "Normal", CheckStatus::Optimal).with_id("gpu.clock_speed"),
+        Check::new("GPU Memory Clock", "Normal", CheckStatus::Optimal).with_id("gpu.memory_clock"),
+        Check::new("GPU Fan Speed", "Automatic", CheckStatus::Info).with_id("gpu.fan_speed"),
+        Check::new("GPU Power Limit", "Default", CheckStatus::Info).with_id("gpu.power_limit"),
+        Check::new("GPU Utilization", "Low at Idle", CheckStatus::Optimal)
+            .with_id("gpu.utilization"),
+        Check::new("VRAM Usage", "Available", CheckStatus::Optimal).with_id("gpu.vram_usage"),
+        Check::new("GPU Core Count", "Detected", CheckStatus::Info).with_id("gpu.core_count"),
+        Check::new("GPU Boost Clock", "Active", CheckStatus::Optimal).with_id("gpu.boost_clock"),
+        Check::new("GPU Memory Bandwidth", "Optimal", CheckStatus::Optimal)
+            .with_id("gpu.memory_bandwidth"),
+        Check::new("GPU PCIe Link Speed", "x16 Gen4", CheckStatus::Optimal)
+            .with_id("gpu.pcie_link_speed"),
+        Check::new("GPU TDP", "Within Limits", CheckStatus::Optimal).with_id("gpu.tdp"),
     ]
     .into_par_iter()
     .collect();
@@ -71,22 +76,24 @@ fn check_gpu_model() -> Check {
     let model = get_gpu_info()
         .map(|(name, _)| name)
         .unwrap_or_else(|| "Unknown".to_string());
-    Check::new("GPU Model", &model, CheckStatus::Info)
+    Check::new("GPU Model", &model, CheckStatus::Info).with_id("gpu.model")
 }
 
 fn check_gpu_vram() -> Check {
     let vram = get_gpu_info()
         .map(|(_, vram)| format!("{} MB", vram / 1024 / 1024))
         .unwrap_or_else(|| "Unknown".to_string());
-    Check::new("GPU VRAM", &vram, CheckStatus::Info)
+    Check::new("GPU VRAM", &vram, CheckStatus::Info).with_id("gpu.vram")
 }
 
-fn check_hags() -> Check {
-    let hags = read_registry_dword(
+fn check_hags(ctx: &CheckContext) -> Check {
+    let (hags, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
         "HwSchMode",
-    );
+    )
+    .split();
 
     let status = if hags == Some(2) {
         CheckStatus::Optimal
@@ -103,42 +110,60 @@ fn check_hags() -> Check {
         },
         status,
     )
+    .with_id("gpu.hags")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+        "HwSchMode",
+        2,
+    ))
     .with_description("HAGS reduces GPU latency on modern GPUs (GTX 1000+, RX 5000+).")
+    .with_mistyped(mistyped)
 }
 
-fn check_tdr_level() -> Check {
-    let tdr = read_registry_dword(
+fn check_tdr_level(ctx: &CheckContext) -> Check {
+    let (tdr, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
         "TdrLevel",
-    );
+    )
+    .split();
 
     Check::new(
         "TDR Level",
         &format!("{}", tdr.unwrap_or(3)),
         CheckStatus::Info,
     )
+    .with_id("gpu.tdr_level")
     .with_description("Timeout Detection and Recovery. 0=disabled (risky), 3=full recovery.")
+    .with_mistyped(mistyped)
 }
 
-fn check_tdr_delay() -> Check {
-    let delay = read_registry_dword(
+fn check_tdr_delay(ctx: &CheckContext) -> Check {
+    let (delay, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
         "TdrDelay",
     )
-    .unwrap_or(2);
+    .split();
+    let delay = delay.unwrap_or(2);
 
     Check::new("TDR Delay", &format!("{}s", delay), CheckStatus::Info)
+        .with_id("gpu.tdr_delay")
         .with_description("GPU timeout threshold. Default is 2 seconds.")
+        .with_mistyped(mistyped)
 }
 
-fn check_game_dvr() -> Check {
-    let dvr_disabled = read_registry_dword(
+fn check_game_dvr(ctx: &CheckContext) -> Check {
+    let (dvr_disabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_CURRENT_USER,
         r"System\GameConfigStore",
         "GameDVR_Enabled",
-    );
+    )
+    .split();
 
     let status = if dvr_disabled == Some(0) {
         CheckStatus::Optimal
@@ -155,15 +180,25 @@ fn check_game_dvr() -> Check {
         },
         status,
     )
+    .with_id("gpu.game_dvr")
+    .with_remediation(Remediation::set_dword(
+        HKEY_CURRENT_USER,
+        r"System\GameConfigStore",
+        "GameDVR_Enabled",
+        0,
+    ))
     .with_description("Game DVR can reduce performance. Disable for gaming.")
+    .with_mistyped(mistyped)
 }
 
-fn check_game_bar() -> Check {
-    let gamebar = read_registry_dword(
+fn check_game_bar(ctx: &CheckContext) -> Check {
+    let (gamebar, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_CURRENT_USER,
         r"SOFTWARE\Microsoft\Windows\CurrentVersion\GameDVR",
         "AppCaptureEnabled",
-    );
+    )
+    .split();
 
     let status = if gamebar == Some(0) {
         CheckStatus::Optimal
@@ -180,15 +215,25 @@ fn check_game_bar() -> Check {
         },
         status,
     )
+    .with_id("gpu.game_bar")
+    .with_remediation(Remediation::set_dword(
+        HKEY_CURRENT_USER,
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\GameDVR",
+        "AppCaptureEnabled",
+        0,
+    ))
     .with_description("Game Bar overlay can impact performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_mpo() -> Check {
-    let mpo = read_registry_dword(
+fn check_mpo(ctx: &CheckContext) -> Check {
+    let (mpo, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows\Dwm",
         "OverlayTestMode",
-    );
+    )
+    .split();
 
     Check::new(
         "MPO (Multi-Plane Overlay)",
@@ -199,7 +244,9 @@ fn check_mpo() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("gpu.mpo")
     .with_description("MPO can cause issues in some games. Test both settings.")
+    .with_mistyped(mistyped)
 }
 
 fn check_fullscreen_optimizations() -> Check {
@@ -208,15 +255,18 @@ fn check_fullscreen_optimizations() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("gpu.fullscreen_optimizations")
     .with_description("Can be disabled per-application for compatibility.")
 }
 
-fn check_nvidia_scheduling() -> Check {
-    let scheduling = read_registry_dword(
+fn check_nvidia_scheduling(ctx: &CheckContext) -> Check {
+    let (scheduling, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers\Scheduler",
         "EnablePreemption",
-    );
+    )
+    .split();
 
     Check::new(
         "NVIDIA GPU Scheduling",
@@ -227,6 +277,8 @@ fn check_nvidia_scheduling() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("gpu.nvidia_scheduling")
+    .with_mistyped(mistyped)
 }
 
 fn check_nvidia_prerendered_frames() -> Check {
@@ -235,6 +287,7 @@ fn check_nvidia_prerendered_frames() -> Check {
         "Application Controlled",
         CheckStatus::Info,
     )
+    .with_id("gpu.nvidia_prerendered_frames")
     .with_description("Lower values (1) reduce latency, higher increase smoothness.")
 }
 
@@ -244,6 +297,7 @@ fn check_nvidia_low_latency() -> Check {
         "Driver Default",
         CheckStatus::Info,
     )
+    .with_id("gpu.nvidia_low_latency")
     .with_description("Set to 'Ultra' or 'On' in NVIDIA Control Panel for lowest latency.")
 }
 
@@ -253,21 +307,25 @@ fn check_nvidia_power_management() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("gpu.nvidia_power_management")
     .with_description("'Prefer Maximum Performance' gives best results for gaming.")
 }
 
 fn check_amd_anti_lag() -> Check {
     Check::new("AMD Anti-Lag", "Not Detected", CheckStatus::Info)
+        .with_id("gpu.amd_anti_lag")
         .with_description("AMD feature to reduce input lag. Enable in Radeon Software.")
 }
 
 fn check_amd_chill() -> Check {
     Check::new("AMD Chill", "Not Detected", CheckStatus::Info)
+        .with_id("gpu.amd_chill")
         .with_description("Power saving feature. Disable for consistent performance.")
 }
 
 fn check_rebar_status() -> Check {
     Check::new("Resizable BAR", "System Dependent", CheckStatus::Info)
+        .with_id("gpu.rebar_status")
         .with_description("ReBAR improves performance. Enable in BIOS if supported.")
 }
 
@@ -277,15 +335,18 @@ fn check_gpu_preemption() -> Check {
         "DMA Buffer",
         CheckStatus::Info,
     )
+    .with_id("gpu.preemption")
     .with_description("Finer preemption = better responsiveness.")
 }
 
-fn check_shader_cache() -> Check {
-    let cache = read_registry_dword(
+fn check_shader_cache(ctx: &CheckContext) -> Check {
+    let (cache, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
         "DisableShaderCache",
-    );
+    )
+    .split();
 
     let status = if cache == Some(0) || cache.is_none() {
         CheckStatus::Optimal
@@ -302,86 +363,104 @@ fn check_shader_cache() -> Check {
         },
         status,
     )
+    .with_id("gpu.shader_cache")
+    .with_remediation(Remediation::delete_value(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+        "DisableShaderCache",
+    ))
     .with_description("Shader cache improves load times.")
+    .with_mistyped(mistyped)
 }
 
 fn check_wddm_version() -> Check {
     Check::new("WDDM Version", "3.0+", CheckStatus::Info)
+        .with_id("gpu.wddm_version")
         .with_description("Windows Display Driver Model version.")
 }
 
 fn check_dx12_ultimate() -> Check {
     Check::new("DirectX 12 Ultimate", "Supported", CheckStatus::Info)
+        .with_id("gpu.dx12_ultimate")
         .with_description("Latest DirectX features support.")
 }
 
 fn check_ray_tracing() -> Check {
-    Check::new("Hardware Ray Tracing", "Available", CheckStatus::Info)
+    Check::new("Hardware Ray Tracing", "Available", CheckStatus::Info).with_id("gpu.ray_tracing")
 }
 
 fn check_variable_rate_shading() -> Check {
     Check::new("Variable Rate Shading", "Tier 2", CheckStatus::Info)
+        .with_id("gpu.variable_rate_shading")
 }
 
 fn check_mesh_shading() -> Check {
-    Check::new("Mesh Shading", "Supported", CheckStatus::Info)
+    Check::new("Mesh Shading", "Supported", CheckStatus::Info).with_id("gpu.mesh_shading")
 }
 
 fn check_sampler_feedback() -> Check {
-    Check::new("Sampler Feedback", "Supported", CheckStatus::Info)
+    Check::new("Sampler Feedback", "Supported", CheckStatus::Info).with_id("gpu.sampler_feedback")
 }
 
 fn check_gpu_acceleration() -> Check {
-    Check::new("Hardware Acceleration", "Enabled", CheckStatus::Optimal)
+    Check::new("Hardware Acceleration", "Enabled", CheckStatus::Optimal).with_id("gpu.acceleration")
 }
 
 fn check_gpu_memory_management() -> Check {
     Check::new("GPU Memory Management", "WDDM 3.0", CheckStatus::Info)
+        .with_id("gpu.memory_management")
 }
 
 fn check_gpu_compute_preemption() -> Check {
     Check::new("Compute Preemption", "Thread Level", CheckStatus::Info)
+        .with_id("gpu.compute_preemption")
 }
 
 fn check_gpu_scheduling_latency() -> Check {
     Check::new("GPU Scheduling Latency", "Optimized", CheckStatus::Info)
+        .with_id("gpu.scheduling_latency")
 }
 
 fn check_dx_diagnostics() -> Check {
     Check::new("DirectX Diagnostics", "No Issues", CheckStatus::Optimal)
+        .with_id("gpu.dx_diagnostics")
 }
 
 fn check_dxgi_flip_model() -> Check {
     Check::new("DXGI Flip Model", "Supported", CheckStatus::Info)
+        .with_id("gpu.dxgi_flip_model")
         .with_description("Modern presentation model for lower latency.")
 }
 
 fn check_present_mon_compatible() -> Check {
     Check::new("PresentMon Compatible", "Yes", CheckStatus::Info)
+        .with_id("gpu.present_mon_compatible")
 }
 
 fn check_gpu_priority_support() -> Check {
-    Check::new("GPU Priority API", "Supported", CheckStatus::Info)
+    Check::new("GPU Priority API", "Supported", CheckStatus::Info).with_id("gpu.priority_support")
 }
 
 fn check_async_compute() -> Check {
-    Check::new("Async Compute", "Supported", CheckStatus::Info)
+    Check::new("Async Compute", "Supported", CheckStatus::Info).with_id("gpu.async_compute")
 }
 
 fn check_multi_adapter() -> Check {
-    Check::new("Multi-Adapter Support", "Available", CheckStatus::Info)
+    Check::new("Multi-Adapter Support", "Available", CheckStatus::Info).with_id("gpu.multi_adapter")
 }
 
 fn check_vr_ready() -> Check {
-    Check::new("VR Ready", "System Dependent", CheckStatus::Info)
+    Check::new("VR Ready", "System Dependent", CheckStatus::Info).with_id("gpu.vr_ready")
 }
 
 fn check_nvidia_reflex() -> Check {
     Check::new("NVIDIA Reflex", "SDK Available", CheckStatus::Info)
+        .with_id("gpu.nvidia_reflex")
         .with_description("Low latency technology for supported games.")
 }
 
 fn check_amd_fsr_support() -> Check {
     Check::new("AMD FSR Support", "Available", CheckStatus::Info)
+        .with_id("gpu.amd_fsr_support")
         .with_description("FidelityFX Super Resolution upscaling.")
 }
@@ -1,38 +1,46 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_input_checks() -> CategoryResults {
+pub fn run_input_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Input");
 
     let checks: Vec<Check> = (0..25)
         .into_par_iter()
-        .map(|i| match i {
-            0 => check_mouse_acceleration(),
-            1 => check_pointer_precision(),
-            2 => check_mouse_speed(),
-            3 => check_mouse_threshold1(),
-            4 => check_mouse_threshold2(),
-            5 => check_keyboard_delay(),
-            6 => check_keyboard_speed(),
-            7 => check_hid_service(),
-            8 => check_tablet_input_service(),
-            9 => check_touch_keyboard_service(),
-            10 => check_mouse_trails(),
-            11 => check_snap_to_default(),
-            12 => check_mouse_sonar(),
-            13 => check_mouse_vanish(),
-            14 => check_pointer_shadow(),
-            15 => check_raw_input(),
-            16 => check_input_lag(),
-            17 => check_polling_rate(),
-            18 => check_usb_selective_suspend(),
-            19 => check_input_device_drivers(),
-            20 => Check::new("Keyboard Layout", "Detected", CheckStatus::Info),
-            21 => Check::new("Numlock State", "Configured", CheckStatus::Info),
-            22 => Check::new("Scroll Lock", "Disabled", CheckStatus::Info),
-            23 => Check::new("Input Language", "Configured", CheckStatus::Info),
-            _ => Check::new("Gamepad Support", "Available", CheckStatus::Info),
+        .map(|i| {
+            Check::timed(|| match i {
+                0 => check_mouse_acceleration(ctx),
+                1 => check_pointer_precision(ctx),
+                2 => check_mouse_speed(ctx),
+                3 => check_mouse_threshold1(ctx),
+                4 => check_mouse_threshold2(ctx),
+                5 => check_keyboard_delay(ctx),
+                6 => check_keyboard_speed(ctx),
+                7 => check_hid_service(),
+                8 => check_tablet_input_service(),
+                9 => check_touch_keyboard_service(),
+                10 => check_mouse_trails(),
+                11 => check_snap_to_default(),
+                12 => check_mouse_sonar(),
+                13 => check_mouse_vanish(),
+                14 => check_pointer_shadow(ctx),
+                15 => check_raw_input(),
+                16 => check_input_lag(),
+                17 => check_polling_rate(),
+                18 => check_usb_selective_suspend(),
+                19 => check_input_device_drivers(),
+                20 => Check::new("Keyboard Layout", "Detected", CheckStatus::Info)
+                    .with_id("input.keyboard_layout"),
+                21 => Check::new("Numlock State", "Configured", CheckStatus::Info)
+                    .with_id("input.numlock_state"),
+                22 => Check::new("Scroll Lock", "Disabled", CheckStatus::Info)
+                    .with_id("input.scroll_lock"),
+                23 => Check::new("Input Language", "Configured", CheckStatus::Info)
+                    .with_id("input.language"),
+                _ => Check::new("Gamepad Support", "Available", CheckStatus::Info)
+                    .with_id("input.gamepad_support"),
+            })
         })
         .collect();
 
@@ -43,9 +51,18 @@ pub fn run_input_checks() -> CategoryResults {
     results
 }
 
-fn check_mouse_acceleration() -> Check {
-    let accel =
-        read_registry_dword(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed").unwrap_or(1);
+/// MouseSpeed is a REG_SZ ("0", "1" or "2"); 0 turns acceleration off. A
+/// REG_DWORD is honoured too, any other type is reported.
+fn mouse_speed(ctx: &CheckContext) -> (u32, Option<Mistyped>) {
+    match lookup_registry_string(ctx, HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed") {
+        RegRead::Value(s) => (s.trim().parse().unwrap_or(1), None),
+        RegRead::TypeMismatch(RegValue::Dword(n)) => (n, None),
+        other => (1, other.split().1),
+    }
+}
+
+fn check_mouse_acceleration(ctx: &CheckContext) -> Check {
+    let (accel, mistyped) = mouse_speed(ctx);
 
     let status = if accel == 0 {
         CheckStatus::Optimal
@@ -58,12 +75,19 @@ fn check_mouse_acceleration() -> Check {
         if accel == 0 { "Disabled" } else { "Enabled" },
         status,
     )
+    .with_id("input.mouse_acceleration")
+    .with_remediation(Remediation::set_string(
+        HKEY_CURRENT_USER,
+        r"Control Panel\Mouse",
+        "MouseSpeed",
+        "0",
+    ))
     .with_description("Disable for precise aiming in FPS games.")
+    .with_mistyped(mistyped)
 }
 
-fn check_pointer_precision() -> Check {
-    let enhance =
-        read_registry_dword(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed").unwrap_or(1);
+fn check_pointer_precision(ctx: &CheckContext) -> Check {
+    let (enhance, mistyped) = mouse_speed(ctx);
 
     let status = if enhance == 0 {
         CheckStatus::Optimal
@@ -76,123 +100,182 @@ fn check_pointer_precision() -> Check {
         if enhance == 0 { "Disabled" } else { "Enabled" },
         status,
     )
+    .with_id("input.pointer_precision")
+    .with_remediation(Remediation::set_string(
+        HKEY_CURRENT_USER,
+        r"Control Panel\Mouse",
+        "MouseSpeed",
+        "0",
+    ))
     .with_description("Windows mouse acceleration. Disable for gaming.")
+    .with_mistyped(mistyped)
 }
 
-fn check_mouse_speed() -> Check {
-    let speed = read_registry_dword(
+fn check_mouse_speed(ctx: &CheckContext) -> Check {
+    let (speed, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_CURRENT_USER,
         r"Control Panel\Mouse",
         "MouseSensitivity",
     )
-    .unwrap_or(10);
+    .split();
+    let speed = speed.unwrap_or(10);
 
     Check::new("Mouse Speed", &format!("{}/20", speed), CheckStatus::Info)
+        .with_id("input.mouse_speed")
         .with_description("6/11 (10/20) is 1:1 ratio, recommended for gaming.")
+        .with_mistyped(mistyped)
 }
 
-fn check_mouse_threshold1() -> Check {
-    let threshold =
-        read_registry_string(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseThreshold1")
-            .unwrap_or_else(|| "0".to_string());
+fn check_mouse_threshold1(ctx: &CheckContext) -> Check {
+    let (threshold, mistyped) = lookup_registry_string(
+        ctx,
+        HKEY_CURRENT_USER,
+        r"Control Panel\Mouse",
+        "MouseThreshold1",
+    )
+    .split();
+    let threshold = threshold.unwrap_or_else(|| "0".to_string());
 
     Check::new(
         "Mouse Acceleration Threshold 1",
         &threshold,
         CheckStatus::Info,
     )
+    .with_id("input.mouse_threshold1")
+    .with_mistyped(mistyped)
 }
 
-fn check_mouse_threshold2() -> Check {
-    let threshold =
-        read_registry_string(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseThreshold2")
-            .unwrap_or_else(|| "0".to_string());
+fn check_mouse_threshold2(ctx: &CheckContext) -> Check {
+    let (threshold, mistyped) = lookup_registry_string(
+        ctx,
+        HKEY_CURRENT_USER,
+        r"Control Panel\Mouse",
+        "MouseThreshold2",
+    )
+    .split();
+    let threshold = threshold.unwrap_or_else(|| "0".to_string());
 
     Check::new(
         "Mouse Acceleration Threshold 2",
         &threshold,
         CheckStatus::Info,
     )
+    .with_id("input.mouse_threshold2")
+    .with_mistyped(mistyped)
 }
 
-fn check_keyboard_delay() -> Check {
-    let delay = read_registry_string(
+fn check_keyboard_delay(ctx: &CheckContext) -> Check {
+    let (delay, mistyped) = lookup_registry_string(
+        ctx,
         HKEY_CURRENT_USER,
         r"Control Panel\Keyboard",
         "KeyboardDelay",
     )
-    .unwrap_or_else(|| "1".to_string());
+    .split();
+    let delay = delay.unwrap_or_else(|| "1".to_string());
 
     Check::new("Keyboard Repeat Delay", &delay, CheckStatus::Info)
+        .with_id("input.keyboard_delay")
         .with_description("0 = shortest delay (250ms).")
+        .with_mistyped(mistyped)
 }
 
-fn check_keyboard_speed() -> Check {
-    let speed = read_registry_string(
+fn check_keyboard_speed(ctx: &CheckContext) -> Check {
+    let (speed, mistyped) = lookup_registry_string(
+        ctx,
         HKEY_CURRENT_USER,
         r"Control Panel\Keyboard",
         "KeyboardSpeed",
     )
-    .unwrap_or_else(|| "31".to_string());
+    .split();
+    let speed = speed.unwrap_or_else(|| "31".to_string());
 
     Check::new("Keyboard Repeat Rate", &speed, CheckStatus::Info)
+        .with_id("input.keyboard_speed")
         .with_description("31 = fastest repeat rate.")
+        .with_mistyped(mistyped)
 }
 
 fn check_hid_service() -> Check {
     Check::new("HID Service", "Running", CheckStatus::Optimal)
+        .with_id("input.hid_service")
         .with_description("Human Interface Device Access service.")
 }
 
 fn check_tablet_input_service() -> Check {
     Check::new("Tablet Input Service", "Disabled", CheckStatus::Info)
+        .with_id("input.tablet_input_service")
         .with_description("Can be disabled on non-touch systems.")
 }
 
 fn check_touch_keyboard_service() -> Check {
     Check::new("Touch Keyboard Service", "Disabled", CheckStatus::Info)
+        .with_id("input.touch_keyboard_service")
         .with_description("Can be disabled on non-touch systems.")
 }
 
 fn check_mouse_trails() -> Check {
-    Check::new("Mouse Trails", "Disabled", CheckStatus::Optimal)
+    Check::new("Mouse Trails", "Disabled", CheckStatus::Optimal).with_id("input.mouse_trails")
 }
 
 fn check_snap_to_default() -> Check {
     Check::new("Snap To Default Button", "Disabled", CheckStatus::Info)
+        .with_id("input.snap_to_default")
 }
 
 fn check_mouse_sonar() -> Check {
     Check::new("Mouse Sonar (Ctrl to Find)", "Disabled", CheckStatus::Info)
+        .with_id("input.mouse_sonar")
 }
 
 fn check_mouse_vanish() -> Check {
     Check::new("Hide Pointer While Typing", "Disabled", CheckStatus::Info)
+        .with_id("input.mouse_vanish")
 }
 
-fn check_pointer_shadow() -> Check {
-    Check::new("Pointer Shadow", "Enabled", CheckStatus::Info)
+/// Bit 13 of UserPreferencesMask is SPI_SETCURSORSHADOW.
+fn check_pointer_shadow(ctx: &CheckContext) -> Check {
+    let (mask, mistyped) = lookup_registry_binary(
+        ctx,
+        HKEY_CURRENT_USER,
+        r"Control Panel\Desktop",
+        "UserPreferencesMask",
+    )
+    .split();
+    let shadow = match mask.as_deref().and_then(|mask| mask.get(1)) {
+        Some(byte) if byte & 0x20 != 0 => "Enabled",
+        Some(_) => "Disabled",
+        None => "Default",
+    };
+    Check::new("Pointer Shadow", shadow, CheckStatus::Info)
+        .with_id("input.pointer_shadow")
+        .with_mistyped(mistyped)
 }
 
 fn check_raw_input() -> Check {
     Check::new("Raw Input API", "Available", CheckStatus::Optimal)
+        .with_id("input.raw_input")
         .with_description("Games should use Raw Input for best precision.")
 }
 
 fn check_input_lag() -> Check {
-    Check::new("Input Lag", "Minimal", CheckStatus::Optimal)
+    Check::new("Input Lag", "Minimal", CheckStatus::Optimal).with_id("input.lag")
 }
 
 fn check_polling_rate() -> Check {
     Check::new("Mouse Polling Rate", "1000 Hz", CheckStatus::Optimal)
+        .with_id("input.polling_rate")
         .with_description("Higher polling rate = lower input lag.")
 }
 
 fn check_usb_selective_suspend() -> Check {
     Check::new("USB Selective Suspend", "Disabled", CheckStatus::Optimal)
+        .with_id("input.usb_selective_suspend")
         .with_description("Prevents USB devices from entering power-saving mode.")
 }
 
 fn check_input_device_drivers() -> Check {
     Check::new("Input Device Drivers", "Up to Date", CheckStatus::Optimal)
+        .with_id("input.device_drivers")
 }
@@ -1,65 +1,76 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_latency_checks() -> CategoryResults {
+pub fn run_latency_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Latency");
 
-    let checks: Vec<Check> = vec![
-        check_hpet_status(),
-        check_tsc_sync_policy(),
-        check_dynamic_tick(),
-        check_system_responsiveness(),
-        check_network_throttling_index(),
-        check_win32_priority_separation(),
+    let checks: Vec<Check> = timed![
+        check_hpet_status(ctx),
+        check_tsc_sync_policy(ctx),
+        check_dynamic_tick(ctx),
+        check_system_responsiveness(ctx),
+        check_network_throttling_index(ctx),
+        check_win32_priority_separation(ctx),
         check_timer_resolution(),
-        check_interrupt_steering(),
-        check_message_signaled_interrupts(),
-        check_dpc_watchdog_period(),
-        check_dpc_timeout(),
-        check_idle_disable(),
-        check_distribute_timers(),
+        check_interrupt_steering(ctx),
+        check_message_signaled_interrupts(ctx),
+        check_dpc_watchdog_period(ctx),
+        check_dpc_timeout(ctx),
+        check_idle_disable(ctx),
+        check_distribute_timers(ctx),
         check_processor_idle_state_policy(),
-        check_latency_sensitive_hints(),
+        check_latency_sensitive_hints(ctx),
         check_cstate_latency(),
         check_platform_clock_source(),
         check_tsc_invariant(),
-        check_use_platform_clock(),
+        check_use_platform_clock(ctx),
         check_synthetic_timer(),
-        check_large_page_drivers(),
+        check_large_page_drivers(ctx),
         check_disable_dynamic_pstate(),
-        check_system_profile_tasks(),
+        check_system_profile_tasks(ctx),
         check_multimedia_system_profile(),
-        check_gpu_priority(),
-        check_scheduling_category(),
+        check_gpu_priority(ctx),
+        check_scheduling_category(ctx),
         check_system_responsiveness_override(),
         check_latency_tolerance(),
         check_interrupt_affinity_policy(),
         check_isr_time_limit(),
         check_dpc_queue_depth(),
         check_threaded_dpc(),
-        check_timer_coalescing(),
+        check_timer_coalescing(ctx),
         check_precision_platform_timer(),
         check_acpi_timer_disabled(),
-        Check::new("Quantum Length", "Default", CheckStatus::Info),
-        Check::new("Foreground Boost", "3", CheckStatus::Info),
-        Check::new("IRQ Priority", "System Default", CheckStatus::Info),
-        Check::new("DMA Channel Allocation", "Optimal", CheckStatus::Info),
-        Check::new("Memory Mapped I/O", "Enabled", CheckStatus::Optimal),
-        Check::new("Real-Time Priority Class", "Available", CheckStatus::Info),
+        Check::new("Quantum Length", "Default", CheckStatus::Info)
+            .with_id("latency.quantum_length"),
+        Check::new("Foreground Boost", "3", CheckStatus::Info).with_id("latency.foreground_boost"),
+        Check::new("IRQ Priority", "System Default", CheckStatus::Info)
+            .with_id("latency.irq_priority"),
+        Check::new("DMA Channel Allocation", "Optimal", CheckStatus::Info)
+            .with_id("latency.dma_channel_allocation"),
+        Check::new("Memory Mapped I/O", "Enabled", CheckStatus::Optimal)
+            .with_id("latency.memory_mapped_io"),
+        Check::new("Real-Time Priority Class", "Available", CheckStatus::Info)
+            .with_id("latency.real_time_priority_class"),
         Check::new(
             "High Priority Thread Support",
             "Enabled",
             CheckStatus::Optimal,
-        ),
-        Check::new("Low Latency Audio", "Supported", CheckStatus::Info),
+        )
+        .with_id("latency.high_priority_thread_support"),
+        Check::new("Low Latency Audio", "Supported", CheckStatus::Info)
+            .with_id("latency.low_latency_audio"),
         Check::new(
             "Kernel Dispatcher Priority",
             "Optimized",
             CheckStatus::Optimal,
-        ),
-        Check::new("Thread Switching Overhead", "Minimal", CheckStatus::Optimal),
-        Check::new("Context Switch Rate", "Normal", CheckStatus::Optimal),
+        )
+        .with_id("latency.kernel_dispatcher_priority"),
+        Check::new("Thread Switching Overhead", "Minimal", CheckStatus::Optimal)
+            .with_id("latency.thread_switching_overhead"),
+        Check::new("Context Switch Rate", "Normal", CheckStatus::Optimal)
+            .with_id("latency.context_switch_rate"),
     ]
     .into_par_iter()
     .collect();
@@ -71,14 +82,16 @@ pub fn run_latency_checks() -> CategoryResults {
     results
 }
 
-fn check_hpet_status() -> Check {
+fn check_hpet_status(ctx: &CheckContext) -> Check {
     // Check HPET (High Precision Event Timer) via BCD or registry
-    let hpet_enabled = read_registry_dword(
+    let (hpet_enabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\TimeProviders\TimerDevice",
         "TimerDevice",
     )
-    .is_some();
+    .split();
+    let hpet_enabled = hpet_enabled.is_some();
 
     let status = if hpet_enabled {
         CheckStatus::Warning
@@ -91,30 +104,43 @@ fn check_hpet_status() -> Check {
         if hpet_enabled { "Enabled" } else { "Disabled" },
         status,
     )
+    .with_id("latency.hpet_status")
+    .with_remediation(Remediation::delete_value(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\TimeProviders\TimerDevice",
+        "TimerDevice",
+    ))
     .with_description("HPET can add latency. Disabled is better for gaming/real-time.")
+    .with_mistyped(mistyped)
 }
 
-fn check_tsc_sync_policy() -> Check {
-    let tsc_policy = read_registry_dword(
+fn check_tsc_sync_policy(ctx: &CheckContext) -> Check {
+    let (tsc_policy, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "GlobalTimerResolutionRequests",
-    );
+    )
+    .split();
 
     Check::new(
         "TSC Sync Policy",
         &format!("{:?}", tsc_policy.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("latency.tsc_sync_policy")
     .with_description("Time Stamp Counter synchronization policy")
+    .with_mistyped(mistyped)
 }
 
-fn check_dynamic_tick() -> Check {
-    let dynamic_tick = read_registry_dword(
+fn check_dynamic_tick(ctx: &CheckContext) -> Check {
+    let (dynamic_tick, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "DisableDynamicTick",
-    );
+    )
+    .split();
 
     let is_disabled = dynamic_tick == Some(1);
     let status = if is_disabled {
@@ -128,16 +154,26 @@ fn check_dynamic_tick() -> Check {
         if is_disabled { "Disabled" } else { "Enabled" },
         status,
     )
+    .with_id("latency.dynamic_tick")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+        "DisableDynamicTick",
+        1,
+    ))
     .with_description("Dynamic tick can increase latency. Disable for lower latency.")
+    .with_mistyped(mistyped)
 }
 
-fn check_system_responsiveness() -> Check {
-    let responsiveness = read_registry_dword(
+fn check_system_responsiveness(ctx: &CheckContext) -> Check {
+    let (responsiveness, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
         "SystemResponsiveness",
     )
-    .unwrap_or(20);
+    .split();
+    let responsiveness = responsiveness.unwrap_or(20);
 
     let status = if responsiveness <= 10 {
         CheckStatus::Optimal
@@ -152,16 +188,26 @@ fn check_system_responsiveness() -> Check {
         &format!("{}%", responsiveness),
         status,
     )
+    .with_id("latency.system_responsiveness")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+        "SystemResponsiveness",
+        10,
+    ))
     .with_description("Controls CPU reservation for multimedia. Lower is better (0-10 optimal).")
+    .with_mistyped(mistyped)
 }
 
-fn check_network_throttling_index() -> Check {
-    let throttling = read_registry_dword(
+fn check_network_throttling_index(ctx: &CheckContext) -> Check {
+    let (throttling, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
         "NetworkThrottlingIndex",
     )
-    .unwrap_or(10);
+    .split();
+    let throttling = throttling.unwrap_or(10);
 
     let status = if throttling == 0xFFFFFFFF {
         CheckStatus::Optimal
@@ -174,16 +220,26 @@ fn check_network_throttling_index() -> Check {
         &format!("{}", throttling),
         status,
     )
+    .with_id("latency.network_throttling_index")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+        "NetworkThrottlingIndex",
+        0xFFFFFFFF,
+    ))
     .with_description("Network packet processing throttling. 0xFFFFFFFF (disabled) is optimal.")
+    .with_mistyped(mistyped)
 }
 
-fn check_win32_priority_separation() -> Check {
-    let priority = read_registry_dword(
+fn check_win32_priority_separation(ctx: &CheckContext) -> Check {
+    let (priority, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\PriorityControl",
         "Win32PrioritySeparation",
     )
-    .unwrap_or(2);
+    .split();
+    let priority = priority.unwrap_or(2);
 
     let status = if priority == 38 || priority == 26 {
         CheckStatus::Optimal
@@ -196,7 +252,9 @@ fn check_win32_priority_separation() -> Check {
         &format!("{}", priority),
         status,
     )
+    .with_id("latency.win32_priority_separation")
     .with_description("Process scheduler priority. 38=long fixed, 26=short variable (gaming).")
+    .with_mistyped(mistyped)
 }
 
 fn check_timer_resolution() -> Check {
@@ -205,15 +263,18 @@ fn check_timer_resolution() -> Check {
         "System Default",
         CheckStatus::Info,
     )
+    .with_id("latency.timer_resolution")
     .with_description("Current system timer resolution. Lower is better for latency.")
 }
 
-fn check_interrupt_steering() -> Check {
-    let steering = read_registry_dword(
+fn check_interrupt_steering(ctx: &CheckContext) -> Check {
+    let (steering, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\PnP\Pci",
         "DeviceInterruptPolicyEnabled",
-    );
+    )
+    .split();
 
     Check::new(
         "Interrupt Steering",
@@ -224,15 +285,19 @@ fn check_interrupt_steering() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("latency.interrupt_steering")
     .with_description("Allows OS to route device interrupts to specific CPUs.")
+    .with_mistyped(mistyped)
 }
 
-fn check_message_signaled_interrupts() -> Check {
-    let msi_supported = read_registry_dword(
+fn check_message_signaled_interrupts(ctx: &CheckContext) -> Check {
+    let (msi_supported, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Services\nvlddmkm",
         "RmMsiAllowed",
-    );
+    )
+    .split();
 
     Check::new(
         "MSI Mode (GPU)",
@@ -243,59 +308,75 @@ fn check_message_signaled_interrupts() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("latency.message_signaled_interrupts")
     .with_description("Message-Signaled Interrupts reduce latency vs. line-based interrupts.")
+    .with_mistyped(mistyped)
 }
 
-fn check_dpc_watchdog_period() -> Check {
-    let period = read_registry_dword(
+fn check_dpc_watchdog_period(ctx: &CheckContext) -> Check {
+    let (period, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "DpcWatchdogPeriod",
-    );
+    )
+    .split();
 
     Check::new(
         "DPC Watchdog Period",
         &format!("{:?}", period.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("latency.dpc_watchdog_period")
     .with_description("Deferred Procedure Call watchdog timeout.")
+    .with_mistyped(mistyped)
 }
 
-fn check_dpc_timeout() -> Check {
-    let timeout = read_registry_dword(
+fn check_dpc_timeout(ctx: &CheckContext) -> Check {
+    let (timeout, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "DpcTimeout",
-    );
+    )
+    .split();
 
     Check::new(
         "DPC Timeout",
         &format!("{:?}", timeout.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("latency.dpc_timeout")
     .with_description("Maximum time for DPC execution.")
+    .with_mistyped(mistyped)
 }
 
-fn check_idle_disable() -> Check {
-    let disabled = read_registry_dword(
+fn check_idle_disable(ctx: &CheckContext) -> Check {
+    let (disabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "IdleSchedulingPolicy",
-    );
+    )
+    .split();
 
     Check::new(
         "Idle Scheduling Policy",
         &format!("{:?}", disabled.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("latency.idle_disable")
+    .with_mistyped(mistyped)
 }
 
-fn check_distribute_timers() -> Check {
-    let distribute = read_registry_dword(
+fn check_distribute_timers(ctx: &CheckContext) -> Check {
+    let (distribute, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "DistributeTimers",
-    );
+    )
+    .split();
 
     Check::new(
         "Distribute Timers",
@@ -306,20 +387,25 @@ fn check_distribute_timers() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("latency.distribute_timers")
     .with_description("Distributes timer interrupts across CPUs.")
+    .with_mistyped(mistyped)
 }
 
 fn check_processor_idle_state_policy() -> Check {
     Check::new("Processor Idle State Policy", "Detected", CheckStatus::Info)
+        .with_id("latency.processor_idle_state_policy")
         .with_description("CPU idle state management policy.")
 }
 
-fn check_latency_sensitive_hints() -> Check {
-    let hints = read_registry_dword(
+fn check_latency_sensitive_hints(ctx: &CheckContext) -> Check {
+    let (hints, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Power",
         "LatencySensitivityHint",
-    );
+    )
+    .split();
 
     Check::new(
         "Latency Sensitivity Hints",
@@ -330,6 +416,8 @@ fn check_latency_sensitive_hints() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("latency.sensitive_hints")
+    .with_mistyped(mistyped)
 }
 
 fn check_cstate_latency() -> Check {
@@ -338,25 +426,30 @@ fn check_cstate_latency() -> Check {
         "System Dependent",
         CheckStatus::Info,
     )
+    .with_id("latency.cstate_latency")
     .with_description("Time to exit CPU C-states. Lower is better.")
 }
 
 fn check_platform_clock_source() -> Check {
     Check::new("Platform Clock Source", "TSC", CheckStatus::Info)
+        .with_id("latency.platform_clock_source")
         .with_description("Primary time source: TSC (best), HPET, or ACPI PM Timer.")
 }
 
 fn check_tsc_invariant() -> Check {
     Check::new("TSC Invariant", "Supported", CheckStatus::Info)
+        .with_id("latency.tsc_invariant")
         .with_description("Time Stamp Counter runs at constant rate regardless of CPU frequency.")
 }
 
-fn check_use_platform_clock() -> Check {
-    let use_platform = read_registry_dword(
+fn check_use_platform_clock(ctx: &CheckContext) -> Check {
+    let (use_platform, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "UsePlatformClock",
-    );
+    )
+    .split();
 
     let status = if use_platform == Some(0) || use_platform.is_none() {
         CheckStatus::Optimal
@@ -373,20 +466,30 @@ fn check_use_platform_clock() -> Check {
         },
         status,
     )
+    .with_id("latency.use_platform_clock")
+    .with_remediation(Remediation::delete_value(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+        "UsePlatformClock",
+    ))
     .with_description("TSC is faster and more accurate than platform clock.")
+    .with_mistyped(mistyped)
 }
 
 fn check_synthetic_timer() -> Check {
     Check::new("Synthetic Timer", "Not Applicable", CheckStatus::Info)
+        .with_id("latency.synthetic_timer")
         .with_description("Hyper-V synthetic timer (VM only).")
 }
 
-fn check_large_page_drivers() -> Check {
-    let large_pages = read_registry_dword(
+fn check_large_page_drivers(ctx: &CheckContext) -> Check {
+    let (large_pages, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "LargeSystemCache",
-    );
+    )
+    .split();
 
     Check::new(
         "Large Page Support",
@@ -397,16 +500,20 @@ fn check_large_page_drivers() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("latency.large_page_drivers")
     .with_description("Large memory pages can reduce TLB misses.")
+    .with_mistyped(mistyped)
 }
 
 fn check_disable_dynamic_pstate() -> Check {
     Check::new("Dynamic P-State", "System Managed", CheckStatus::Info)
+        .with_id("latency.disable_dynamic_pstate")
         .with_description("CPU frequency scaling management.")
 }
 
-fn check_system_profile_tasks() -> Check {
+fn check_system_profile_tasks(ctx: &CheckContext) -> Check {
     let tasks_exist = registry_key_exists(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks",
     );
@@ -416,21 +523,25 @@ fn check_system_profile_tasks() -> Check {
         if tasks_exist { "Configured" } else { "Default" },
         CheckStatus::Info,
     )
+    .with_id("latency.system_profile_tasks")
     .with_description("MMCSS task priority configuration.")
 }
 
 fn check_multimedia_system_profile() -> Check {
     Check::new("Multimedia System Profile", "Active", CheckStatus::Info)
+        .with_id("latency.multimedia_system_profile")
         .with_description("MMCSS multimedia class scheduler service configuration.")
 }
 
-fn check_gpu_priority() -> Check {
-    let priority = read_registry_dword(
+fn check_gpu_priority(ctx: &CheckContext) -> Check {
+    let (priority, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
         "GPU Priority",
     )
-    .unwrap_or(8);
+    .split();
+    let priority = priority.unwrap_or(8);
 
     let status = if priority >= 8 {
         CheckStatus::Optimal
@@ -439,16 +550,26 @@ fn check_gpu_priority() -> Check {
     };
 
     Check::new("GPU Priority (Games)", &format!("{}", priority), status)
+        .with_id("latency.gpu_priority")
+        .with_remediation(Remediation::set_dword(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
+            "GPU Priority",
+            8,
+        ))
         .with_description("GPU scheduling priority for games. 8 is optimal.")
+        .with_mistyped(mistyped)
 }
 
-fn check_scheduling_category() -> Check {
-    let category = read_registry_string(
+fn check_scheduling_category(ctx: &CheckContext) -> Check {
+    let (category, mistyped) = lookup_registry_string(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
         "Scheduling Category",
     )
-    .unwrap_or_else(|| "Medium".to_string());
+    .split();
+    let category = category.unwrap_or_else(|| "Medium".to_string());
 
     let status = if category == "High" {
         CheckStatus::Optimal
@@ -457,7 +578,15 @@ fn check_scheduling_category() -> Check {
     };
 
     Check::new("Scheduling Category (Games)", &category, status)
+        .with_id("latency.scheduling_category")
+        .with_remediation(Remediation::set_string(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Games",
+            "Scheduling Category",
+            "High",
+        ))
         .with_description("CPU scheduling priority. 'High' is optimal for games.")
+        .with_mistyped(mistyped)
 }
 
 fn check_system_responsiveness_override() -> Check {
@@ -466,11 +595,13 @@ fn check_system_responsiveness_override() -> Check {
         "Not Set",
         CheckStatus::Info,
     )
+    .with_id("latency.system_responsiveness_override")
     .with_description("Application-specific responsiveness overrides.")
 }
 
 fn check_latency_tolerance() -> Check {
     Check::new("Latency Tolerance", "System Default", CheckStatus::Info)
+        .with_id("latency.tolerance")
         .with_description("Device latency tolerance settings.")
 }
 
@@ -480,45 +611,55 @@ fn check_interrupt_affinity_policy() -> Check {
         "System Managed",
         CheckStatus::Info,
     )
+    .with_id("latency.interrupt_affinity_policy")
     .with_description("CPU affinity for device interrupts.")
 }
 
 fn check_isr_time_limit() -> Check {
     Check::new("ISR Time Limit", "System Default", CheckStatus::Info)
+        .with_id("latency.isr_time_limit")
         .with_description("Interrupt Service Routine execution time limit.")
 }
 
 fn check_dpc_queue_depth() -> Check {
     Check::new("DPC Queue Depth", "System Default", CheckStatus::Info)
+        .with_id("latency.dpc_queue_depth")
         .with_description("Deferred Procedure Call queue depth.")
 }
 
 fn check_threaded_dpc() -> Check {
     Check::new("Threaded DPC", "System Managed", CheckStatus::Info)
+        .with_id("latency.threaded_dpc")
         .with_description("Deferred Procedure Calls executed in threads.")
 }
 
-fn check_timer_coalescing() -> Check {
-    let coalescing = read_registry_dword(
+fn check_timer_coalescing(ctx: &CheckContext) -> Check {
+    let (coalescing, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
         "CoalescingTimerInterval",
-    );
+    )
+    .split();
 
     Check::new(
         "Timer Coalescing",
         &format!("{:?}", coalescing.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("latency.timer_coalescing")
     .with_description("Groups timer expirations to reduce wakeups.")
+    .with_mistyped(mistyped)
 }
 
 fn check_precision_platform_timer() -> Check {
     Check::new("Precision Platform Timer", "Available", CheckStatus::Info)
+        .with_id("latency.precision_platform_timer")
         .with_description("High-resolution hardware timer availability.")
 }
 
 fn check_acpi_timer_disabled() -> Check {
     Check::new("ACPI PM Timer", "System Managed", CheckStatus::Info)
+        .with_id("latency.acpi_timer_disabled")
         .with_description("ACPI Power Management Timer usage.")
 }
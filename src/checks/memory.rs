@@ -1,31 +1,32 @@
+use super::context::CheckContext;
 use super::utils::*;
 use super::wmi_helper::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_memory_checks() -> CategoryResults {
+pub fn run_memory_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Memory");
 
-    let checks: Vec<Check> = vec![
-        check_ram_speed(),
-        check_ram_channel(),
-        check_page_file(),
+    let checks: Vec<Check> = timed![
+        check_ram_speed(ctx),
+        check_ram_channel(ctx),
+        check_page_file(ctx),
         check_memory_compression(),
-        check_prefetch(),
+        check_prefetch(ctx),
         check_superfetch(),
-        check_large_system_cache(),
+        check_large_system_cache(ctx),
         check_ndu_service(),
-        check_second_level_cache(),
-        check_clear_pagefile_at_shutdown(),
-        check_disable_paging_executive(),
+        check_second_level_cache(ctx),
+        check_clear_pagefile_at_shutdown(ctx),
+        check_disable_paging_executive(ctx),
         check_large_page_minimum(),
         check_system_cache_limit(),
         check_io_page_lock_limit(),
         check_memory_management(),
-        check_total_ram(),
-        check_available_ram(),
-        check_ram_manufacturer(),
-        check_ram_voltage(),
+        check_total_ram(ctx),
+        check_available_ram(ctx),
+        check_ram_manufacturer(ctx),
+        check_ram_voltage(ctx),
     ]
     .into_par_iter()
     .collect();
@@ -37,18 +38,21 @@ pub fn run_memory_checks() -> CategoryResults {
     results
 }
 
-fn check_ram_speed() -> Check {
-    let speed = query_wmi_u32("Win32_PhysicalMemory", "Speed")
+fn check_ram_speed(ctx: &CheckContext) -> Check {
+    let (speed, failed) = split_failure(query_wmi_u32(ctx, "Win32_PhysicalMemory", "Speed"));
+    let speed = speed
         .map(|s| format!("{} MHz", s))
         .unwrap_or_else(|| "Unknown".to_string());
 
     Check::new("RAM Speed", &speed, CheckStatus::Info)
+        .with_id("memory.ram_speed")
         .with_description("Current RAM frequency. Check BIOS for XMP/DOCP profile.")
+        .with_error(failed)
 }
 
-fn check_ram_channel() -> Check {
-    let count = count_wmi_instances("Win32_PhysicalMemory");
-    let channel = match count {
+fn check_ram_channel(ctx: &CheckContext) -> Check {
+    let count = count_wmi_instances(ctx, "Win32_PhysicalMemory");
+    let channel = match count.clone().unwrap_or(0) {
         0 => "Unknown",
         1 => "Single Channel",
         2 => "Dual Channel",
@@ -57,97 +61,159 @@ fn check_ram_channel() -> Check {
     };
 
     Check::new("RAM Channel Configuration", channel, CheckStatus::Info)
+        .with_id("memory.ram_channel")
         .with_description("Dual channel provides 2x memory bandwidth vs single channel.")
+        .with_error(count.err())
 }
 
-fn check_page_file() -> Check {
-    let pf = read_registry_string(
+/// Describes one PagingFiles entry: "C:\pagefile.sys 4096 8192", where
+/// "0 0" (or no sizes) means system managed and "?:" means every drive.
+fn describe_paging_file(entry: &str) -> String {
+    let mut parts = entry.split_whitespace();
+    let path = parts.next().unwrap_or_default();
+    let sizes: Vec<u64> = parts.filter_map(|p| p.parse().ok()).collect();
+    let location = if path.starts_with("?:") {
+        "all drives".to_string()
+    } else {
+        path.to_string()
+    };
+    match sizes.as_slice() {
+        [min, max] if *min > 0 || *max > 0 => format!("{} {}-{} MB", location, min, max),
+        _ => format!("{} (system managed)", location),
+    }
+}
+
+fn check_page_file(ctx: &CheckContext) -> Check {
+    let pf = lookup_registry_multi_string(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "PagingFiles",
     );
-    let (val, st) = if pf.is_some() {
-        ("Configured", CheckStatus::Optimal)
-    } else {
-        ("Not Set", CheckStatus::Warning)
+    let (val, st) = match pf {
+        RegRead::Value(entries) => {
+            let described: Vec<String> = entries
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .map(|e| describe_paging_file(e))
+                .collect();
+            if described.is_empty() {
+                ("Disabled".to_string(), CheckStatus::Warning)
+            } else {
+                (described.join(", "), CheckStatus::Optimal)
+            }
+        }
+        RegRead::TypeMismatch(found) => (
+            format!("Stored as {} (expected REG_MULTI_SZ)", found.type_name()),
+            CheckStatus::Warning,
+        ),
+        RegRead::Missing => ("Not Set".to_string(), CheckStatus::Warning),
     };
-    Check::new("Page File", val, st)
+    Check::new("Page File", &val, st)
+        .with_id("memory.page_file")
+        .with_remediation(Remediation::set_value(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+            "PagingFiles",
+            RegValue::MultiString(vec![r"?:\pagefile.sys".to_string()]),
+        ))
         .with_description("System-managed or 1.5x RAM size recommended.")
 }
 
 fn check_memory_compression() -> Check {
     Check::new("Memory Compression", "Enabled", CheckStatus::Optimal)
+        .with_id("memory.compression")
         .with_description("Reduces physical memory usage with minimal CPU cost.")
 }
 
-fn check_prefetch() -> Check {
-    let v = read_registry_dword(
+fn check_prefetch(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management\PrefetchParameters",
         "EnablePrefetcher",
     )
-    .unwrap_or(3);
+    .split();
+    let v = v.unwrap_or(3);
     Check::new("Prefetch", &format!("{}", v), CheckStatus::Info)
+        .with_id("memory.prefetch")
         .with_description("0=disabled, 1=app, 2=boot, 3=both. Keep enabled for HDDs.")
+        .with_mistyped(mistyped)
 }
 
 fn check_superfetch() -> Check {
     Check::new("Superfetch (SysMain)", "System Managed", CheckStatus::Info)
+        .with_id("memory.superfetch")
         .with_description("Preloads frequently used apps. Can disable on SSDs.")
 }
 
-fn check_large_system_cache() -> Check {
-    let v = read_registry_dword(
+fn check_large_system_cache(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "LargeSystemCache",
-    );
+    )
+    .split();
     Check::new(
         "Large System Cache",
         if v == Some(1) { "Enabled" } else { "Disabled" },
         CheckStatus::Info,
     )
+    .with_id("memory.large_system_cache")
     .with_description("For file servers. Keep disabled for workstations.")
+    .with_mistyped(mistyped)
 }
 
 fn check_ndu_service() -> Check {
     Check::new("NDU (Network Data Usage)", "Running", CheckStatus::Info)
+        .with_id("memory.ndu_service")
         .with_description("Can be disabled to save memory if not needed.")
 }
 
-fn check_second_level_cache() -> Check {
-    let v = read_registry_dword(
+fn check_second_level_cache(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "SecondLevelDataCache",
-    );
+    )
+    .split();
     Check::new(
         "Second Level Data Cache",
         &format!("{} KB", v.unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("memory.second_level_cache")
+    .with_mistyped(mistyped)
 }
 
-fn check_clear_pagefile_at_shutdown() -> Check {
-    let v = read_registry_dword(
+fn check_clear_pagefile_at_shutdown(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "ClearPageFileAtShutdown",
-    );
+    )
+    .split();
     Check::new(
         "Clear PageFile at Shutdown",
         if v == Some(1) { "Enabled" } else { "Disabled" },
         CheckStatus::Info,
     )
+    .with_id("memory.clear_pagefile_at_shutdown")
     .with_description("Security feature. Increases shutdown time.")
+    .with_mistyped(mistyped)
 }
 
-fn check_disable_paging_executive() -> Check {
-    let v = read_registry_dword(
+fn check_disable_paging_executive(ctx: &CheckContext) -> Check {
+    let (v, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
         "DisablePagingExecutive",
-    );
+    )
+    .split();
     Check::new(
         "Disable Paging Executive",
         if v == Some(1) { "Enabled" } else { "Disabled" },
@@ -157,49 +223,87 @@ fn check_disable_paging_executive() -> Check {
             CheckStatus::Warning
         },
     )
+    .with_id("memory.disable_paging_executive")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+        "DisablePagingExecutive",
+        1,
+    ))
     .with_description("Keeps kernel in RAM. Enable if you have 16GB+ RAM.")
+    .with_mistyped(mistyped)
 }
 
 fn check_large_page_minimum() -> Check {
     Check::new("Large Page Minimum", "System Default", CheckStatus::Info)
+        .with_id("memory.large_page_minimum")
         .with_description("Minimum memory for large page allocation.")
 }
 
 fn check_system_cache_limit() -> Check {
     Check::new("System Cache Limit", "Dynamic", CheckStatus::Info)
+        .with_id("memory.system_cache_limit")
 }
 
 fn check_io_page_lock_limit() -> Check {
     Check::new("I/O Page Lock Limit", "System Managed", CheckStatus::Info)
+        .with_id("memory.io_page_lock_limit")
 }
 
 fn check_memory_management() -> Check {
-    Check::new("Memory Management", "Optimized", CheckStatus::Optimal)
+    Check::new("Memory Management", "Optimized", CheckStatus::Optimal).with_id("memory.management")
 }
 
-fn check_total_ram() -> Check {
-    let total = query_wmi_u64("Win32_ComputerSystem", "TotalPhysicalMemory")
+fn check_total_ram(ctx: &CheckContext) -> Check {
+    let (total, failed) = split_failure(query_wmi_u64(
+        ctx,
+        "Win32_ComputerSystem",
+        "TotalPhysicalMemory",
+    ));
+    let total = total
         .map(|bytes| format!("{:.2} GB", bytes as f64 / 1024.0 / 1024.0 / 1024.0))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("Total RAM", &total, CheckStatus::Info)
+        .with_id("memory.total_ram")
+        .with_error(failed)
 }
 
-fn check_available_ram() -> Check {
-    let available = query_wmi_u64("Win32_OperatingSystem", "FreePhysicalMemory")
+fn check_available_ram(ctx: &CheckContext) -> Check {
+    let (available, failed) = split_failure(query_wmi_u64(
+        ctx,
+        "Win32_OperatingSystem",
+        "FreePhysicalMemory",
+    ));
+    let available = available
         .map(|kb| format!("{:.2} GB", kb as f64 / 1024.0 / 1024.0))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("Available RAM", &available, CheckStatus::Info)
+        .with_id("memory.available_ram")
+        .with_error(failed)
 }
 
-fn check_ram_manufacturer() -> Check {
-    let manufacturer = query_wmi_string("Win32_PhysicalMemory", "Manufacturer")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_ram_manufacturer(ctx: &CheckContext) -> Check {
+    let (manufacturer, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_PhysicalMemory",
+        "Manufacturer",
+    ));
+    let manufacturer = manufacturer.unwrap_or_else(|| "Unknown".to_string());
     Check::new("RAM Manufacturer", manufacturer.trim(), CheckStatus::Info)
+        .with_id("memory.ram_manufacturer")
+        .with_error(failed)
 }
 
-fn check_ram_voltage() -> Check {
-    let voltage = query_wmi_u32("Win32_PhysicalMemory", "ConfiguredVoltage")
+fn check_ram_voltage(ctx: &CheckContext) -> Check {
+    let (voltage, failed) = split_failure(query_wmi_u32(
+        ctx,
+        "Win32_PhysicalMemory",
+        "ConfiguredVoltage",
+    ));
+    let voltage = voltage
         .map(|mv| format!("{:.2}V", mv as f64 / 1000.0))
         .unwrap_or_else(|| "Unknown".to_string());
     Check::new("RAM Voltage", &voltage, CheckStatus::Info)
+        .with_id("memory.ram_voltage")
+        .with_error(failed)
 }
@@ -1,4 +1,6 @@
 mod audio;
+mod catalog;
+mod context;
 mod cpu;
 mod gpu;
 mod gpu_helper;
@@ -8,7 +10,11 @@ mod memory;
 mod network;
 mod platform;
 mod power;
+mod regf;
+mod registry;
+mod rules;
 mod security;
+mod service_graph;
 mod service_helper;
 mod services;
 mod stability;
@@ -16,8 +22,11 @@ mod storage;
 mod thermal;
 mod utils;
 mod wmi_helper;
+mod wmi_source;
 
 pub use audio::run_audio_checks;
+pub use catalog::{annotate_check, catalog, find_check, CheckMeta, DataSource};
+pub use context::CheckContext;
 pub use cpu::run_cpu_checks;
 pub use gpu::run_gpu_checks;
 pub use input::run_input_checks;
@@ -26,8 +35,57 @@ pub use memory::run_memory_checks;
 pub use network::run_network_checks;
 pub use platform::run_platform_checks;
 pub use power::run_power_checks;
+pub use regf::{Hive, KeyNode, OfflineRegistry, RegfError, ValueNode};
+pub use registry::{
+    canonical_key_path, Hkey, LiveRegistry, MemoryRegistry, Mistyped, RegRead, RegType, RegValue,
+    RegistrySource, TrackingRegistry, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, REG_BINARY, REG_DWORD,
+    REG_EXPAND_SZ, REG_MULTI_SZ, REG_QWORD, REG_SZ,
+};
+pub use rules::{Expected, Operator, Rule, RuleError, RuleSet};
 pub use security::run_security_checks;
+pub use service_graph::{AffectedCheck, Dependent, ServiceAnalysis, ServiceGraph};
+pub use service_helper::{
+    query_service_status, read_service_config, user_service_instances, ServiceConfig, SERVICES_KEY,
+    SERVICE_USERSERVICE_INSTANCE, SERVICE_USER_SERVICE,
+};
 pub use services::run_services_checks;
 pub use stability::run_stability_checks;
 pub use storage::run_storage_checks;
 pub use thermal::run_thermal_checks;
+pub use wmi_source::{LiveWmi, RecordingWmi, ReplayWmi, WmiFixture, WmiRow, WmiSource, WmiValue};
+
+use crate::types::{Category, CategoryResults};
+
+/// Runs one category, stamps catalog metadata onto its results and appends
+/// any user-defined rules that extend it.
+pub fn run_category(ctx: &CheckContext, category: Category) -> CategoryResults {
+    let mut results = match category {
+        Category::Latency => run_latency_checks(ctx),
+        Category::Cpu => run_cpu_checks(ctx),
+        Category::Gpu => run_gpu_checks(ctx),
+        Category::Memory => run_memory_checks(ctx),
+        Category::Storage => run_storage_checks(ctx),
+        Category::Network => run_network_checks(ctx),
+        Category::Audio => run_audio_checks(ctx),
+        Category::Input => run_input_checks(ctx),
+        Category::Stability => run_stability_checks(ctx),
+        Category::Services => run_services_checks(ctx),
+        Category::Security => run_security_checks(ctx),
+        Category::Platform => run_platform_checks(ctx),
+        Category::Thermal => run_thermal_checks(ctx),
+        Category::Power => run_power_checks(ctx),
+    };
+    results.checks.iter_mut().for_each(annotate_check);
+    results
+        .checks
+        .extend(ctx.rules().run_builtin(ctx, category));
+    results
+}
+
+/// Runs a built-in category by name, or a custom category declared by rules.
+pub fn run_category_named(ctx: &CheckContext, name: &str) -> CategoryResults {
+    match Category::from_name(name) {
+        Some(category) => run_category(ctx, category),
+        None => ctx.rules().run_custom(ctx, name),
+    }
+}
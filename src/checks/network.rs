@@ -1,18 +1,19 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_network_checks() -> CategoryResults {
+pub fn run_network_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Network");
 
-    let checks: Vec<Check> = vec![
-        check_nagle_algorithm(),
+    let checks: Vec<Check> = timed![
+        check_nagle_algorithm(ctx),
         check_rss(),
         check_checksum_offload(),
         check_interrupt_moderation(),
         check_flow_control(),
         check_dns_configuration(),
-        check_qos_throttling(),
+        check_qos_throttling(ctx),
         check_netbios(),
         check_ipv6(),
         check_tcp_autotuning(),
@@ -34,18 +35,17 @@ pub fn run_network_checks() -> CategoryResults {
         check_network_profile(),
         check_dns_cache(),
         check_lmhosts_lookup(),
-        check_netbios_over_tcpip(),
         check_network_adapter_binding(),
         check_qos_packet_scheduler(),
         check_smb_version(),
         check_smb_signing(),
         check_smb_encryption(),
         check_network_congestion_provider(),
-        Check::new("Network Adapter", "Detected", CheckStatus::Info),
-        Check::new("Link Speed", "Gigabit+", CheckStatus::Optimal),
-        Check::new("Network Latency", "Low", CheckStatus::Optimal),
-        Check::new("Packet Loss", "None", CheckStatus::Optimal),
-        Check::new("MTU Size", "1500", CheckStatus::Info),
+        Check::new("Network Adapter", "Detected", CheckStatus::Info).with_id("network.adapter"),
+        Check::new("Link Speed", "Gigabit+", CheckStatus::Optimal).with_id("network.link_speed"),
+        Check::new("Network Latency", "Low", CheckStatus::Optimal).with_id("network.latency"),
+        Check::new("Packet Loss", "None", CheckStatus::Optimal).with_id("network.packet_loss"),
+        Check::new("MTU Size", "1500", CheckStatus::Info).with_id("network.mtu_size"),
     ]
     .into_par_iter()
     .collect();
@@ -57,29 +57,84 @@ pub fn run_network_checks() -> CategoryResults {
     results
 }
 
-fn check_nagle_algorithm() -> Check {
-    let tcp_ack = read_registry_dword(
-        HKEY_LOCAL_MACHINE,
-        r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces",
-        "TcpAckFrequency",
+const TCPIP_INTERFACES: &str = r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces";
+
+/// Interfaces with an IPv4 address, static or DHCP-assigned.
+fn has_ip_address(ctx: &CheckContext, path: &str) -> bool {
+    let assigned = |ip: &str| !ip.is_empty() && ip != "0.0.0.0";
+    let dhcp = matches!(
+        lookup_registry_string(ctx, HKEY_LOCAL_MACHINE, path, "DhcpIPAddress"),
+        RegRead::Value(ip) if assigned(&ip)
+    );
+    let fixed = matches!(
+        lookup_registry_multi_string(ctx, HKEY_LOCAL_MACHINE, path, "IPAddress"),
+        RegRead::Value(ips) if ips.iter().any(|ip| assigned(ip))
     );
+    dhcp || fixed
+}
+
+fn check_nagle_algorithm(ctx: &CheckContext) -> Check {
+    let interfaces: Vec<String> = registry_subkeys(ctx, HKEY_LOCAL_MACHINE, TCPIP_INTERFACES)
+        .into_iter()
+        .map(|guid| format!(r"{}\{}", TCPIP_INTERFACES, guid))
+        .collect();
+    let active: Vec<&String> = interfaces
+        .iter()
+        .filter(|path| has_ip_address(ctx, path))
+        .collect();
+    // Without any addressed interface, judge every configured one.
+    let judged: Vec<&String> = if active.is_empty() {
+        interfaces.iter().collect()
+    } else {
+        active
+    };
 
-    let status = if tcp_ack == Some(1) {
-        CheckStatus::Optimal
+    let mut disabled = 0;
+    let mut mismatched = Vec::new();
+    for path in &judged {
+        match lookup_registry_dword(ctx, HKEY_LOCAL_MACHINE, path, "TcpAckFrequency") {
+            RegRead::Value(1) => disabled += 1,
+            RegRead::TypeMismatch(found) => mismatched.push(found.type_name()),
+            _ => {}
+        }
+    }
+
+    let (value, status) = if judged.is_empty() {
+        ("No TCP/IP interfaces found".to_string(), CheckStatus::Info)
+    } else if !mismatched.is_empty() {
+        (
+            format!(
+                "TcpAckFrequency stored as {} on {} interface(s)",
+                mismatched[0],
+                mismatched.len()
+            ),
+            CheckStatus::Warning,
+        )
+    } else if disabled == judged.len() {
+        ("Disabled".to_string(), CheckStatus::Optimal)
+    } else if disabled > 0 {
+        (
+            format!("Disabled on {}/{} interfaces", disabled, judged.len()),
+            CheckStatus::Warning,
+        )
     } else {
-        CheckStatus::Warning
+        ("Enabled".to_string(), CheckStatus::Warning)
     };
 
-    Check::new(
-        "Nagle Algorithm",
-        if tcp_ack == Some(1) {
-            "Disabled"
-        } else {
-            "Enabled"
-        },
-        status,
-    )
-    .with_description("Disable Nagle for lower latency in gaming and real-time apps.")
+    let steps = judged
+        .iter()
+        .flat_map(|path| {
+            [
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, path, "TcpAckFrequency", 1),
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, path, "TCPNoDelay", 1),
+            ]
+        })
+        .collect();
+
+    Check::new("Nagle Algorithm", &value, status)
+        .with_id("network.nagle_algorithm")
+        .with_remediation(Remediation::Batch { steps })
+        .with_description("Disable Nagle for lower latency in gaming and real-time apps.")
 }
 
 fn check_rss() -> Check {
@@ -88,35 +143,42 @@ fn check_rss() -> Check {
         "Enabled",
         CheckStatus::Optimal,
     )
+    .with_id("network.rss")
     .with_description("Distributes network processing across CPU cores.")
 }
 
 fn check_checksum_offload() -> Check {
     Check::new("Checksum Offload", "Enabled", CheckStatus::Optimal)
+        .with_id("network.checksum_offload")
         .with_description("Offloads checksum calculation to NIC.")
 }
 
 fn check_interrupt_moderation() -> Check {
     Check::new("Interrupt Moderation", "Adaptive", CheckStatus::Info)
+        .with_id("network.interrupt_moderation")
         .with_description("'Off' for lowest latency, 'Adaptive' balances throughput/latency.")
 }
 
 fn check_flow_control() -> Check {
     Check::new("Flow Control", "Enabled", CheckStatus::Info)
+        .with_id("network.flow_control")
         .with_description("Can disable for gaming to reduce micro-stutters.")
 }
 
 fn check_dns_configuration() -> Check {
     Check::new("DNS Servers", "Configured", CheckStatus::Info)
+        .with_id("network.dns_configuration")
         .with_description("Use fast DNS like 1.1.1.1 or 8.8.8.8 for better response times.")
 }
 
-fn check_qos_throttling() -> Check {
-    let qos = read_registry_dword(
+fn check_qos_throttling(ctx: &CheckContext) -> Check {
+    let (qos, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Policies\Microsoft\Windows\Psched",
         "NonBestEffortLimit",
-    );
+    )
+    .split();
 
     let status = if qos == Some(0) {
         CheckStatus::Optimal
@@ -129,35 +191,48 @@ fn check_qos_throttling() -> Check {
         &format!("{}%", qos.unwrap_or(20)),
         status,
     )
+    .with_id("network.qos_throttling")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Policies\Microsoft\Windows\Psched",
+        "NonBestEffortLimit",
+        0,
+    ))
     .with_description("Set to 0 to disable bandwidth reservation.")
+    .with_mistyped(mistyped)
 }
 
 fn check_netbios() -> Check {
     Check::new("NetBIOS over TCP/IP", "Disabled", CheckStatus::Optimal)
+        .with_id("network.netbios")
         .with_description("Legacy protocol. Disable if not needed.")
 }
 
 fn check_ipv6() -> Check {
     Check::new("IPv6", "Enabled", CheckStatus::Info)
+        .with_id("network.ipv6")
         .with_description("Keep enabled unless it causes issues.")
 }
 
 fn check_tcp_autotuning() -> Check {
     Check::new("TCP Window Auto-Tuning", "Normal", CheckStatus::Optimal)
+        .with_id("network.tcp_autotuning")
         .with_description("Optimizes TCP window size. Keep on 'normal' or 'experimental'.")
 }
 
 fn check_tcp_timestamps() -> Check {
-    Check::new("TCP Timestamps", "Enabled", CheckStatus::Info)
+    Check::new("TCP Timestamps", "Enabled", CheckStatus::Info).with_id("network.tcp_timestamps")
 }
 
 fn check_tcp_window_scaling() -> Check {
     Check::new("TCP Window Scaling", "Enabled", CheckStatus::Optimal)
+        .with_id("network.tcp_window_scaling")
         .with_description("Allows larger TCP windows for better throughput.")
 }
 
 fn check_tcp_chimney() -> Check {
     Check::new("TCP Chimney Offload", "Automatic", CheckStatus::Info)
+        .with_id("network.tcp_chimney")
         .with_description("Legacy feature. 'Automatic' is recommended.")
 }
 
@@ -167,50 +242,59 @@ fn check_network_adapter_power() -> Check {
         "Disabled",
         CheckStatus::Optimal,
     )
+    .with_id("network.adapter_power")
     .with_description("Prevent adapter from sleeping for consistent connectivity.")
 }
 
 fn check_network_throttling() -> Check {
-    Check::new("Network Throttling", "Disabled", CheckStatus::Optimal)
+    Check::new("Network Throttling", "Disabled", CheckStatus::Optimal).with_id("network.throttling")
 }
 
 fn check_tcp_optimizer() -> Check {
     Check::new("TCP Optimizer Settings", "Configured", CheckStatus::Info)
+        .with_id("network.tcp_optimizer")
 }
 
 fn check_receive_buffers() -> Check {
     Check::new("Receive Buffers", "2048", CheckStatus::Info)
+        .with_id("network.receive_buffers")
         .with_description("Increase for high-bandwidth connections.")
 }
 
 fn check_transmit_buffers() -> Check {
     Check::new("Transmit Buffers", "2048", CheckStatus::Info)
+        .with_id("network.transmit_buffers")
         .with_description("Increase for high-bandwidth connections.")
 }
 
 fn check_jumbo_frames() -> Check {
     Check::new("Jumbo Frames", "Disabled", CheckStatus::Info)
+        .with_id("network.jumbo_frames")
         .with_description("Enable only if entire network supports it.")
 }
 
 fn check_large_send_offload() -> Check {
     Check::new("Large Send Offload (LSO)", "Enabled", CheckStatus::Optimal)
+        .with_id("network.large_send_offload")
 }
 
 fn check_tcp_offload_engine() -> Check {
     Check::new("TCP Offload Engine", "Enabled", CheckStatus::Optimal)
+        .with_id("network.tcp_offload_engine")
 }
 
 fn check_network_discovery() -> Check {
-    Check::new("Network Discovery", "Enabled", CheckStatus::Info)
+    Check::new("Network Discovery", "Enabled", CheckStatus::Info).with_id("network.discovery")
 }
 
 fn check_file_printer_sharing() -> Check {
     Check::new("File and Printer Sharing", "Enabled", CheckStatus::Info)
+        .with_id("network.file_printer_sharing")
 }
 
 fn check_windows_firewall() -> Check {
     Check::new("Windows Firewall", "Enabled", CheckStatus::Optimal)
+        .with_id("network.windows_firewall")
         .with_description("Keep enabled for security.")
 }
 
@@ -220,52 +304,55 @@ fn check_network_location() -> Check {
         "Running",
         CheckStatus::Optimal,
     )
+    .with_id("network.location")
 }
 
 fn check_network_profile() -> Check {
-    Check::new("Network Profile", "Private", CheckStatus::Info)
+    Check::new("Network Profile", "Private", CheckStatus::Info).with_id("network.profile")
 }
 
 fn check_dns_cache() -> Check {
-    Check::new("DNS Client Cache", "Enabled", CheckStatus::Optimal)
+    Check::new("DNS Client Cache", "Enabled", CheckStatus::Optimal).with_id("network.dns_cache")
 }
 
 fn check_lmhosts_lookup() -> Check {
     Check::new("LMHOSTS Lookup", "Disabled", CheckStatus::Info)
+        .with_id("network.lmhosts_lookup")
         .with_description("Legacy feature. Can be disabled.")
 }
 
-fn check_netbios_over_tcpip() -> Check {
-    Check::new("NetBIOS over TCP/IP", "Disabled", CheckStatus::Optimal)
-}
-
 fn check_network_adapter_binding() -> Check {
     Check::new(
         "Network Adapter Binding Order",
         "Optimized",
         CheckStatus::Info,
     )
+    .with_id("network.adapter_binding")
 }
 
 fn check_qos_packet_scheduler() -> Check {
     Check::new("QoS Packet Scheduler", "Enabled", CheckStatus::Info)
+        .with_id("network.qos_packet_scheduler")
 }
 
 fn check_smb_version() -> Check {
     Check::new("SMB Version", "3.1.1", CheckStatus::Optimal)
+        .with_id("network.smb_version")
         .with_description("SMB 3+ provides encryption and better performance.")
 }
 
 fn check_smb_signing() -> Check {
     Check::new("SMB Signing", "Enabled", CheckStatus::Info)
+        .with_id("network.smb_signing")
         .with_description("Security feature with slight performance cost.")
 }
 
 fn check_smb_encryption() -> Check {
-    Check::new("SMB Encryption", "Negotiated", CheckStatus::Info)
+    Check::new("SMB Encryption", "Negotiated", CheckStatus::Info).with_id("network.smb_encryption")
 }
 
 fn check_network_congestion_provider() -> Check {
     Check::new("Network Congestion Provider", "CUBIC", CheckStatus::Info)
+        .with_id("network.congestion_provider")
         .with_description("CUBIC is the modern congestion control algorithm.")
 }
@@ -1,48 +1,88 @@
+use super::context::CheckContext;
+use super::registry::filetime_to_utc;
 use super::utils::*;
 use super::wmi_helper::*;
 use crate::types::*;
 
-pub fn run_platform_checks() -> CategoryResults {
+pub fn run_platform_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Platform");
 
-    results.add_check(check_windows_version());
-    results.add_check(check_windows_build());
-    results.add_check(check_windows_edition());
-    results.add_check(check_system_type());
-    results.add_check(check_bios_mode());
-    results.add_check(check_manufacturer());
-    results.add_check(check_model());
-    results.add_check(check_system_uptime());
+    results.add_check(Check::timed(|| check_windows_version(ctx)));
+    results.add_check(Check::timed(|| check_windows_build(ctx)));
+    results.add_check(Check::timed(|| check_windows_edition(ctx)));
+    results.add_check(Check::timed(|| check_install_date(ctx)));
+    results.add_check(Check::timed(|| check_system_type(ctx)));
+    results.add_check(Check::timed(check_bios_mode));
+    results.add_check(Check::timed(|| check_manufacturer(ctx)));
+    results.add_check(Check::timed(|| check_model(ctx)));
+    results.add_check(Check::timed(|| check_system_uptime(ctx)));
 
     results
 }
 
-fn check_windows_version() -> Check {
-    let version = query_wmi_string("Win32_OperatingSystem", "Caption")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_windows_version(ctx: &CheckContext) -> Check {
+    let (version, failed) =
+        split_failure(query_wmi_string(ctx, "Win32_OperatingSystem", "Caption"));
+    let version = version.unwrap_or_else(|| "Unknown".to_string());
     Check::new("Windows Version", &version, CheckStatus::Info)
+        .with_id("platform.windows_version")
+        .with_error(failed)
 }
 
-fn check_windows_build() -> Check {
-    let build = query_wmi_string("Win32_OperatingSystem", "BuildNumber")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_windows_build(ctx: &CheckContext) -> Check {
+    let (build, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_OperatingSystem",
+        "BuildNumber",
+    ));
+    let build = build.unwrap_or_else(|| "Unknown".to_string());
     Check::new("Windows Build", &build, CheckStatus::Info)
+        .with_id("platform.windows_build")
+        .with_error(failed)
 }
 
-fn check_windows_edition() -> Check {
-    let edition = read_registry_string(
+fn check_windows_edition(ctx: &CheckContext) -> Check {
+    let (edition, mistyped) = lookup_registry_string(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
         "EditionID",
     )
-    .unwrap_or_else(|| "Unknown".to_string());
+    .split();
+    let edition = edition.unwrap_or_else(|| "Unknown".to_string());
     Check::new("Windows Edition", &edition, CheckStatus::Info)
+        .with_id("platform.windows_edition")
+        .with_mistyped(mistyped)
 }
 
-fn check_system_type() -> Check {
-    let arch = query_wmi_string("Win32_OperatingSystem", "OSArchitecture")
-        .unwrap_or_else(|| "Unknown".to_string());
+/// InstallTime is a REG_QWORD FILETIME.
+fn check_install_date(ctx: &CheckContext) -> Check {
+    let (installed, mistyped) = lookup_registry_qword(
+        ctx,
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+        "InstallTime",
+    )
+    .split();
+    let date = installed.and_then(filetime_to_utc).map_or_else(
+        || "Unknown".to_string(),
+        |at| at.format("%Y-%m-%d").to_string(),
+    );
+    Check::new("Windows Install Date", &date, CheckStatus::Info)
+        .with_id("platform.install_date")
+        .with_mistyped(mistyped)
+}
+
+fn check_system_type(ctx: &CheckContext) -> Check {
+    let (arch, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_OperatingSystem",
+        "OSArchitecture",
+    ));
+    let arch = arch.unwrap_or_else(|| "Unknown".to_string());
     Check::new("System Architecture", &arch, CheckStatus::Info)
+        .with_id("platform.system_type")
+        .with_error(failed)
 }
 
 fn check_bios_mode() -> Check {
@@ -53,23 +93,37 @@ fn check_bios_mode() -> Check {
     } else {
         "Unknown"
     };
-    Check::new("BIOS Mode", mode, CheckStatus::Info)
+    Check::new("BIOS Mode", mode, CheckStatus::Info).with_id("platform.bios_mode")
 }
 
-fn check_manufacturer() -> Check {
-    let manufacturer = query_wmi_string("Win32_ComputerSystem", "Manufacturer")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_manufacturer(ctx: &CheckContext) -> Check {
+    let (manufacturer, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_ComputerSystem",
+        "Manufacturer",
+    ));
+    let manufacturer = manufacturer.unwrap_or_else(|| "Unknown".to_string());
     Check::new("System Manufacturer", &manufacturer, CheckStatus::Info)
+        .with_id("platform.manufacturer")
+        .with_error(failed)
 }
 
-fn check_model() -> Check {
-    let model =
-        query_wmi_string("Win32_ComputerSystem", "Model").unwrap_or_else(|| "Unknown".to_string());
+fn check_model(ctx: &CheckContext) -> Check {
+    let (model, failed) = split_failure(query_wmi_string(ctx, "Win32_ComputerSystem", "Model"));
+    let model = model.unwrap_or_else(|| "Unknown".to_string());
     Check::new("System Model", &model, CheckStatus::Info)
+        .with_id("platform.model")
+        .with_error(failed)
 }
 
-fn check_system_uptime() -> Check {
-    let last_boot = query_wmi_string("Win32_OperatingSystem", "LastBootUpTime")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_system_uptime(ctx: &CheckContext) -> Check {
+    let (last_boot, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_OperatingSystem",
+        "LastBootUpTime",
+    ));
+    let last_boot = last_boot.unwrap_or_else(|| "Unknown".to_string());
     Check::new("Last Boot Time", &last_boot, CheckStatus::Info)
+        .with_id("platform.system_uptime")
+        .with_error(failed)
 }
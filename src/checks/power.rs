@@ -1,51 +1,59 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_power_checks() -> CategoryResults {
+pub fn run_power_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Power");
 
     let checks: Vec<Check> = (0..30)
         .into_par_iter()
-        .map(|i| match i {
-            0 => check_pcie_aspm(),
-            1 => check_usb_selective_suspend(),
-            2 => check_fast_startup(),
-            3 => check_hybrid_sleep(),
-            4 => check_hibernate(),
-            5 => check_monitor_timeout(),
-            6 => check_disk_timeout(),
-            7 => check_sleep_timeout(),
-            8 => check_pci_link_state(),
-            9 => check_ahci_link_power(),
-            10 => check_wifi_power_saving(),
-            11 => check_power_throttling(),
-            12 => check_cpu_power_management(),
-            13 => check_display_power_saving(),
-            14 => check_hard_disk_power(),
-            15 => check_sleep_state(),
-            16 => check_away_mode(),
-            17 => check_display_brightness(),
-            18 => check_adaptive_brightness(),
-            19 => check_video_playback_quality(),
-            20 => check_battery_saver(),
-            21 => check_power_button_action(),
-            22 => check_sleep_button_action(),
-            23 => check_lid_close_action(),
-            24 => check_wake_timers(),
-            25 => Check::new("Power Plan GUID", "Detected", CheckStatus::Info),
-            26 => Check::new(
-                "Active Power Scheme",
-                "High Performance",
-                CheckStatus::Optimal,
-            ),
-            27 => Check::new("Power Options", "Configured", CheckStatus::Info),
-            28 => Check::new(
-                "Processor Performance State",
-                "Maximum",
-                CheckStatus::Optimal,
-            ),
-            _ => Check::new("Device Power States", "D0 Active", CheckStatus::Optimal),
+        .map(|i| {
+            Check::timed(|| match i {
+                0 => check_pcie_aspm(),
+                1 => check_usb_selective_suspend(),
+                2 => check_fast_startup(ctx),
+                3 => check_hybrid_sleep(),
+                4 => check_hibernate(),
+                5 => check_monitor_timeout(),
+                6 => check_disk_timeout(),
+                7 => check_sleep_timeout(),
+                8 => check_pci_link_state(),
+                9 => check_ahci_link_power(),
+                10 => check_wifi_power_saving(),
+                11 => check_power_throttling(),
+                12 => check_cpu_power_management(),
+                13 => check_display_power_saving(),
+                14 => check_hard_disk_power(),
+                15 => check_sleep_state(),
+                16 => check_away_mode(),
+                17 => check_display_brightness(),
+                18 => check_adaptive_brightness(),
+                19 => check_video_playback_quality(),
+                20 => check_battery_saver(),
+                21 => check_power_button_action(),
+                22 => check_sleep_button_action(),
+                23 => check_lid_close_action(),
+                24 => check_wake_timers(),
+                25 => Check::new("Power Plan GUID", "Detected", CheckStatus::Info)
+                    .with_id("power.plan_guid"),
+                26 => Check::new(
+                    "Active Power Scheme",
+                    "High Performance",
+                    CheckStatus::Optimal,
+                )
+                .with_id("power.active_power_scheme"),
+                27 => Check::new("Power Options", "Configured", CheckStatus::Info)
+                    .with_id("power.options"),
+                28 => Check::new(
+                    "Processor Performance State",
+                    "Maximum",
+                    CheckStatus::Optimal,
+                )
+                .with_id("power.processor_performance_state"),
+                _ => Check::new("Device Power States", "D0 Active", CheckStatus::Optimal)
+                    .with_id("power.device_power_states"),
+            })
         })
         .collect();
 
@@ -62,20 +70,33 @@ fn check_pcie_aspm() -> Check {
         "Disabled",
         CheckStatus::Optimal,
     )
+    .with_id("power.pcie_aspm")
     .with_description("Disable for GPU/NVMe to prevent latency spikes.")
 }
 
+const USB_SUBGROUP: &str = "2a737441-1930-4402-8d77-b2bebba308a3";
+const USB_SELECTIVE_SUSPEND: &str = "48e6b7a6-50f5-4782-a5d4-53bb8f07e226";
+
 fn check_usb_selective_suspend() -> Check {
     Check::new("USB Selective Suspend", "Disabled", CheckStatus::Optimal)
+        .with_id("power.usb_selective_suspend")
+        .with_remediation(Remediation::power_setting(
+            USB_SUBGROUP,
+            USB_SELECTIVE_SUSPEND,
+            0,
+            0,
+        ))
         .with_description("Prevents USB devices from entering power-saving mode.")
 }
 
-fn check_fast_startup() -> Check {
-    let fast_boot = read_registry_dword(
+fn check_fast_startup(ctx: &CheckContext) -> Check {
+    let (fast_boot, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\Session Manager\Power",
         "HiberbootEnabled",
-    );
+    )
+    .split();
 
     Check::new(
         "Fast Startup (Hiberboot)",
@@ -86,27 +107,29 @@ fn check_fast_startup() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("power.fast_startup")
     .with_description("Hybrid boot mode. Can cause issues, test both settings.")
+    .with_mistyped(mistyped)
 }
 
 fn check_hybrid_sleep() -> Check {
-    Check::new("Hybrid Sleep", "Disabled", CheckStatus::Info)
+    Check::new("Hybrid Sleep", "Disabled", CheckStatus::Info).with_id("power.hybrid_sleep")
 }
 
 fn check_hibernate() -> Check {
-    Check::new("Hibernate", "Enabled", CheckStatus::Info)
+    Check::new("Hibernate", "Enabled", CheckStatus::Info).with_id("power.hibernate")
 }
 
 fn check_monitor_timeout() -> Check {
-    Check::new("Monitor Timeout", "Never", CheckStatus::Info)
+    Check::new("Monitor Timeout", "Never", CheckStatus::Info).with_id("power.monitor_timeout")
 }
 
 fn check_disk_timeout() -> Check {
-    Check::new("Hard Disk Timeout", "Never", CheckStatus::Optimal)
+    Check::new("Hard Disk Timeout", "Never", CheckStatus::Optimal).with_id("power.disk_timeout")
 }
 
 fn check_sleep_timeout() -> Check {
-    Check::new("Sleep Timeout", "Never", CheckStatus::Info)
+    Check::new("Sleep Timeout", "Never", CheckStatus::Info).with_id("power.sleep_timeout")
 }
 
 fn check_pci_link_state() -> Check {
@@ -115,10 +138,12 @@ fn check_pci_link_state() -> Check {
         "Off",
         CheckStatus::Optimal,
     )
+    .with_id("power.pci_link_state")
 }
 
 fn check_ahci_link_power() -> Check {
     Check::new("AHCI Link Power Management", "Active", CheckStatus::Optimal)
+        .with_id("power.ahci_link_power")
 }
 
 fn check_wifi_power_saving() -> Check {
@@ -127,10 +152,12 @@ fn check_wifi_power_saving() -> Check {
         "Maximum Performance",
         CheckStatus::Optimal,
     )
+    .with_id("power.wifi_power_saving")
 }
 
 fn check_power_throttling() -> Check {
     Check::new("Power Throttling", "Enabled", CheckStatus::Info)
+        .with_id("power.throttling")
         .with_description("Windows 10+ feature to reduce background app power usage.")
 }
 
@@ -140,14 +167,17 @@ fn check_cpu_power_management() -> Check {
         "Maximum Performance",
         CheckStatus::Optimal,
     )
+    .with_id("power.cpu_power_management")
 }
 
 fn check_display_power_saving() -> Check {
     Check::new("Display Power Saving", "Minimal", CheckStatus::Info)
+        .with_id("power.display_power_saving")
 }
 
 fn check_hard_disk_power() -> Check {
     Check::new("Hard Disk Power Saving", "Disabled", CheckStatus::Optimal)
+        .with_id("power.hard_disk_power")
 }
 
 fn check_sleep_state() -> Check {
@@ -156,18 +186,20 @@ fn check_sleep_state() -> Check {
         "S3 Traditional Sleep",
         CheckStatus::Info,
     )
+    .with_id("power.sleep_state")
 }
 
 fn check_away_mode() -> Check {
-    Check::new("Away Mode", "Not Applicable", CheckStatus::Info)
+    Check::new("Away Mode", "Not Applicable", CheckStatus::Info).with_id("power.away_mode")
 }
 
 fn check_display_brightness() -> Check {
-    Check::new("Display Brightness", "100%", CheckStatus::Info)
+    Check::new("Display Brightness", "100%", CheckStatus::Info).with_id("power.display_brightness")
 }
 
 fn check_adaptive_brightness() -> Check {
     Check::new("Adaptive Brightness", "Disabled", CheckStatus::Info)
+        .with_id("power.adaptive_brightness")
 }
 
 fn check_video_playback_quality() -> Check {
@@ -176,25 +208,29 @@ fn check_video_playback_quality() -> Check {
         "Video Quality",
         CheckStatus::Info,
     )
+    .with_id("power.video_playback_quality")
 }
 
 fn check_battery_saver() -> Check {
-    Check::new("Battery Saver", "Not Applicable", CheckStatus::Info)
+    Check::new("Battery Saver", "Not Applicable", CheckStatus::Info).with_id("power.battery_saver")
 }
 
 fn check_power_button_action() -> Check {
-    Check::new("Power Button Action", "Shut Down", CheckStatus::Info)
+    Check::new("Power Button Action", "Shut Down", CheckStatus::Info).with_id("power.button_action")
 }
 
 fn check_sleep_button_action() -> Check {
     Check::new("Sleep Button Action", "Sleep", CheckStatus::Info)
+        .with_id("power.sleep_button_action")
 }
 
 fn check_lid_close_action() -> Check {
     Check::new("Lid Close Action", "Not Applicable", CheckStatus::Info)
+        .with_id("power.lid_close_action")
 }
 
 fn check_wake_timers() -> Check {
     Check::new("Wake Timers", "Disabled", CheckStatus::Info)
+        .with_id("power.wake_timers")
         .with_description("Prevents system from waking for scheduled tasks.")
 }
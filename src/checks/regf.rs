@@ -0,0 +1,397 @@
+//! Reader for offline registry hive files (the "regf" format).
+//!
+//! Lets the auditor run against SYSTEM, SOFTWARE and NTUSER.DAT files copied
+//! from a mounted image, a backup or a forensic capture. Only the primary hive
+//! file is read; pending transaction logs (.LOG1/.LOG2) are not replayed.
+
+use super::registry::{filetime_to_utc, Hkey, RegValue, RegistrySource, REG_DWORD};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+const BASE_BLOCK_SIZE: usize = 4096;
+const KEY_COMP_NAME: u16 = 0x0020;
+const VALUE_COMP_NAME: u16 = 0x0001;
+const DATA_INLINE: u32 = 0x8000_0000;
+const BIG_DATA_SEGMENT: usize = 16344;
+const MAX_LIST_DEPTH: usize = 8;
+
+#[derive(thiserror::Error, Debug)]
+pub enum RegfError {
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a registry hive (bad {0} signature)")]
+    Signature(&'static str),
+    #[error("corrupt hive: {0}")]
+    Corrupt(String),
+}
+
+/// A parsed hive file held in memory.
+pub struct Hive {
+    data: Vec<u8>,
+    root: u32,
+    minor_version: u32,
+}
+
+/// A key node ("nk" cell) inside a hive.
+#[derive(Clone, Copy)]
+pub struct KeyNode<'a> {
+    hive: &'a Hive,
+    offset: u32,
+}
+
+/// A value ("vk" cell) inside a hive.
+#[derive(Clone, Copy)]
+pub struct ValueNode<'a> {
+    hive: &'a Hive,
+    offset: u32,
+}
+
+fn u16_at(buf: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn u64_at(buf: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(buf.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+fn decode_name(raw: &[u8], compressed: bool) -> String {
+    if compressed {
+        raw.iter().map(|&b| b as char).collect()
+    } else {
+        decode_utf16(raw)
+    }
+}
+
+fn decode_utf16(raw: &[u8]) -> String {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+    String::from_utf16_lossy(&units[..len])
+}
+
+impl Hive {
+    pub fn open(path: &Path) -> Result<Self, RegfError> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, RegfError> {
+        if data.get(0..4) != Some(b"regf".as_slice()) {
+            return Err(RegfError::Signature("regf"));
+        }
+        if data.get(BASE_BLOCK_SIZE..BASE_BLOCK_SIZE + 4) != Some(b"hbin".as_slice()) {
+            return Err(RegfError::Signature("hbin"));
+        }
+
+        let minor_version = u32_at(&data, 24).unwrap_or(0);
+        let root = u32_at(&data, 36).ok_or_else(|| RegfError::Corrupt("no root".into()))?;
+
+        let hive = Self {
+            data,
+            root,
+            minor_version,
+        };
+        if hive.cell_with_sig(root, b"nk").is_none() {
+            return Err(RegfError::Corrupt(format!(
+                "root cell 0x{:x} is not a key node",
+                root
+            )));
+        }
+        Ok(hive)
+    }
+
+    /// Cell payload (without the size header) at a hive-bin relative offset.
+    fn cell(&self, offset: u32) -> Option<&[u8]> {
+        let pos = BASE_BLOCK_SIZE.checked_add(offset as usize)?;
+        let raw_size = u32_at(&self.data, pos)? as i32;
+        let size = raw_size.unsigned_abs() as usize;
+        if size < 4 {
+            return None;
+        }
+        self.data.get(pos + 4..pos + size)
+    }
+
+    fn cell_with_sig(&self, offset: u32, sig: &[u8; 2]) -> Option<&[u8]> {
+        self.cell(offset)
+            .filter(|c| c.get(0..2) == Some(sig.as_slice()))
+    }
+
+    pub fn root(&self) -> KeyNode<'_> {
+        KeyNode {
+            hive: self,
+            offset: self.root,
+        }
+    }
+
+    /// Resolves a backslash-separated path below the root, case-insensitively.
+    pub fn open_key(&self, path: &str) -> Option<KeyNode<'_>> {
+        path.split('\\')
+            .filter(|part| !part.is_empty())
+            .try_fold(self.root(), |key, part| key.subkey(part))
+    }
+
+    /// Offsets of the key nodes referenced by a subkey list cell.
+    fn collect_subkeys(&self, list: u32, depth: usize, out: &mut Vec<u32>) {
+        if depth > MAX_LIST_DEPTH {
+            return;
+        }
+        let Some(cell) = self.cell(list) else {
+            return;
+        };
+        let count = u16_at(cell, 2).unwrap_or(0) as usize;
+        match cell.get(0..2) {
+            Some(b"lf") | Some(b"lh") => {
+                for i in 0..count {
+                    if let Some(off) = u32_at(cell, 4 + i * 8) {
+                        out.push(off);
+                    }
+                }
+            }
+            Some(b"li") => {
+                for i in 0..count {
+                    if let Some(off) = u32_at(cell, 4 + i * 4) {
+                        out.push(off);
+                    }
+                }
+            }
+            Some(b"ri") => {
+                for i in 0..count {
+                    if let Some(off) = u32_at(cell, 4 + i * 4) {
+                        self.collect_subkeys(off, depth + 1, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> KeyNode<'a> {
+    fn nk(&self) -> &'a [u8] {
+        self.hive.cell_with_sig(self.offset, b"nk").unwrap_or(&[])
+    }
+
+    pub fn name(&self) -> String {
+        let nk = self.nk();
+        let flags = u16_at(nk, 2).unwrap_or(0);
+        let len = u16_at(nk, 72).unwrap_or(0) as usize;
+        nk.get(76..76 + len)
+            .map(|raw| decode_name(raw, flags & KEY_COMP_NAME != 0))
+            .unwrap_or_default()
+    }
+
+    /// Last write time as a raw FILETIME (100ns ticks since 1601).
+    pub fn last_written(&self) -> u64 {
+        u64_at(self.nk(), 4).unwrap_or(0)
+    }
+
+    pub fn subkeys(&self) -> Vec<KeyNode<'a>> {
+        let nk = self.nk();
+        let mut offsets = Vec::new();
+        if u32_at(nk, 20).unwrap_or(0) > 0 {
+            if let Some(list) = u32_at(nk, 28) {
+                self.hive.collect_subkeys(list, 0, &mut offsets);
+            }
+        }
+        offsets
+            .into_iter()
+            .filter(|&off| self.hive.cell_with_sig(off, b"nk").is_some())
+            .map(|offset| KeyNode {
+                hive: self.hive,
+                offset,
+            })
+            .collect()
+    }
+
+    pub fn subkey(&self, name: &str) -> Option<KeyNode<'a>> {
+        self.subkeys()
+            .into_iter()
+            .find(|k| k.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn values(&self) -> Vec<ValueNode<'a>> {
+        let nk = self.nk();
+        let count = u32_at(nk, 36).unwrap_or(0) as usize;
+        let list = match (count, u32_at(nk, 40)) {
+            (0, _) | (_, None) => return Vec::new(),
+            (_, Some(list)) => self.hive.cell(list).unwrap_or(&[]),
+        };
+        (0..count)
+            .filter_map(|i| u32_at(list, i * 4))
+            .filter(|&off| self.hive.cell_with_sig(off, b"vk").is_some())
+            .map(|offset| ValueNode {
+                hive: self.hive,
+                offset,
+            })
+            .collect()
+    }
+
+    /// Looks up a value by name; an empty name is the key's default value.
+    pub fn value(&self, name: &str) -> Option<ValueNode<'a>> {
+        self.values()
+            .into_iter()
+            .find(|v| v.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl<'a> ValueNode<'a> {
+    fn vk(&self) -> &'a [u8] {
+        self.hive.cell_with_sig(self.offset, b"vk").unwrap_or(&[])
+    }
+
+    pub fn name(&self) -> String {
+        let vk = self.vk();
+        let len = u16_at(vk, 2).unwrap_or(0) as usize;
+        let flags = u16_at(vk, 16).unwrap_or(0);
+        vk.get(20..20 + len)
+            .map(|raw| decode_name(raw, flags & VALUE_COMP_NAME != 0))
+            .unwrap_or_default()
+    }
+
+    pub fn data_type(&self) -> u32 {
+        u32_at(self.vk(), 12).unwrap_or(0)
+    }
+
+    /// Raw value data, following inline storage and big-data ("db") chains.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        let vk = self.vk();
+        let raw_size = u32_at(vk, 4)?;
+        let offset = u32_at(vk, 8)?;
+
+        if raw_size & DATA_INLINE != 0 {
+            let size = (raw_size & !DATA_INLINE) as usize;
+            return Some(offset.to_le_bytes()[..size.min(4)].to_vec());
+        }
+
+        let size = raw_size as usize;
+        if size > BIG_DATA_SEGMENT && self.hive.minor_version >= 4 {
+            if let Some(db) = self.hive.cell_with_sig(offset, b"db") {
+                return self.big_data(db, size);
+            }
+        }
+        self.hive.cell(offset)?.get(..size).map(<[u8]>::to_vec)
+    }
+
+    fn big_data(&self, db: &[u8], size: usize) -> Option<Vec<u8>> {
+        let segments = u16_at(db, 2)? as usize;
+        let list = self.hive.cell(u32_at(db, 4)?)?;
+        let mut out = Vec::with_capacity(size);
+        for i in 0..segments {
+            let segment = self.hive.cell(u32_at(list, i * 4)?)?;
+            let take = (size - out.len()).min(BIG_DATA_SEGMENT).min(segment.len());
+            out.extend_from_slice(&segment[..take]);
+        }
+        (out.len() == size).then_some(out)
+    }
+}
+
+/// Hive files mounted where a live system would expose them.
+///
+/// SYSTEM and SOFTWARE appear under HKLM, NTUSER.DAT under HKCU.
+/// `CurrentControlSet` is resolved through `SYSTEM\Select\Current`.
+#[derive(Default)]
+pub struct OfflineRegistry {
+    system: Option<Hive>,
+    software: Option<Hive>,
+    ntuser: Option<Hive>,
+    current_control_set: Option<String>,
+}
+
+impl OfflineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_system(mut self, hive: Hive) -> Self {
+        self.current_control_set = hive
+            .open_key("Select")
+            .and_then(|select| select.value("Current"))
+            .filter(|v| v.data_type() == REG_DWORD)
+            .and_then(|v| v.data())
+            .and_then(|d| u32_at(&d, 0))
+            .map(|n| format!("ControlSet{:03}", n));
+        self.system = Some(hive);
+        self
+    }
+
+    pub fn with_software(mut self, hive: Hive) -> Self {
+        self.software = Some(hive);
+        self
+    }
+
+    pub fn with_ntuser(mut self, hive: Hive) -> Self {
+        self.ntuser = Some(hive);
+        self
+    }
+
+    /// Picks the hive that backs `subkey` and returns the path inside it.
+    fn locate(&self, hkey: Hkey, subkey: &str) -> Option<(&Hive, String)> {
+        let subkey = subkey.trim_matches('\\');
+        match hkey {
+            Hkey::CurrentUser => Some((self.ntuser.as_ref()?, subkey.to_string())),
+            Hkey::LocalMachine => {
+                let (top, rest) = subkey.split_once('\\').unwrap_or((subkey, ""));
+                if top.eq_ignore_ascii_case("SOFTWARE") {
+                    Some((self.software.as_ref()?, rest.to_string()))
+                } else if top.eq_ignore_ascii_case("SYSTEM") {
+                    let (first, tail) = rest.split_once('\\').unwrap_or((rest, ""));
+                    let rest = match &self.current_control_set {
+                        Some(ccs) if first.eq_ignore_ascii_case("CurrentControlSet") => {
+                            format!("{}\\{}", ccs, tail)
+                        }
+                        _ => rest.to_string(),
+                    };
+                    Some((self.system.as_ref()?, rest))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn key(&self, hkey: Hkey, subkey: &str) -> Option<KeyNode<'_>> {
+        let (hive, path) = self.locate(hkey, subkey)?;
+        hive.open_key(&path)
+    }
+}
+
+impl RegistrySource for OfflineRegistry {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue> {
+        let value = self.key(hkey, subkey)?.value(value_name)?;
+        Some(RegValue::from_raw(value.data_type(), &value.data()?))
+    }
+
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool {
+        self.key(hkey, subkey).is_some()
+    }
+
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>> {
+        Some(
+            self.key(hkey, subkey)?
+                .subkeys()
+                .iter()
+                .map(KeyNode::name)
+                .collect(),
+        )
+    }
+
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        Some(
+            self.key(hkey, subkey)?
+                .values()
+                .iter()
+                .filter_map(|v| Some((v.name(), RegValue::from_raw(v.data_type(), &v.data()?))))
+                .collect(),
+        )
+    }
+
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>> {
+        filetime_to_utc(self.key(hkey, subkey)?.last_written())
+    }
+}
@@ -0,0 +1,896 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Hkey {
+    #[serde(rename = "HKLM")]
+    LocalMachine,
+    #[serde(rename = "HKCU")]
+    CurrentUser,
+    #[serde(rename = "HKCR")]
+    ClassesRoot,
+    #[serde(rename = "HKU")]
+    Users,
+    #[serde(rename = "HKCC")]
+    CurrentConfig,
+}
+
+pub const HKEY_LOCAL_MACHINE: Hkey = Hkey::LocalMachine;
+pub const HKEY_CURRENT_USER: Hkey = Hkey::CurrentUser;
+
+impl Hkey {
+    /// The root name as regedit writes it in .reg files.
+    pub fn full_name(&self) -> &'static str {
+        match self {
+            Hkey::LocalMachine => "HKEY_LOCAL_MACHINE",
+            Hkey::CurrentUser => "HKEY_CURRENT_USER",
+            Hkey::ClassesRoot => "HKEY_CLASSES_ROOT",
+            Hkey::Users => "HKEY_USERS",
+            Hkey::CurrentConfig => "HKEY_CURRENT_CONFIG",
+        }
+    }
+
+    /// Accepts both the long and the abbreviated root name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "HKEY_LOCAL_MACHINE" | "HKLM" => Some(Hkey::LocalMachine),
+            "HKEY_CURRENT_USER" | "HKCU" => Some(Hkey::CurrentUser),
+            "HKEY_CLASSES_ROOT" | "HKCR" => Some(Hkey::ClassesRoot),
+            "HKEY_USERS" | "HKU" => Some(Hkey::Users),
+            "HKEY_CURRENT_CONFIG" | "HKCC" => Some(Hkey::CurrentConfig),
+            _ => None,
+        }
+    }
+}
+
+pub const REG_SZ: u32 = 1;
+pub const REG_EXPAND_SZ: u32 = 2;
+pub const REG_BINARY: u32 = 3;
+pub const REG_DWORD: u32 = 4;
+pub const REG_MULTI_SZ: u32 = 7;
+pub const REG_QWORD: u32 = 11;
+
+/// Where check modules read registry values from.
+///
+/// `LiveRegistry` talks to the running machine; `MemoryRegistry` holds
+/// captured or hand-built state so checks can be audited anywhere.
+pub trait RegistrySource: Send + Sync {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue>;
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool;
+    /// Names of the direct subkeys, or `None` if the key does not exist.
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>>;
+    /// Every value stored directly under the key, or `None` if it does not exist.
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>>;
+    /// When the key or one of its values was last modified, if known.
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>>;
+
+    fn read_dword(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<u32> {
+        match self.read_value(hkey, subkey, value_name)? {
+            RegValue::Dword(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn read_string(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<String> {
+        match self.read_value(hkey, subkey, value_name)? {
+            RegValue::String(s) | RegValue::ExpandString(s) => Some(s),
+            RegValue::MultiString(items) => items.into_iter().next(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum RegValue {
+    String(String),
+    ExpandString(String),
+    MultiString(Vec<String>),
+    Dword(u32),
+    Qword(u64),
+    Binary(Vec<u8>),
+    /// Any other type (REG_NONE, REG_LINK, resource lists, ...) kept verbatim.
+    Other(u32, Vec<u8>),
+}
+
+impl RegValue {
+    /// Decodes raw registry data of the given type.
+    pub fn from_raw(kind: u32, data: &[u8]) -> Self {
+        match kind {
+            REG_SZ => RegValue::String(utf16_to_string(data)),
+            REG_EXPAND_SZ => RegValue::ExpandString(utf16_to_string(data)),
+            REG_MULTI_SZ => RegValue::MultiString(utf16_to_multi(data)),
+            REG_BINARY => RegValue::Binary(data.to_vec()),
+            REG_DWORD if data.len() == 4 => {
+                RegValue::Dword(u32::from_le_bytes(data.try_into().unwrap()))
+            }
+            REG_QWORD if data.len() == 8 => {
+                RegValue::Qword(u64::from_le_bytes(data.try_into().unwrap()))
+            }
+            _ => RegValue::Other(kind, data.to_vec()),
+        }
+    }
+
+    pub fn kind(&self) -> u32 {
+        match self {
+            RegValue::String(_) => REG_SZ,
+            RegValue::ExpandString(_) => REG_EXPAND_SZ,
+            RegValue::MultiString(_) => REG_MULTI_SZ,
+            RegValue::Dword(_) => REG_DWORD,
+            RegValue::Qword(_) => REG_QWORD,
+            RegValue::Binary(_) => REG_BINARY,
+            RegValue::Other(kind, _) => *kind,
+        }
+    }
+
+    /// The registry type name, e.g. `REG_DWORD`.
+    pub fn type_name(&self) -> String {
+        match self.kind() {
+            REG_SZ => "REG_SZ".to_string(),
+            REG_EXPAND_SZ => "REG_EXPAND_SZ".to_string(),
+            REG_BINARY => "REG_BINARY".to_string(),
+            REG_DWORD => "REG_DWORD".to_string(),
+            REG_MULTI_SZ => "REG_MULTI_SZ".to_string(),
+            REG_QWORD => "REG_QWORD".to_string(),
+            other => format!("type {}", other),
+        }
+    }
+
+    /// The bytes the registry stores for this value.
+    pub fn to_raw(&self) -> Vec<u8> {
+        fn utf16z(s: &str) -> Vec<u8> {
+            s.encode_utf16()
+                .chain(std::iter::once(0))
+                .flat_map(u16::to_le_bytes)
+                .collect()
+        }
+
+        match self {
+            RegValue::String(s) | RegValue::ExpandString(s) => utf16z(s),
+            RegValue::MultiString(items) => {
+                let mut raw: Vec<u8> = items.iter().flat_map(|s| utf16z(s)).collect();
+                raw.extend_from_slice(&[0, 0]);
+                raw
+            }
+            RegValue::Dword(v) => v.to_le_bytes().to_vec(),
+            RegValue::Qword(v) => v.to_le_bytes().to_vec(),
+            RegValue::Binary(data) | RegValue::Other(_, data) => data.clone(),
+        }
+    }
+}
+
+/// Outcome of reading a value as a specific type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegRead<T> {
+    Value(T),
+    Missing,
+    /// The value exists but is stored with a different type, e.g. a DWORD
+    /// setting written as REG_SZ "1". Windows ignores such values.
+    TypeMismatch(RegValue),
+}
+
+impl<T: RegType> RegRead<T> {
+    /// The usable value, if any, and what was stored instead when the type
+    /// is wrong. Checks grade the former and report the latter through
+    /// `Check::with_mistyped`.
+    pub fn split(self) -> (Option<T>, Option<Mistyped>) {
+        match self {
+            RegRead::Value(v) => (Some(v), None),
+            RegRead::Missing => (None, None),
+            RegRead::TypeMismatch(found) => (
+                None,
+                Some(Mistyped {
+                    found,
+                    expected: T::TYPE_NAME,
+                }),
+            ),
+        }
+    }
+}
+
+/// The registry type a typed read expects.
+pub trait RegType {
+    const TYPE_NAME: &'static str;
+}
+
+impl RegType for String {
+    const TYPE_NAME: &'static str = "REG_SZ";
+}
+
+impl RegType for u32 {
+    const TYPE_NAME: &'static str = "REG_DWORD";
+}
+
+impl RegType for u64 {
+    const TYPE_NAME: &'static str = "REG_QWORD";
+}
+
+impl RegType for Vec<String> {
+    const TYPE_NAME: &'static str = "REG_MULTI_SZ";
+}
+
+impl RegType for Vec<u8> {
+    const TYPE_NAME: &'static str = "REG_BINARY";
+}
+
+/// A value stored with a different type than the check reads it as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mistyped {
+    pub found: RegValue,
+    pub expected: &'static str,
+}
+
+impl std::fmt::Display for Mistyped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Stored as {} (expected {})",
+            self.found.type_name(),
+            self.expected
+        )
+    }
+}
+
+/// Converts a FILETIME (100ns ticks since 1601) to UTC; zero means unknown.
+pub(crate) fn filetime_to_utc(ticks: u64) -> Option<DateTime<Utc>> {
+    const UNIX_EPOCH_TICKS: i64 = 116_444_736_000_000_000;
+    if ticks == 0 {
+        return None;
+    }
+    let since_unix = i64::try_from(ticks).ok()? - UNIX_EPOCH_TICKS;
+    DateTime::from_timestamp(
+        since_unix.div_euclid(10_000_000),
+        (since_unix.rem_euclid(10_000_000) * 100) as u32,
+    )
+}
+
+fn utf16_units(data: &[u8]) -> Vec<u16> {
+    data.chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect()
+}
+
+pub(crate) fn utf16_to_string(data: &[u8]) -> String {
+    let units = utf16_units(data);
+    let len = units.iter().position(|&c| c == 0).unwrap_or(units.len());
+    String::from_utf16_lossy(&units[..len])
+}
+
+fn utf16_to_multi(data: &[u8]) -> Vec<String> {
+    utf16_units(data)
+        .split(|&c| c == 0)
+        .take_while(|s| !s.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+/// The registry of the machine we are running on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveRegistry;
+
+#[cfg(windows)]
+impl RegistrySource for LiveRegistry {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue> {
+        let (kind, data) = live::OpenKey::open(hkey, subkey)?.query(value_name)?;
+        Some(RegValue::from_raw(kind, &data))
+    }
+
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool {
+        live::OpenKey::open(hkey, subkey).is_some()
+    }
+
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>> {
+        Some(live::OpenKey::open(hkey, subkey)?.subkey_names())
+    }
+
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        let values = live::OpenKey::open(hkey, subkey)?.raw_values();
+        Some(
+            values
+                .into_iter()
+                .map(|(name, kind, data)| (name, RegValue::from_raw(kind, &data)))
+                .collect(),
+        )
+    }
+
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>> {
+        filetime_to_utc(live::OpenKey::open(hkey, subkey)?.last_write())
+    }
+}
+
+#[cfg(windows)]
+impl LiveRegistry {
+    /// Writes a value, creating the key (and its parents) if needed.
+    pub fn set_value(
+        &self,
+        hkey: Hkey,
+        subkey: &str,
+        value_name: &str,
+        value: &RegValue,
+    ) -> std::io::Result<()> {
+        live::set_value(hkey, subkey, value_name, value.kind(), &value.to_raw())
+    }
+
+    /// Deletes a value; a value that is already gone is not an error.
+    pub fn delete_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> std::io::Result<()> {
+        live::delete_value(hkey, subkey, value_name)
+    }
+}
+
+#[cfg(not(windows))]
+impl LiveRegistry {
+    pub fn set_value(
+        &self,
+        _hkey: Hkey,
+        _subkey: &str,
+        _value_name: &str,
+        _value: &RegValue,
+    ) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "registry writes need Windows",
+        ))
+    }
+
+    pub fn delete_value(
+        &self,
+        _hkey: Hkey,
+        _subkey: &str,
+        _value_name: &str,
+    ) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "registry writes need Windows",
+        ))
+    }
+}
+
+#[cfg(not(windows))]
+impl RegistrySource for LiveRegistry {
+    fn read_value(&self, _hkey: Hkey, _subkey: &str, _value_name: &str) -> Option<RegValue> {
+        None
+    }
+
+    fn key_exists(&self, _hkey: Hkey, _subkey: &str) -> bool {
+        false
+    }
+
+    fn subkeys(&self, _hkey: Hkey, _subkey: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    fn values(&self, _hkey: Hkey, _subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        None
+    }
+
+    fn last_write_time(&self, _hkey: Hkey, _subkey: &str) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+#[cfg(windows)]
+mod live {
+    use super::Hkey;
+    use std::ptr;
+    use winapi::shared::minwindef::{FILETIME, HKEY};
+    use winapi::um::winnt::{KEY_READ, KEY_SET_VALUE, KEY_WRITE, REG_OPTION_NON_VOLATILE};
+    use winapi::um::winreg::{
+        RegCloseKey, RegCreateKeyExW, RegDeleteValueW, RegEnumKeyExW, RegEnumValueW, RegOpenKeyExW,
+        RegQueryInfoKeyW, RegQueryValueExW, RegSetValueExW, HKEY_CLASSES_ROOT, HKEY_CURRENT_CONFIG,
+        HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, HKEY_USERS,
+    };
+
+    const ERROR_SUCCESS: i32 = 0;
+    const ERROR_FILE_NOT_FOUND: i32 = 2;
+
+    fn os_error(status: i32) -> std::io::Error {
+        std::io::Error::from_raw_os_error(status)
+    }
+
+    pub fn set_value(
+        hkey: Hkey,
+        subkey: &str,
+        value_name: &str,
+        kind: u32,
+        data: &[u8],
+    ) -> std::io::Result<()> {
+        let subkey_wide = wide(subkey);
+        let value_wide = wide(value_name);
+        let mut handle: HKEY = ptr::null_mut();
+        let status = unsafe {
+            RegCreateKeyExW(
+                root(hkey),
+                subkey_wide.as_ptr(),
+                0,
+                ptr::null_mut(),
+                REG_OPTION_NON_VOLATILE,
+                KEY_WRITE,
+                ptr::null_mut(),
+                &mut handle,
+                ptr::null_mut(),
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return Err(os_error(status));
+        }
+        let key = OpenKey(handle);
+        let status = unsafe {
+            RegSetValueExW(
+                key.0,
+                value_wide.as_ptr(),
+                0,
+                kind,
+                data.as_ptr(),
+                data.len() as u32,
+            )
+        };
+        if status == ERROR_SUCCESS {
+            Ok(())
+        } else {
+            Err(os_error(status))
+        }
+    }
+
+    pub fn delete_value(hkey: Hkey, subkey: &str, value_name: &str) -> std::io::Result<()> {
+        let subkey_wide = wide(subkey);
+        let value_wide = wide(value_name);
+        let mut handle: HKEY = ptr::null_mut();
+        let status = unsafe {
+            RegOpenKeyExW(
+                root(hkey),
+                subkey_wide.as_ptr(),
+                0,
+                KEY_SET_VALUE,
+                &mut handle,
+            )
+        };
+        match status {
+            ERROR_SUCCESS => {}
+            ERROR_FILE_NOT_FOUND => return Ok(()),
+            other => return Err(os_error(other)),
+        }
+        let key = OpenKey(handle);
+        match unsafe { RegDeleteValueW(key.0, value_wide.as_ptr()) } {
+            ERROR_SUCCESS | ERROR_FILE_NOT_FOUND => Ok(()),
+            other => Err(os_error(other)),
+        }
+    }
+
+    pub fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn root(hkey: Hkey) -> HKEY {
+        match hkey {
+            Hkey::LocalMachine => HKEY_LOCAL_MACHINE,
+            Hkey::CurrentUser => HKEY_CURRENT_USER,
+            Hkey::ClassesRoot => HKEY_CLASSES_ROOT,
+            Hkey::Users => HKEY_USERS,
+            Hkey::CurrentConfig => HKEY_CURRENT_CONFIG,
+        }
+    }
+
+    /// Sizes reported by RegQueryInfoKeyW, in characters / bytes.
+    struct KeyInfo {
+        subkeys: u32,
+        max_subkey_len: u32,
+        values: u32,
+        max_value_name_len: u32,
+        max_value_len: u32,
+        last_write: u64,
+    }
+
+    pub struct OpenKey(HKEY);
+
+    impl OpenKey {
+        pub fn open(hkey: Hkey, subkey: &str) -> Option<Self> {
+            let subkey_wide = wide(subkey);
+            let mut handle: HKEY = ptr::null_mut();
+            let status = unsafe {
+                RegOpenKeyExW(root(hkey), subkey_wide.as_ptr(), 0, KEY_READ, &mut handle)
+            };
+            if status == ERROR_SUCCESS {
+                Some(OpenKey(handle))
+            } else {
+                None
+            }
+        }
+
+        fn info(&self) -> Option<KeyInfo> {
+            let mut info = KeyInfo {
+                subkeys: 0,
+                max_subkey_len: 0,
+                values: 0,
+                max_value_name_len: 0,
+                max_value_len: 0,
+                last_write: 0,
+            };
+            let mut last_write = FILETIME {
+                dwLowDateTime: 0,
+                dwHighDateTime: 0,
+            };
+            let status = unsafe {
+                RegQueryInfoKeyW(
+                    self.0,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    &mut info.subkeys,
+                    &mut info.max_subkey_len,
+                    ptr::null_mut(),
+                    &mut info.values,
+                    &mut info.max_value_name_len,
+                    &mut info.max_value_len,
+                    ptr::null_mut(),
+                    &mut last_write,
+                )
+            };
+            info.last_write =
+                (u64::from(last_write.dwHighDateTime) << 32) | u64::from(last_write.dwLowDateTime);
+            (status == ERROR_SUCCESS).then_some(info)
+        }
+
+        /// Returns the value type and raw data, sizing the buffer from the registry.
+        pub fn query(&self, value_name: &str) -> Option<(u32, Vec<u8>)> {
+            let value_wide = wide(value_name);
+            let mut size = 0u32;
+            let mut data_type = 0u32;
+
+            unsafe {
+                if RegQueryValueExW(
+                    self.0,
+                    value_wide.as_ptr(),
+                    ptr::null_mut(),
+                    &mut data_type,
+                    ptr::null_mut(),
+                    &mut size,
+                ) != ERROR_SUCCESS
+                {
+                    return None;
+                }
+
+                let mut buffer = vec![0u8; size as usize];
+                if RegQueryValueExW(
+                    self.0,
+                    value_wide.as_ptr(),
+                    ptr::null_mut(),
+                    &mut data_type,
+                    buffer.as_mut_ptr(),
+                    &mut size,
+                ) != ERROR_SUCCESS
+                {
+                    return None;
+                }
+                buffer.truncate(size as usize);
+                Some((data_type, buffer))
+            }
+        }
+
+        /// Raw FILETIME of the last write, 0 if it cannot be queried.
+        pub fn last_write(&self) -> u64 {
+            self.info().map(|info| info.last_write).unwrap_or(0)
+        }
+
+        pub fn subkey_names(&self) -> Vec<String> {
+            let Some(info) = self.info() else {
+                return Vec::new();
+            };
+            let mut names = Vec::with_capacity(info.subkeys as usize);
+            let mut buffer = vec![0u16; info.max_subkey_len as usize + 1];
+
+            for index in 0..info.subkeys {
+                let mut len = buffer.len() as u32;
+                let status = unsafe {
+                    RegEnumKeyExW(
+                        self.0,
+                        index,
+                        buffer.as_mut_ptr(),
+                        &mut len,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                    )
+                };
+                if status == ERROR_SUCCESS {
+                    names.push(String::from_utf16_lossy(&buffer[..len as usize]));
+                }
+            }
+            names
+        }
+
+        /// Every value under the key as (name, type, raw data).
+        pub fn raw_values(&self) -> Vec<(String, u32, Vec<u8>)> {
+            let Some(info) = self.info() else {
+                return Vec::new();
+            };
+            let mut values = Vec::with_capacity(info.values as usize);
+            let mut name = vec![0u16; info.max_value_name_len as usize + 1];
+            let mut data = vec![0u8; info.max_value_len as usize];
+
+            for index in 0..info.values {
+                let mut name_len = name.len() as u32;
+                let mut data_len = data.len() as u32;
+                let mut data_type = 0u32;
+                let status = unsafe {
+                    RegEnumValueW(
+                        self.0,
+                        index,
+                        name.as_mut_ptr(),
+                        &mut name_len,
+                        ptr::null_mut(),
+                        &mut data_type,
+                        data.as_mut_ptr(),
+                        &mut data_len,
+                    )
+                };
+                if status == ERROR_SUCCESS {
+                    values.push((
+                        String::from_utf16_lossy(&name[..name_len as usize]),
+                        data_type,
+                        data[..data_len as usize].to_vec(),
+                    ));
+                }
+            }
+            values
+        }
+    }
+
+    impl Drop for OpenKey {
+        fn drop(&mut self) {
+            unsafe {
+                RegCloseKey(self.0);
+            }
+        }
+    }
+}
+
+/// An in-memory registry, keyed case-insensitively like the real one.
+///
+/// Parent keys of every stored key exist implicitly.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRegistry {
+    keys: BTreeMap<(Hkey, String), MemoryKey>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MemoryKey {
+    /// The path as first written, so enumeration keeps the original case.
+    path: String,
+    values: Vec<(String, RegValue)>,
+    last_write: Option<DateTime<Utc>>,
+}
+
+fn normalize(subkey: &str) -> String {
+    subkey.trim_matches('\\').to_lowercase()
+}
+
+/// An HKLM subkey in the form used to match it against known keys: lower
+/// case, without empty segments, and with a numbered `ControlSetNNN` read as
+/// `CurrentControlSet`, since a write through either lands on the same value.
+pub fn canonical_key_path(subkey: &str) -> String {
+    let subkey = subkey.trim().to_ascii_lowercase();
+    let mut segments: Vec<&str> = subkey.split('\\').filter(|s| !s.is_empty()).collect();
+    let numbered = |segment: &str| {
+        segment
+            .strip_prefix("controlset")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+    if segments.len() > 1 && segments[0] == "system" && numbered(segments[1]) {
+        segments[1] = "currentcontrolset";
+    }
+    segments.join("\\")
+}
+
+impl MemoryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, hkey: Hkey, subkey: &str) -> Self {
+        self.create_key(hkey, subkey);
+        self
+    }
+
+    pub fn with_dword(mut self, hkey: Hkey, subkey: &str, value_name: &str, value: u32) -> Self {
+        self.set_value(hkey, subkey, value_name, RegValue::Dword(value));
+        self
+    }
+
+    pub fn with_string(mut self, hkey: Hkey, subkey: &str, value_name: &str, value: &str) -> Self {
+        self.set_value(
+            hkey,
+            subkey,
+            value_name,
+            RegValue::String(value.to_string()),
+        );
+        self
+    }
+
+    pub fn with_value(
+        mut self,
+        hkey: Hkey,
+        subkey: &str,
+        value_name: &str,
+        value: RegValue,
+    ) -> Self {
+        self.set_value(hkey, subkey, value_name, value);
+        self
+    }
+
+    pub fn with_last_write(mut self, hkey: Hkey, subkey: &str, time: DateTime<Utc>) -> Self {
+        self.create_key(hkey, subkey);
+        self.keys
+            .get_mut(&(hkey, normalize(subkey)))
+            .unwrap()
+            .last_write = Some(time);
+        self
+    }
+
+    pub fn create_key(&mut self, hkey: Hkey, subkey: &str) {
+        self.keys
+            .entry((hkey, normalize(subkey)))
+            .or_insert_with(|| MemoryKey {
+                path: subkey.trim_matches('\\').to_string(),
+                values: Vec::new(),
+                last_write: None,
+            });
+    }
+
+    pub fn set_value(&mut self, hkey: Hkey, subkey: &str, value_name: &str, value: RegValue) {
+        self.create_key(hkey, subkey);
+        let values = &mut self
+            .keys
+            .get_mut(&(hkey, normalize(subkey)))
+            .unwrap()
+            .values;
+        match values
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value_name))
+        {
+            Some(slot) => slot.1 = value,
+            None => values.push((value_name.to_string(), value)),
+        }
+    }
+
+    /// Removes a value, returning whether it existed.
+    pub fn delete_value(&mut self, hkey: Hkey, subkey: &str, value_name: &str) -> bool {
+        let Some(key) = self.keys.get_mut(&(hkey, normalize(subkey))) else {
+            return false;
+        };
+        let before = key.values.len();
+        key.values
+            .retain(|(name, _)| !name.eq_ignore_ascii_case(value_name));
+        key.values.len() != before
+    }
+
+    pub fn value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<&RegValue> {
+        self.keys
+            .get(&(hkey, normalize(subkey)))?
+            .values
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(value_name))
+            .map(|(_, value)| value)
+    }
+}
+
+impl RegistrySource for MemoryRegistry {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue> {
+        self.value(hkey, subkey, value_name).cloned()
+    }
+
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool {
+        let wanted = normalize(subkey);
+        let prefix = format!("{}\\", wanted);
+        self.keys
+            .keys()
+            .any(|(h, path)| *h == hkey && (*path == wanted || path.starts_with(&prefix)))
+    }
+
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>> {
+        if !self.key_exists(hkey, subkey) {
+            return None;
+        }
+        let wanted = normalize(subkey);
+        let depth = if wanted.is_empty() {
+            0
+        } else {
+            wanted.split('\\').count()
+        };
+        let prefix = if wanted.is_empty() {
+            String::new()
+        } else {
+            format!("{}\\", wanted)
+        };
+
+        let mut seen = BTreeSet::new();
+        let mut names = Vec::new();
+        for ((h, path), key) in &self.keys {
+            if *h != hkey || !path.starts_with(&prefix) || *path == wanted {
+                continue;
+            }
+            if let Some(name) = key.path.split('\\').nth(depth) {
+                if seen.insert(name.to_lowercase()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        Some(names)
+    }
+
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        match self.keys.get(&(hkey, normalize(subkey))) {
+            Some(key) => Some(key.values.clone()),
+            None if self.key_exists(hkey, subkey) => Some(Vec::new()),
+            None => None,
+        }
+    }
+
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>> {
+        self.keys.get(&(hkey, normalize(subkey)))?.last_write
+    }
+}
+
+/// Wraps another source and remembers every key that was read through it.
+///
+/// Running the checks through this tells the backup which keys they touch.
+pub struct TrackingRegistry {
+    inner: Arc<dyn RegistrySource>,
+    touched: Mutex<BTreeSet<(Hkey, String)>>,
+}
+
+impl TrackingRegistry {
+    pub fn new(inner: Arc<dyn RegistrySource>) -> Self {
+        Self {
+            inner,
+            touched: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Touched keys, deduplicated case-insensitively and sorted.
+    pub fn touched(&self) -> Vec<(Hkey, String)> {
+        let touched = self.touched.lock().unwrap();
+        let mut seen = BTreeSet::new();
+        touched
+            .iter()
+            .filter(|(hkey, path)| seen.insert((*hkey, path.to_lowercase())))
+            .cloned()
+            .collect()
+    }
+
+    fn touch(&self, hkey: Hkey, subkey: &str) {
+        self.touched
+            .lock()
+            .unwrap()
+            .insert((hkey, subkey.trim_matches('\\').to_string()));
+    }
+}
+
+impl RegistrySource for TrackingRegistry {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue> {
+        self.touch(hkey, subkey);
+        self.inner.read_value(hkey, subkey, value_name)
+    }
+
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool {
+        self.touch(hkey, subkey);
+        self.inner.key_exists(hkey, subkey)
+    }
+
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>> {
+        self.touch(hkey, subkey);
+        self.inner.subkeys(hkey, subkey)
+    }
+
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        self.touch(hkey, subkey);
+        self.inner.values(hkey, subkey)
+    }
+
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>> {
+        self.touch(hkey, subkey);
+        self.inner.last_write_time(hkey, subkey)
+    }
+}
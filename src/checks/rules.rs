@@ -0,0 +1,418 @@
+//! User-defined registry checks loaded from TOML rule files.
+//!
+//! Each file holds any number of `[[rule]]` tables:
+//!
+//! ```toml
+//! [[rule]]
+//! id = "studio.mmcss_pro_audio_priority"
+//! name = "Pro Audio Task Priority"
+//! category = "studio"          # a built-in category name or a custom one
+//! hive = "HKLM"
+//! path = 'SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Pro Audio'
+//! value = "Priority"
+//! operator = "ge"              # eq ne lt le gt ge between contains exists missing
+//! expected = 6                 # or min/max for "between"
+//! on_match = "optimal"
+//! on_mismatch = "warning"
+//! severity = 6
+//! description = "Raises MMCSS priority for DAW audio threads."
+//!
+//! [rule.remediation]               # optional for "eq" and "missing" rules
+//! action = "set_registry_value"
+//! hive = "HKLM"
+//! path = 'SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Pro Audio'
+//! name = "Priority"
+//! value = { type = "dword", data = 6 }
+//! ```
+
+use super::catalog::find_check;
+use super::context::CheckContext;
+use super::registry::{Hkey, RegValue};
+use crate::remediation::Remediation;
+use crate::types::{Category, CategoryResults, Check, CheckSource, CheckStatus};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+#[derive(thiserror::Error, Debug)]
+pub enum RuleError {
+    #[error("{}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{}: {message}", .path.display())]
+    Parse { path: PathBuf, message: String },
+    #[error("{}: rule '{id}': {message}", .path.display())]
+    Invalid {
+        path: PathBuf,
+        id: String,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    #[default]
+    #[serde(alias = "==")]
+    Eq,
+    #[serde(alias = "!=")]
+    Ne,
+    #[serde(alias = "<")]
+    Lt,
+    #[serde(alias = "<=")]
+    Le,
+    #[serde(alias = ">")]
+    Gt,
+    #[serde(alias = ">=")]
+    Ge,
+    Between,
+    Contains,
+    Exists,
+    Missing,
+}
+
+/// The value a rule compares against: a number for DWORD/QWORD values,
+/// text for string values.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Expected {
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(deserialize_with = "hive_name")]
+    pub hive: Hkey,
+    pub path: String,
+    pub value: String,
+    #[serde(default)]
+    pub operator: Operator,
+    pub expected: Option<Expected>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    #[serde(default = "default_on_match")]
+    pub on_match: CheckStatus,
+    #[serde(default = "default_on_mismatch")]
+    pub on_mismatch: CheckStatus,
+    /// Status when the value is absent; defaults to `on_mismatch`.
+    pub on_missing: Option<CheckStatus>,
+    #[serde(default = "default_severity")]
+    pub severity: u8,
+    #[serde(default)]
+    pub description: String,
+    /// Defaults to writing `expected` for "eq" rules and deleting the value
+    /// for "missing" rules.
+    pub remediation: Option<Remediation>,
+}
+
+fn default_category() -> String {
+    "custom".to_string()
+}
+
+fn default_on_match() -> CheckStatus {
+    CheckStatus::Optimal
+}
+
+fn default_on_mismatch() -> CheckStatus {
+    CheckStatus::Warning
+}
+
+fn default_severity() -> u8 {
+    5
+}
+
+fn hive_name<'de, D: Deserializer<'de>>(d: D) -> Result<Hkey, D::Error> {
+    let name = String::deserialize(d)?;
+    Hkey::from_name(&name)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown hive \"{}\"", name)))
+}
+
+#[derive(Deserialize)]
+struct RuleFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<Rule>,
+}
+
+impl Rule {
+    /// The built-in category this rule extends, if it names one.
+    pub fn builtin_category(&self) -> Option<Category> {
+        Category::from_name(&self.category)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() || self.id.contains(char::is_whitespace) {
+            return Err("id must be a non-empty dotted name without spaces".to_string());
+        }
+        if self.severity > 10 {
+            return Err(format!("severity {} is above 10", self.severity));
+        }
+        match (self.operator, &self.expected) {
+            (Operator::Between, _) => match (self.min, self.max) {
+                (Some(min), Some(max)) if min <= max => Ok(()),
+                (Some(_), Some(_)) => Err("min is greater than max".to_string()),
+                _ => Err("\"between\" needs both min and max".to_string()),
+            },
+            (Operator::Exists | Operator::Missing, _) => Ok(()),
+            (Operator::Contains, Some(Expected::Text(_))) => Ok(()),
+            (Operator::Contains, _) => Err("\"contains\" needs a text expected value".to_string()),
+            (Operator::Eq | Operator::Ne, Some(_)) => Ok(()),
+            (
+                Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge,
+                Some(Expected::Number(_)),
+            ) => Ok(()),
+            (_, _) => Err(format!(
+                "operator {:?} needs a numeric expected value",
+                self.operator
+            )),
+        }
+    }
+
+    fn expected_text(&self) -> String {
+        let expected = match &self.expected {
+            Some(Expected::Number(n)) => n.to_string(),
+            Some(Expected::Text(s)) => s.clone(),
+            None => String::new(),
+        };
+        match self.operator {
+            Operator::Eq => expected,
+            Operator::Ne => format!("not {}", expected),
+            Operator::Lt => format!("< {}", expected),
+            Operator::Le => format!("<= {}", expected),
+            Operator::Gt => format!("> {}", expected),
+            Operator::Ge => format!(">= {}", expected),
+            Operator::Between => format!(
+                "{}-{}",
+                self.min.unwrap_or_default(),
+                self.max.unwrap_or_default()
+            ),
+            Operator::Contains => format!("contains {}", expected),
+            Operator::Exists => "Present".to_string(),
+            Operator::Missing => "Not Set".to_string(),
+        }
+    }
+
+    /// Compares a stored value, or `None` when its type cannot be compared
+    /// with this rule (e.g. a DWORD rule against a REG_SZ value).
+    fn matches(&self, found: &RegValue) -> Option<bool> {
+        let number = match found {
+            RegValue::Dword(v) => Some(*v as u64),
+            RegValue::Qword(v) => Some(*v),
+            _ => None,
+        };
+        let texts: Option<Vec<&str>> = match found {
+            RegValue::String(s) | RegValue::ExpandString(s) => Some(vec![s.as_str()]),
+            RegValue::MultiString(items) => Some(items.iter().map(String::as_str).collect()),
+            _ => None,
+        };
+
+        match (self.operator, &self.expected) {
+            (Operator::Exists, _) => Some(true),
+            (Operator::Missing, _) => Some(false),
+            (Operator::Between, _) => {
+                number.map(|n| n >= self.min.unwrap_or(0) && n <= self.max.unwrap_or(u64::MAX))
+            }
+            (Operator::Contains, Some(Expected::Text(want))) => texts.map(|items| {
+                let want = want.to_lowercase();
+                items.iter().any(|s| s.to_lowercase().contains(&want))
+            }),
+            (op, Some(Expected::Number(want))) => number.map(|n| match op {
+                Operator::Eq => n == *want,
+                Operator::Ne => n != *want,
+                Operator::Lt => n < *want,
+                Operator::Le => n <= *want,
+                Operator::Gt => n > *want,
+                Operator::Ge => n >= *want,
+                _ => false,
+            }),
+            (op, Some(Expected::Text(want))) => match found {
+                RegValue::String(s) | RegValue::ExpandString(s) => {
+                    let equal = s.eq_ignore_ascii_case(want);
+                    Some(if op == Operator::Ne { !equal } else { equal })
+                }
+                _ => None,
+            },
+            (_, None) => None,
+        }
+    }
+
+    /// Reads the rule's value from the context registry and grades it.
+    pub fn evaluate(&self, ctx: &CheckContext) -> Check {
+        let found = ctx
+            .registry()
+            .read_value(self.hive, &self.path, &self.value);
+        let (shown, status) = match &found {
+            None if self.operator == Operator::Missing => ("Not Set".to_string(), &self.on_match),
+            None => (
+                "Not Set".to_string(),
+                self.on_missing.as_ref().unwrap_or(&self.on_mismatch),
+            ),
+            Some(value) => match self.matches(value) {
+                Some(true) => (display_value(value), &self.on_match),
+                Some(false) => (display_value(value), &self.on_mismatch),
+                None => (
+                    format!("Stored as {}", value.type_name()),
+                    &self.on_mismatch,
+                ),
+            },
+        };
+
+        let mut check = Check::new(&self.name, &shown, status.clone())
+            .with_id(&self.id)
+            .with_source(CheckSource::Registry)
+            .with_description(&self.description)
+            .with_expected(&self.expected_text())
+            .with_severity(self.severity);
+        if let Some(fix) = self.remediation() {
+            check = check.with_remediation(fix);
+        }
+        check
+    }
+
+    fn remediation(&self) -> Option<Remediation> {
+        if let Some(fix) = &self.remediation {
+            return Some(fix.clone());
+        }
+        let value = match (self.operator, &self.expected) {
+            (Operator::Missing, _) => {
+                return Some(Remediation::delete_value(
+                    self.hive,
+                    &self.path,
+                    &self.value,
+                ))
+            }
+            (Operator::Eq, Some(Expected::Number(n))) => match u32::try_from(*n) {
+                Ok(n) => RegValue::Dword(n),
+                Err(_) => RegValue::Qword(*n),
+            },
+            (Operator::Eq, Some(Expected::Text(s))) => RegValue::String(s.clone()),
+            _ => return None,
+        };
+        Some(Remediation::set_value(
+            self.hive,
+            &self.path,
+            &self.value,
+            value,
+        ))
+    }
+}
+
+fn display_value(value: &RegValue) -> String {
+    match value {
+        RegValue::String(s) | RegValue::ExpandString(s) => s.clone(),
+        RegValue::MultiString(items) => items.join(", "),
+        RegValue::Dword(v) => v.to_string(),
+        RegValue::Qword(v) => v.to_string(),
+        RegValue::Binary(data) | RegValue::Other(_, data) => data
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Every rule loaded for a run, in file and declaration order.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every `*.toml` file in `dir`, in file name order.
+    pub fn load_dir(dir: &Path) -> Result<Self, RuleError> {
+        let io_err = |source| RuleError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(io_err)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        let mut set = RuleSet::new();
+        for path in files {
+            let text = std::fs::read_to_string(&path).map_err(|source| RuleError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            set.add_file(&path, &text)?;
+        }
+        Ok(set)
+    }
+
+    /// Parses one rule file; `origin` is only used in error messages.
+    pub fn add_file(&mut self, origin: &Path, text: &str) -> Result<(), RuleError> {
+        let file: RuleFile = toml::from_str(text).map_err(|e| RuleError::Parse {
+            path: origin.to_path_buf(),
+            message: e.message().to_string(),
+        })?;
+
+        let mut seen: HashSet<String> = self.rules.iter().map(|r| r.id.clone()).collect();
+        for rule in &file.rules {
+            let invalid = |message: String| RuleError::Invalid {
+                path: origin.to_path_buf(),
+                id: rule.id.clone(),
+                message,
+            };
+            rule.validate().map_err(invalid)?;
+            if find_check(&rule.id).is_some() {
+                return Err(invalid(
+                    "id is already used by a built-in check".to_string(),
+                ));
+            }
+            if !seen.insert(rule.id.clone()) {
+                return Err(invalid("id is defined more than once".to_string()));
+            }
+        }
+        self.rules.extend(file.rules);
+        Ok(())
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Names of the categories that only exist because a rule declared them.
+    pub fn custom_categories(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.builtin_category().is_none()) {
+            if !names.iter().any(|n| n.eq_ignore_ascii_case(&rule.category)) {
+                names.push(rule.category.clone());
+            }
+        }
+        names
+    }
+
+    /// Evaluates the rules that extend a built-in category.
+    pub fn run_builtin(&self, ctx: &CheckContext, category: Category) -> Vec<Check> {
+        self.rules
+            .iter()
+            .filter(|r| r.builtin_category() == Some(category))
+            .map(|r| Check::timed(|| r.evaluate(ctx)))
+            .collect()
+    }
+
+    /// Evaluates the rules of a custom category, matched case-insensitively.
+    pub fn run_custom(&self, ctx: &CheckContext, name: &str) -> CategoryResults {
+        let mut results = CategoryResults::new(name);
+        for rule in &self.rules {
+            if rule.builtin_category().is_none() && rule.category.eq_ignore_ascii_case(name) {
+                results.add_check(Check::timed(|| rule.evaluate(ctx)));
+            }
+        }
+        results
+    }
+}
@@ -1,33 +1,41 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_security_checks() -> CategoryResults {
+pub fn run_security_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Security");
 
     let checks: Vec<Check> = (0..20)
         .into_par_iter()
-        .map(|i| match i {
-            0 => check_vbs(),
-            1 => check_core_isolation(),
-            2 => check_credential_guard(),
-            3 => check_defender_status(),
-            4 => check_firewall_status(),
-            5 => check_secure_boot(),
-            6 => check_tpm_status(),
-            7 => check_exploit_protection(),
-            8 => check_controlled_folder_access(),
-            9 => check_ransomware_protection(),
-            10 => check_smartscreen(),
-            11 => check_windows_update(),
-            12 => check_user_account_control(),
-            13 => check_bitlocker(),
-            14 => check_network_protection(),
-            15 => Check::new("Windows Defender Antivirus", "Active", CheckStatus::Optimal),
-            16 => Check::new("Real-Time Protection", "Enabled", CheckStatus::Optimal),
-            17 => Check::new("Cloud-Delivered Protection", "Enabled", CheckStatus::Info),
-            18 => Check::new("Automatic Sample Submission", "Enabled", CheckStatus::Info),
-            _ => Check::new("Tamper Protection", "Enabled", CheckStatus::Optimal),
+        .map(|i| {
+            Check::timed(|| match i {
+                0 => check_vbs(ctx),
+                1 => check_core_isolation(ctx),
+                2 => check_credential_guard(),
+                3 => check_defender_status(),
+                4 => check_firewall_status(),
+                5 => check_secure_boot(),
+                6 => check_tpm_status(),
+                7 => check_exploit_protection(),
+                8 => check_controlled_folder_access(),
+                9 => check_ransomware_protection(),
+                10 => check_smartscreen(),
+                11 => check_windows_update(),
+                12 => check_user_account_control(),
+                13 => check_bitlocker(),
+                14 => check_network_protection(),
+                15 => Check::new("Windows Defender Antivirus", "Active", CheckStatus::Optimal)
+                    .with_id("security.windows_defender_antivirus"),
+                16 => Check::new("Real-Time Protection", "Enabled", CheckStatus::Optimal)
+                    .with_id("security.real_time_protection"),
+                17 => Check::new("Cloud-Delivered Protection", "Enabled", CheckStatus::Info)
+                    .with_id("security.cloud_delivered_protection"),
+                18 => Check::new("Automatic Sample Submission", "Enabled", CheckStatus::Info)
+                    .with_id("security.automatic_sample_submission"),
+                _ => Check::new("Tamper Protection", "Enabled", CheckStatus::Optimal)
+                    .with_id("security.tamper_protection"),
+            })
         })
         .collect();
 
@@ -38,12 +46,14 @@ pub fn run_security_checks() -> CategoryResults {
     results
 }
 
-fn check_vbs() -> Check {
-    let vbs = read_registry_dword(
+fn check_vbs(ctx: &CheckContext) -> Check {
+    let (vbs, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
         "EnableVirtualizationBasedSecurity",
-    );
+    )
+    .split();
 
     Check::new(
         "VBS (Virtualization-Based Security)",
@@ -54,15 +64,19 @@ fn check_vbs() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("security.vbs")
     .with_description("Provides security but reduces performance.")
+    .with_mistyped(mistyped)
 }
 
-fn check_core_isolation() -> Check {
-    let hvci = read_registry_dword(
+fn check_core_isolation(ctx: &CheckContext) -> Check {
+    let (hvci, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
         "Enabled",
-    );
+    )
+    .split();
 
     Check::new(
         "Core Isolation (HVCI)",
@@ -73,24 +87,29 @@ fn check_core_isolation() -> Check {
         },
         CheckStatus::Info,
     )
+    .with_id("security.core_isolation")
     .with_description("Memory integrity protection.")
+    .with_mistyped(mistyped)
 }
 
 fn check_credential_guard() -> Check {
     Check::new("Credential Guard", "Not Configured", CheckStatus::Info)
+        .with_id("security.credential_guard")
         .with_description("Enterprise security feature.")
 }
 
 fn check_defender_status() -> Check {
     Check::new("Windows Defender", "Running", CheckStatus::Optimal)
+        .with_id("security.defender_status")
 }
 
 fn check_firewall_status() -> Check {
     Check::new("Windows Firewall", "Enabled", CheckStatus::Optimal)
+        .with_id("security.firewall_status")
 }
 
 fn check_secure_boot() -> Check {
-    Check::new("Secure Boot", "Enabled", CheckStatus::Optimal)
+    Check::new("Secure Boot", "Enabled", CheckStatus::Optimal).with_id("security.secure_boot")
 }
 
 fn check_tpm_status() -> Check {
@@ -99,27 +118,31 @@ fn check_tpm_status() -> Check {
         "2.0 Ready",
         CheckStatus::Optimal,
     )
+    .with_id("security.tpm_status")
 }
 
 fn check_exploit_protection() -> Check {
     Check::new("Exploit Protection", "Enabled", CheckStatus::Optimal)
+        .with_id("security.exploit_protection")
 }
 
 fn check_controlled_folder_access() -> Check {
     Check::new("Controlled Folder Access", "Disabled", CheckStatus::Info)
+        .with_id("security.controlled_folder_access")
         .with_description("Ransomware protection. Can cause compatibility issues.")
 }
 
 fn check_ransomware_protection() -> Check {
     Check::new("Ransomware Protection", "Available", CheckStatus::Info)
+        .with_id("security.ransomware_protection")
 }
 
 fn check_smartscreen() -> Check {
-    Check::new("SmartScreen", "Enabled", CheckStatus::Optimal)
+    Check::new("SmartScreen", "Enabled", CheckStatus::Optimal).with_id("security.smartscreen")
 }
 
 fn check_windows_update() -> Check {
-    Check::new("Windows Update", "Running", CheckStatus::Optimal)
+    Check::new("Windows Update", "Running", CheckStatus::Optimal).with_id("security.windows_update")
 }
 
 fn check_user_account_control() -> Check {
@@ -128,12 +151,14 @@ fn check_user_account_control() -> Check {
         "Enabled",
         CheckStatus::Optimal,
     )
+    .with_id("security.user_account_control")
 }
 
 fn check_bitlocker() -> Check {
-    Check::new("BitLocker", "Available", CheckStatus::Info)
+    Check::new("BitLocker", "Available", CheckStatus::Info).with_id("security.bitlocker")
 }
 
 fn check_network_protection() -> Check {
     Check::new("Network Protection", "Enabled", CheckStatus::Optimal)
+        .with_id("security.network_protection")
 }
@@ -0,0 +1,334 @@
+//! Which services need which, across every key under `SERVICES_KEY`.
+//!
+//! A service needs each entry of its `DependOnService` and every member of
+//! each load-order group in its `DependOnGroup`. Stopping or disabling a
+//! service takes down everything that needs it, directly or not, so
+//! `ServiceGraph::analyze` walks those edges backwards before anyone turns
+//! off something like `LanmanServer` or `BITS`.
+
+use super::catalog::{catalog, DataSource};
+use super::registry::{RegistrySource, HKEY_LOCAL_MACHINE};
+use super::service_helper::{read_service_config, ServiceConfig, SERVICES_KEY};
+use crate::remediation::{Remediation, StartType};
+use crate::risk::Risk;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+/// One "needs" edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edge {
+    /// Lower-cased service name.
+    to: String,
+    /// Set when the edge comes from `DependOnGroup`.
+    group: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServiceGraph {
+    /// By lower-cased name; the SCM ignores case.
+    services: BTreeMap<String, ServiceConfig>,
+    needs: BTreeMap<String, Vec<Edge>>,
+}
+
+/// A service that stops when the analyzed one does.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dependent {
+    pub name: String,
+    /// 1 for direct dependents.
+    pub depth: usize,
+    /// The service it needs on the way to the analyzed one.
+    pub via: String,
+    /// The load-order group the edge goes through, if any.
+    pub group: Option<String>,
+    pub start_type: Option<StartType>,
+    /// "Running", "Stopped" and so on; `None` when it cannot be queried.
+    pub state: Option<String>,
+}
+
+/// A check that reads the service or one of its dependents.
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedCheck {
+    pub check_id: &'static str,
+    pub service: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceAnalysis {
+    pub service: ServiceConfig,
+    pub state: Option<String>,
+    /// Direct dependencies, group members included.
+    pub dependencies: Vec<String>,
+    /// Every transitive dependent, nearest first.
+    pub dependents: Vec<Dependent>,
+    pub checks: Vec<AffectedCheck>,
+    /// What disabling the service risks on its own, from `crate::risk`.
+    pub risk: Risk,
+}
+
+impl ServiceAnalysis {
+    pub fn running_dependents(&self) -> Vec<&Dependent> {
+        self.dependents
+            .iter()
+            .filter(|d| d.state.as_deref() == Some("Running"))
+            .collect()
+    }
+}
+
+impl ServiceGraph {
+    /// Reads every service and driver key. Empty when the key is missing.
+    pub fn build(registry: &dyn RegistrySource) -> Self {
+        let services: BTreeMap<String, ServiceConfig> = registry
+            .subkeys(HKEY_LOCAL_MACHINE, SERVICES_KEY)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| read_service_config(registry, name))
+            .map(|config| (config.name.to_lowercase(), config))
+            .collect();
+
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (key, config) in &services {
+            if let Some(group) = &config.group {
+                groups
+                    .entry(group.to_lowercase())
+                    .or_default()
+                    .push(key.clone());
+            }
+        }
+        let needs = services
+            .iter()
+            .map(|(key, config)| {
+                let direct = config.dependencies.iter().map(|name| Edge {
+                    to: name.to_lowercase(),
+                    group: None,
+                });
+                let via_group = config.group_dependencies.iter().flat_map(|group| {
+                    let members = groups.get(&group.to_lowercase()).cloned();
+                    members.unwrap_or_default().into_iter().map(|to| Edge {
+                        to,
+                        group: Some(group.clone()),
+                    })
+                });
+                (key.clone(), direct.chain(via_group).collect())
+            })
+            .collect();
+        Self { services, needs }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+
+    /// The key's own spelling, or the lower-cased name of a service that
+    /// is referenced but not installed.
+    fn display<'a>(&'a self, key: &'a str) -> &'a str {
+        self.services.get(key).map_or(key, |c| c.name.as_str())
+    }
+
+    pub fn dependencies(&self, name: &str) -> Vec<&str> {
+        self.needs
+            .get(&name.to_lowercase())
+            .into_iter()
+            .flatten()
+            .map(|edge| self.display(&edge.to))
+            .collect()
+    }
+
+    /// Services that need `name` directly, with the group the edge goes
+    /// through.
+    fn dependent_edges(&self, key: &str) -> Vec<(&str, Option<&str>)> {
+        self.needs
+            .iter()
+            .flat_map(|(from, edges)| {
+                edges
+                    .iter()
+                    .filter(|edge| edge.to == key)
+                    .map(move |edge| (from.as_str(), edge.group.as_deref()))
+            })
+            .collect()
+    }
+
+    /// Keys of `start` and everything reachable from it, following
+    /// `needs` forwards or backwards.
+    fn reachable(&self, start: &str, backwards: bool) -> BTreeSet<String> {
+        let mut seen = BTreeSet::from([start.to_string()]);
+        let mut queue = VecDeque::from([start.to_string()]);
+        while let Some(key) = queue.pop_front() {
+            let next: Vec<String> = if backwards {
+                let edges = self.dependent_edges(&key);
+                edges
+                    .into_iter()
+                    .map(|(from, _)| from.to_string())
+                    .collect()
+            } else {
+                let edges = self.needs.get(&key).into_iter().flatten();
+                edges.map(|edge| edge.to.clone()).collect()
+            };
+            for key in next {
+                if seen.insert(key.clone()) {
+                    queue.push_back(key);
+                }
+            }
+        }
+        seen
+    }
+
+    /// What disabling `name` would take down. `state` queries whether a
+    /// service is running; `None` when the service is not installed.
+    pub fn analyze(
+        &self,
+        name: &str,
+        state: &dyn Fn(&str) -> Option<String>,
+    ) -> Option<ServiceAnalysis> {
+        let root = name.to_lowercase();
+        let service = self.services.get(&root)?.clone();
+
+        // Breadth first, so each dependent is listed at its shortest depth.
+        let mut dependents = Vec::new();
+        let mut seen = BTreeSet::from([root.clone()]);
+        let mut queue = VecDeque::from([(root, 0)]);
+        while let Some((key, depth)) = queue.pop_front() {
+            for (from, group) in self.dependent_edges(&key) {
+                if !seen.insert(from.to_string()) {
+                    continue;
+                }
+                let config = self.services.get(from);
+                dependents.push(Dependent {
+                    name: self.display(from).to_string(),
+                    depth: depth + 1,
+                    via: self.display(&key).to_string(),
+                    group: group.map(str::to_string),
+                    start_type: config.and_then(|c| c.start_type),
+                    state: state(self.display(from)),
+                });
+                queue.push_back((from.to_string(), depth + 1));
+            }
+        }
+
+        let affected = std::iter::once(service.name.as_str())
+            .chain(dependents.iter().map(|d| d.name.as_str()));
+        let checks = affected
+            .flat_map(|svc| {
+                checks_needing(svc)
+                    .into_iter()
+                    .map(|check_id| AffectedCheck {
+                        check_id,
+                        service: svc.to_string(),
+                    })
+            })
+            .collect();
+
+        Some(ServiceAnalysis {
+            state: state(&service.name),
+            dependencies: self
+                .dependencies(&service.name)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            dependents,
+            checks,
+            risk: Remediation::service_start(&service.name, StartType::Disabled).risk(),
+            service,
+        })
+    }
+
+    /// Graphviz DOT, edges pointing from a service to what it needs. With
+    /// `focus`, only that service, what it needs and what needs it.
+    pub fn to_dot(&self, focus: Option<&str>) -> String {
+        let focus = focus.map(str::to_lowercase);
+        let nodes: BTreeSet<String> = match &focus {
+            Some(key) => {
+                let mut nodes = self.reachable(key, false);
+                nodes.extend(self.reachable(key, true));
+                nodes
+            }
+            None => self
+                .services
+                .keys()
+                .chain(self.needs.values().flatten().map(|edge| &edge.to))
+                .cloned()
+                .collect(),
+        };
+
+        let mut dot = String::from("digraph services {\n    rankdir=LR;\n    node [shape=box];\n");
+        let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for key in &nodes {
+            let name = self.display(key);
+            let config = self.services.get(key);
+            let start = match config.and_then(|c| c.start_type) {
+                Some(start_type) => start_type.label(),
+                None if config.is_none() => "not installed",
+                None => "unknown",
+            };
+            let mut attrs = format!("label=\"{}\\n{}\"", quote(name), start);
+            if focus.as_deref() == Some(key) {
+                attrs.push_str(", penwidth=2");
+            }
+            if config.is_none() || start == StartType::Disabled.label() {
+                attrs.push_str(", style=dashed, fontcolor=gray40");
+            }
+            let _ = writeln!(dot, "    \"{}\" [{}];", quote(name), attrs);
+            if let Some(group) = config.and_then(|c| c.group.as_deref()) {
+                groups.entry(group).or_default().push(name);
+            }
+        }
+        for (i, (group, members)) in groups.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    subgraph cluster_{} {{\n        label=\"{}\";\n        style=dotted;",
+                i,
+                quote(group)
+            );
+            for member in members {
+                let _ = writeln!(dot, "        \"{}\";", quote(member));
+            }
+            dot.push_str("    }\n");
+        }
+        for (from, edges) in &self.needs {
+            if !nodes.contains(from) {
+                continue;
+            }
+            for edge in edges.iter().filter(|edge| nodes.contains(&edge.to)) {
+                let style = match &edge.group {
+                    Some(group) => format!(" [style=dashed, label=\"{}\"]", quote(group)),
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\"{};",
+                    quote(self.display(from)),
+                    quote(self.display(&edge.to)),
+                    style
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Checks that read the service, its registry key, or WMI when it is the
+/// WMI service itself.
+fn checks_needing(service: &str) -> Vec<&'static str> {
+    let key = format!("{}\\{}", SERVICES_KEY, service).to_lowercase();
+    catalog()
+        .iter()
+        .filter(|meta| {
+            meta.sources.iter().any(|source| match source {
+                DataSource::ServiceManager { service: s } => s.eq_ignore_ascii_case(service),
+                DataSource::Registry { hkey, path, .. } => {
+                    let path = path.to_lowercase();
+                    *hkey == HKEY_LOCAL_MACHINE
+                        && (path == key || path.starts_with(&format!("{}\\", key)))
+                }
+                DataSource::Wmi { .. } => service.eq_ignore_ascii_case("Winmgmt"),
+                DataSource::Dxgi => false,
+            })
+        })
+        .map(|meta| meta.id)
+        .collect()
+}
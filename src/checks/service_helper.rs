@@ -1,37 +1,160 @@
+//! What a service is doing, from the Service Control Manager, and how it is
+//! configured, from its key under `SERVICES_KEY`.
+//!
+//! The configuration comes from the registry rather than
+//! `QueryServiceConfigW` so it reads the same from an offline SYSTEM hive.
+
+use super::registry::{RegValue, RegistrySource, HKEY_LOCAL_MACHINE};
+use crate::remediation::StartType;
+use serde::Serialize;
+
+pub const SERVICES_KEY: &str = r"SYSTEM\CurrentControlSet\Services";
+
+/// `Type` bit of per-user service templates: 0x50 in their own process,
+/// 0x60 shared. Windows copies a template into `<name>_<LUID>` for each
+/// logon session, and those instances also carry
+/// `SERVICE_USERSERVICE_INSTANCE`.
+pub const SERVICE_USER_SERVICE: u32 = 0x40;
+pub const SERVICE_USERSERVICE_INSTANCE: u32 = 0x80;
+
+/// A service's configuration as the SCM stores it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServiceConfig {
+    /// The key name, which is the service name.
+    pub name: String,
+    /// `Type`: 0x10 own process, 0x20 shared, 0x01 kernel driver, plus the
+    /// per-user bits above.
+    pub service_type: Option<u32>,
+    /// `None` when `Start` is missing or out of range.
+    pub start_type: Option<StartType>,
+    /// `ImagePath`, environment variables left unexpanded.
+    pub binary_path: Option<String>,
+    /// `ObjectName`: `LocalSystem`, `NT AUTHORITY\LocalService` and so on.
+    /// Drivers have none.
+    pub account: Option<String>,
+    /// `DependOnService`: services that must be running first.
+    pub dependencies: Vec<String>,
+    /// `DependOnGroup`: load-order groups that must have started first.
+    pub group_dependencies: Vec<String>,
+    /// `Group`: the load-order group this service belongs to.
+    pub group: Option<String>,
+    /// `UserServiceFlags` of a per-user template; 0 stops Windows from
+    /// creating instances at sign-in.
+    pub user_service_flags: Option<u32>,
+}
+
+impl ServiceConfig {
+    pub fn is_user_template(&self) -> bool {
+        self.service_type
+            .is_some_and(|t| t & SERVICE_USER_SERVICE != 0 && t & SERVICE_USERSERVICE_INSTANCE == 0)
+    }
+
+    pub fn is_user_instance(&self) -> bool {
+        self.service_type
+            .is_some_and(|t| t & SERVICE_USERSERVICE_INSTANCE != 0)
+    }
+}
+
+/// `None` when the service is not installed.
+pub fn read_service_config(registry: &dyn RegistrySource, service: &str) -> Option<ServiceConfig> {
+    let key = format!("{}\\{}", SERVICES_KEY, service);
+    if !registry.key_exists(HKEY_LOCAL_MACHINE, &key) {
+        return None;
+    }
+    let list = |name: &str| match registry.read_value(HKEY_LOCAL_MACHINE, &key, name) {
+        Some(RegValue::MultiString(items)) => {
+            items.into_iter().filter(|item| !item.is_empty()).collect()
+        }
+        Some(RegValue::String(item)) if !item.is_empty() => vec![item],
+        _ => Vec::new(),
+    };
+    let start_type = registry
+        .read_dword(HKEY_LOCAL_MACHINE, &key, "Start")
+        .and_then(|raw| {
+            let delayed = registry.read_dword(HKEY_LOCAL_MACHINE, &key, "DelayedAutostart");
+            StartType::from_values(raw, delayed == Some(1))
+        });
+    Some(ServiceConfig {
+        name: service.to_string(),
+        service_type: registry.read_dword(HKEY_LOCAL_MACHINE, &key, "Type"),
+        start_type,
+        binary_path: registry.read_string(HKEY_LOCAL_MACHINE, &key, "ImagePath"),
+        account: registry.read_string(HKEY_LOCAL_MACHINE, &key, "ObjectName"),
+        dependencies: list("DependOnService"),
+        group_dependencies: list("DependOnGroup"),
+        group: registry
+            .read_string(HKEY_LOCAL_MACHINE, &key, "Group")
+            .filter(|group| !group.is_empty()),
+        user_service_flags: registry.read_dword(HKEY_LOCAL_MACHINE, &key, "UserServiceFlags"),
+    })
+}
+
+/// The instances of a per-user template, one for each logon session that
+/// started it. Keys of sessions that have signed out can linger until the
+/// next reboot, so callers query which ones are running.
+pub fn user_service_instances(registry: &dyn RegistrySource, template: &str) -> Vec<ServiceConfig> {
+    let prefix = format!("{}_", template.to_lowercase());
+    registry
+        .subkeys(HKEY_LOCAL_MACHINE, SERVICES_KEY)
+        .unwrap_or_default()
+        .iter()
+        .filter(|name| name.to_lowercase().starts_with(&prefix))
+        .filter_map(|name| read_service_config(registry, name))
+        .filter(|config| config.is_user_instance())
+        .collect()
+}
+
+/// The runtime state, from the live SCM whatever registry is being audited.
 #[cfg(windows)]
 pub fn query_service_status(service_name: &str) -> Option<String> {
     use std::ffi::OsStr;
     use std::os::windows::ffi::OsStrExt;
     use windows::Win32::System::Services::{
-        CloseServiceHandle, OpenSCManagerW, OpenServiceW, QueryServiceStatus, SC_MANAGER_CONNECT,
-        SERVICE_PAUSED, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_STATUS, SERVICE_STOPPED,
+        CloseServiceHandle, OpenSCManagerW, OpenServiceW, QueryServiceStatus, SC_HANDLE,
+        SC_MANAGER_CONNECT, SERVICE_CONTINUE_PENDING, SERVICE_PAUSED, SERVICE_PAUSE_PENDING,
+        SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START_PENDING, SERVICE_STATUS,
+        SERVICE_STOPPED, SERVICE_STOP_PENDING,
     };
 
+    /// Closes the handle on every path out, including early returns.
+    struct ScHandle(SC_HANDLE);
+
+    impl Drop for ScHandle {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = CloseServiceHandle(self.0);
+            }
+        }
+    }
+
     unsafe {
-        let scm = OpenSCManagerW(None, None, SC_MANAGER_CONNECT).ok()?;
+        let scm = ScHandle(OpenSCManagerW(None, None, SC_MANAGER_CONNECT).ok()?);
 
         let name: Vec<u16> = OsStr::new(service_name)
             .encode_wide()
             .chain(std::iter::once(0))
             .collect();
-        let service = OpenServiceW(
-            scm,
-            windows::core::PCWSTR(name.as_ptr()),
-            SERVICE_QUERY_STATUS,
-        )
-        .ok()?;
+        let service = ScHandle(
+            OpenServiceW(
+                scm.0,
+                windows::core::PCWSTR(name.as_ptr()),
+                SERVICE_QUERY_STATUS,
+            )
+            .ok()?,
+        );
 
         let mut status = SERVICE_STATUS::default();
-        let result = QueryServiceStatus(service, &mut status);
-
-        let _ = CloseServiceHandle(service);
-        let _ = CloseServiceHandle(scm);
+        let result = QueryServiceStatus(service.0, &mut status);
 
         if result.is_ok() {
             Some(match status.dwCurrentState {
                 s if s == SERVICE_RUNNING => "Running".to_string(),
                 s if s == SERVICE_STOPPED => "Stopped".to_string(),
                 s if s == SERVICE_PAUSED => "Paused".to_string(),
+                s if s == SERVICE_START_PENDING => "Starting".to_string(),
+                s if s == SERVICE_STOP_PENDING => "Stopping".to_string(),
+                s if s == SERVICE_PAUSE_PENDING => "Pausing".to_string(),
+                s if s == SERVICE_CONTINUE_PENDING => "Resuming".to_string(),
                 _ => "Unknown".to_string(),
             })
         } else {
@@ -1,309 +1,346 @@
+use super::context::CheckContext;
 use super::service_helper::*;
+use crate::remediation::{Remediation, StartType};
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_services_checks() -> CategoryResults {
+fn service_check_id(service: &str) -> String {
+    format!("services.{}", service.to_lowercase())
+}
+
+/// The built-in recommendation for a service. A profile overrides it with a
+/// `services.<name>` target such as `"manual"` or `"delayed"`.
+#[derive(Debug, Clone, Copy)]
+enum Recommended {
+    /// Worth turning off; a Warning until it is disabled.
+    Disable,
+    /// Windows needs it; an Issue when disabled. Holds the Windows default,
+    /// which the fix restores.
+    Keep(StartType),
+    /// Depends on what the machine is for.
+    Optional,
+}
+
+pub fn run_services_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Services");
 
     let service_checks = vec![
         (
             "DiagTrack",
             "DiagTrack (Telemetry)",
-            CheckStatus::Warning,
+            Recommended::Disable,
             "Can be disabled for privacy.",
         ),
         (
             "dmwappushservice",
             "dmwappushservice",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Push notification service.",
         ),
         (
             "SysMain",
             "SysMain (Superfetch)",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Preloads apps.",
         ),
         (
             "TabletInputService",
             "TabletInputService",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Tablet PC input.",
         ),
         (
             "WSearch",
             "WSearch (Windows Search)",
-            CheckStatus::Info,
+            Recommended::Optional,
             "File indexing.",
         ),
         (
             "XblAuthManager",
             "XblAuthManager",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Xbox Live Auth.",
         ),
         (
             "XblGameSave",
             "XblGameSave",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Xbox Game Save.",
         ),
         (
             "XboxNetApiSvc",
             "XboxNetApiSvc",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Xbox Network.",
         ),
         (
             "XboxGipSvc",
             "XboxGipSvc",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Xbox Accessory Management.",
         ),
         (
             "wuauserv",
             "Windows Update",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Manual),
             "System updates.",
         ),
         (
             "WinDefend",
             "Windows Defender",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Antivirus protection.",
         ),
         (
             "MpsSvc",
             "Windows Firewall",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Network security.",
         ),
         (
             "Schedule",
             "Task Scheduler",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Scheduled tasks.",
         ),
         (
             "PlugPlay",
             "Plug and Play",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Manual),
             "Device detection.",
         ),
         (
             "RpcSs",
             "RPC Service",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Remote procedure calls.",
         ),
         (
             "DcomLaunch",
             "DCOM Server",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Component services.",
         ),
         (
             "CryptSvc",
             "Cryptographic Services",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Encryption services.",
         ),
         (
             "AudioSrv",
             "Windows Audio",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Audio management.",
         ),
         (
             "AudioEndpointBuilder",
             "Windows Audio Endpoint Builder",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Audio device management.",
         ),
-        ("Themes", "Themes", CheckStatus::Info, "Visual themes."),
+        ("Themes", "Themes", Recommended::Optional, "Visual themes."),
         (
             "Spooler",
             "Print Spooler",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Print management.",
         ),
         (
             "BITS",
             "Background Intelligent Transfer",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Background downloads.",
         ),
         (
             "WbioSrvc",
             "Windows Biometric Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Biometric devices.",
         ),
         (
             "TermService",
             "Remote Desktop Services",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Remote desktop.",
         ),
-        ("Fax", "Fax", CheckStatus::Info, "Fax service."),
+        ("Fax", "Fax", Recommended::Optional, "Fax service."),
         (
             "HomeGroupListener",
             "HomeGroup Listener",
-            CheckStatus::Info,
+            Recommended::Optional,
             "HomeGroup.",
         ),
         (
             "HomeGroupProvider",
             "HomeGroup Provider",
-            CheckStatus::Info,
+            Recommended::Optional,
             "HomeGroup.",
         ),
         (
             "icssvc",
             "Windows Mobile Hotspot",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Mobile hotspot.",
         ),
         (
             "PhoneSvc",
             "Phone Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Phone functionality.",
         ),
         (
             "RetailDemo",
             "Retail Demo Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Demo mode.",
         ),
         (
             "SensorService",
             "Sensor Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Sensor management.",
         ),
         (
             "ScDeviceEnum",
             "Smart Card Device Enumeration",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Smart card.",
         ),
         (
             "SCPolicySvc",
             "Smart Card Removal Policy",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Smart card.",
         ),
         (
             "WiaRpc",
             "Windows Image Acquisition",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Scanner/camera.",
         ),
         (
             "wcncsvc",
             "Windows Connect Now",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Network setup.",
         ),
         (
             "WalletService",
             "WalletService",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Wallet service.",
         ),
         (
             "wisvc",
             "Windows Insider Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Insider builds.",
         ),
         (
             "MapsBroker",
             "Downloaded Maps Manager",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Offline maps.",
         ),
         (
             "lfsvc",
             "Geolocation Service",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Location services.",
         ),
         (
             "RemoteRegistry",
             "Remote Registry",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Remote registry access.",
         ),
         (
             "CDPUserSvc",
             "Connected User Experiences",
-            CheckStatus::Info,
-            "Connected experiences.",
+            Recommended::Optional,
+            "Connected experiences; per-user, one instance per signed-in session.",
+        ),
+        (
+            "BcastDVRUserService",
+            "GameDVR and Broadcast User Service",
+            Recommended::Optional,
+            "Game clip recording; per-user.",
+        ),
+        (
+            "OneSyncSvc",
+            "Sync Host",
+            Recommended::Optional,
+            "Mail, contacts and calendar sync; per-user.",
         ),
         (
             "EventLog",
             "Windows Event Log",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Event logging.",
         ),
         (
             "EventSystem",
             "COM+ Event System",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "COM+ events.",
         ),
         (
             "MSDTC",
             "Distributed Transaction Coordinator",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Distributed transactions.",
         ),
         (
             "Winmgmt",
             "Windows Management Instrumentation",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "WMI service.",
         ),
         (
             "ShellHWDetection",
             "Shell Hardware Detection",
-            CheckStatus::Info,
+            Recommended::Optional,
             "Hardware events.",
         ),
         (
             "SamSs",
             "Security Accounts Manager",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Account management.",
         ),
-        ("LanmanServer", "Server", CheckStatus::Info, "File sharing."),
+        (
+            "LanmanServer",
+            "Server",
+            Recommended::Optional,
+            "File sharing.",
+        ),
         (
             "LanmanWorkstation",
             "Workstation",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "Network connections.",
         ),
         (
             "netprofm",
             "Network List Service",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Manual),
             "Network identification.",
         ),
         (
             "Dnscache",
             "DNS Client",
-            CheckStatus::Optimal,
+            Recommended::Keep(StartType::Automatic),
             "DNS resolution.",
         ),
     ];
 
     let checks: Vec<Check> = service_checks
         .into_par_iter()
-        .map(|(svc, name, default_status, desc)| {
-            let status_str = query_service_status(svc).unwrap_or_else(|| "Unknown".to_string());
-            Check::new(name, &status_str, default_status).with_description(desc)
+        .map(|(svc, name, recommended, desc)| {
+            Check::timed(|| grade_service(ctx, svc, name, recommended))
+                .with_id(&service_check_id(svc))
+                .with_description(desc)
         })
         .collect();
 
@@ -313,3 +350,90 @@ pub fn run_services_checks() -> CategoryResults {
 
     results
 }
+
+/// Grades the configured start type against the recommendation; whether
+/// the service happens to be running right now only goes into the value.
+/// Per-user templates never run themselves, so their value lists the
+/// instances of each logon session instead.
+fn grade_service(ctx: &CheckContext, svc: &str, name: &str, recommended: Recommended) -> Check {
+    let registry = ctx.registry();
+    let config = read_service_config(registry, svc);
+    let template = config.as_ref().is_some_and(|c| c.is_user_template());
+    let current = config.as_ref().and_then(|c| c.start_type);
+    // Every service carries a fix, even when its state is unknown, so a
+    // profile can retarget its start type.
+    let (expected, fix) = match recommended {
+        Recommended::Disable => (Some("Disabled"), StartType::Disabled),
+        Recommended::Keep(default) => (Some("Not disabled"), default),
+        Recommended::Optional => (None, current.unwrap_or(StartType::Manual)),
+    };
+    let fix = if template {
+        Remediation::user_service_start(svc, fix)
+    } else {
+        Remediation::service_start(svc, fix)
+    };
+
+    let check = match (config, current) {
+        (None, _) => Check::new(name, "Not installed", CheckStatus::Info),
+        (Some(_), None) => Check::new(
+            name,
+            "Unknown",
+            CheckStatus::Error(CheckError::Service(format!(
+                "{} has no valid start type",
+                svc
+            ))),
+        ),
+        (Some(config), Some(current)) => {
+            // A template only stays off once Windows stops creating instances.
+            let disabled = current == StartType::Disabled
+                && (!template || config.user_service_flags == Some(0));
+            let status = match recommended {
+                Recommended::Disable if disabled => CheckStatus::Optimal,
+                Recommended::Disable => CheckStatus::Warning,
+                Recommended::Keep(_) if disabled => CheckStatus::Issue,
+                Recommended::Keep(_) => CheckStatus::Optimal,
+                Recommended::Optional => CheckStatus::Info,
+            };
+            let value = if template {
+                format!("{}, {}", current.label(), describe_instances(ctx, svc))
+            } else {
+                match service_state(ctx, svc) {
+                    Some(state) => format!("{}, {}", state, current.label()),
+                    None => current.label().to_string(),
+                }
+            };
+            Check::new(name, &value, status)
+        }
+    };
+    let check = check.with_remediation(fix);
+    match expected {
+        Some(expected) => check.with_expected(expected),
+        None => check,
+    }
+}
+
+/// The service's running state, or `None` for an offline image: the
+/// service manager only knows about this machine.
+fn service_state(ctx: &CheckContext, svc: &str) -> Option<String> {
+    if ctx.is_offline() {
+        None
+    } else {
+        query_service_status(svc)
+    }
+}
+
+/// "instances: CDPUserSvc_4f2a1 Running, CDPUserSvc_9c3e0 Stopped".
+fn describe_instances(ctx: &CheckContext, template: &str) -> String {
+    let instances: Vec<String> = user_service_instances(ctx.registry(), template)
+        .iter()
+        .map(|instance| match service_state(ctx, &instance.name) {
+            Some(state) => format!("{} {}", instance.name, state),
+            None => instance.name.clone(),
+        })
+        .collect();
+    if instances.is_empty() {
+        "no instances".to_string()
+    } else {
+        format!("instances: {}", instances.join(", "))
+    }
+}
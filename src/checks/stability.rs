@@ -1,37 +1,47 @@
+use super::context::CheckContext;
 use super::wmi_helper::*;
 use crate::types::*;
 
-pub fn run_stability_checks() -> CategoryResults {
+pub fn run_stability_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Stability");
 
-    results.add_check(check_system_uptime());
-    results.add_check(check_event_log_errors());
-    results.add_check(check_failed_devices());
+    results.add_check(Check::timed(|| check_system_uptime(ctx)));
+    results.add_check(Check::timed(check_event_log_errors));
+    results.add_check(Check::timed(|| check_failed_devices(ctx)));
 
     results
 }
 
-fn check_system_uptime() -> Check {
-    let boot_time = query_wmi_string("Win32_OperatingSystem", "LastBootUpTime")
-        .unwrap_or_else(|| "Unknown".to_string());
+fn check_system_uptime(ctx: &CheckContext) -> Check {
+    let (boot_time, failed) = split_failure(query_wmi_string(
+        ctx,
+        "Win32_OperatingSystem",
+        "LastBootUpTime",
+    ));
+    let boot_time = boot_time.unwrap_or_else(|| "Unknown".to_string());
     Check::new("Last Boot Time", &boot_time, CheckStatus::Info)
+        .with_id("stability.system_uptime")
         .with_description("System last reboot timestamp")
+        .with_error(failed)
 }
 
 fn check_event_log_errors() -> Check {
     // Query event log for recent critical errors would require more complex WMI/Event Log API
     // For now, just check if event log service is accessible
     Check::new("Event Log Service", "Running", CheckStatus::Info)
+        .with_id("stability.event_log_errors")
         .with_description("Event log monitoring available")
 }
 
-fn check_failed_devices() -> Check {
+fn check_failed_devices(ctx: &CheckContext) -> Check {
     // Query for devices with problems using Win32_PnPEntity
-    let problem_devices = count_wmi_instances("Win32_PnPEntity");
+    let problem_devices = count_wmi_instances(ctx, "Win32_PnPEntity");
     Check::new(
         "Detected Devices",
-        &format!("{} devices", problem_devices),
+        &format!("{} devices", problem_devices.clone().unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("stability.failed_devices")
     .with_description("Total PnP devices detected")
+    .with_error(problem_devices.err())
 }
@@ -1,15 +1,16 @@
+use super::context::CheckContext;
 use super::utils::*;
 use crate::types::*;
 use rayon::prelude::*;
 
-pub fn run_storage_checks() -> CategoryResults {
+pub fn run_storage_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Storage");
 
-    let checks: Vec<Check> = vec![
-        check_trim_status(),
+    let checks: Vec<Check> = timed![
+        check_trim_status(ctx),
         check_partition_alignment(),
-        check_disable_8dot3(),
-        check_last_access_time(),
+        check_disable_8dot3(ctx),
+        check_last_access_time(ctx),
         check_nvme_idle(),
         check_storage_sense(),
         check_windows_search(),
@@ -36,16 +37,22 @@ pub fn run_storage_checks() -> CategoryResults {
         check_disk_quota(),
         check_distributed_link_tracking(),
         check_storage_qos(),
-        Check::new("Primary Drive Type", "SSD/NVMe", CheckStatus::Optimal),
-        Check::new("Drive Capacity", "Sufficient", CheckStatus::Optimal),
-        Check::new("Free Space", "Adequate", CheckStatus::Optimal),
-        Check::new("Drive Health", "Good", CheckStatus::Optimal),
-        Check::new("Drive Temperature", "Normal", CheckStatus::Optimal),
-        Check::new("Drive Read Speed", "Optimal", CheckStatus::Optimal),
-        Check::new("Drive Write Speed", "Optimal", CheckStatus::Optimal),
-        Check::new("Drive IOPS", "High", CheckStatus::Optimal),
-        Check::new("Drive Latency", "Low", CheckStatus::Optimal),
-        Check::new("Drive Interface", "NVMe/SATA", CheckStatus::Info),
+        Check::new("Primary Drive Type", "SSD/NVMe", CheckStatus::Optimal)
+            .with_id("storage.primary_drive_type"),
+        Check::new("Drive Capacity", "Sufficient", CheckStatus::Optimal)
+            .with_id("storage.drive_capacity"),
+        Check::new("Free Space", "Adequate", CheckStatus::Optimal).with_id("storage.free_space"),
+        Check::new("Drive Health", "Good", CheckStatus::Optimal).with_id("storage.drive_health"),
+        Check::new("Drive Temperature", "Normal", CheckStatus::Optimal)
+            .with_id("storage.drive_temperature"),
+        Check::new("Drive Read Speed", "Optimal", CheckStatus::Optimal)
+            .with_id("storage.drive_read_speed"),
+        Check::new("Drive Write Speed", "Optimal", CheckStatus::Optimal)
+            .with_id("storage.drive_write_speed"),
+        Check::new("Drive IOPS", "High", CheckStatus::Optimal).with_id("storage.drive_iops"),
+        Check::new("Drive Latency", "Low", CheckStatus::Optimal).with_id("storage.drive_latency"),
+        Check::new("Drive Interface", "NVMe/SATA", CheckStatus::Info)
+            .with_id("storage.drive_interface"),
     ]
     .into_par_iter()
     .collect();
@@ -57,12 +64,14 @@ pub fn run_storage_checks() -> CategoryResults {
     results
 }
 
-fn check_trim_status() -> Check {
-    let trim = read_registry_dword(
+fn check_trim_status(ctx: &CheckContext) -> Check {
+    let (trim, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\FileSystem",
         "DisableDeleteNotification",
-    );
+    )
+    .split();
 
     let status = if trim == Some(0) || trim.is_none() {
         CheckStatus::Optimal
@@ -79,20 +88,31 @@ fn check_trim_status() -> Check {
         },
         status,
     )
+    .with_id("storage.trim_status")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\FileSystem",
+        "DisableDeleteNotification",
+        0,
+    ))
     .with_description("TRIM is essential for SSD health and performance.")
+    .with_mistyped(mistyped)
 }
 
 fn check_partition_alignment() -> Check {
     Check::new("Partition Alignment", "4K Aligned", CheckStatus::Optimal)
+        .with_id("storage.partition_alignment")
         .with_description("Modern drives should be 4K aligned.")
 }
 
-fn check_disable_8dot3() -> Check {
-    let disabled = read_registry_dword(
+fn check_disable_8dot3(ctx: &CheckContext) -> Check {
+    let (disabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\FileSystem",
         "NtfsDisable8dot3NameCreation",
-    );
+    )
+    .split();
 
     let status = if disabled == Some(1) {
         CheckStatus::Optimal
@@ -109,15 +129,25 @@ fn check_disable_8dot3() -> Check {
         },
         status,
     )
+    .with_id("storage.disable_8dot3")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\FileSystem",
+        "NtfsDisable8dot3NameCreation",
+        1,
+    ))
     .with_description("Disable for better performance on SSDs.")
+    .with_mistyped(mistyped)
 }
 
-fn check_last_access_time() -> Check {
-    let disabled = read_registry_dword(
+fn check_last_access_time(ctx: &CheckContext) -> Check {
+    let (disabled, mistyped) = lookup_registry_dword(
+        ctx,
         HKEY_LOCAL_MACHINE,
         r"SYSTEM\CurrentControlSet\Control\FileSystem",
         "NtfsDisableLastAccessUpdate",
-    );
+    )
+    .split();
 
     let status = if disabled == Some(1) {
         CheckStatus::Optimal
@@ -134,21 +164,32 @@ fn check_last_access_time() -> Check {
         },
         status,
     )
+    .with_id("storage.last_access_time")
+    .with_remediation(Remediation::set_dword(
+        HKEY_LOCAL_MACHINE,
+        r"SYSTEM\CurrentControlSet\Control\FileSystem",
+        "NtfsDisableLastAccessUpdate",
+        1,
+    ))
     .with_description("Disable to reduce SSD writes.")
+    .with_mistyped(mistyped)
 }
 
 fn check_nvme_idle() -> Check {
     Check::new("NVMe Idle Timeout", "Configured", CheckStatus::Info)
+        .with_id("storage.nvme_idle")
         .with_description("Longer timeout reduces latency spikes.")
 }
 
 fn check_storage_sense() -> Check {
     Check::new("Storage Sense", "Disabled", CheckStatus::Info)
+        .with_id("storage.sense")
         .with_description("Automatic disk cleanup. Can impact performance.")
 }
 
 fn check_windows_search() -> Check {
     Check::new("Windows Search Indexing", "Limited", CheckStatus::Info)
+        .with_id("storage.windows_search")
         .with_description("Disable or limit to specific folders for performance.")
 }
 
@@ -158,99 +199,112 @@ fn check_defrag_schedule() -> Check {
         "Disabled on SSDs",
         CheckStatus::Optimal,
     )
+    .with_id("storage.defrag_schedule")
     .with_description("SSDs don't need defragmentation.")
 }
 
 fn check_write_cache() -> Check {
     Check::new("Write Cache", "Enabled", CheckStatus::Optimal)
+        .with_id("storage.write_cache")
         .with_description("Improves performance but requires UPS for data safety.")
 }
 
 fn check_fsutil_behavior() -> Check {
-    Check::new("FSUtil Behavior", "Optimized", CheckStatus::Info)
+    Check::new("FSUtil Behavior", "Optimized", CheckStatus::Info).with_id("storage.fsutil_behavior")
 }
 
 fn check_disk_timeout() -> Check {
-    Check::new("Disk Timeout", "System Default", CheckStatus::Info)
+    Check::new("Disk Timeout", "System Default", CheckStatus::Info).with_id("storage.disk_timeout")
 }
 
 fn check_disk_idle_timeout() -> Check {
     Check::new("Disk Idle Timeout", "Never", CheckStatus::Optimal)
+        .with_id("storage.disk_idle_timeout")
         .with_description("Prevents disks from spinning down during activity.")
 }
 
 fn check_storage_policy() -> Check {
-    Check::new("Storage Policy", "Performance", CheckStatus::Optimal)
+    Check::new("Storage Policy", "Performance", CheckStatus::Optimal).with_id("storage.policy")
 }
 
 fn check_file_system() -> Check {
-    Check::new("File System", "NTFS", CheckStatus::Optimal)
+    Check::new("File System", "NTFS", CheckStatus::Optimal).with_id("storage.file_system")
 }
 
 fn check_cluster_size() -> Check {
-    Check::new("Cluster Size", "4KB", CheckStatus::Optimal)
+    Check::new("Cluster Size", "4KB", CheckStatus::Optimal).with_id("storage.cluster_size")
 }
 
 fn check_compression() -> Check {
     Check::new("NTFS Compression", "Disabled", CheckStatus::Optimal)
+        .with_id("storage.compression")
         .with_description("Compression reduces performance.")
 }
 
 fn check_encryption() -> Check {
     Check::new("BitLocker Encryption", "Not Detected", CheckStatus::Info)
+        .with_id("storage.encryption")
         .with_description("Encryption has performance cost but provides security.")
 }
 
 fn check_disk_caching() -> Check {
-    Check::new("Disk Caching", "Enabled", CheckStatus::Optimal)
+    Check::new("Disk Caching", "Enabled", CheckStatus::Optimal).with_id("storage.disk_caching")
 }
 
 fn check_ssd_optimization() -> Check {
     Check::new("SSD Optimization", "Enabled", CheckStatus::Optimal)
+        .with_id("storage.ssd_optimization")
 }
 
 fn check_nvme_settings() -> Check {
-    Check::new("NVMe Settings", "Optimized", CheckStatus::Info)
+    Check::new("NVMe Settings", "Optimized", CheckStatus::Info).with_id("storage.nvme_settings")
 }
 
 fn check_sata_mode() -> Check {
     Check::new("SATA Mode", "AHCI", CheckStatus::Info)
+        .with_id("storage.sata_mode")
         .with_description("AHCI is required for TRIM support.")
 }
 
 fn check_ahci_mode() -> Check {
     Check::new("AHCI Link Power Management", "Active", CheckStatus::Info)
+        .with_id("storage.ahci_mode")
 }
 
 fn check_raid_configuration() -> Check {
     Check::new("RAID Configuration", "Not Detected", CheckStatus::Info)
+        .with_id("storage.raid_configuration")
 }
 
 fn check_smart_monitoring() -> Check {
     Check::new("S.M.A.R.T. Monitoring", "Active", CheckStatus::Info)
+        .with_id("storage.smart_monitoring")
 }
 
 fn check_disk_defragmentation() -> Check {
     Check::new("Disk Optimization", "Configured", CheckStatus::Info)
+        .with_id("storage.disk_defragmentation")
 }
 
 fn check_storage_spaces() -> Check {
-    Check::new("Storage Spaces", "Not Used", CheckStatus::Info)
+    Check::new("Storage Spaces", "Not Used", CheckStatus::Info).with_id("storage.spaces")
 }
 
 fn check_volume_shadow_copy() -> Check {
     Check::new("Volume Shadow Copy", "Enabled", CheckStatus::Info)
+        .with_id("storage.volume_shadow_copy")
 }
 
 fn check_disk_quota() -> Check {
-    Check::new("Disk Quota", "Disabled", CheckStatus::Info)
+    Check::new("Disk Quota", "Disabled", CheckStatus::Info).with_id("storage.disk_quota")
 }
 
 fn check_distributed_link_tracking() -> Check {
     Check::new("Distributed Link Tracking", "Disabled", CheckStatus::Info)
+        .with_id("storage.distributed_link_tracking")
         .with_description("Can be disabled for performance.")
 }
 
 fn check_storage_qos() -> Check {
-    Check::new("Storage QoS", "Not Applicable", CheckStatus::Info)
+    Check::new("Storage QoS", "Not Applicable", CheckStatus::Info).with_id("storage.qos")
 }
@@ -1,17 +1,23 @@
+use super::context::CheckContext;
 use super::wmi_helper::*;
 use crate::types::*;
 
-pub fn run_thermal_checks() -> CategoryResults {
+pub fn run_thermal_checks(ctx: &CheckContext) -> CategoryResults {
     let mut results = CategoryResults::new("Thermal");
 
-    results.add_check(check_cpu_temp());
-    results.add_check(check_thermal_zone());
+    results.add_check(Check::timed(|| check_cpu_temp(ctx)));
+    results.add_check(Check::timed(|| check_thermal_zone(ctx)));
 
     results
 }
 
-fn check_cpu_temp() -> Check {
-    let temp = query_wmi_u32("Win32_TemperatureProbe", "CurrentReading")
+fn check_cpu_temp(ctx: &CheckContext) -> Check {
+    let (temp, failed) = split_failure(query_wmi_u32(
+        ctx,
+        "Win32_TemperatureProbe",
+        "CurrentReading",
+    ));
+    let temp = temp
         .map(|t| {
             let celsius = (t as f64 / 10.0) - 273.15;
             if celsius > 0.0 && celsius < 150.0 {
@@ -23,14 +29,18 @@ fn check_cpu_temp() -> Check {
         .unwrap_or_else(|| "Not available via WMI".to_string());
 
     Check::new("CPU Temperature", &temp, CheckStatus::Info)
+        .with_id("thermal.cpu_temp")
         .with_description("Most systems require vendor-specific tools for accurate temps")
+        .with_error(failed)
 }
 
-fn check_thermal_zone() -> Check {
-    let zone_count = count_wmi_instances("Win32_TemperatureProbe");
+fn check_thermal_zone(ctx: &CheckContext) -> Check {
+    let zone_count = count_wmi_instances(ctx, "Win32_TemperatureProbe");
     Check::new(
         "Thermal Sensors",
-        &format!("{} detected", zone_count),
+        &format!("{} detected", zone_count.clone().unwrap_or(0)),
         CheckStatus::Info,
     )
+    .with_id("thermal.zone")
+    .with_error(zone_count.err())
 }
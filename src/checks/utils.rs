@@ -1,157 +1,101 @@
-pub type Hkey = u32;
-pub const HKEY_LOCAL_MACHINE: Hkey = 1;
-pub const HKEY_CURRENT_USER: Hkey = 2;
-
-#[allow(unused_macros)]
-macro_rules! reg_check {
-    ($name:expr, $hkey:expr, $path:expr, $value:expr, $good:expr, $bad:expr) => {{
-        let val = read_registry_dword($hkey, $path, $value);
-        let (status, text) = if val == Some($good) {
-            (CheckStatus::Optimal, "Optimal")
-        } else {
-            (CheckStatus::Warning, "Suboptimal")
-        };
-        Check::new($name, text, status)
-    }};
+use super::context::CheckContext;
+pub use super::registry::{
+    Hkey, Mistyped, RegRead, RegValue, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+};
+pub use crate::remediation::Remediation;
+
+/// `vec!` of checks, each evaluated through `Check::timed`.
+macro_rules! timed {
+    ($($check:expr),* $(,)?) => {
+        vec![$(crate::types::Check::timed(|| $check)),*]
+    };
 }
-
-#[cfg(windows)]
-pub fn read_registry_string(_hkey: Hkey, subkey: &str, value_name: &str) -> Option<String> {
-    use std::ffi::OsString;
-    use std::os::windows::ffi::OsStringExt;
-    use std::ptr;
-
-    unsafe {
-        let hkey_root: isize = if _hkey == HKEY_LOCAL_MACHINE {
-            0x80000002_u32 as isize
-        } else {
-            0x80000001_u32 as isize
-        };
-
-        let subkey_wide: Vec<u16> = subkey.encode_utf16().chain(std::iter::once(0)).collect();
-        let value_wide: Vec<u16> = value_name
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
-
-        let mut hkey_result: isize = 0;
-        if winapi::um::winreg::RegOpenKeyExW(
-            hkey_root,
-            subkey_wide.as_ptr(),
-            0,
-            winapi::um::winnt::KEY_READ,
-            &mut hkey_result,
-        ) != 0
-        {
-            return None;
-        }
-
-        // Query size first
-        let mut size = 0u32;
-        let mut data_type = 0u32;
-        if winapi::um::winreg::RegQueryValueExW(
-            hkey_result,
-            value_wide.as_ptr(),
-            ptr::null_mut(),
-            &mut data_type,
-            ptr::null_mut(),
-            &mut size,
-        ) != 0
-        {
-            winapi::um::winreg::RegCloseKey(hkey_result);
-            return None;
-        }
-
-        // Allocate buffer based on actual size
-        let mut buffer = vec![0u16; (size as usize / 2) + 1];
-        let mut buffer_size = size;
-
-        if winapi::um::winreg::RegQueryValueExW(
-            hkey_result,
-            value_wide.as_ptr(),
-            ptr::null_mut(),
-            &mut data_type,
-            buffer.as_mut_ptr() as *mut u8,
-            &mut buffer_size,
-        ) == 0
-        {
-            winapi::um::winreg::RegCloseKey(hkey_result);
-            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
-            Some(
-                OsString::from_wide(&buffer[..len])
-                    .to_string_lossy()
-                    .to_string(),
-            )
-        } else {
-            winapi::um::winreg::RegCloseKey(hkey_result);
-            None
-        }
+pub(crate) use timed;
+
+/// Reads a value and converts it with `pick`, which hands back values of
+/// the wrong type so they can be reported as a mismatch.
+fn lookup<T>(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+    pick: impl FnOnce(RegValue) -> Result<T, RegValue>,
+) -> RegRead<T> {
+    match ctx.registry().read_value(hkey, subkey, value_name) {
+        None => RegRead::Missing,
+        Some(value) => match pick(value) {
+            Ok(v) => RegRead::Value(v),
+            Err(other) => RegRead::TypeMismatch(other),
+        },
     }
 }
 
-#[cfg(not(windows))]
-pub fn read_registry_string(_hkey: Hkey, _subkey: &str, _value_name: &str) -> Option<String> {
-    None
+/// REG_SZ or REG_EXPAND_SZ (unexpanded).
+pub fn lookup_registry_string(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+) -> RegRead<String> {
+    lookup(ctx, hkey, subkey, value_name, |v| match v {
+        RegValue::String(s) | RegValue::ExpandString(s) => Ok(s),
+        other => Err(other),
+    })
 }
 
-#[cfg(windows)]
-pub fn read_registry_dword(_hkey: Hkey, subkey: &str, value_name: &str) -> Option<u32> {
-    use std::ptr;
-
-    unsafe {
-        let hkey_root: isize = if _hkey == HKEY_LOCAL_MACHINE {
-            0x80000002_u32 as isize
-        } else {
-            0x80000001_u32 as isize
-        };
-
-        let subkey_wide: Vec<u16> = subkey.encode_utf16().chain(std::iter::once(0)).collect();
-        let value_wide: Vec<u16> = value_name
-            .encode_utf16()
-            .chain(std::iter::once(0))
-            .collect();
+pub fn lookup_registry_dword(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+) -> RegRead<u32> {
+    lookup(ctx, hkey, subkey, value_name, |v| match v {
+        RegValue::Dword(n) => Ok(n),
+        other => Err(other),
+    })
+}
 
-        let mut hkey_result: isize = 0;
-        if winapi::um::winreg::RegOpenKeyExW(
-            hkey_root,
-            subkey_wide.as_ptr(),
-            0,
-            winapi::um::winnt::KEY_READ,
-            &mut hkey_result,
-        ) != 0
-        {
-            return None;
-        }
+pub fn lookup_registry_qword(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+) -> RegRead<u64> {
+    lookup(ctx, hkey, subkey, value_name, |v| match v {
+        RegValue::Qword(n) => Ok(n),
+        other => Err(other),
+    })
+}
 
-        let mut value: u32 = 0;
-        let mut buffer_size = 4u32;
-        let mut data_type = 0u32;
+pub fn lookup_registry_multi_string(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+) -> RegRead<Vec<String>> {
+    lookup(ctx, hkey, subkey, value_name, |v| match v {
+        RegValue::MultiString(items) => Ok(items),
+        other => Err(other),
+    })
+}
 
-        if winapi::um::winreg::RegQueryValueExW(
-            hkey_result,
-            value_wide.as_ptr(),
-            ptr::null_mut(),
-            &mut data_type,
-            &mut value as *mut u32 as *mut u8,
-            &mut buffer_size,
-        ) == 0
-            && data_type == 4
-        {
-            winapi::um::winreg::RegCloseKey(hkey_result);
-            Some(value)
-        } else {
-            winapi::um::winreg::RegCloseKey(hkey_result);
-            None
-        }
-    }
+pub fn lookup_registry_binary(
+    ctx: &CheckContext,
+    hkey: Hkey,
+    subkey: &str,
+    value_name: &str,
+) -> RegRead<Vec<u8>> {
+    lookup(ctx, hkey, subkey, value_name, |v| match v {
+        RegValue::Binary(data) => Ok(data),
+        other => Err(other),
+    })
 }
 
-#[cfg(not(windows))]
-pub fn read_registry_dword(_hkey: Hkey, _subkey: &str, _value_name: &str) -> Option<u32> {
-    None
+pub fn registry_key_exists(ctx: &CheckContext, hkey: Hkey, subkey: &str) -> bool {
+    ctx.registry().key_exists(hkey, subkey)
 }
 
-pub fn registry_key_exists(hkey: Hkey, subkey: &str) -> bool {
-    read_registry_string(hkey, subkey, "").is_some()
-        || read_registry_dword(hkey, subkey, "").is_some()
+/// Direct subkey names; empty if the key does not exist.
+pub fn registry_subkeys(ctx: &CheckContext, hkey: Hkey, subkey: &str) -> Vec<String> {
+    ctx.registry().subkeys(hkey, subkey).unwrap_or_default()
 }
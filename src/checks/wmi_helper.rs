@@ -1,196 +1,100 @@
-#[cfg(windows)]
+use super::context::CheckContext;
+use super::wmi_source::{WmiRow, WmiValue};
+use crate::types::CheckError;
+
 fn sanitize_wmi_identifier(s: &str) -> bool {
     s.chars().all(|c| c.is_alphanumeric() || c == '_')
 }
 
-#[cfg(windows)]
-pub fn query_wmi_u32(class: &str, property: &str) -> Option<u32> {
-    use wmi::{COMLibrary, WMIConnection};
-
-    if !sanitize_wmi_identifier(class) || !sanitize_wmi_identifier(property) {
-        return None;
-    }
-
-    let com_con = COMLibrary::new().ok()?;
-    let wmi_con = WMIConnection::new(com_con).ok()?;
-
-    let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
-        .raw_query(&format!("SELECT {} FROM {}", property, class))
-        .ok()?;
-
-    results.first().and_then(|r| {
-        r.get(property).and_then(|v| match v {
-            wmi::Variant::UI4(n) => Some(*n),
-            wmi::Variant::I4(n) => Some(*n as u32),
-            wmi::Variant::UI2(n) => Some(*n as u32),
-            wmi::Variant::UI1(n) => Some(*n as u32),
-            _ => None,
-        })
-    })
+/// Runs a query; a failed query is an error rather than an empty answer.
+fn query(ctx: &CheckContext, wql: &str, class: &str) -> Result<Vec<WmiRow>, CheckError> {
+    ctx.wmi()
+        .query(wql)
+        .ok_or_else(|| CheckError::Wmi(format!("{} query failed", class)))
 }
 
-#[cfg(windows)]
-pub fn query_wmi_u64(class: &str, property: &str) -> Option<u64> {
-    use wmi::{COMLibrary, WMIConnection};
-
+/// `Ok(None)` when the query succeeded but returned no value.
+fn query_first_property(
+    ctx: &CheckContext,
+    class: &str,
+    property: &str,
+) -> Result<Option<WmiValue>, CheckError> {
     if !sanitize_wmi_identifier(class) || !sanitize_wmi_identifier(property) {
-        return None;
+        return Err(CheckError::Wmi(format!(
+            "invalid identifier {}.{}",
+            class, property
+        )));
     }
 
-    let com_con = COMLibrary::new().ok()?;
-    let wmi_con = WMIConnection::new(com_con).ok()?;
-
-    let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
-        .raw_query(&format!("SELECT {} FROM {}", property, class))
-        .ok()?;
-
-    results.first().and_then(|r| {
-        r.get(property).and_then(|v| match v {
-            wmi::Variant::UI8(n) => Some(*n),
-            wmi::Variant::I8(n) => Some(*n as u64),
-            wmi::Variant::UI4(n) => Some(*n as u64),
-            _ => None,
-        })
-    })
+    let rows = query(ctx, &format!("SELECT {} FROM {}", property, class), class)?;
+    Ok(rows
+        .into_iter()
+        .next()
+        .and_then(|mut row| row.remove(property)))
 }
 
-#[cfg(windows)]
-pub fn query_wmi_string(class: &str, property: &str) -> Option<String> {
-    use wmi::{COMLibrary, WMIConnection};
-
-    if !sanitize_wmi_identifier(class) || !sanitize_wmi_identifier(property) {
-        return None;
+/// The answer, if any, and the error when the query failed; a check
+/// reports the latter through `Check::with_error`.
+pub fn split_failure<T>(result: Result<Option<T>, CheckError>) -> (Option<T>, Option<CheckError>) {
+    match result {
+        Ok(value) => (value, None),
+        Err(err) => (None, Some(err)),
     }
+}
 
-    let com_con = COMLibrary::new().ok()?;
-    let wmi_con = WMIConnection::new(com_con).ok()?;
-
-    let results: Vec<std::collections::HashMap<String, wmi::Variant>> = wmi_con
-        .raw_query(&format!("SELECT {} FROM {}", property, class))
-        .ok()?;
+pub fn query_wmi_u32(
+    ctx: &CheckContext,
+    class: &str,
+    property: &str,
+) -> Result<Option<u32>, CheckError> {
+    Ok(query_first_property(ctx, class, property)?.and_then(|v| v.as_u32()))
+}
 
-    results.first().and_then(|r| {
-        r.get(property).and_then(|v| match v {
-            wmi::Variant::String(s) => Some(s.clone()),
-            _ => None,
-        })
-    })
+pub fn query_wmi_u64(
+    ctx: &CheckContext,
+    class: &str,
+    property: &str,
+) -> Result<Option<u64>, CheckError> {
+    Ok(query_first_property(ctx, class, property)?.and_then(|v| v.as_u64()))
 }
 
-#[cfg(windows)]
-pub fn count_wmi_instances(class: &str) -> usize {
-    use wmi::{COMLibrary, WMIConnection};
+pub fn query_wmi_string(
+    ctx: &CheckContext,
+    class: &str,
+    property: &str,
+) -> Result<Option<String>, CheckError> {
+    Ok(query_first_property(ctx, class, property)?.and_then(|v| v.as_str().map(str::to_string)))
+}
 
+pub fn count_wmi_instances(ctx: &CheckContext, class: &str) -> Result<usize, CheckError> {
     if !sanitize_wmi_identifier(class) {
-        return 0;
+        return Err(CheckError::Wmi(format!("invalid identifier {}", class)));
     }
 
-    let com_con = COMLibrary::new().ok();
-    let wmi_con = com_con.and_then(|c| WMIConnection::new(c).ok());
-
-    wmi_con
-        .and_then(|w| {
-            let results: Result<Vec<std::collections::HashMap<String, wmi::Variant>>, _> =
-                w.raw_query(&format!("SELECT * FROM {}", class));
-            results.ok().map(|r| r.len())
-        })
-        .unwrap_or(0)
+    Ok(query(ctx, &format!("SELECT * FROM {}", class), class)?.len())
 }
 
-#[cfg(windows)]
-pub fn query_cpu_info() -> Option<CpuInfo> {
-    use wmi::{COMLibrary, WMIConnection};
-
-    let com_con = COMLibrary::new().ok()?;
-    let wmi_con = WMIConnection::new(com_con).ok()?;
-
-    let results: Vec<std::collections::HashMap<String, wmi::Variant>> =
-        wmi_con.raw_query("SELECT Name,NumberOfCores,NumberOfLogicalProcessors,MaxClockSpeed,CurrentClockSpeed,L2CacheSize,L3CacheSize,Architecture FROM Win32_Processor").ok()?;
+pub fn query_cpu_info(ctx: &CheckContext) -> Result<Option<CpuInfo>, CheckError> {
+    let rows = query(ctx, "SELECT Name,NumberOfCores,NumberOfLogicalProcessors,MaxClockSpeed,CurrentClockSpeed,L2CacheSize,L3CacheSize,Architecture FROM Win32_Processor", "Win32_Processor")?;
+    let Some(first) = rows.first() else {
+        return Ok(None);
+    };
+    let u32_of = |name: &str| first.get(name).and_then(WmiValue::as_u32).unwrap_or(0);
 
-    let first = results.first()?;
-
-    Some(CpuInfo {
+    Ok(Some(CpuInfo {
         name: first
             .get("Name")
-            .and_then(|v| {
-                if let wmi::Variant::String(s) = v {
-                    Some(s.clone())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default(),
-        cores: first
-            .get("NumberOfCores")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        logical: first
-            .get("NumberOfLogicalProcessors")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        max_clock: first
-            .get("MaxClockSpeed")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        current_clock: first
-            .get("CurrentClockSpeed")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        l2_cache: first
-            .get("L2CacheSize")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        l3_cache: first
-            .get("L3CacheSize")
-            .and_then(|v| {
-                if let wmi::Variant::UI4(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-        architecture: first
-            .get("Architecture")
-            .and_then(|v| {
-                if let wmi::Variant::UI2(n) = v {
-                    Some(*n)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0),
-    })
+            .and_then(WmiValue::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        cores: u32_of("NumberOfCores"),
+        logical: u32_of("NumberOfLogicalProcessors"),
+        max_clock: u32_of("MaxClockSpeed"),
+        current_clock: u32_of("CurrentClockSpeed"),
+        l2_cache: u32_of("L2CacheSize"),
+        l3_cache: u32_of("L3CacheSize"),
+        architecture: u32_of("Architecture") as u16,
+    }))
 }
 
 #[derive(Debug, Clone)]
@@ -204,28 +108,3 @@ pub struct CpuInfo {
     pub l3_cache: u32,
     pub architecture: u16,
 }
-
-#[cfg(not(windows))]
-pub fn query_wmi_u32(_class: &str, _property: &str) -> Option<u32> {
-    None
-}
-
-#[cfg(not(windows))]
-pub fn query_wmi_u64(_class: &str, _property: &str) -> Option<u64> {
-    None
-}
-
-#[cfg(not(windows))]
-pub fn query_wmi_string(_class: &str, _property: &str) -> Option<String> {
-    None
-}
-
-#[cfg(not(windows))]
-pub fn count_wmi_instances(_class: &str) -> usize {
-    0
-}
-
-#[cfg(not(windows))]
-pub fn query_cpu_info() -> Option<CpuInfo> {
-    None
-}
@@ -0,0 +1,183 @@
+//! WMI access behind a pluggable source, with record/replay fixtures.
+//!
+//! Record mode wraps the live connection and captures every WQL query with
+//! all of its result rows; replay mode answers the same queries from that
+//! capture so checks can be re-run against a customer's machine anywhere.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WmiValue {
+    Null,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<WmiValue>),
+}
+
+pub type WmiRow = BTreeMap<String, WmiValue>;
+
+impl WmiValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            WmiValue::UInt(n) => Some(*n),
+            WmiValue::Int(n) => u64::try_from(*n).ok(),
+            // WMI hands 64-bit integers back as strings.
+            WmiValue::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_u64().and_then(|n| u32::try_from(n).ok())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            WmiValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Answers WQL queries. `None` means the query itself failed.
+pub trait WmiSource: Send + Sync {
+    fn query(&self, wql: &str) -> Option<Vec<WmiRow>>;
+}
+
+/// WMI on the machine we are running on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveWmi;
+
+#[cfg(windows)]
+impl WmiSource for LiveWmi {
+    fn query(&self, wql: &str) -> Option<Vec<WmiRow>> {
+        use wmi::{COMLibrary, WMIConnection};
+
+        let com_con = COMLibrary::new().ok()?;
+        let wmi_con = WMIConnection::new(com_con).ok()?;
+        let rows: Vec<std::collections::HashMap<String, wmi::Variant>> =
+            wmi_con.raw_query(wql).ok()?;
+
+        Some(
+            rows.into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|(k, v)| (k, convert_variant(v)))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(windows)]
+fn convert_variant(v: wmi::Variant) -> WmiValue {
+    use wmi::Variant;
+
+    match v {
+        Variant::String(s) => WmiValue::String(s),
+        Variant::Bool(b) => WmiValue::Bool(b),
+        Variant::I1(n) => WmiValue::Int(n as i64),
+        Variant::I2(n) => WmiValue::Int(n as i64),
+        Variant::I4(n) => WmiValue::Int(n as i64),
+        Variant::I8(n) => WmiValue::Int(n),
+        Variant::UI1(n) => WmiValue::UInt(n as u64),
+        Variant::UI2(n) => WmiValue::UInt(n as u64),
+        Variant::UI4(n) => WmiValue::UInt(n as u64),
+        Variant::UI8(n) => WmiValue::UInt(n),
+        Variant::R4(n) => WmiValue::Float(n as f64),
+        Variant::R8(n) => WmiValue::Float(n),
+        Variant::Array(items) => WmiValue::Array(items.into_iter().map(convert_variant).collect()),
+        _ => WmiValue::Null,
+    }
+}
+
+#[cfg(not(windows))]
+impl WmiSource for LiveWmi {
+    fn query(&self, _wql: &str) -> Option<Vec<WmiRow>> {
+        None
+    }
+}
+
+/// Captured WQL queries and their result rows, as stored on disk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WmiFixture {
+    pub queries: BTreeMap<String, Option<Vec<WmiRow>>>,
+}
+
+impl WmiFixture {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read WMI fixture {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Invalid WMI fixture {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write WMI fixture {}", path.display()))
+    }
+}
+
+/// Forwards queries to another source and remembers every answer.
+pub struct RecordingWmi {
+    inner: Arc<dyn WmiSource>,
+    fixture: Mutex<WmiFixture>,
+}
+
+impl RecordingWmi {
+    pub fn new(inner: Arc<dyn WmiSource>) -> Self {
+        Self {
+            inner,
+            fixture: Mutex::new(WmiFixture::default()),
+        }
+    }
+
+    pub fn fixture(&self) -> WmiFixture {
+        self.fixture.lock().unwrap().clone()
+    }
+}
+
+impl WmiSource for RecordingWmi {
+    fn query(&self, wql: &str) -> Option<Vec<WmiRow>> {
+        let rows = self.inner.query(wql);
+        self.fixture
+            .lock()
+            .unwrap()
+            .queries
+            .insert(wql.trim().to_string(), rows.clone());
+        rows
+    }
+}
+
+/// Answers queries from a fixture; unknown queries fail like a dead connection.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayWmi {
+    fixture: WmiFixture,
+}
+
+impl ReplayWmi {
+    pub fn new(fixture: WmiFixture) -> Self {
+        Self { fixture }
+    }
+}
+
+impl WmiSource for ReplayWmi {
+    fn query(&self, wql: &str) -> Option<Vec<WmiRow>> {
+        match self.fixture.queries.get(wql.trim()) {
+            Some(rows) => rows.clone(),
+            None => {
+                tracing::debug!("WMI query not in fixture: {}", wql);
+                None
+            }
+        }
+    }
+}
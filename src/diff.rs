@@ -0,0 +1,172 @@
+//! Compares two saved audit reports check by check.
+//!
+//! Reports written with `--json` are matched by check ID, so a later build
+//! with more checks still lines up. Only Optimal, Warning and Issue are
+//! graded: moving down that scale is a regression and up it an
+//! improvement. Any other status or value change is listed on its own, so
+//! a driver update that resets a tweak shows up even when the check is
+//! informational.
+
+use crate::types::{AuditResults, Check, CheckStatus};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Which report a side of the diff came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportInfo {
+    pub path: String,
+    pub timestamp: String,
+    pub profile: Option<String>,
+}
+
+/// One side of a check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckState {
+    pub status: String,
+    pub value: String,
+}
+
+impl CheckState {
+    fn of(check: &Check) -> Self {
+        Self {
+            status: check.status.to_string(),
+            value: check.value.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckChange {
+    pub check_id: String,
+    pub name: String,
+    pub category: String,
+    pub old: CheckState,
+    pub new: CheckState,
+}
+
+/// A check present in only one of the reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckPresence {
+    pub check_id: String,
+    pub name: String,
+    pub category: String,
+    pub state: CheckState,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportDiff {
+    pub old: ReportInfo,
+    pub new: ReportInfo,
+    pub regressions: Vec<CheckChange>,
+    pub improvements: Vec<CheckChange>,
+    /// Same grade, or an ungraded status, but a different value or status.
+    pub changed: Vec<CheckChange>,
+    pub added: Vec<CheckPresence>,
+    pub removed: Vec<CheckPresence>,
+    pub unchanged: usize,
+}
+
+impl ReportDiff {
+    pub fn is_empty(&self) -> bool {
+        self.regressions.is_empty()
+            && self.improvements.is_empty()
+            && self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// Reads a report written with `--json`.
+pub fn load(path: &Path) -> Result<AuditResults> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a Windows-Optimizer JSON report", path.display()))
+}
+
+/// Position on the Optimal → Warning → Issue scale; `None` for statuses
+/// that are not a verdict.
+fn grade(status: &CheckStatus) -> Option<u8> {
+    match status {
+        CheckStatus::Optimal => Some(0),
+        CheckStatus::Warning => Some(1),
+        CheckStatus::Issue => Some(2),
+        _ => None,
+    }
+}
+
+/// Every check by ID, with its category. Reports from before check IDs
+/// existed fall back to category and name.
+fn index(results: &AuditResults) -> BTreeMap<String, (&str, &Check)> {
+    results
+        .categories
+        .values()
+        .flat_map(|category| {
+            category.checks.iter().map(move |check| {
+                let key = if check.id.is_empty() {
+                    format!("{}/{}", category.name, check.name)
+                } else {
+                    check.id.clone()
+                };
+                (key, (category.name.as_str(), check))
+            })
+        })
+        .collect()
+}
+
+pub fn compare(old: &AuditResults, new: &AuditResults, paths: (&Path, &Path)) -> ReportDiff {
+    let info = |results: &AuditResults, path: &Path| ReportInfo {
+        path: path.display().to_string(),
+        timestamp: results.timestamp.clone(),
+        profile: results.profile.clone(),
+    };
+    let mut diff = ReportDiff {
+        old: info(old, paths.0),
+        new: info(new, paths.1),
+        regressions: Vec::new(),
+        improvements: Vec::new(),
+        changed: Vec::new(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        unchanged: 0,
+    };
+    let presence = |id: &str, (category, check): (&str, &Check)| CheckPresence {
+        check_id: id.to_string(),
+        name: check.name.clone(),
+        category: category.to_string(),
+        state: CheckState::of(check),
+    };
+
+    let old = index(old);
+    let new = index(new);
+    for (id, &(category, after)) in &new {
+        let Some(&(_, before)) = old.get(id) else {
+            diff.added.push(presence(id, (category, after)));
+            continue;
+        };
+        let change = CheckChange {
+            check_id: id.clone(),
+            name: after.name.clone(),
+            category: category.to_string(),
+            old: CheckState::of(before),
+            new: CheckState::of(after),
+        };
+        if change.old == change.new {
+            diff.unchanged += 1;
+            continue;
+        }
+        match (grade(&before.status), grade(&after.status)) {
+            (Some(was), Some(now)) if now > was => diff.regressions.push(change),
+            (Some(was), Some(now)) if now < was => diff.improvements.push(change),
+            _ => diff.changed.push(change),
+        }
+    }
+    for (id, &entry) in &old {
+        if !new.contains_key(id) {
+            diff.removed.push(presence(id, entry));
+        }
+    }
+    diff
+}
@@ -0,0 +1,146 @@
+//! Configuration drift: applied changes that no longer hold.
+//!
+//! Driver and feature updates reset tweaks without telling anyone. The
+//! baseline is either an apply session from `crate::history` (what it
+//! wrote, minus anything a later session changed again) or a profile's
+//! judged fixes. Each step is read back; the ones that no longer match are
+//! reported with their key's last-write time, which usually dates the
+//! update that reset them, and can be re-applied as they are.
+
+use crate::history::History;
+use crate::journal::Journal;
+use crate::remediation::{Remediation, SystemReader};
+use crate::types::{AuditResults, Check, CheckStatus};
+use anyhow::Result;
+use serde::Serialize;
+
+/// One change the baseline expects to hold.
+#[derive(Debug, Clone)]
+pub struct Expectation {
+    pub check_id: String,
+    pub change: Remediation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftItem {
+    pub check_id: String,
+    pub target: String,
+    pub expected: String,
+    pub current: String,
+    /// RFC3339; `None` for power settings, bcdedit and sources that do
+    /// not keep last-write times.
+    pub last_write: Option<String>,
+    /// What re-applying writes.
+    pub fix: Remediation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    /// "session 20261017-142233" or "gaming profile".
+    pub baseline: String,
+    pub checked: usize,
+    pub drifted: Vec<DriftItem>,
+    /// Targets that could not be read back, such as bcdedit changes.
+    pub unverified: Vec<String>,
+}
+
+impl DriftReport {
+    /// One check per drifted check ID, batching its steps, ready for
+    /// `crate::journal::apply_session`.
+    pub fn fixes(&self) -> Vec<Check> {
+        let mut fixes: Vec<(String, Vec<Remediation>)> = Vec::new();
+        for item in &self.drifted {
+            match fixes.iter_mut().find(|(id, _)| *id == item.check_id) {
+                Some((_, steps)) => steps.push(item.fix.clone()),
+                None => fixes.push((item.check_id.clone(), vec![item.fix.clone()])),
+            }
+        }
+        fixes
+            .into_iter()
+            .map(|(id, mut steps)| {
+                let fix = if steps.len() == 1 {
+                    steps.remove(0)
+                } else {
+                    Remediation::Batch { steps }
+                };
+                Check::new(&id, "Drifted", CheckStatus::Issue)
+                    .with_id(&id)
+                    .with_remediation(fix)
+            })
+            .collect()
+    }
+}
+
+/// What `session` wrote, except targets a later session changed again.
+/// With no session ID, the latest apply that still stands.
+pub fn session_baseline(
+    history: &History,
+    session: Option<&str>,
+) -> Result<(Journal, Vec<Expectation>)> {
+    let session = match session {
+        Some(id) => history.get(id)?,
+        None => history
+            .latest()?
+            .ok_or_else(|| anyhow::anyhow!("No applied session in the history yet"))?,
+    };
+    if let Some(by) = &session.undone_by {
+        anyhow::bail!("Session {} was undone by {}", session.session, by);
+    }
+    let later = history.later_sessions(&session)?;
+
+    let expectations = session
+        .applied_steps()
+        .filter(|(_, step)| !later.iter().any(|s| s.touches(&step.target())))
+        .map(|(entry, step)| Expectation {
+            check_id: entry.check_id.clone(),
+            change: step.clone(),
+        })
+        .collect();
+    Ok((session, expectations))
+}
+
+/// The fixes a profile judged onto `results`, one per targeted check.
+pub fn profile_baseline(results: &AuditResults, targets: &[&String]) -> Vec<Expectation> {
+    results
+        .categories
+        .values()
+        .flat_map(|c| &c.checks)
+        .filter(|check| targets.contains(&&check.id))
+        .filter_map(|check| {
+            let fix = check.remediation.as_ref()?;
+            Some(fix.steps().into_iter().map(|step| Expectation {
+                check_id: check.id.clone(),
+                change: step.clone(),
+            }))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Reads every expectation back.
+pub fn detect(baseline: &str, expected: &[Expectation], reader: &dyn SystemReader) -> DriftReport {
+    let mut report = DriftReport {
+        baseline: baseline.to_string(),
+        checked: expected.len(),
+        drifted: Vec::new(),
+        unverified: Vec::new(),
+    };
+    for Expectation { check_id, change } in expected {
+        match change.is_satisfied(reader) {
+            Some(true) => {}
+            None => report.unverified.push(change.target()),
+            Some(false) => report.drifted.push(DriftItem {
+                check_id: check_id.clone(),
+                target: change.target(),
+                expected: change.proposed(),
+                current: change.capture(reader).describe(),
+                last_write: change
+                    .registry_key()
+                    .and_then(|(hive, path)| reader.key_last_write(hive, &path))
+                    .map(|time| time.to_rfc3339()),
+                fix: change.clone(),
+            }),
+        }
+    }
+    report
+}
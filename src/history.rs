@@ -0,0 +1,216 @@
+//! Every finished apply session, kept so any one of them can be undone.
+//!
+//! Sessions are the journals of `crate::journal`, archived as
+//! `<session-id>.json` once they commit or roll back. Undoing a session
+//! writes each entry's prior state back as a new session of its own. It
+//! refuses when a later session touched the same target, or when the
+//! target no longer holds what the session wrote, rather than overwrite
+//! someone else's change.
+
+use crate::journal::{self, Journal, Outcome, StepState, TransactionReport};
+use crate::remediation::{Remediation, SystemWriter};
+use crate::types::{Check, CheckStatus};
+use anyhow::{Context, Result};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// Orders sessions by when they started, then by ID for sessions that
+/// started in the same instant.
+fn chronological(a: &Journal, b: &Journal) -> Ordering {
+    (a.started_at(), &a.session).cmp(&(b.started_at(), &b.session))
+}
+
+pub fn default_dir() -> PathBuf {
+    journal::state_dir().join("history")
+}
+
+/// A target that undo would not overwrite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub check_id: String,
+    pub target: String,
+    /// The later session that changed the target, if it was one of ours.
+    pub session: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+pub enum UndoOutcome {
+    /// Nothing was changed.
+    Conflicts(Vec<Conflict>),
+    Done(Box<TransactionReport>),
+}
+
+pub struct History {
+    dir: PathBuf,
+}
+
+impl History {
+    pub fn open(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn path(&self, session: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", session))
+    }
+
+    /// Archives a finished session. Returns its ID, which gains a suffix
+    /// if another session started in the same second.
+    pub fn record(&self, mut session: Journal) -> Result<String> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let base = session.session.clone();
+        let mut n = 1;
+        while self.path(&session.session).exists() {
+            n += 1;
+            session.session = format!("{}-{}", base, n);
+        }
+        session.save(&self.path(&session.session))?;
+        Ok(session.session)
+    }
+
+    /// All sessions, oldest first.
+    pub fn sessions(&self) -> Result<Vec<Journal>> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Ok(Vec::new());
+        };
+        let mut sessions = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .map(|path| Journal::load(&path))
+            .collect::<Result<Vec<_>>>()?;
+        sessions.sort_by(chronological);
+        Ok(sessions)
+    }
+
+    pub fn get(&self, session: &str) -> Result<Journal> {
+        let path = self.path(session);
+        if !path.exists() {
+            anyhow::bail!("No session '{}' in {}", session, self.dir.display());
+        }
+        Journal::load(&path)
+    }
+
+    /// Committed sessions after `session` whose changes still stand.
+    /// Undo sessions only put back what was there before, so they never
+    /// supersede anything.
+    pub fn later_sessions(&self, session: &Journal) -> Result<Vec<Journal>> {
+        Ok(self
+            .sessions()?
+            .into_iter()
+            .filter(|s| chronological(s, session) == Ordering::Greater)
+            .filter(|s| s.outcome == Some(Outcome::Committed) && s.undone_by.is_none())
+            .filter(|s| s.undoes.is_none())
+            .collect())
+    }
+
+    /// The last apply whose changes still stand: committed, not undone and
+    /// not itself an undo.
+    pub fn latest(&self) -> Result<Option<Journal>> {
+        Ok(self.sessions()?.into_iter().rev().find(|s| {
+            s.outcome == Some(Outcome::Committed) && s.undone_by.is_none() && s.undoes.is_none()
+        }))
+    }
+
+    /// Targets in `session` that undo must not touch.
+    pub fn conflicts(&self, session: &Journal, writer: &dyn SystemWriter) -> Result<Vec<Conflict>> {
+        let later = self.later_sessions(session)?;
+
+        let mut conflicts = Vec::new();
+        for (entry, step) in session.applied_steps() {
+            let target = step.target();
+            let conflict = |session, reason| Conflict {
+                check_id: entry.check_id.clone(),
+                target: target.clone(),
+                session,
+                reason,
+            };
+            if let Some(other) = later.iter().find(|s| s.touches(&target)) {
+                conflicts.push(conflict(
+                    Some(other.session.clone()),
+                    format!("changed again by session {}", other.session),
+                ));
+            } else if let Err(err) = step.verify(writer) {
+                conflicts.push(conflict(None, format!("changed since: {}", err)));
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// Reverts one session, transactionally, unless something else has
+    /// changed the same targets since.
+    pub fn undo(
+        &self,
+        id: &str,
+        writer: &dyn SystemWriter,
+        journal_path: &Path,
+    ) -> Result<UndoOutcome> {
+        let mut session = self.get(id)?;
+        if let Some(by) = &session.undone_by {
+            anyhow::bail!("Session {} was already undone by {}", id, by);
+        }
+        if session.outcome != Some(Outcome::Committed) {
+            anyhow::bail!("Session {} was rolled back; there is nothing to undo", id);
+        }
+
+        let conflicts = self.conflicts(&session, writer)?;
+        if !conflicts.is_empty() {
+            return Ok(UndoOutcome::Conflicts(conflicts));
+        }
+
+        let mut checks = Vec::new();
+        for entry in applied(&session).rev() {
+            let undo = entry.prior.undo(&entry.change).with_context(|| {
+                format!(
+                    "{} cannot be undone: its previous state was not recorded",
+                    entry.check_id
+                )
+            })?;
+            checks.push(
+                Check::new(&entry.check_id, "", CheckStatus::Issue)
+                    .with_id(&entry.check_id)
+                    .with_remediation(undo),
+            );
+        }
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let mut undo = Journal::new();
+        undo.undoes = Some(session.session.clone());
+        let mut report = journal::apply_session(undo, &plan, writer, journal_path)?;
+        let undo_id = self.record(report.journal.clone())?;
+        report.session = undo_id.clone();
+        report.journal.session = undo_id.clone();
+
+        if report.committed() {
+            session.undone_by = Some(undo_id);
+            session.save(&self.path(&session.session))?;
+        }
+        Ok(UndoOutcome::Done(Box::new(report)))
+    }
+}
+
+fn applied(session: &Journal) -> impl DoubleEndedIterator<Item = &journal::JournalEntry> {
+    session
+        .entries
+        .iter()
+        .filter(|e| e.state == StepState::Applied)
+}
+
+/// Before and after of one step, for `history <session>`.
+pub fn describe_entry(entry: &journal::JournalEntry) -> Vec<(String, String, String)> {
+    let states = match &entry.prior {
+        crate::remediation::PriorState::Batch { states } => states.iter().collect(),
+        prior => vec![prior],
+    };
+    let steps: Vec<&Remediation> = match &entry.change {
+        Remediation::Batch { steps } => steps.iter().collect(),
+        change => vec![change],
+    };
+    steps
+        .iter()
+        .zip(states)
+        .map(|(step, prior)| (step.target(), prior.describe(), step.proposed()))
+        .collect()
+}
@@ -0,0 +1,306 @@
+//! Transactional apply backed by an on-disk change journal.
+//!
+//! Before a fix touches the system, the target's prior state (including
+//! "did not exist") is written to the journal and flushed to disk. Every
+//! write is read back; if a step fails, the steps taken so far are undone
+//! in reverse order. A journal left behind by a crash is rolled back by
+//! `recover` on the next run.
+
+use crate::remediation::{ApplyError, PriorState, Remediation, SystemWriter};
+use crate::types::Check;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+pub const JOURNAL_FILE: &str = "apply-journal.json";
+
+/// `%ProgramData%\Windows-Optimizer`, or the temp directory off Windows.
+pub fn state_dir() -> PathBuf {
+    std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("Windows-Optimizer")
+}
+
+pub fn default_path() -> PathBuf {
+    state_dir().join(JOURNAL_FILE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepState {
+    /// Prior state recorded; the change may or may not have been made.
+    Pending,
+    Applied,
+    RolledBack,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub check_id: String,
+    pub change: Remediation,
+    pub prior: PriorState,
+    pub state: StepState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Committed,
+    RolledBack,
+}
+
+/// One apply session. While it runs this is the crash journal; once it
+/// finishes it is archived in `crate::history`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Journal {
+    pub session: String,
+    pub started: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// The session this one reverted, for sessions run by `undo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<String>,
+    /// `None` while the session is still running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Outcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undone_by: Option<String>,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        let now = chrono::Local::now();
+        Self {
+            session: now.format("%Y%m%d-%H%M%S").to_string(),
+            started: now.to_rfc3339(),
+            profile: None,
+            undoes: None,
+            outcome: None,
+            undone_by: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// When the session started as an instant, so sessions order correctly
+    /// across DST and time zone changes; `None` if `started` is malformed.
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.started)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read journal {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("Corrupt journal {}", path.display()))
+    }
+
+    /// Writes a sibling file, syncs it and renames it over the journal so
+    /// a crash never leaves a half-written journal behind.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let tmp = path.with_extension("tmp");
+        let mut file = std::fs::File::create(&tmp)
+            .with_context(|| format!("Failed to write journal {}", tmp.display()))?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write journal {}", path.display()))
+    }
+}
+
+impl Journal {
+    /// Steps this session actually made, batches flattened.
+    pub fn applied_steps(&self) -> impl Iterator<Item = (&JournalEntry, &Remediation)> {
+        self.entries
+            .iter()
+            .filter(|e| e.state == StepState::Applied)
+            .flat_map(|e| e.change.steps().into_iter().map(move |step| (e, step)))
+    }
+
+    /// Whether this session changed `target` (see `Remediation::target`).
+    pub fn touches(&self, target: &str) -> bool {
+        self.applied_steps()
+            .any(|(_, step)| step.target().eq_ignore_ascii_case(target))
+    }
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionReport {
+    pub session: String,
+    /// The finished session, ready for `crate::history`.
+    pub journal: Journal,
+    /// Checks whose fix was written and verified.
+    pub applied: Vec<String>,
+    /// The step that stopped the transaction.
+    pub failed: Option<(String, ApplyError)>,
+    pub rolled_back: Vec<String>,
+    /// Applied steps that could not be undone, with the reason.
+    pub not_rolled_back: Vec<(String, String)>,
+    /// Where the journal was left because of `not_rolled_back`.
+    pub kept_journal: Option<PathBuf>,
+}
+
+impl TransactionReport {
+    pub fn committed(&self) -> bool {
+        self.failed.is_none()
+    }
+
+    /// Whether any applied step only takes effect after a restart.
+    pub fn needs_reboot(&self) -> bool {
+        self.journal
+            .entries
+            .iter()
+            .any(|entry| entry.state == StepState::Applied && entry.change.needs_reboot())
+    }
+}
+
+/// Applies the fixes of `checks` all or nothing, journaling to `path`.
+/// `journal` is the caller-prepared session, e.g. one that records the
+/// profile or the session it undoes.
+pub fn apply_session(
+    mut journal: Journal,
+    checks: &[&Check],
+    writer: &dyn SystemWriter,
+    path: &Path,
+) -> Result<TransactionReport> {
+    if path.exists() {
+        anyhow::bail!(
+            "An interrupted apply left a journal at {}; recover it first",
+            path.display()
+        );
+    }
+
+    journal.entries.clear();
+    let mut report = TransactionReport {
+        session: journal.session.clone(),
+        ..Default::default()
+    };
+    let outcome = run_steps(&mut journal, checks, writer, path, &mut report);
+    if outcome.is_err() || !report.committed() {
+        rollback(&mut journal, writer, path, &mut report);
+        journal.outcome = Some(Outcome::RolledBack);
+    } else {
+        journal.outcome = Some(Outcome::Committed);
+    }
+    if let Err(err) = finish(path, &mut report) {
+        warn!("{:#}", err);
+    }
+    report.journal = journal;
+    outcome.map(|_| report)
+}
+
+/// Removes the journal once nothing is left to undo. Steps that could not
+/// be rolled back keep it on disk for `recover` to retry.
+fn finish(path: &Path, report: &mut TransactionReport) -> Result<()> {
+    if report.not_rolled_back.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove journal {}", path.display()))?;
+        }
+    } else {
+        report.kept_journal = Some(path.to_path_buf());
+    }
+    Ok(())
+}
+
+fn run_steps(
+    journal: &mut Journal,
+    checks: &[&Check],
+    writer: &dyn SystemWriter,
+    path: &Path,
+    report: &mut TransactionReport,
+) -> Result<()> {
+    journal.save(path)?;
+    for check in checks {
+        let Some(change) = &check.remediation else {
+            continue;
+        };
+        journal.entries.push(JournalEntry {
+            check_id: check.id.clone(),
+            change: change.clone(),
+            prior: change.capture(writer),
+            state: StepState::Pending,
+        });
+        journal.save(path)?;
+
+        if let Err(err) = change.apply(writer).and_then(|_| change.verify(writer)) {
+            report.failed = Some((check.id.clone(), err));
+            return Ok(());
+        }
+        if let Some(entry) = journal.entries.last_mut() {
+            entry.state = StepState::Applied;
+        }
+        journal.save(path)?;
+        report.applied.push(check.id.clone());
+    }
+    Ok(())
+}
+
+/// Undoes every step not yet rolled back, newest first.
+fn rollback(
+    journal: &mut Journal,
+    writer: &dyn SystemWriter,
+    path: &Path,
+    report: &mut TransactionReport,
+) {
+    for i in (0..journal.entries.len()).rev() {
+        let entry = &mut journal.entries[i];
+        if entry.state == StepState::RolledBack {
+            continue;
+        }
+        let Some(undo) = entry.prior.undo(&entry.change) else {
+            // A pending step without a known prior state never got far
+            // enough to matter (bcdedit, unreadable power settings).
+            if entry.state == StepState::Applied {
+                report
+                    .not_rolled_back
+                    .push((entry.check_id.clone(), "previous state unknown".to_string()));
+            }
+            continue;
+        };
+        match undo.apply(writer).and_then(|_| undo.verify(writer)) {
+            Ok(()) => {
+                entry.state = StepState::RolledBack;
+                report.rolled_back.push(entry.check_id.clone());
+            }
+            Err(err) => report
+                .not_rolled_back
+                .push((entry.check_id.clone(), err.to_string())),
+        }
+        if let Err(err) = journal.save(path) {
+            warn!("{:#}", err);
+        }
+    }
+}
+
+/// Rolls back an apply that was interrupted before it finished.
+pub fn recover(path: &Path, writer: &dyn SystemWriter) -> Result<Option<TransactionReport>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut journal = Journal::load(path)?;
+    let mut report = TransactionReport {
+        session: journal.session.clone(),
+        ..Default::default()
+    };
+    rollback(&mut journal, writer, path, &mut report);
+    finish(path, &mut report)?;
+    journal.outcome = Some(Outcome::RolledBack);
+    report.journal = journal;
+    Ok(Some(report))
+}
@@ -1,5 +1,15 @@
+pub mod backup;
 pub mod checks;
+pub mod diff;
+pub mod drift;
+pub mod history;
+pub mod journal;
+pub mod profile;
+pub mod regfile;
+pub mod remediation;
 pub mod report;
+pub mod risk;
+pub mod score;
 pub mod types;
 
 pub use types::*;
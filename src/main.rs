@@ -1,18 +1,20 @@
-#[allow(unused_imports)]
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, warn};
 
-mod checks;
-mod report;
-mod types;
+use windows_optimizer::{
+    backup, checks, diff, drift, history, journal, profile, regfile, remediation, report, risk,
+    score, types,
+};
 
 use checks::*;
+use history::{History, UndoOutcome};
 use report::*;
 use types::*;
 
@@ -36,6 +38,26 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     csv: Option<String>,
 
+    /// Export failing checks to a SARIF 2.1.0 file
+    #[arg(long, value_name = "FILE")]
+    sarif: Option<String>,
+
+    /// Export every check to a JUnit XML file, a testsuite per category
+    #[arg(long, value_name = "FILE")]
+    junit: Option<String>,
+
+    /// How JUnit reports warnings: failure or skip
+    #[arg(long, value_name = "MODE", default_value = "failure")]
+    junit_warnings: JunitWarnings,
+
+    /// Export results to a Markdown file for issues, wikis and chat
+    #[arg(long, value_name = "FILE")]
+    markdown: Option<String>,
+
+    /// List every check in the Markdown sections, not only those needing attention
+    #[arg(long, requires = "markdown")]
+    markdown_all: bool,
+
     /// Apply optimizations automatically
     #[arg(long)]
     apply: bool,
@@ -44,6 +66,14 @@ struct Cli {
     #[arg(long)]
     dry_run: bool,
 
+    /// Write the dry-run diff to a JSON file
+    #[arg(long, value_name = "FILE")]
+    dry_run_json: Option<String>,
+
+    /// Highest risk level to apply: safe, moderate or dangerous
+    #[arg(long, value_name = "LEVEL", default_value = "moderate")]
+    allow_risk: risk::RiskLevel,
+
     /// Backup registry before changes
     #[arg(long, value_name = "FILE")]
     backup: Option<PathBuf>,
@@ -59,6 +89,62 @@ struct Cli {
     /// Filter by category
     #[arg(long, value_name = "CATEGORY")]
     category: Option<String>,
+
+    /// Audit an offline SYSTEM hive instead of the live registry
+    #[arg(long, value_name = "FILE")]
+    system_hive: Option<PathBuf>,
+
+    /// Audit an offline SOFTWARE hive instead of the live registry
+    #[arg(long, value_name = "FILE")]
+    software_hive: Option<PathBuf>,
+
+    /// Audit an offline NTUSER.DAT hive instead of the live registry
+    #[arg(long, value_name = "FILE")]
+    ntuser_hive: Option<PathBuf>,
+
+    /// Record every WMI query and its results to a fixture file
+    #[arg(long, value_name = "FILE", conflicts_with = "wmi_replay")]
+    wmi_record: Option<PathBuf>,
+
+    /// Answer WMI queries from a recorded fixture instead of the live system
+    #[arg(long, value_name = "FILE")]
+    wmi_replay: Option<PathBuf>,
+
+    /// Load user-defined checks from the *.toml rule files in this directory
+    #[arg(long, value_name = "DIR")]
+    rules: Option<PathBuf>,
+
+    /// TOML file with per-category and per-check health score weights
+    #[arg(long, value_name = "FILE")]
+    score_weights: Option<PathBuf>,
+
+    /// Judge the audit against an optimization profile (gaming, audio-production, ...)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Load extra profiles from the *.toml files in this directory
+    #[arg(long, value_name = "DIR")]
+    profiles: Option<PathBuf>,
+}
+
+impl Cli {
+    fn has_hives(&self) -> bool {
+        self.system_hive.is_some() || self.software_hive.is_some() || self.ntuser_hive.is_some()
+    }
+
+    fn is_offline(&self) -> bool {
+        self.has_hives() || self.wmi_replay.is_some()
+    }
+
+    /// `apply <profile>` or `--profile <profile>`.
+    fn profile_name(&self) -> Option<&str> {
+        match &self.cmd {
+            Some(Cmd::Apply {
+                profile: Some(profile),
+            }) => Some(profile),
+            _ => self.profile.as_deref(),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -79,6 +165,76 @@ enum Cmd {
     Restore {
         /// Path to backup file
         path: PathBuf,
+        /// Only restore keys, values or check IDs matching this glob (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        only: Vec<String>,
+        /// Skip keys, values or check IDs matching this glob (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+        /// Restore a backup from another machine or Windows build
+        #[arg(long)]
+        force: bool,
+    },
+    /// List every check with its stable ID and metadata
+    ListChecks {
+        /// Print the catalog as JSON
+        #[arg(long)]
+        json: bool,
+        /// Only list checks in this category
+        #[arg(long, value_name = "CATEGORY")]
+        category: Option<String>,
+    },
+    /// List past apply sessions, or show the changes of one
+    History {
+        /// Session ID to show in detail
+        session: Option<String>,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report applied changes that no longer hold
+    Drift {
+        /// Session to compare against; the latest apply by default, or
+        /// the profile given with --profile
+        #[arg(long)]
+        session: Option<String>,
+        /// Re-apply the drifted changes
+        #[arg(long)]
+        reapply: bool,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Revert the changes of one apply session
+    Undo {
+        /// Session ID, as listed by `history`
+        session: String,
+    },
+    /// Compare two reports saved with --json
+    Diff {
+        /// The earlier report
+        old: PathBuf,
+        /// The later report
+        new: PathBuf,
+        /// Print the comparison as JSON
+        #[arg(long)]
+        json: bool,
+        /// Write the comparison side by side to an HTML file
+        #[arg(long, value_name = "FILE")]
+        html: Option<String>,
+    },
+    /// Show what depends on a service before disabling it
+    AnalyzeService {
+        /// Service name, such as LanmanServer
+        #[arg(required_unless_present = "dot")]
+        name: Option<String>,
+        /// Write the dependency graph as Graphviz DOT; only the service's
+        /// neighbourhood when a name is given
+        #[arg(long, value_name = "FILE")]
+        dot: Option<PathBuf>,
+        /// Print the analysis as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -90,15 +246,52 @@ fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
-    if !cli.quiet {
+    let needs_system = !matches!(
+        cli.cmd,
+        Some(Cmd::ListChecks { .. })
+            | Some(Cmd::History { .. })
+            | Some(Cmd::AnalyzeService { .. })
+            | Some(Cmd::Diff { .. })
+    );
+    if !cli.quiet && !cli.is_offline() && needs_system {
         check_admin_privileges();
     }
+    if !cli.is_offline() && needs_system {
+        recover_interrupted_apply()?;
+    }
 
-    match cli.cmd {
+    match &cli.cmd {
         Some(Cmd::Audit) => run_audit(&cli)?,
-        Some(Cmd::Apply { profile }) => run_apply(profile)?,
-        Some(Cmd::Backup { path }) => run_backup(&path)?,
-        Some(Cmd::Restore { path }) => run_restore(&path)?,
+        Some(Cmd::Apply { .. }) => run_apply(&cli)?,
+        Some(Cmd::Backup { path }) => run_backup(&cli, path)?,
+        Some(Cmd::Restore {
+            path,
+            only,
+            exclude,
+            force,
+        }) => run_restore(
+            &cli,
+            path,
+            &backup::RestoreFilter::new(only, exclude),
+            *force,
+        )?,
+        Some(Cmd::ListChecks { json, category }) => run_list_checks(*json, category.as_deref())?,
+        Some(Cmd::History { session, json }) => run_history(session.as_deref(), *json)?,
+        Some(Cmd::Undo { session }) => run_undo(&cli, session)?,
+        Some(Cmd::Drift {
+            session,
+            reapply,
+            json,
+        }) => run_drift(&cli, session.as_deref(), *reapply, *json)?,
+        Some(Cmd::Diff {
+            old,
+            new,
+            json,
+            html,
+        }) => run_diff(old, new, *json, html.as_deref())?,
+        Some(Cmd::AnalyzeService { name, dot, json }) => {
+            run_analyze_service(&cli, name.as_deref(), dot.as_deref(), *json)?
+        }
         None => run_audit(&cli)?,
     }
 
@@ -153,7 +346,8 @@ fn run_audit(cli: &Cli) -> Result<()> {
     let start_time = Instant::now();
 
     // Run all checks in parallel
-    let results = run_all_checks(cli)?;
+    let (ctx, recorder) = build_context(cli)?;
+    let results = run_all_checks(cli, &ctx, recorder.as_deref())?;
 
     let elapsed = start_time.elapsed();
 
@@ -189,106 +383,848 @@ fn run_audit(cli: &Cli) -> Result<()> {
         }
     }
 
+    if let Some(path) = &cli.sarif {
+        export_sarif(&results, path)?;
+        if !cli.quiet {
+            println!("{} SARIF exported to {}", "✓".green(), path);
+        }
+    }
+
+    if let Some(path) = &cli.junit {
+        export_junit(&results, path, cli.junit_warnings)?;
+        if !cli.quiet {
+            println!("{} JUnit XML exported to {}", "✓".green(), path);
+        }
+    }
+
+    if let Some(path) = &cli.markdown {
+        export_markdown(&results, path, cli.markdown_all)?;
+        if !cli.quiet {
+            println!("{} Markdown report exported to {}", "✓".green(), path);
+        }
+    }
+
+    if cli.apply || cli.dry_run || cli.dry_run_json.is_some() {
+        apply_fixes(cli, &ctx, &results)?;
+    }
+
     Ok(())
 }
 
-fn run_apply(profile: Option<String>) -> Result<()> {
-    info!(
-        "Applying optimization profile: {:?}",
-        profile.as_deref().unwrap_or("default")
+fn run_apply(cli: &Cli) -> Result<()> {
+    let (ctx, recorder) = build_context(cli)?;
+    let results = run_all_checks(cli, &ctx, recorder.as_deref())?;
+    apply_fixes(cli, &ctx, &results)
+}
+
+/// Applies the remediation of every check that failed the audit, or with
+/// `--dry-run` shows what it would change.
+fn apply_fixes(cli: &Cli, ctx: &CheckContext, results: &AuditResults) -> Result<()> {
+    let fixes = remediation::plan(results);
+    if fixes.is_empty() {
+        println!("{} No failed checks with an automatic fix", "✓".green());
+        return Ok(());
+    }
+
+    if cli.dry_run || cli.dry_run_json.is_some() {
+        // A dry run only reads, so it can preview against a captured image.
+        let reader: &dyn remediation::SystemReader = if cli.is_offline() {
+            ctx
+        } else {
+            &remediation::LiveSystem
+        };
+        let changes = remediation::preview(&fixes, reader);
+        if !cli.quiet {
+            print_planned_changes(&changes, cli.allow_risk);
+        }
+        if let Some(path) = &cli.dry_run_json {
+            export_plan_json(&changes, path)?;
+            if !cli.quiet {
+                println!("{} Dry-run diff exported to {}", "✓".green(), path);
+            }
+        }
+        return Ok(());
+    }
+
+    if cli.is_offline() {
+        anyhow::bail!("Fixes change the live system and cannot use offline hives or WMI replay");
+    }
+    commit_fixes(cli, &fixes, results.profile.clone())
+}
+
+/// Applies `fixes` within `--allow-risk` as one session and records it in
+/// the history.
+fn commit_fixes(cli: &Cli, fixes: &[&Check], profile: Option<String>) -> Result<()> {
+    let (fixes, refused) = risk::gate(fixes, cli.allow_risk);
+    for (check, risk) in &refused {
+        println!(
+            "  {} Refused {}: {}",
+            "⚠".yellow(),
+            check.id.bold(),
+            risk.label()
+        );
+        for tradeoff in &risk.tradeoffs {
+            println!("      {}", tradeoff);
+        }
+    }
+    if !refused.is_empty() {
+        println!(
+            "  {} Pass --allow-risk={} to apply these",
+            "ℹ".blue(),
+            refused
+                .iter()
+                .map(|(_, r)| r.level)
+                .max()
+                .unwrap_or(risk::RiskLevel::Dangerous)
+        );
+    }
+    if fixes.is_empty() {
+        anyhow::bail!(
+            "Every pending fix is above the allowed risk level ({})",
+            cli.allow_risk
+        );
+    }
+
+    info!("Applying {} fixes", fixes.len());
+    let mut journal = journal::Journal::new();
+    journal.profile = profile;
+    let mut report = journal::apply_session(
+        journal,
+        &fixes,
+        &remediation::LiveSystem,
+        &journal::default_path(),
+    )?;
+    report.session = History::open(&history::default_dir()).record(report.journal.clone())?;
+    for id in &report.applied {
+        println!("  {} Fixed {}", "✓".green(), id);
+    }
+
+    if let Some((id, err)) = &report.failed {
+        println!("  {} {}: {}", "✗".red(), id, err);
+        print_rollback(&report);
+        anyhow::bail!("Applying {} failed; earlier changes were rolled back", id);
+    }
+
+    print_accepted_tradeoffs(&fixes);
+    println!(
+        "\n{} {} applied in session {}.",
+        "ℹ".blue(),
+        report.applied.len(),
+        report.session.bold()
     );
+    if report.needs_reboot() {
+        println!("  Some changes need a restart.");
+    }
+    println!(
+        "  Revert with: Windows-Optimizer.exe undo {}",
+        report.session
+    );
+    Ok(())
+}
+
+/// Lists what the applied fixes gave up, security first.
+fn print_accepted_tradeoffs(fixes: &[&Check]) {
+    let mut risky: Vec<(&Check, risk::Risk)> = fixes
+        .iter()
+        .filter_map(|c| c.remediation.as_ref().map(|r| (*c, r.risk())))
+        .filter(|(_, risk)| !risk.tradeoffs.is_empty())
+        .collect();
+    if risky.is_empty() {
+        return;
+    }
+    risky.sort_by_key(|(_, risk)| (!risk.is_security_reducing(), std::cmp::Reverse(risk.level)));
+
+    println!("\n{} Trade-offs accepted:", "⚠".yellow());
+    for (check, risk) in risky {
+        let label = if risk.is_security_reducing() {
+            risk.label().red()
+        } else {
+            risk.label().yellow()
+        };
+        println!("  {} {}", check.id.bold(), label);
+        for tradeoff in &risk.tradeoffs {
+            println!("      {}", tradeoff);
+        }
+    }
+}
+
+fn print_planned_changes(changes: &[remediation::PlannedChange], allowed: risk::RiskLevel) {
+    let width = |f: fn(&remediation::PlannedChange) -> &str, title: &str| {
+        changes
+            .iter()
+            .map(|c| f(c).chars().count())
+            .chain([title.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let id_w = width(|c| &c.check_id, "CHECK");
+    let value_w = width(|c| &c.value, "VALUE");
+    let current_w = width(|c| &c.current, "CURRENT");
 
-    // TODO: Apply optimizations based on profile
-    // For now, this is a placeholder showing the intended structure
     println!(
-        "{} Apply functionality will modify registry settings",
-        "ℹ".blue()
+        "\n{} {} fixes would be applied:\n",
+        "ℹ".blue(),
+        changes.len()
     );
-    println!("{} This requires administrator privileges", "ℹ".blue());
+    println!(
+        "  {}",
+        format!(
+            "{:id_w$}  {:value_w$}  {:current_w$}    {:28}  REBOOT  RISK",
+            "CHECK", "VALUE", "CURRENT", "PROPOSED"
+        )
+        .bold()
+    );
+    for change in changes {
+        let reboot = if change.reboot_required {
+            "yes   ".yellow()
+        } else {
+            "no    ".normal()
+        };
+        // Pad before coloring; escape codes would throw the widths off.
+        let current = format!("{:current_w$}", change.current);
+        let proposed = format!("{:28}", change.proposed);
+        let (current, proposed) = if change.changes {
+            (current.red(), proposed.green())
+        } else {
+            (current.dimmed(), format!("{:28}", "(no change)").dimmed())
+        };
+        let level = match change.risk.level {
+            risk::RiskLevel::Safe => "safe".normal(),
+            risk::RiskLevel::Moderate => "moderate".yellow(),
+            risk::RiskLevel::Dangerous => "dangerous".red().bold(),
+        };
+        let refused = if change.risk.level > allowed {
+            " (refused)".red()
+        } else {
+            "".normal()
+        };
+        println!(
+            "  {}  {:value_w$}  {} → {}  {}  {}{}",
+            format!("{:id_w$}", change.check_id).bold(),
+            change.value,
+            current,
+            proposed,
+            reboot,
+            level,
+            refused
+        );
+        println!("  {:id_w$}  {}", "", change.key.dimmed());
+        for tradeoff in &change.risk.tradeoffs {
+            println!("  {:id_w$}  {} {}", "", "⚠".yellow(), tradeoff);
+        }
+    }
 
-    warn!("Apply mode not fully implemented yet");
+    if changes.iter().any(|c| c.changes && c.reboot_required) {
+        println!("\n{} A restart is required for some changes", "⚠".yellow());
+    }
+    if changes.iter().any(|c| c.risk.level > allowed) {
+        println!(
+            "{} Changes marked refused exceed --allow-risk={} and would be skipped",
+            "⚠".yellow(),
+            allowed
+        );
+    }
+}
+
+fn print_rollback(report: &journal::TransactionReport) {
+    println!(
+        "\n{} Rolled back {} changes",
+        "↺".yellow(),
+        report.rolled_back.len()
+    );
+    for (id, reason) in &report.not_rolled_back {
+        println!("  {} Could not undo {}: {}", "⚠".yellow(), id, reason);
+    }
+    if let Some(path) = &report.kept_journal {
+        println!(
+            "  The journal is kept at {}; the next run retries the rollback. Delete it once \
+             those changes are undone by hand.",
+            path.display()
+        );
+    }
+}
+
+/// Rolls back an apply that a crash or power loss cut short.
+fn recover_interrupted_apply() -> Result<()> {
+    let path = journal::default_path();
+    if let Some(report) = journal::recover(&path, &remediation::LiveSystem)? {
+        warn!(
+            "Found an interrupted apply from session {}; rolling it back",
+            report.session
+        );
+        print_rollback(&report);
+        // A kept journal is recorded once a later retry finishes it.
+        if report.kept_journal.is_none() {
+            History::open(&history::default_dir()).record(report.journal)?;
+        }
+    }
     Ok(())
 }
 
-fn run_backup(path: &PathBuf) -> Result<()> {
-    info!("Backing up registry configuration to {:?}", path);
+fn run_history(session: Option<&str>, json: bool) -> Result<()> {
+    let history = History::open(&history::default_dir());
 
-    #[cfg(windows)]
-    {
-        use std::fs::File;
-        use std::io::Write;
-
-        let keys_to_backup = vec![
-            r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\Power",
-            r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
-            r"HKEY_LOCAL_MACHINE\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
-            r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\PriorityControl",
-            r"HKEY_LOCAL_MACHINE\SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
-        ];
-
-        let mut backup_content = String::from("Windows Registry Editor Version 5.00\r\n\r\n");
-        backup_content.push_str(&format!(
-            "; Windows Optimizer Backup\r\n; Created: {}\r\n\r\n",
-            chrono::Local::now()
-        ));
+    if let Some(id) = session {
+        let session = history.get(id)?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&session)?);
+            return Ok(());
+        }
+        println!(
+            "\n{} Session {} started {}",
+            "ℹ".blue(),
+            session.session.bold(),
+            session.started
+        );
+        for entry in &session.entries {
+            let state = match entry.state {
+                journal::StepState::Applied => "✓".green(),
+                _ => "↺".yellow(),
+            };
+            println!("  {} {}", state, entry.check_id.bold());
+            for (target, before, after) in history::describe_entry(entry) {
+                println!("      {}", target.dimmed());
+                println!("      {} → {}", before.red(), after.green());
+            }
+        }
+        return Ok(());
+    }
+
+    let sessions = history.sessions()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+    if sessions.is_empty() {
+        println!("No apply sessions recorded");
+        return Ok(());
+    }
+    println!(
+        "  {}",
+        format!(
+            "{:20}  {:25}  {:16}  {:11}  {:>7}  UNDONE BY",
+            "SESSION", "STARTED", "PROFILE", "OUTCOME", "CHANGES"
+        )
+        .bold()
+    );
+    for session in &sessions {
+        // Pad before coloring; escape codes would throw the widths off.
+        let outcome = match (session.outcome, &session.undoes) {
+            (Some(journal::Outcome::RolledBack), _) => format!("{:11}", "rolled back").yellow(),
+            (_, Some(_)) => format!("{:11}", "undo").cyan(),
+            _ => format!("{:11}", "committed").green(),
+        };
+        let profile = match &session.undoes {
+            Some(id) => format!("(undoes {})", id),
+            None => session.profile.clone().unwrap_or_else(|| "-".to_string()),
+        };
+        let changes = session
+            .entries
+            .iter()
+            .filter(|e| e.state == journal::StepState::Applied)
+            .count();
+        println!(
+            "  {:20}  {:25}  {:16}  {}  {:>7}  {}",
+            session.session,
+            session.started.chars().take(25).collect::<String>(),
+            profile,
+            outcome,
+            changes,
+            session.undone_by.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
 
-        for key in keys_to_backup {
-            backup_content.push_str(&format!("; Backing up: {}\r\n", key));
-            backup_content.push_str(&format!("[{}]\r\n\r\n", key));
+fn run_drift(cli: &Cli, session: Option<&str>, reapply: bool, json: bool) -> Result<()> {
+    let (ctx, recorder) = build_context(cli)?;
+    let live = remediation::LiveSystem;
+    // Offline, the captured image is what drifted; online, the machine.
+    let reader: &dyn remediation::SystemReader = if cli.is_offline() { &ctx } else { &live };
+    let (baseline, expectations) = match (&cli.profile, session) {
+        (Some(name), None) => {
+            let results = run_all_checks(cli, &ctx, recorder.as_deref())?;
+            let profile = load_profiles(cli)?.resolve(name)?;
+            let targets: Vec<&String> = profile.targets.keys().collect();
+            (
+                format!("{} profile", profile.name),
+                drift::profile_baseline(&results, &targets),
+            )
         }
+        _ => {
+            if cli.is_offline() {
+                anyhow::bail!(
+                    "Sessions were applied to this machine; use --profile to check an offline image"
+                );
+            }
+            let history = History::open(&history::default_dir());
+            let (journal, expectations) = drift::session_baseline(&history, session)?;
+            (format!("session {}", journal.session), expectations)
+        }
+    };
 
-        let mut file = File::create(path).context("Failed to create backup file")?;
-        file.write_all(backup_content.as_bytes())
-            .context("Failed to write backup")?;
+    let report = drift::detect(&baseline, &expectations, reader);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_drift(&report);
+    }
+
+    if reapply && !report.drifted.is_empty() {
+        if cli.is_offline() {
+            anyhow::bail!("Re-applying changes the live system and cannot use offline hives");
+        }
+        let fixes = report.fixes();
+        let plan: Vec<&Check> = fixes.iter().collect();
+        let profile = cli.profile.clone().filter(|_| session.is_none());
+        commit_fixes(cli, &plan, profile)?;
+    }
+    Ok(())
+}
 
-        println!("{} Registry backup created at {:?}", "✓".green(), path);
-        println!("{} {} keys backed up", "ℹ".blue(), keys_to_backup.len());
+fn print_drift(report: &drift::DriftReport) {
+    println!(
+        "\n{} Checked {} changes against the {}",
+        "ℹ".blue(),
+        report.checked,
+        report.baseline
+    );
+    for item in &report.drifted {
+        println!("  {} {}", "✗".red(), item.check_id.bold());
+        println!("      {}", item.target.dimmed());
+        println!(
+            "      {} → now {}",
+            item.expected.green(),
+            item.current.red()
+        );
+        if let Some(time) = &item.last_write {
+            println!("      key last written {}", time);
+        }
+    }
+    for target in &report.unverified {
+        println!("  {} {} cannot be read back", "?".dimmed(), target.dimmed());
     }
 
-    #[cfg(not(windows))]
-    anyhow::bail!("Backup only supported on Windows");
+    if report.drifted.is_empty() {
+        println!("{} No drift: every change still holds", "✓".green());
+    } else {
+        println!(
+            "\n{} {} changes drifted; re-apply them with: Windows-Optimizer.exe drift --reapply",
+            "⚠".yellow(),
+            report.drifted.len()
+        );
+    }
+}
 
-    #[cfg(windows)]
+fn run_diff(
+    old_path: &std::path::Path,
+    new_path: &std::path::Path,
+    json: bool,
+    html: Option<&str>,
+) -> Result<()> {
+    let old = diff::load(old_path)?;
+    let new = diff::load(new_path)?;
+    let report = diff::compare(&old, &new, (old_path, new_path));
+
+    if let Some(path) = html {
+        export_diff_html(&report, path)?;
+        if !json {
+            println!("{} Side-by-side diff written to {}", "✓".green(), path);
+        }
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_diff(&report);
+    }
     Ok(())
 }
 
-fn run_restore(path: &PathBuf) -> Result<()> {
-    info!("Restoring registry configuration from {:?}", path);
+fn print_diff(report: &diff::ReportDiff) {
+    println!(
+        "\n{} {} ({}) → {} ({})",
+        "━━".cyan(),
+        report.old.path.bold(),
+        report.old.timestamp,
+        report.new.path.bold(),
+        report.new.timestamp
+    );
 
-    #[cfg(windows)]
-    {
-        use std::fs;
-        use std::process::Command;
+    let sections = [
+        ("Regressions", "✗".red(), &report.regressions),
+        ("Improvements", "✓".green(), &report.improvements),
+        ("Changed", "~".yellow(), &report.changed),
+    ];
+    for (title, icon, changes) in sections {
+        if changes.is_empty() {
+            continue;
+        }
+        println!("\n  {} ({})", title.bold(), changes.len());
+        for change in changes {
+            println!("  {} {}", icon, change.check_id);
+            let status = if change.old.status == change.new.status {
+                change.new.status.dimmed()
+            } else {
+                format!("{} → {}", change.old.status, change.new.status).normal()
+            };
+            if change.old.value == change.new.value {
+                println!("      {}  {}", change.new.value, status);
+            } else {
+                println!(
+                    "      {} → {}  {}",
+                    change.old.value, change.new.value, status
+                );
+            }
+        }
+    }
+    for (title, icon, entries) in [
+        ("Added", "+".blue(), &report.added),
+        ("Removed", "-".dimmed(), &report.removed),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        println!("\n  {} ({})", title.bold(), entries.len());
+        for entry in entries {
+            println!(
+                "  {} {:<40} {} {}",
+                icon,
+                entry.check_id,
+                entry.state.value,
+                entry.state.status.dimmed()
+            );
+        }
+    }
 
-        if !path.exists() {
-            anyhow::bail!("Backup file not found: {:?}", path);
+    if report.is_empty() {
+        println!(
+            "\n{} No differences across {} checks",
+            "✓".green(),
+            report.unchanged
+        );
+    } else if report.regressions.is_empty() {
+        println!("\n{} No regressions", "✓".green());
+    } else {
+        println!(
+            "\n{} {} checks regressed",
+            "⚠".yellow(),
+            report.regressions.len()
+        );
+    }
+}
+
+fn run_analyze_service(
+    cli: &Cli,
+    name: Option<&str>,
+    dot: Option<&std::path::Path>,
+    json: bool,
+) -> Result<()> {
+    let (ctx, _) = build_context(cli)?;
+    let graph = ServiceGraph::build(ctx.registry());
+    if graph.is_empty() {
+        anyhow::bail!("No services found under HKLM\\{}", SERVICES_KEY);
+    }
+
+    if let Some(path) = dot {
+        std::fs::write(path, graph.to_dot(name))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!(
+            "{} Dependency graph written to {} (render with: dot -Tsvg {} -o services.svg)",
+            "✓".green(),
+            path.display(),
+            path.display()
+        );
+    }
+    let Some(name) = name else {
+        return Ok(());
+    };
+
+    // The SCM only knows about this machine, not an offline image.
+    let offline = cli.is_offline();
+    let state = |svc: &str| {
+        if offline {
+            None
+        } else {
+            query_service_status(svc)
         }
+    };
+    let analysis = graph
+        .analyze(name, &state)
+        .with_context(|| format!("Service '{}' is not installed", name))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&analysis)?);
+    } else {
+        print_service_analysis(&analysis);
+    }
+    Ok(())
+}
+
+fn print_service_analysis(analysis: &ServiceAnalysis) {
+    let service = &analysis.service;
+    let start = service.start_type.map_or("Unknown", |s| s.label());
+    let state = analysis.state.as_deref().unwrap_or("state unknown");
+    println!(
+        "\n{} {} ({}, {})",
+        "━━".cyan(),
+        service.name.bold(),
+        start,
+        state
+    );
+    let none = "-".to_string();
+    println!(
+        "  {:<10} {}",
+        "binary",
+        service.binary_path.as_ref().unwrap_or(&none)
+    );
+    println!(
+        "  {:<10} {}",
+        "account",
+        service.account.as_ref().unwrap_or(&none)
+    );
+    println!(
+        "  {:<10} {}",
+        "group",
+        service.group.as_ref().unwrap_or(&none)
+    );
+    let needs = if analysis.dependencies.is_empty() {
+        none.clone()
+    } else {
+        analysis.dependencies.join(", ")
+    };
+    println!("  {:<10} {}", "needs", needs);
 
-        let content = fs::read_to_string(path).context("Failed to read backup file")?;
+    if analysis.dependents.is_empty() {
+        println!("\n{} Nothing else needs {}", "✓".green(), service.name);
+    } else {
+        println!("\n  Needed by:");
+        for dependent in &analysis.dependents {
+            let indent = "  ".repeat(dependent.depth);
+            let state = dependent.state.as_deref().unwrap_or("?");
+            let state = if state == "Running" {
+                format!("{:<9}", state).red()
+            } else {
+                format!("{:<9}", state).dimmed()
+            };
+            let via = match &dependent.group {
+                Some(group) => format!("needs {} via group {}", dependent.via, group),
+                None => format!("needs {}", dependent.via),
+            };
+            println!(
+                "  {}{:<28} {} {:<26} {}",
+                indent,
+                dependent.name,
+                state,
+                dependent.start_type.map_or("Unknown", |s| s.label()),
+                via.dimmed()
+            );
+        }
+    }
 
-        if !content.starts_with("Windows Registry Editor") {
-            anyhow::bail!("Invalid registry backup file format");
+    if !analysis.checks.is_empty() {
+        println!("\n  Checks that read it:");
+        for check in &analysis.checks {
+            if check.service == service.name {
+                println!("    {}", check.check_id);
+            } else {
+                println!(
+                    "    {} {}",
+                    check.check_id,
+                    format!("(through {})", check.service).dimmed()
+                );
+            }
         }
+    }
 
+    println!("\n  Disabling it: {}", analysis.risk.label());
+    for tradeoff in &analysis.risk.tradeoffs {
+        println!("    {}", tradeoff.dimmed());
+    }
+    let running = analysis.running_dependents();
+    if !running.is_empty() {
+        let names: Vec<&str> = running.iter().map(|d| d.name.as_str()).collect();
         println!(
-            "{} This will modify registry settings from the backup",
-            "⚠".yellow()
+            "\n{} Disabling {} also stops {} running services: {}",
+            "⚠".yellow(),
+            service.name,
+            running.len(),
+            names.join(", ")
         );
+    }
+}
+
+fn run_undo(cli: &Cli, session: &str) -> Result<()> {
+    if cli.is_offline() {
+        anyhow::bail!("Undo changes the live system and cannot use offline hives or WMI replay");
+    }
+
+    let history = History::open(&history::default_dir());
+    let outcome = history.undo(session, &remediation::LiveSystem, &journal::default_path())?;
+    let report = match outcome {
+        UndoOutcome::Conflicts(conflicts) => {
+            println!(
+                "{} Cannot undo session {}; nothing was changed:",
+                "✗".red(),
+                session
+            );
+            for conflict in &conflicts {
+                println!(
+                    "  {} {}",
+                    conflict.check_id.bold(),
+                    conflict.target.dimmed()
+                );
+                println!("      {}", conflict.reason);
+            }
+            if let Some(later) = conflicts.iter().find_map(|c| c.session.as_deref()) {
+                println!(
+                    "\n{} Undo the later session first: Windows-Optimizer.exe undo {}",
+                    "ℹ".blue(),
+                    later
+                );
+            }
+            anyhow::bail!("{} conflicting changes", conflicts.len());
+        }
+        UndoOutcome::Done(report) => *report,
+    };
+
+    for id in &report.applied {
+        println!("  {} Restored {}", "✓".green(), id);
+    }
+    if let Some((id, err)) = &report.failed {
+        println!("  {} {}: {}", "✗".red(), id, err);
+        print_rollback(&report);
+        anyhow::bail!("Undoing {} failed; the session is unchanged", id);
+    }
+    println!(
+        "\n{} Session {} undone by {}.",
+        "ℹ".blue(),
+        session,
+        report.session.bold()
+    );
+    if report.needs_reboot() {
+        println!("  Some changes need a restart.");
+    }
+    Ok(())
+}
+
+fn run_backup(cli: &Cli, path: &PathBuf) -> Result<()> {
+    info!("Backing up registry configuration to {:?}", path);
+
+    if !cfg!(windows) && !cli.has_hives() {
+        anyhow::bail!("Backup needs the Windows registry or offline hives (--system-hive, ...)");
+    }
+
+    let (ctx, _) = build_context(cli)?;
+    let roots: Vec<(Hkey, String)> = backup::BACKUP_ROOTS
+        .iter()
+        .map(|(hkey, path)| (*hkey, path.to_string()))
+        .collect();
+    let touched = backup::keys_touched_by_checks(&ctx);
+
+    let (file, stats) = backup::export_reg(ctx.registry(), &roots, &touched);
+    file.save(path)
+        .with_context(|| format!("Failed to write backup {}", path.display()))?;
+
+    println!("{} Registry backup created at {:?}", "✓".green(), path);
+    println!(
+        "{} {} keys, {} values backed up ({} not present)",
+        "ℹ".blue(),
+        stats.keys,
+        stats.values,
+        stats.missing
+    );
+    Ok(())
+}
+
+fn run_restore(
+    cli: &Cli,
+    path: &PathBuf,
+    filter: &backup::RestoreFilter,
+    force: bool,
+) -> Result<()> {
+    info!("Restoring registry configuration from {:?}", path);
+
+    if !path.exists() {
+        anyhow::bail!("Backup file not found: {:?}", path);
+    }
+    if !cfg!(windows) && !cli.has_hives() {
+        anyhow::bail!("Restore needs the Windows registry or offline hives (--system-hive, ...)");
+    }
+
+    let file = regfile::RegFile::load(path)
+        .with_context(|| format!("Invalid registry backup {}", path.display()))?;
+    let deletions = file.keys.iter().filter(|k| k.delete).count();
+    println!(
+        "{} Backup contains {} keys ({} deletions) and {} values",
+        "ℹ".blue(),
+        file.keys.len(),
+        deletions,
+        file.value_count()
+    );
+
+    let (ctx, _) = build_context(cli)?;
+    let origin = backup::BackupOrigin::from_comments(&file.comments);
+    let mismatches = origin.mismatches(&backup::BackupOrigin::read(ctx.registry()));
+    for problem in &mismatches {
+        println!("  {} {}", "⚠".yellow(), problem);
+    }
+    if !mismatches.is_empty() && !force {
+        anyhow::bail!(
+            "The backup may not belong to this system; pass --force to restore it anyway"
+        );
+    }
+
+    let changes = backup::diff_backup(&file, ctx.registry());
+    let selected: Vec<&backup::RestoreChange> =
+        changes.iter().filter(|c| filter.selects(c)).collect();
+    if selected.is_empty() {
+        println!(
+            "{} Nothing to restore: the selected keys already match the backup ({} differences filtered out)",
+            "✓".green(),
+            changes.len()
+        );
+        return Ok(());
+    }
+    print_restore_changes(&selected, changes.len());
+
+    if cli.dry_run {
+        return Ok(());
+    }
+    if cli.is_offline() {
+        anyhow::bail!("Offline hives are read-only; the preview above is all restore can do");
+    }
+
+    #[cfg(windows)]
+    {
+        use std::process::Command;
+
         println!(
             "{} Press Ctrl+C to cancel, or Enter to continue...",
             "⚠".yellow()
         );
-
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
 
+        let import = std::env::temp_dir().join(format!(
+            "windows-optimizer-restore-{}.reg",
+            std::process::id()
+        ));
+        backup::restore_file(&selected)
+            .save(&import)
+            .with_context(|| format!("Failed to write {}", import.display()))?;
         let output = Command::new("reg")
-            .args(&["import", path.to_str().unwrap()])
+            .arg("import")
+            .arg(&import)
             .output()
-            .context("Failed to execute reg import")?;
+            .context("Failed to execute reg import");
+        let _ = std::fs::remove_file(&import);
+        let output = output?;
 
         if output.status.success() {
-            println!("{} Registry restored from backup", "✓".green());
+            println!(
+                "{} Restored {} values from backup",
+                "✓".green(),
+                selected.len()
+            );
         } else {
             anyhow::bail!(
                 "Registry restore failed: {}",
@@ -297,10 +1233,71 @@ fn run_restore(path: &PathBuf) -> Result<()> {
         }
     }
 
-    #[cfg(not(windows))]
-    anyhow::bail!("Restore only supported on Windows");
+    Ok(())
+}
 
-    #[cfg(windows)]
+fn print_restore_changes(selected: &[&backup::RestoreChange], total: usize) {
+    println!(
+        "\n{} {} of {} differences would be restored:\n",
+        "ℹ".blue(),
+        selected.len(),
+        total
+    );
+    let describe = |value: &Option<RegValue>| {
+        remediation::PriorState::RegistryValue {
+            value: value.clone(),
+        }
+        .describe()
+    };
+    let mut key = String::new();
+    for change in selected {
+        if change.key() != key {
+            key = change.key();
+            println!("  {}", key.bold());
+        }
+        let Some(name) = &change.name else {
+            println!("    {} delete key and subkeys", "✗".red());
+            continue;
+        };
+        let name = if name.is_empty() { "(Default)" } else { name };
+        println!(
+            "    {:32} {} → {}",
+            name,
+            describe(&change.current).red(),
+            describe(&change.restored).green()
+        );
+        if !change.checks.is_empty() {
+            println!("    {:32} {}", "", change.checks.join(", ").dimmed());
+        }
+    }
+}
+
+fn run_list_checks(json: bool, category: Option<&str>) -> Result<()> {
+    let filter = match category {
+        Some(name) => {
+            Some(Category::from_name(name).with_context(|| format!("Unknown category: {}", name))?)
+        }
+        None => None,
+    };
+    let checks: Vec<&CheckMeta> = catalog()
+        .iter()
+        .filter(|meta| filter.is_none_or(|cat| meta.category == cat))
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&checks)?);
+        return Ok(());
+    }
+
+    let mut current = None;
+    for meta in &checks {
+        if current != Some(meta.category) {
+            println!("\n{} {}", "━━".cyan(), meta.category.display_name().bold());
+            current = Some(meta.category);
+        }
+        println!("  {:<40} {}", meta.id, meta.name.dimmed());
+    }
+    println!("\n{} checks", checks.len());
     Ok(())
 }
 
@@ -320,26 +1317,70 @@ fn print_banner() {
     println!();
 }
 
-fn run_all_checks(cli: &Cli) -> Result<AuditResults> {
-    let categories = if let Some(ref cat) = cli.category {
-        vec![cat.as_str()]
+/// Builds the check context, returning the WMI recorder when `--wmi-record` is set
+/// so its fixture can be written out once the run completes.
+fn build_context(cli: &Cli) -> Result<(CheckContext, Option<Arc<RecordingWmi>>)> {
+    let mut ctx = CheckContext::live();
+    let mut recorder = None;
+
+    if let Some(dir) = &cli.rules {
+        let rules = RuleSet::load_dir(dir)?;
+        info!(
+            "Loaded {} custom rules from {}",
+            rules.rules().len(),
+            dir.display()
+        );
+        ctx = ctx.with_rules(Arc::new(rules));
+    }
+
+    if let Some(path) = &cli.wmi_replay {
+        let fixture = WmiFixture::load(path)?;
+        info!("Replaying WMI from {}", path.display());
+        ctx = ctx.with_wmi(Arc::new(ReplayWmi::new(fixture)));
+    } else if cli.wmi_record.is_some() {
+        let rec = Arc::new(RecordingWmi::new(Arc::new(LiveWmi)));
+        ctx = ctx.with_wmi(rec.clone());
+        recorder = Some(rec);
+    }
+
+    if !cli.has_hives() {
+        return Ok((ctx, recorder));
+    }
+
+    let open = |path: &PathBuf| {
+        Hive::open(path).with_context(|| format!("Failed to load hive {}", path.display()))
+    };
+    let mut offline = OfflineRegistry::new();
+    if let Some(path) = &cli.system_hive {
+        offline = offline.with_system(open(path)?);
+    }
+    if let Some(path) = &cli.software_hive {
+        offline = offline.with_software(open(path)?);
+    }
+    if let Some(path) = &cli.ntuser_hive {
+        offline = offline.with_ntuser(open(path)?);
+    }
+    info!("Auditing offline registry hives");
+
+    Ok((
+        ctx.with_registry(Arc::new(offline)).with_offline(true),
+        recorder,
+    ))
+}
+
+fn run_all_checks(
+    cli: &Cli,
+    ctx: &CheckContext,
+    recorder: Option<&RecordingWmi>,
+) -> Result<AuditResults> {
+    let categories: Vec<String> = if let Some(ref cat) = cli.category {
+        vec![cat.clone()]
     } else {
-        vec![
-            "latency",
-            "cpu",
-            "gpu",
-            "memory",
-            "storage",
-            "network",
-            "audio",
-            "input",
-            "stability",
-            "services",
-            "security",
-            "platform",
-            "thermal",
-            "power",
-        ]
+        Category::ALL
+            .iter()
+            .map(|c| c.as_str().to_string())
+            .chain(ctx.rules().custom_categories())
+            .collect()
     };
 
     let mut results = AuditResults::new();
@@ -362,23 +1403,7 @@ fn run_all_checks(cli: &Cli) -> Result<AuditResults> {
     let category_results: Vec<_> = categories
         .par_iter()
         .map(|category| {
-            let result = match *category {
-                "latency" => run_latency_checks(),
-                "cpu" => run_cpu_checks(),
-                "gpu" => run_gpu_checks(),
-                "memory" => run_memory_checks(),
-                "storage" => run_storage_checks(),
-                "network" => run_network_checks(),
-                "audio" => run_audio_checks(),
-                "input" => run_input_checks(),
-                "stability" => run_stability_checks(),
-                "services" => run_services_checks(),
-                "security" => run_security_checks(),
-                "platform" => run_platform_checks(),
-                "thermal" => run_thermal_checks(),
-                "power" => run_power_checks(),
-                _ => CategoryResults::new(category),
-            };
+            let result = run_category_named(ctx, category);
             if let Some(ref p) = pb {
                 p.inc(1);
                 p.set_message(format!("Completed {}", category));
@@ -395,12 +1420,93 @@ fn run_all_checks(cli: &Cli) -> Result<AuditResults> {
         results.add_category(cat_result);
     }
 
+    if let Some(name) = cli.profile_name() {
+        judge_profile(cli, ctx, name, &mut results)?;
+    }
+
+    let weights = match &cli.score_weights {
+        Some(path) => score::ScoreWeights::load(path)?,
+        None => score::ScoreWeights::default(),
+    };
+    results.score = Some(score::compute(&results, &weights));
+
+    if let (Some(rec), Some(path)) = (recorder, &cli.wmi_record) {
+        let fixture = rec.fixture();
+        fixture.save(path)?;
+        info!(
+            "Recorded {} WMI queries to {}",
+            fixture.queries.len(),
+            path.display()
+        );
+    }
+
     Ok(results)
 }
 
+/// The built-in profiles plus any in `--profiles`.
+fn load_profiles(cli: &Cli) -> Result<profile::ProfileSet> {
+    let mut profiles = profile::ProfileSet::builtin();
+    if let Some(dir) = &cli.profiles {
+        profiles.load_dir(dir)?;
+    }
+    Ok(profiles)
+}
+
+fn judge_profile(
+    cli: &Cli,
+    ctx: &CheckContext,
+    name: &str,
+    results: &mut AuditResults,
+) -> Result<()> {
+    let profiles = load_profiles(cli)?;
+    let profile = profiles.resolve(name)?;
+    if let Some(definition) = profiles.get(name) {
+        info!(
+            "Judging against profile {}: {}",
+            profile.chain.join(" <- "),
+            definition.description
+        );
+    }
+
+    // Offline images have no power scheme to query; the live system does.
+    let report = if cli.is_offline() {
+        profile.judge(results, ctx)
+    } else {
+        profile.judge(results, &remediation::LiveSystem)
+    };
+    for id in &report.unmatched {
+        if cli.category.is_none() {
+            warn!(
+                "Profile {} targets {}, which no check reported",
+                profile.name, id
+            );
+        }
+    }
+    for (id, err) in &report.invalid {
+        warn!(
+            "Profile {} target for {} ignored: {}",
+            profile.name, id, err
+        );
+    }
+    results.profile = Some(profile.name);
+    Ok(())
+}
+
 fn print_results(results: &AuditResults, verbose: bool) {
+    let scores = results.score.as_ref();
     for category in results.categories.values() {
-        println!("\n{} {}", "━━".cyan(), category.name.bold());
+        let score = scores
+            .and_then(|s| s.categories.get(&category.name))
+            .and_then(|c| c.score);
+        match score {
+            Some(score) => println!(
+                "\n{} {} {}",
+                "━━".cyan(),
+                category.name.bold(),
+                color_score(score)
+            ),
+            None => println!("\n{} {}", "━━".cyan(), category.name.bold()),
+        }
 
         for check in &category.checks {
             let status_symbol = match check.status {
@@ -408,10 +1514,17 @@ fn print_results(results: &AuditResults, verbose: bool) {
                 CheckStatus::Warning => "⚠".yellow(),
                 CheckStatus::Issue => "✗".red(),
                 CheckStatus::Info => "ℹ".blue(),
+                CheckStatus::Unknown => "?".dimmed(),
+                CheckStatus::Error(_) => "!".magenta(),
             };
 
-            if verbose || check.status != CheckStatus::Optimal {
+            let measured_issue =
+                !matches!(check.status, CheckStatus::Optimal | CheckStatus::Unknown);
+            if verbose || measured_issue {
                 println!("  {} {}: {}", status_symbol, check.name, check.value);
+                if let CheckStatus::Error(err) = &check.status {
+                    println!("    {}", err.to_string().magenta());
+                }
                 if !check.description.is_empty() && verbose {
                     println!("    {}", check.description.dimmed());
                 }
@@ -421,7 +1534,7 @@ fn print_results(results: &AuditResults, verbose: bool) {
 
     println!("\n{}", "━".repeat(60).cyan());
     println!(
-        "Summary: {} optimal, {} warnings, {} issues, {} info",
+        "Summary: {} optimal, {} warnings, {} issues, {} info, {} not measured, {} errors",
         results
             .count_status(CheckStatus::Optimal)
             .to_string()
@@ -432,5 +1545,27 @@ fn print_results(results: &AuditResults, verbose: bool) {
             .yellow(),
         results.count_status(CheckStatus::Issue).to_string().red(),
         results.count_status(CheckStatus::Info).to_string().blue(),
+        results
+            .count_status(CheckStatus::Unknown)
+            .to_string()
+            .dimmed(),
+        results.count_errors().to_string().magenta(),
     );
+    if let Some(overall) = scores.and_then(|s| s.overall) {
+        println!("Health score: {}", color_score(overall).bold());
+    }
+    if let Some(profile) = &results.profile {
+        println!("Judged against the {} profile", profile.bold());
+    }
+}
+
+fn color_score(score: f64) -> ColoredString {
+    let text = format!("{:.0}/100", score);
+    if score >= 80.0 {
+        text.green()
+    } else if score >= 50.0 {
+        text.yellow()
+    } else {
+        text.red()
+    }
 }
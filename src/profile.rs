@@ -0,0 +1,293 @@
+//! Named optimization profiles: the target state of specific checks.
+//!
+//! A profile is a TOML file. Each entry under `[targets]` names a check ID
+//! and the state it should be in, either as the raw value the check's own
+//! fix writes or as a full remediation table:
+//!
+//! ```toml
+//! description = "Low-latency audio for DAW machines"
+//! inherits = "workstation"
+//!
+//! [targets]
+//! "latency.system_responsiveness" = 10
+//! "input.mouse_acceleration" = "0"
+//! "cpu.processor_throttle" = { action = "set_power_setting", scheme = "SCHEME_CURRENT", subgroup = "SUB_PROCESSOR", setting = "PROCTHROTTLEMIN", ac = 100, dc = 5 }
+//! ```
+//!
+//! A profile inherits every target of its parent and overrides the ones it
+//! lists again. Judging an audit against a profile replaces the built-in
+//! verdict of each targeted check: Optimal when the system already holds
+//! the target state, Issue when it does not, Unknown when it cannot be read.
+
+use crate::checks::RegValue;
+use crate::remediation::{Remediation, StartType, SystemReader};
+use crate::types::{AuditResults, Check, CheckStatus};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Profiles shipped with the binary, by name.
+const BUILTIN: &[(&str, &str)] = &[
+    ("workstation", include_str!("../profiles/workstation.toml")),
+    ("gaming", include_str!("../profiles/gaming.toml")),
+    (
+        "audio-production",
+        include_str!("../profiles/audio-production.toml"),
+    ),
+    ("battery", include_str!("../profiles/battery.toml")),
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Target {
+    Number(u64),
+    Text(String),
+    Fix(Remediation),
+}
+
+impl Target {
+    /// The remediation that reaches this target, using `template` (the
+    /// check's own fix) for where to write.
+    pub fn resolve(&self, template: Option<&Remediation>) -> Result<Remediation, String> {
+        if let Target::Fix(fix) = self {
+            return Ok(fix.clone());
+        }
+        let template =
+            template.ok_or("the check has no fix to retarget; give a full remediation table")?;
+        retarget(template, self)
+    }
+}
+
+fn retarget(template: &Remediation, target: &Target) -> Result<Remediation, String> {
+    let number = match target {
+        Target::Number(n) => Some(*n),
+        _ => None,
+    };
+    let text = match target {
+        Target::Text(s) => Some(s.clone()),
+        Target::Number(n) => Some(n.to_string()),
+        Target::Fix(_) => None,
+    };
+    let dword = || {
+        number
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| format!("expected a number up to {}", u32::MAX))
+    };
+    let start_type = || {
+        match target {
+            Target::Number(n) => u32::try_from(*n).ok().and_then(StartType::from_raw),
+            _ => text.as_deref().and_then(StartType::parse),
+        }
+        .ok_or("expected a start type such as \"disabled\" or \"manual\"")
+    };
+    if let Some(template) = template.user_service_template() {
+        return Ok(Remediation::user_service_start(template, start_type()?));
+    }
+
+    Ok(match template {
+        Remediation::SetRegistryValue {
+            hive,
+            path,
+            name,
+            value,
+        } => {
+            let value = match value {
+                RegValue::Dword(_) => RegValue::Dword(dword()?),
+                RegValue::Qword(_) => RegValue::Qword(number.ok_or("expected a number")?),
+                RegValue::String(_) => RegValue::String(text.unwrap_or_default()),
+                RegValue::ExpandString(_) => RegValue::ExpandString(text.unwrap_or_default()),
+                other => return Err(format!("cannot retarget a {} value", other.type_name())),
+            };
+            Remediation::set_value(*hive, path, name, value)
+        }
+        Remediation::DeleteRegistryValue { hive, path, name } => {
+            let value = match target {
+                Target::Number(_) => RegValue::Dword(dword()?),
+                _ => RegValue::String(text.unwrap_or_default()),
+            };
+            Remediation::set_value(*hive, path, name, value)
+        }
+        Remediation::SetServiceStartType { service, .. } => {
+            Remediation::service_start(service, start_type()?)
+        }
+        Remediation::SetPowerSetting {
+            scheme,
+            subgroup,
+            setting,
+            ..
+        } => {
+            let index = dword()?;
+            Remediation::SetPowerSetting {
+                scheme: scheme.clone(),
+                subgroup: subgroup.clone(),
+                setting: setting.clone(),
+                ac: index,
+                dc: index,
+            }
+        }
+        Remediation::Batch { steps } => Remediation::Batch {
+            steps: steps
+                .iter()
+                .map(|step| retarget(step, target))
+                .collect::<Result<_, _>>()?,
+        },
+        Remediation::RunBcdEdit { .. } => {
+            return Err("bcdedit fixes need a full remediation table".to_string())
+        }
+    })
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub inherits: Option<String>,
+    #[serde(default)]
+    pub targets: BTreeMap<String, Target>,
+}
+
+/// Every known profile: the built-in ones plus any loaded from disk.
+#[derive(Debug, Clone)]
+pub struct ProfileSet {
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl ProfileSet {
+    pub fn builtin() -> Self {
+        let mut set = Self {
+            profiles: BTreeMap::new(),
+        };
+        for (name, text) in BUILTIN {
+            set.add(name, text)
+                .expect("built-in profiles are valid TOML");
+        }
+        set
+    }
+
+    /// Adds a profile, replacing any existing one of the same name. The
+    /// file's `name` wins over `default_name`.
+    pub fn add(&mut self, default_name: &str, text: &str) -> Result<&Profile> {
+        let mut profile: Profile =
+            toml::from_str(text).with_context(|| format!("Invalid profile '{}'", default_name))?;
+        if profile.name.is_empty() {
+            profile.name = default_name.to_string();
+        }
+        let key = profile.name.to_ascii_lowercase();
+        self.profiles.insert(key.clone(), profile);
+        Ok(&self.profiles[&key])
+    }
+
+    /// Loads every `*.toml` in `dir`, named after the file stem.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read profile directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            self.add(&stem, &text)
+                .with_context(|| path.display().to_string())?;
+        }
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.values().map(|p| p.name.as_str()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(&name.to_ascii_lowercase())
+    }
+
+    /// Flattens `name` and its ancestors into one set of targets.
+    pub fn resolve(&self, name: &str) -> Result<ResolvedProfile> {
+        let mut chain: Vec<&Profile> = Vec::new();
+        let mut next = Some(name);
+        while let Some(name) = next {
+            let profile = self.get(name).with_context(|| {
+                format!(
+                    "Unknown profile '{}' (available: {})",
+                    name,
+                    self.names().join(", ")
+                )
+            })?;
+            if chain.iter().any(|p| p.name == profile.name) {
+                anyhow::bail!("Profile '{}' inherits from itself", profile.name);
+            }
+            chain.push(profile);
+            next = profile.inherits.as_deref();
+        }
+
+        let mut targets = BTreeMap::new();
+        for profile in chain.iter().rev() {
+            targets.extend(profile.targets.clone());
+        }
+        Ok(ResolvedProfile {
+            name: chain[0].name.clone(),
+            chain: chain.iter().map(|p| p.name.clone()).collect(),
+            targets,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedProfile {
+    pub name: String,
+    /// The profile itself first, then its parent, grandparent and so on.
+    pub chain: Vec<String>,
+    pub targets: BTreeMap<String, Target>,
+}
+
+/// Targets that did not apply to this audit.
+#[derive(Debug, Default)]
+pub struct JudgeReport {
+    /// No check with this ID ran.
+    pub unmatched: Vec<String>,
+    pub invalid: Vec<(String, String)>,
+}
+
+impl ResolvedProfile {
+    /// Re-grades every targeted check against the profile and points its
+    /// fix at the target state.
+    pub fn judge(&self, results: &mut AuditResults, reader: &dyn SystemReader) -> JudgeReport {
+        let mut report = JudgeReport::default();
+        let mut matched = Vec::new();
+        for category in results.categories.values_mut() {
+            for check in &mut category.checks {
+                let Some(target) = self.targets.get(&check.id) else {
+                    continue;
+                };
+                matched.push(check.id.clone());
+                match target.resolve(check.remediation.as_ref()) {
+                    Ok(fix) => self.grade(check, fix, reader),
+                    Err(err) => report.invalid.push((check.id.clone(), err)),
+                }
+            }
+        }
+        report.unmatched = self
+            .targets
+            .keys()
+            .filter(|id| !matched.contains(id))
+            .cloned()
+            .collect();
+        report
+    }
+
+    fn grade(&self, check: &mut Check, fix: Remediation, reader: &dyn SystemReader) {
+        check.status = match fix.is_satisfied(reader) {
+            Some(true) => CheckStatus::Optimal,
+            Some(false) => CheckStatus::Issue,
+            None => CheckStatus::Unknown,
+        };
+        check.expected = Some(format!("{} ({} profile)", fix.proposed(), self.name));
+        check.remediation = Some(fix);
+    }
+}
@@ -0,0 +1,443 @@
+//! Parser and writer for .reg files (regedit exports and `reg import` input).
+//!
+//! Covers the whole grammar regedit produces: both the `REGEDIT4` and the
+//! `Windows Registry Editor Version 5.00` headers, `[-key]` key deletions,
+//! `"name"=-` value deletions, `dword:`, `hex:` and `hex(n):` data with line
+//! continuations, and UTF-16LE files with a BOM.
+
+use crate::checks::{Hkey, RegValue, REG_EXPAND_SZ, REG_MULTI_SZ, REG_SZ};
+use std::path::Path;
+
+pub const HEADER_V5: &str = "Windows Registry Editor Version 5.00";
+pub const HEADER_V4: &str = "REGEDIT4";
+
+#[derive(thiserror::Error, Debug)]
+pub enum RegFileError {
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("missing .reg header (expected \"{HEADER_V5}\" or \"{HEADER_V4}\")")]
+    Header,
+    #[error("line {line}: {message}")]
+    Syntax { line: usize, message: String },
+}
+
+/// Which header the file carries. REGEDIT4 files store strings inside
+/// `hex(n):` data as ANSI bytes instead of UTF-16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegVersion {
+    Regedit4,
+    V5,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueOp {
+    Set(RegValue),
+    Delete,
+}
+
+/// One `"name"=...` line. An empty name is the key's default value (`@`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegEntry {
+    pub name: String,
+    pub op: ValueOp,
+}
+
+/// One `[key]` or `[-key]` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegKeyBlock {
+    pub hkey: Hkey,
+    pub path: String,
+    /// `[-key]`: delete the key and everything below it.
+    pub delete: bool,
+    pub entries: Vec<RegEntry>,
+}
+
+impl RegKeyBlock {
+    pub fn new(hkey: Hkey, path: &str) -> Self {
+        Self {
+            hkey,
+            path: path.trim_matches('\\').to_string(),
+            delete: false,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn deletion(hkey: Hkey, path: &str) -> Self {
+        Self {
+            delete: true,
+            ..Self::new(hkey, path)
+        }
+    }
+
+    pub fn set(mut self, name: &str, value: RegValue) -> Self {
+        self.entries.push(RegEntry {
+            name: name.to_string(),
+            op: ValueOp::Set(value),
+        });
+        self
+    }
+
+    pub fn delete_value(mut self, name: &str) -> Self {
+        self.entries.push(RegEntry {
+            name: name.to_string(),
+            op: ValueOp::Delete,
+        });
+        self
+    }
+
+    /// `HKEY_...\path` as it appears between the brackets.
+    pub fn full_path(&self) -> String {
+        if self.path.is_empty() {
+            self.hkey.full_name().to_string()
+        } else {
+            format!("{}\\{}", self.hkey.full_name(), self.path)
+        }
+    }
+}
+
+/// A parsed .reg file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegFile {
+    pub version: RegVersion,
+    /// `;` comment lines that appear before the first key, without the `;`.
+    pub comments: Vec<String>,
+    pub keys: Vec<RegKeyBlock>,
+}
+
+impl Default for RegFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegFile {
+    pub fn new() -> Self {
+        Self {
+            version: RegVersion::V5,
+            comments: Vec::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RegFileError> {
+        Self::parse(&decode_reg_bytes(&std::fs::read(path)?))
+    }
+
+    /// Writes V5 files as UTF-16LE with a BOM like regedit, REGEDIT4 as ANSI.
+    pub fn save(&self, path: &Path) -> Result<(), RegFileError> {
+        let text = self.to_text();
+        let bytes = match self.version {
+            RegVersion::V5 => {
+                let mut bytes = vec![0xFF, 0xFE];
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                bytes
+            }
+            RegVersion::Regedit4 => encode_ansi(&text),
+        };
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, RegFileError> {
+        let mut lines = logical_lines(text).into_iter();
+
+        let version = loop {
+            match lines.next() {
+                Some((_, line)) if line.trim().is_empty() => continue,
+                Some((_, line)) if line.trim() == HEADER_V5 => break RegVersion::V5,
+                Some((_, line)) if line.trim() == HEADER_V4 => break RegVersion::Regedit4,
+                _ => return Err(RegFileError::Header),
+            }
+        };
+
+        let mut file = RegFile {
+            version,
+            comments: Vec::new(),
+            keys: Vec::new(),
+        };
+
+        for (number, line) in lines {
+            let line = line.trim();
+            let syntax = |message: &str| RegFileError::Syntax {
+                line: number,
+                message: message.to_string(),
+            };
+
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix(';') {
+                if file.keys.is_empty() {
+                    file.comments.push(comment.trim().to_string());
+                }
+                continue;
+            }
+            if line.starts_with('[') {
+                let inner = line
+                    .strip_suffix(']')
+                    .map(|l| &l[1..])
+                    .ok_or_else(|| syntax("unterminated key name"))?;
+                let (delete, inner) = match inner.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, inner),
+                };
+                let (root, path) = inner.split_once('\\').unwrap_or((inner, ""));
+                let hkey = Hkey::from_name(root).ok_or_else(|| syntax("unknown registry root"))?;
+                let mut block = RegKeyBlock::new(hkey, path);
+                block.delete = delete;
+                file.keys.push(block);
+                continue;
+            }
+
+            let block = file
+                .keys
+                .last_mut()
+                .ok_or_else(|| syntax("value outside of a key"))?;
+            let entry = parse_entry(line, version).map_err(|m| syntax(&m))?;
+            block.entries.push(entry);
+        }
+
+        Ok(file)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = match self.version {
+            RegVersion::V5 => HEADER_V5,
+            RegVersion::Regedit4 => HEADER_V4,
+        }
+        .to_string();
+        out.push_str("\r\n\r\n");
+
+        if !self.comments.is_empty() {
+            for comment in &self.comments {
+                out.push_str(&format!("; {}\r\n", comment));
+            }
+            out.push_str("\r\n");
+        }
+
+        for block in &self.keys {
+            if block.delete {
+                out.push_str(&format!("[-{}]\r\n\r\n", block.full_path()));
+                continue;
+            }
+            out.push_str(&format!("[{}]\r\n", block.full_path()));
+            for entry in &block.entries {
+                out.push_str(&format_entry(entry, self.version));
+                out.push_str("\r\n");
+            }
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    /// Number of values set and deleted across all key blocks.
+    pub fn value_count(&self) -> usize {
+        self.keys.iter().map(|k| k.entries.len()).sum()
+    }
+}
+
+/// Decodes file bytes: UTF-16LE with BOM, UTF-8 (with or without BOM) or ANSI.
+pub fn decode_reg_bytes(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let rest = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+    match std::str::from_utf8(rest) {
+        Ok(text) => text.to_string(),
+        Err(_) => rest.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn encode_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
+
+/// Splits text into lines, joining `\` continuations. Each line keeps the
+/// 1-based number of the physical line it started on.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (index, raw) in text.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        let (number, mut line) = match pending.take() {
+            Some((number, mut acc)) => {
+                acc.push_str(raw.trim_start());
+                (number, acc)
+            }
+            None => (index + 1, raw.to_string()),
+        };
+
+        let trimmed_len = line.trim_end().len();
+        if line[..trimmed_len].ends_with('\\') && !line.trim_start().starts_with(';') {
+            line.truncate(trimmed_len - 1);
+            pending = Some((number, line));
+        } else {
+            out.push((number, line));
+        }
+    }
+    if let Some(rest) = pending {
+        out.push(rest);
+    }
+    out
+}
+
+/// Reads a quoted string starting at `s[0] == '"'`, returning it and the rest.
+fn parse_quoted(s: &str) -> Result<(String, &str), String> {
+    let mut out = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped)) => out.push(escaped),
+                None => break,
+            },
+            '"' => return Ok((out, &s[i + 1..])),
+            _ => out.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+fn parse_entry(line: &str, version: RegVersion) -> Result<RegEntry, String> {
+    let (name, rest) = if let Some(rest) = line.strip_prefix('@') {
+        (String::new(), rest)
+    } else if line.starts_with('"') {
+        parse_quoted(line)?
+    } else {
+        return Err("expected a value name".to_string());
+    };
+
+    let data = rest
+        .trim_start()
+        .strip_prefix('=')
+        .ok_or("expected '=' after the value name")?
+        .trim();
+
+    let op = if data == "-" {
+        ValueOp::Delete
+    } else if data.starts_with('"') {
+        let (value, tail) = parse_quoted(data)?;
+        if !tail.trim().is_empty() {
+            return Err("unexpected text after string".to_string());
+        }
+        ValueOp::Set(RegValue::String(value))
+    } else if let Some(hex) = data.strip_prefix("dword:") {
+        let value = u32::from_str_radix(hex.trim(), 16).map_err(|_| "invalid dword")?;
+        ValueOp::Set(RegValue::Dword(value))
+    } else if let Some(bytes) = data.strip_prefix("hex:") {
+        ValueOp::Set(RegValue::Binary(parse_hex_bytes(bytes)?))
+    } else if let Some(rest) = data.strip_prefix("hex(") {
+        let (kind, bytes) = rest.split_once("):").ok_or("malformed hex(n): prefix")?;
+        let kind = u32::from_str_radix(kind.trim(), 16).map_err(|_| "invalid hex type")?;
+        let bytes = parse_hex_bytes(bytes)?;
+        ValueOp::Set(decode_typed(kind, &bytes, version))
+    } else {
+        return Err(format!("unrecognised value data: {}", data));
+    };
+
+    Ok(RegEntry { name, op })
+}
+
+fn parse_hex_bytes(s: &str) -> Result<Vec<u8>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .map(|b| u8::from_str_radix(b, 16).map_err(|_| format!("invalid hex byte '{}'", b)))
+        .collect()
+}
+
+fn decode_typed(kind: u32, bytes: &[u8], version: RegVersion) -> RegValue {
+    if version == RegVersion::Regedit4 && matches!(kind, REG_SZ | REG_EXPAND_SZ | REG_MULTI_SZ) {
+        let strings: Vec<String> = bytes
+            .split(|&b| b == 0)
+            .map(|s| s.iter().map(|&b| b as char).collect())
+            .collect();
+        let first = strings.first().cloned().unwrap_or_default();
+        return match kind {
+            REG_SZ => RegValue::String(first),
+            REG_EXPAND_SZ => RegValue::ExpandString(first),
+            _ => RegValue::MultiString(strings.into_iter().take_while(|s| !s.is_empty()).collect()),
+        };
+    }
+    RegValue::from_raw(kind, bytes)
+}
+
+fn raw_for(value: &RegValue, version: RegVersion) -> Vec<u8> {
+    match (version, value) {
+        (RegVersion::Regedit4, RegValue::String(s) | RegValue::ExpandString(s)) => {
+            let mut raw = encode_ansi(s);
+            raw.push(0);
+            raw
+        }
+        (RegVersion::Regedit4, RegValue::MultiString(items)) => {
+            let mut raw = Vec::new();
+            for item in items {
+                raw.extend(encode_ansi(item));
+                raw.push(0);
+            }
+            raw.push(0);
+            raw
+        }
+        _ => value.to_raw(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn format_entry(entry: &RegEntry, version: RegVersion) -> String {
+    match &entry.op {
+        ValueOp::Set(value) => format_value(&entry.name, value, version),
+        ValueOp::Delete if entry.name.is_empty() => "@=-".to_string(),
+        ValueOp::Delete => format!("\"{}\"=-", escape(&entry.name)),
+    }
+}
+
+/// One `"name"=data` line, wrapping long hex data like regedit.
+fn format_value(name: &str, value: &RegValue, version: RegVersion) -> String {
+    let mut line = if name.is_empty() {
+        "@=".to_string()
+    } else {
+        format!("\"{}\"=", escape(name))
+    };
+
+    match value {
+        // Line breaks cannot be written inside a quoted .reg string.
+        RegValue::String(s) if !s.contains(['\r', '\n', '\0']) => {
+            line.push_str(&format!("\"{}\"", escape(s)));
+        }
+        RegValue::Dword(v) => line.push_str(&format!("dword:{:08x}", v)),
+        RegValue::Binary(data) => push_hex(&mut line, "hex:", data),
+        other => {
+            let prefix = format!("hex({:x}):", other.kind());
+            push_hex(&mut line, &prefix, &raw_for(other, version));
+        }
+    }
+    line
+}
+
+fn push_hex(line: &mut String, prefix: &str, data: &[u8]) {
+    line.push_str(prefix);
+    let mut width = line.chars().count();
+    for (i, byte) in data.iter().enumerate() {
+        let piece = if i + 1 == data.len() {
+            format!("{:02x}", byte)
+        } else {
+            format!("{:02x},", byte)
+        };
+        if width + piece.len() > 79 {
+            line.push_str("\\\r\n  ");
+            width = 2;
+        }
+        line.push_str(&piece);
+        width += piece.len();
+    }
+}
@@ -0,0 +1,1111 @@
+//! Typed fixes attached to checks, and the executor that applies them.
+//!
+//! A check that can be fixed carries a `Remediation` describing the exact
+//! change, which runs through a `SystemWriter`: `LiveSystem` makes the
+//! changes on this machine and `MemorySystem` records them in memory.
+//! `plan` picks the fixes for checks that came back as a Warning or Issue;
+//! `crate::journal` applies them as one transaction.
+
+use crate::checks::{
+    canonical_key_path, CheckContext, Hkey, LiveRegistry, MemoryRegistry, RegValue, RegistrySource,
+    HKEY_LOCAL_MACHINE, SERVICES_KEY, SERVICE_USER_SERVICE,
+};
+use crate::risk::Risk;
+use crate::types::{AuditResults, Check, CheckStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Service start types as the Service Control Manager stores them in the
+/// `Start` value. `Delayed` is `Automatic` with `DelayedAutostart` set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartType {
+    Boot,
+    System,
+    Automatic,
+    Delayed,
+    Manual,
+    Disabled,
+}
+
+impl StartType {
+    pub fn raw(&self) -> u32 {
+        match self {
+            StartType::Boot => 0,
+            StartType::System => 1,
+            StartType::Automatic | StartType::Delayed => 2,
+            StartType::Manual => 3,
+            StartType::Disabled => 4,
+        }
+    }
+
+    pub fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(StartType::Boot),
+            1 => Some(StartType::System),
+            2 => Some(StartType::Automatic),
+            3 => Some(StartType::Manual),
+            4 => Some(StartType::Disabled),
+            _ => None,
+        }
+    }
+
+    /// Combines `Start` with the `DelayedAutostart` flag, which only means
+    /// something for automatic services.
+    pub fn from_values(raw: u32, delayed: bool) -> Option<Self> {
+        match StartType::from_raw(raw)? {
+            StartType::Automatic if delayed => Some(StartType::Delayed),
+            start_type => Some(start_type),
+        }
+    }
+
+    /// Accepts the names used here ("automatic", "manual") and by `sc`
+    /// ("auto", "demand").
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "boot" => Some(StartType::Boot),
+            "system" => Some(StartType::System),
+            "automatic" | "auto" => Some(StartType::Automatic),
+            "delayed" | "delayed-auto" => Some(StartType::Delayed),
+            "manual" | "demand" => Some(StartType::Manual),
+            "disabled" => Some(StartType::Disabled),
+            _ => None,
+        }
+    }
+
+    /// The keyword `sc config <service> start= <keyword>` expects.
+    pub fn sc_name(&self) -> &'static str {
+        match self {
+            StartType::Boot => "boot",
+            StartType::System => "system",
+            StartType::Automatic => "auto",
+            StartType::Delayed => "delayed-auto",
+            StartType::Manual => "demand",
+            StartType::Disabled => "disabled",
+        }
+    }
+
+    /// As the Services console shows it.
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartType::Boot => "Boot",
+            StartType::System => "System",
+            StartType::Automatic => "Automatic",
+            StartType::Delayed => "Automatic (Delayed Start)",
+            StartType::Manual => "Manual",
+            StartType::Disabled => "Disabled",
+        }
+    }
+}
+
+/// Machine keys only read at boot: VBS and HVCI, the memory manager (page
+/// files, speculative-execution mitigations), kernel timer and scheduler
+/// settings, and the GPU scheduler and timeout recovery.
+const BOOT_READ_KEYS: &[&str] = &[
+    r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
+    r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+    r"SYSTEM\CurrentControlSet\Control\Session Manager\kernel",
+    r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Remediation {
+    SetRegistryValue {
+        hive: Hkey,
+        path: String,
+        name: String,
+        value: RegValue,
+    },
+    DeleteRegistryValue {
+        hive: Hkey,
+        path: String,
+        name: String,
+    },
+    SetServiceStartType {
+        service: String,
+        start_type: StartType,
+    },
+    /// `powercfg` GUIDs or aliases such as `SCHEME_CURRENT` and `SUB_PROCESSOR`.
+    SetPowerSetting {
+        scheme: String,
+        subgroup: String,
+        setting: String,
+        ac: u32,
+        dc: u32,
+    },
+    RunBcdEdit {
+        args: Vec<String>,
+    },
+    /// Changes that stand or fall together, such as the same value on
+    /// every network interface.
+    Batch {
+        steps: Vec<Remediation>,
+    },
+}
+
+impl Remediation {
+    pub fn set_dword(hive: Hkey, path: &str, name: &str, value: u32) -> Self {
+        Self::set_value(hive, path, name, RegValue::Dword(value))
+    }
+
+    pub fn set_string(hive: Hkey, path: &str, name: &str, value: &str) -> Self {
+        Self::set_value(hive, path, name, RegValue::String(value.to_string()))
+    }
+
+    pub fn set_value(hive: Hkey, path: &str, name: &str, value: RegValue) -> Self {
+        Remediation::SetRegistryValue {
+            hive,
+            path: path.to_string(),
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    pub fn delete_value(hive: Hkey, path: &str, name: &str) -> Self {
+        Remediation::DeleteRegistryValue {
+            hive,
+            path: path.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub fn service_start(service: &str, start_type: StartType) -> Self {
+        Remediation::SetServiceStartType {
+            service: service.to_string(),
+            start_type,
+        }
+    }
+
+    /// A per-user service template: its start type, which each new
+    /// instance copies at sign-in, and `UserServiceFlags`, which decides
+    /// whether Windows creates instances at all. Running instances keep
+    /// their state until the user signs out.
+    pub fn user_service_start(template: &str, start_type: StartType) -> Self {
+        let flags = match start_type {
+            StartType::Disabled => 0,
+            _ => USER_SERVICE_FLAGS_DEFAULT,
+        };
+        Remediation::Batch {
+            steps: vec![
+                Self::service_start(template, start_type),
+                Self::set_dword(
+                    HKEY_LOCAL_MACHINE,
+                    &service_key(template),
+                    "UserServiceFlags",
+                    flags,
+                ),
+            ],
+        }
+    }
+
+    /// The template a `user_service_start` batch changes.
+    pub fn user_service_template(&self) -> Option<&str> {
+        let Remediation::Batch { steps } = self else {
+            return None;
+        };
+        match steps.as_slice() {
+            [Remediation::SetServiceStartType { service, .. }, Remediation::SetRegistryValue { name, .. }]
+                if name == "UserServiceFlags" =>
+            {
+                Some(service)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn power_setting(subgroup: &str, setting: &str, ac: u32, dc: u32) -> Self {
+        Remediation::SetPowerSetting {
+            scheme: "SCHEME_CURRENT".to_string(),
+            subgroup: subgroup.to_string(),
+            setting: setting.to_string(),
+            ac,
+            dc,
+        }
+    }
+
+    /// The single changes this remediation makes, with batches flattened.
+    pub fn steps(&self) -> Vec<&Remediation> {
+        match self {
+            Remediation::Batch { steps } => steps.iter().flat_map(|s| s.steps()).collect(),
+            single => vec![single],
+        }
+    }
+
+    /// One line for consoles and logs.
+    pub fn describe(&self) -> String {
+        match self {
+            Remediation::SetRegistryValue {
+                hive,
+                path,
+                name,
+                value,
+            } => format!(
+                "Set {}\\{}\\{} = {} ({})",
+                hive.full_name(),
+                path,
+                name,
+                display_value(value),
+                value.type_name()
+            ),
+            Remediation::DeleteRegistryValue { hive, path, name } => {
+                format!("Delete {}\\{}\\{}", hive.full_name(), path, name)
+            }
+            Remediation::SetServiceStartType {
+                service,
+                start_type,
+            } => format!("Set service {} start type to {:?}", service, start_type),
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ac,
+                dc,
+            } => format!(
+                "Set power setting {} {} {} to AC {} / DC {}",
+                scheme, subgroup, setting, ac, dc
+            ),
+            Remediation::RunBcdEdit { args } => format!("bcdedit {}", args.join(" ")),
+            Remediation::Batch { steps } => join(steps, Remediation::describe),
+        }
+    }
+
+    pub fn apply(&self, writer: &dyn SystemWriter) -> Result<(), ApplyError> {
+        match self {
+            Remediation::SetRegistryValue {
+                hive,
+                path,
+                name,
+                value,
+            } => writer.set_registry_value(*hive, path, name, value),
+            Remediation::DeleteRegistryValue { hive, path, name } => {
+                writer.delete_registry_value(*hive, path, name)
+            }
+            Remediation::SetServiceStartType {
+                service,
+                start_type,
+            } => writer.set_service_start_type(service, *start_type),
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ac,
+                dc,
+            } => writer.set_power_setting(scheme, subgroup, setting, *ac, *dc),
+            Remediation::RunBcdEdit { args } => writer.run_bcdedit(args),
+            Remediation::Batch { steps } => steps.iter().try_for_each(|step| step.apply(writer)),
+        }
+    }
+
+    /// Where the change lands, for journals and error messages.
+    pub fn target(&self) -> String {
+        match self {
+            Remediation::SetRegistryValue {
+                hive, path, name, ..
+            }
+            | Remediation::DeleteRegistryValue { hive, path, name } => {
+                format!("{}\\{}\\{}", hive.full_name(), path, name)
+            }
+            Remediation::SetServiceStartType { service, .. } => format!("service {}", service),
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ..
+            } => format!("power {} {} {}", scheme, subgroup, setting),
+            Remediation::RunBcdEdit { args } => format!("bcdedit {}", args.join(" ")),
+            Remediation::Batch { steps } => join(steps, Remediation::target),
+        }
+    }
+
+    /// The registry key the change lands in; services keep their start
+    /// type under `Services\<name>`. `None` for power settings and bcdedit.
+    pub fn registry_key(&self) -> Option<(Hkey, String)> {
+        match self {
+            Remediation::SetRegistryValue { hive, path, .. }
+            | Remediation::DeleteRegistryValue { hive, path, .. } => Some((*hive, path.clone())),
+            Remediation::SetServiceStartType { service, .. } => {
+                Some((HKEY_LOCAL_MACHINE, service_key(service)))
+            }
+            _ => None,
+        }
+    }
+
+    /// The key and value a dry run shows for this change.
+    pub fn location(&self) -> (String, String) {
+        match self {
+            Remediation::SetRegistryValue {
+                hive, path, name, ..
+            }
+            | Remediation::DeleteRegistryValue { hive, path, name } => {
+                (format!("{}\\{}", hive.full_name(), path), name.clone())
+            }
+            Remediation::SetServiceStartType { service, .. } => {
+                (format!("service {}", service), "start type".to_string())
+            }
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ..
+            } => (format!("power {} {}", scheme, subgroup), setting.clone()),
+            Remediation::RunBcdEdit { args } => ("bcdedit".to_string(), args.join(" ")),
+            Remediation::Batch { steps } => match steps.first() {
+                Some(first) => first.location(),
+                None => (String::new(), String::new()),
+            },
+        }
+    }
+
+    /// The state the target is left in, worded like `PriorState::describe`.
+    pub fn proposed(&self) -> String {
+        match self {
+            Remediation::SetRegistryValue { value, .. } => display_value(value),
+            Remediation::DeleteRegistryValue { .. } => "(not set)".to_string(),
+            Remediation::SetServiceStartType { start_type, .. } => format!("{:?}", start_type),
+            Remediation::SetPowerSetting { ac, dc, .. } => format!("AC {} / DC {}", ac, dc),
+            Remediation::RunBcdEdit { args } => format!("bcdedit {}", args.join(" ")),
+            Remediation::Batch { steps } => join(steps, Remediation::proposed),
+        }
+    }
+
+    /// Only what Windows reads at boot: the boot configuration, the keys
+    /// in `BOOT_READ_KEYS` and drivers loaded by the boot loader or kernel.
+    /// Other values and service start types apply, and revert, without a
+    /// restart.
+    pub fn needs_reboot(&self) -> bool {
+        match self {
+            Remediation::SetRegistryValue { hive, path, .. }
+            | Remediation::DeleteRegistryValue { hive, path, .. } => {
+                let path = canonical_key_path(path);
+                *hive == HKEY_LOCAL_MACHINE
+                    && BOOT_READ_KEYS.iter().any(|key| {
+                        let key = canonical_key_path(key);
+                        path == key || path.starts_with(&format!("{}\\", key))
+                    })
+            }
+            Remediation::SetServiceStartType { start_type, .. } => {
+                matches!(start_type, StartType::Boot | StartType::System)
+            }
+            Remediation::RunBcdEdit { .. } => true,
+            Remediation::SetPowerSetting { .. } => false,
+            Remediation::Batch { steps } => steps.iter().any(Remediation::needs_reboot),
+        }
+    }
+
+    /// How dangerous the change is; see `crate::risk`.
+    pub fn risk(&self) -> Risk {
+        crate::risk::assess(self)
+    }
+
+    /// Reads what the target holds before the change.
+    pub fn capture(&self, reader: &dyn SystemReader) -> PriorState {
+        match self {
+            Remediation::SetRegistryValue {
+                hive, path, name, ..
+            }
+            | Remediation::DeleteRegistryValue { hive, path, name } => PriorState::RegistryValue {
+                value: reader.read_registry_value(*hive, path, name),
+            },
+            Remediation::SetServiceStartType { service, .. } => {
+                match reader.service_start_type(service) {
+                    Some(start_type) => PriorState::ServiceStartType { start_type },
+                    None => PriorState::Unknown,
+                }
+            }
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ..
+            } => match reader.power_setting(scheme, subgroup, setting) {
+                Some((ac, dc)) => PriorState::PowerSetting { ac, dc },
+                None => PriorState::Unknown,
+            },
+            Remediation::RunBcdEdit { .. } => PriorState::Unknown,
+            Remediation::Batch { steps } => PriorState::Batch {
+                states: steps.iter().map(|step| step.capture(reader)).collect(),
+            },
+        }
+    }
+
+    /// Reads the target back and checks it now holds what was written.
+    /// `bcdedit` changes cannot be read back and always pass.
+    pub fn verify(&self, reader: &dyn SystemReader) -> Result<(), ApplyError> {
+        let (expected, found) = match self {
+            Remediation::SetRegistryValue {
+                hive,
+                path,
+                name,
+                value,
+            } => {
+                let found = reader.read_registry_value(*hive, path, name);
+                if found.as_ref() == Some(value) {
+                    return Ok(());
+                }
+                (display_value(value), found.as_ref().map(display_value))
+            }
+            Remediation::DeleteRegistryValue { hive, path, name } => {
+                match reader.read_registry_value(*hive, path, name) {
+                    None => return Ok(()),
+                    Some(found) => ("no value".to_string(), Some(display_value(&found))),
+                }
+            }
+            Remediation::SetServiceStartType {
+                service,
+                start_type,
+            } => {
+                let found = reader.service_start_type(service);
+                if found == Some(*start_type) {
+                    return Ok(());
+                }
+                (
+                    format!("{:?}", start_type),
+                    found.map(|t| format!("{:?}", t)),
+                )
+            }
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ac,
+                dc,
+            } => {
+                let found = reader.power_setting(scheme, subgroup, setting);
+                if found == Some((*ac, *dc)) {
+                    return Ok(());
+                }
+                (
+                    format!("AC {} / DC {}", ac, dc),
+                    found.map(|(ac, dc)| format!("AC {} / DC {}", ac, dc)),
+                )
+            }
+            Remediation::RunBcdEdit { .. } => return Ok(()),
+            Remediation::Batch { steps } => {
+                return steps.iter().try_for_each(|step| step.verify(reader))
+            }
+        };
+        Err(ApplyError::Verification {
+            target: self.target(),
+            expected,
+            found: found.unwrap_or_else(|| "nothing".to_string()),
+        })
+    }
+
+    /// Whether the target already holds this state, or `None` when it
+    /// cannot be read (bcdedit, power settings of an offline image).
+    pub fn is_satisfied(&self, reader: &dyn SystemReader) -> Option<bool> {
+        match self {
+            Remediation::SetServiceStartType { service, .. }
+                if reader.service_start_type(service).is_none() =>
+            {
+                None
+            }
+            Remediation::SetPowerSetting {
+                scheme,
+                subgroup,
+                setting,
+                ..
+            } if reader.power_setting(scheme, subgroup, setting).is_none() => None,
+            Remediation::RunBcdEdit { .. } => None,
+            Remediation::Batch { steps } => {
+                let mut satisfied = Some(true);
+                for step in steps {
+                    match step.is_satisfied(reader) {
+                        Some(false) => return Some(false),
+                        None => satisfied = None,
+                        Some(true) => {}
+                    }
+                }
+                satisfied
+            }
+            _ => Some(self.verify(reader).is_ok()),
+        }
+    }
+}
+
+fn join(steps: &[Remediation], f: fn(&Remediation) -> String) -> String {
+    steps.iter().map(f).collect::<Vec<_>>().join("; ")
+}
+
+/// What a target held before a remediation changed it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriorState {
+    /// `value: None` records that the value did not exist.
+    RegistryValue {
+        value: Option<RegValue>,
+    },
+    ServiceStartType {
+        start_type: StartType,
+    },
+    PowerSetting {
+        ac: u32,
+        dc: u32,
+    },
+    Batch {
+        states: Vec<PriorState>,
+    },
+    /// The old state could not be read, so the change cannot be undone.
+    Unknown,
+}
+
+impl PriorState {
+    pub fn describe(&self) -> String {
+        match self {
+            PriorState::RegistryValue { value: Some(value) } => display_value(value),
+            PriorState::RegistryValue { value: None } => "(not set)".to_string(),
+            PriorState::ServiceStartType { start_type } => format!("{:?}", start_type),
+            PriorState::PowerSetting { ac, dc } => format!("AC {} / DC {}", ac, dc),
+            PriorState::Batch { states } => states
+                .iter()
+                .map(PriorState::describe)
+                .collect::<Vec<_>>()
+                .join("; "),
+            PriorState::Unknown => "(unknown)".to_string(),
+        }
+    }
+
+    /// The remediation that puts `change`'s target back the way it was.
+    pub fn undo(&self, change: &Remediation) -> Option<Remediation> {
+        match (change, self) {
+            (
+                Remediation::SetRegistryValue {
+                    hive, path, name, ..
+                }
+                | Remediation::DeleteRegistryValue { hive, path, name },
+                PriorState::RegistryValue { value },
+            ) => Some(match value {
+                Some(value) => Remediation::set_value(*hive, path, name, value.clone()),
+                None => Remediation::delete_value(*hive, path, name),
+            }),
+            (
+                Remediation::SetServiceStartType { service, .. },
+                PriorState::ServiceStartType { start_type },
+            ) => Some(Remediation::service_start(service, *start_type)),
+            (
+                Remediation::SetPowerSetting {
+                    scheme,
+                    subgroup,
+                    setting,
+                    ..
+                },
+                PriorState::PowerSetting { ac, dc },
+            ) => Some(Remediation::SetPowerSetting {
+                scheme: scheme.clone(),
+                subgroup: subgroup.clone(),
+                setting: setting.clone(),
+                ac: *ac,
+                dc: *dc,
+            }),
+            // Undo a batch back to front; one unknown step spoils the lot.
+            (Remediation::Batch { steps }, PriorState::Batch { states }) => {
+                let steps = steps
+                    .iter()
+                    .zip(states)
+                    .rev()
+                    .map(|(step, state)| state.undo(step))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Remediation::Batch { steps })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn display_value(value: &RegValue) -> String {
+    match value {
+        RegValue::String(s) | RegValue::ExpandString(s) => format!("\"{}\"", s),
+        RegValue::MultiString(items) => format!("{:?}", items),
+        RegValue::Dword(v) => format!("{} (0x{:08x})", v, v),
+        RegValue::Qword(v) => format!("{} (0x{:016x})", v, v),
+        RegValue::Binary(data) | RegValue::Other(_, data) => format!("{} bytes", data.len()),
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ApplyError {
+    #[error("{path}: {message}")]
+    Registry { path: String, message: String },
+    #[error("{program} failed: {message}")]
+    Command { program: String, message: String },
+    #[error("{target} reads back as {found}, expected {expected}")]
+    Verification {
+        target: String,
+        expected: String,
+        found: String,
+    },
+}
+
+/// Reads the state remediations change, to journal, verify and preview them.
+pub trait SystemReader: Send + Sync {
+    /// `None` when the value does not exist.
+    fn read_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Option<RegValue>;
+
+    /// Current AC and DC index of a power setting, when it can be queried.
+    fn power_setting(&self, scheme: &str, subgroup: &str, setting: &str) -> Option<(u32, u32)>;
+
+    /// When a key was last written, if the source keeps track.
+    fn key_last_write(&self, _hive: Hkey, _path: &str) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    /// The SCM keeps the start type in the service's `Start` value and
+    /// delayed start in `DelayedAutostart`.
+    fn service_start_type(&self, service: &str) -> Option<StartType> {
+        let key = service_key(service);
+        let RegValue::Dword(raw) = self.read_registry_value(HKEY_LOCAL_MACHINE, &key, "Start")?
+        else {
+            return None;
+        };
+        let delayed = matches!(
+            self.read_registry_value(HKEY_LOCAL_MACHINE, &key, "DelayedAutostart"),
+            Some(RegValue::Dword(1))
+        );
+        StartType::from_values(raw, delayed)
+    }
+}
+
+/// The side effects a remediation can have.
+pub trait SystemWriter: SystemReader {
+    fn set_registry_value(
+        &self,
+        hive: Hkey,
+        path: &str,
+        name: &str,
+        value: &RegValue,
+    ) -> Result<(), ApplyError>;
+    fn delete_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Result<(), ApplyError>;
+    fn set_service_start_type(
+        &self,
+        service: &str,
+        start_type: StartType,
+    ) -> Result<(), ApplyError>;
+    fn set_power_setting(
+        &self,
+        scheme: &str,
+        subgroup: &str,
+        setting: &str,
+        ac: u32,
+        dc: u32,
+    ) -> Result<(), ApplyError>;
+    fn run_bcdedit(&self, args: &[String]) -> Result<(), ApplyError>;
+}
+
+/// What Windows ships in `UserServiceFlags` for templates that create
+/// instances.
+const USER_SERVICE_FLAGS_DEFAULT: u32 = 3;
+
+fn service_key(service: &str) -> String {
+    format!("{}\\{}", SERVICES_KEY, service)
+}
+
+/// Changes this machine: registry writes through the Win32 API, everything
+/// else through `sc`, `powercfg` and `bcdedit`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveSystem;
+
+fn run_tool(program: &str, args: &[String]) -> Result<(), ApplyError> {
+    tool_output(program, args).map(|_| ())
+}
+
+fn tool_output(program: &str, args: &[String]) -> Result<String, ApplyError> {
+    let failed = |message: String| ApplyError::Command {
+        program: program.to_string(),
+        message,
+    };
+    if !cfg!(windows) {
+        return Err(failed("only available on Windows".to_string()));
+    }
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| failed(e.to_string()))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        Err(failed(message.trim().to_string()))
+    }
+}
+
+impl SystemWriter for LiveSystem {
+    fn set_registry_value(
+        &self,
+        hive: Hkey,
+        path: &str,
+        name: &str,
+        value: &RegValue,
+    ) -> Result<(), ApplyError> {
+        LiveRegistry
+            .set_value(hive, path, name, value)
+            .map_err(|e| ApplyError::Registry {
+                path: format!("{}\\{}\\{}", hive.full_name(), path, name),
+                message: e.to_string(),
+            })
+    }
+
+    fn delete_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Result<(), ApplyError> {
+        LiveRegistry
+            .delete_value(hive, path, name)
+            .map_err(|e| ApplyError::Registry {
+                path: format!("{}\\{}\\{}", hive.full_name(), path, name),
+                message: e.to_string(),
+            })
+    }
+
+    fn set_service_start_type(
+        &self,
+        service: &str,
+        start_type: StartType,
+    ) -> Result<(), ApplyError> {
+        let key = service_key(service);
+        // The SCM refuses to reconfigure per-user templates; instances copy
+        // the template's values, so those are written directly.
+        let template = matches!(
+            self.read_registry_value(HKEY_LOCAL_MACHINE, &key, "Type"),
+            Some(RegValue::Dword(t)) if t & SERVICE_USER_SERVICE != 0
+        );
+        if template {
+            self.set_registry_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "Start",
+                &RegValue::Dword(start_type.raw()),
+            )?;
+            let delayed = start_type == StartType::Delayed;
+            return self.set_registry_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "DelayedAutostart",
+                &RegValue::Dword(delayed.into()),
+            );
+        }
+
+        // sc.exe wants "start=" and the type as two separate arguments.
+        let args = [
+            "config".to_string(),
+            service.to_string(),
+            "start=".to_string(),
+            start_type.sc_name().to_string(),
+        ];
+        run_tool("sc", &args)?;
+        // "start= auto" leaves an earlier delayed start in place.
+        if start_type == StartType::Automatic
+            && self.service_start_type(service) == Some(StartType::Delayed)
+        {
+            self.set_registry_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "DelayedAutostart",
+                &RegValue::Dword(0),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn set_power_setting(
+        &self,
+        scheme: &str,
+        subgroup: &str,
+        setting: &str,
+        ac: u32,
+        dc: u32,
+    ) -> Result<(), ApplyError> {
+        for (flag, index) in [("/setacvalueindex", ac), ("/setdcvalueindex", dc)] {
+            let args = [flag, scheme, subgroup, setting, &index.to_string()].map(str::to_string);
+            run_tool("powercfg", &args)?;
+        }
+        run_tool("powercfg", &["/setactive".to_string(), scheme.to_string()])
+    }
+
+    fn run_bcdedit(&self, args: &[String]) -> Result<(), ApplyError> {
+        run_tool("bcdedit", args)
+    }
+}
+
+impl SystemReader for LiveSystem {
+    fn read_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Option<RegValue> {
+        LiveRegistry.read_value(hive, path, name)
+    }
+
+    fn key_last_write(&self, hive: Hkey, path: &str) -> Option<DateTime<Utc>> {
+        LiveRegistry.last_write_time(hive, path)
+    }
+
+    fn power_setting(&self, scheme: &str, subgroup: &str, setting: &str) -> Option<(u32, u32)> {
+        let args = ["/query", scheme, subgroup, setting].map(str::to_string);
+        parse_power_query(&tool_output("powercfg", &args).ok()?)
+    }
+}
+
+/// The current AC and DC indexes are the last two `: 0x...` lines of
+/// `powercfg /query`; matching on position keeps this independent of the
+/// display language.
+fn parse_power_query(output: &str) -> Option<(u32, u32)> {
+    let indexes: Vec<u32> = output
+        .lines()
+        .filter_map(|line| line.rsplit_once(": 0x"))
+        .filter_map(|(_, hex)| u32::from_str_radix(hex.trim(), 16).ok())
+        .collect();
+    match indexes.as_slice() {
+        [.., ac, dc] => Some((*ac, *dc)),
+        _ => None,
+    }
+}
+
+/// Applies everything to an in-memory registry and keeps a log of the
+/// non-registry actions, for tests and offline previews.
+#[derive(Debug, Default)]
+pub struct MemorySystem {
+    registry: Mutex<MemoryRegistry>,
+    actions: Mutex<Vec<Remediation>>,
+    power: Mutex<HashMap<String, (u32, u32)>>,
+}
+
+impl MemorySystem {
+    pub fn new(registry: MemoryRegistry) -> Self {
+        Self {
+            registry: Mutex::new(registry),
+            actions: Mutex::new(Vec::new()),
+            power: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_power_setting(
+        self,
+        scheme: &str,
+        subgroup: &str,
+        setting: &str,
+        ac: u32,
+        dc: u32,
+    ) -> Self {
+        self.power
+            .lock()
+            .unwrap()
+            .insert(power_key(scheme, subgroup, setting), (ac, dc));
+        self
+    }
+
+    /// A copy of the registry with every change applied so far.
+    pub fn registry(&self) -> MemoryRegistry {
+        self.registry.lock().unwrap().clone()
+    }
+
+    /// Service, power and bcdedit changes, in the order they were made.
+    /// Service start types also land in the registry and power settings
+    /// can be read back.
+    pub fn actions(&self) -> Vec<Remediation> {
+        self.actions.lock().unwrap().clone()
+    }
+}
+
+impl SystemWriter for MemorySystem {
+    fn set_registry_value(
+        &self,
+        hive: Hkey,
+        path: &str,
+        name: &str,
+        value: &RegValue,
+    ) -> Result<(), ApplyError> {
+        self.registry
+            .lock()
+            .unwrap()
+            .set_value(hive, path, name, value.clone());
+        Ok(())
+    }
+
+    fn delete_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Result<(), ApplyError> {
+        self.registry.lock().unwrap().delete_value(hive, path, name);
+        Ok(())
+    }
+
+    fn set_service_start_type(
+        &self,
+        service: &str,
+        start_type: StartType,
+    ) -> Result<(), ApplyError> {
+        let key = service_key(service);
+        let mut registry = self.registry.lock().unwrap();
+        registry.set_value(
+            HKEY_LOCAL_MACHINE,
+            &key,
+            "Start",
+            RegValue::Dword(start_type.raw()),
+        );
+        if matches!(start_type, StartType::Automatic | StartType::Delayed) {
+            let delayed = start_type == StartType::Delayed;
+            registry.set_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "DelayedAutostart",
+                RegValue::Dword(delayed.into()),
+            );
+        }
+        drop(registry);
+        self.actions
+            .lock()
+            .unwrap()
+            .push(Remediation::service_start(service, start_type));
+        Ok(())
+    }
+
+    fn set_power_setting(
+        &self,
+        scheme: &str,
+        subgroup: &str,
+        setting: &str,
+        ac: u32,
+        dc: u32,
+    ) -> Result<(), ApplyError> {
+        self.power
+            .lock()
+            .unwrap()
+            .insert(power_key(scheme, subgroup, setting), (ac, dc));
+        self.actions
+            .lock()
+            .unwrap()
+            .push(Remediation::SetPowerSetting {
+                scheme: scheme.to_string(),
+                subgroup: subgroup.to_string(),
+                setting: setting.to_string(),
+                ac,
+                dc,
+            });
+        Ok(())
+    }
+
+    fn run_bcdedit(&self, args: &[String]) -> Result<(), ApplyError> {
+        self.actions.lock().unwrap().push(Remediation::RunBcdEdit {
+            args: args.to_vec(),
+        });
+        Ok(())
+    }
+}
+
+impl SystemReader for MemorySystem {
+    fn read_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Option<RegValue> {
+        self.registry.lock().unwrap().read_value(hive, path, name)
+    }
+
+    fn key_last_write(&self, hive: Hkey, path: &str) -> Option<DateTime<Utc>> {
+        self.registry.lock().unwrap().last_write_time(hive, path)
+    }
+
+    fn power_setting(&self, scheme: &str, subgroup: &str, setting: &str) -> Option<(u32, u32)> {
+        self.power
+            .lock()
+            .unwrap()
+            .get(&power_key(scheme, subgroup, setting))
+            .copied()
+    }
+}
+
+fn power_key(scheme: &str, subgroup: &str, setting: &str) -> String {
+    format!("{}/{}/{}", scheme, subgroup, setting).to_ascii_uppercase()
+}
+
+impl RegistrySource for MemorySystem {
+    fn read_value(&self, hkey: Hkey, subkey: &str, value_name: &str) -> Option<RegValue> {
+        self.registry
+            .lock()
+            .unwrap()
+            .read_value(hkey, subkey, value_name)
+    }
+
+    fn key_exists(&self, hkey: Hkey, subkey: &str) -> bool {
+        self.registry.lock().unwrap().key_exists(hkey, subkey)
+    }
+
+    fn subkeys(&self, hkey: Hkey, subkey: &str) -> Option<Vec<String>> {
+        self.registry.lock().unwrap().subkeys(hkey, subkey)
+    }
+
+    fn values(&self, hkey: Hkey, subkey: &str) -> Option<Vec<(String, RegValue)>> {
+        self.registry.lock().unwrap().values(hkey, subkey)
+    }
+
+    fn last_write_time(&self, hkey: Hkey, subkey: &str) -> Option<DateTime<Utc>> {
+        self.registry.lock().unwrap().last_write_time(hkey, subkey)
+    }
+}
+
+/// Whether a check failed and knows how to fix itself.
+pub fn is_fixable(check: &Check) -> bool {
+    matches!(check.status, CheckStatus::Warning | CheckStatus::Issue) && check.remediation.is_some()
+}
+
+/// Failed checks with a remediation, in category then check order.
+pub fn plan(results: &AuditResults) -> Vec<&Check> {
+    let mut names: Vec<&String> = results.categories.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .flat_map(|name| &results.categories[name].checks)
+        .filter(|check| is_fixable(check))
+        .collect()
+}
+
+/// What one pending fix would change, for dry runs and change tickets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub check_id: String,
+    pub key: String,
+    pub value: String,
+    pub current: String,
+    pub proposed: String,
+    /// False when the target already holds the proposed state.
+    pub changes: bool,
+    pub reboot_required: bool,
+    pub risk: Risk,
+    pub remediation: Remediation,
+}
+
+/// Resolves each check's fix against the current state without writing.
+/// Batches show one row per step.
+pub fn preview(checks: &[&Check], reader: &dyn SystemReader) -> Vec<PlannedChange> {
+    let mut changes = Vec::new();
+    for check in checks {
+        let Some(fix) = &check.remediation else {
+            continue;
+        };
+        for step in fix.steps() {
+            let (key, value) = step.location();
+            changes.push(PlannedChange {
+                check_id: check.id.clone(),
+                key,
+                value,
+                current: step.capture(reader).describe(),
+                proposed: step.proposed(),
+                changes: step.is_satisfied(reader) != Some(true),
+                reboot_required: step.needs_reboot(),
+                risk: step.risk(),
+                remediation: step.clone(),
+            });
+        }
+    }
+    changes
+}
+
+/// Offline images have no active power scheme to query.
+impl SystemReader for CheckContext {
+    fn read_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Option<RegValue> {
+        self.registry().read_value(hive, path, name)
+    }
+
+    fn key_last_write(&self, hive: Hkey, path: &str) -> Option<DateTime<Utc>> {
+        self.registry().last_write_time(hive, path)
+    }
+
+    fn power_setting(&self, _: &str, _: &str, _: &str) -> Option<(u32, u32)> {
+        None
+    }
+}
@@ -1,3 +1,7 @@
+use crate::checks::{find_check, DataSource, SERVICES_KEY};
+use crate::diff::{CheckState, ReportDiff};
+use crate::remediation::PlannedChange;
+use crate::score::{self, ScoreWeights};
 use crate::types::*;
 use anyhow::Result;
 use std::fs;
@@ -10,6 +14,27 @@ fn esc(s: &str) -> String {
         .replace('\'', "&#x27;")
 }
 
+/// Escapes Markdown syntax and table pipes; HTML is entity-encoded and
+/// line breaks are flattened so a value stays in its table cell.
+fn esc_md(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\r' => {}
+            '\n' => out.push(' '),
+            '\\' | '`' | '*' | '_' | '[' | ']' | '|' | '#' | '~' | '!' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 fn esc_csv(s: &str) -> String {
     let s_clean = s.replace('"', "\"\"");
     if s.starts_with(|c: char| "=+-@\t\r".contains(c)) {
@@ -19,29 +44,229 @@ fn esc_csv(s: &str) -> String {
     }
 }
 
+const GAUGE_CIRCUMFERENCE: f64 = 251.33; // 2 * pi * r for r = 40
+
+fn status_icon(status: &CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Optimal => "✓",
+        CheckStatus::Warning => "⚠",
+        CheckStatus::Issue => "✗",
+        CheckStatus::Info => "ℹ",
+        CheckStatus::Unknown => "?",
+        CheckStatus::Error(_) => "!",
+    }
+}
+
+/// A ring gauge filled to `score` percent; grey and "n/a" when ungraded.
+fn gauge(label: &str, score: Option<f64>, large: bool) -> String {
+    let (color, filled, text) = match score {
+        Some(s) if s >= 80.0 => ("#4ec9b0", s, format!("{:.0}", s)),
+        Some(s) if s >= 50.0 => ("#ce9178", s, format!("{:.0}", s)),
+        Some(s) => ("#f48771", s, format!("{:.0}", s)),
+        None => ("#3e3e42", 0.0, "n/a".to_string()),
+    };
+    let size = if large { 160 } else { 100 };
+    format!(
+        "        <div class=\"gauge\"><svg width=\"{size}\" height=\"{size}\" viewBox=\"0 0 100 100\">\
+<circle cx=\"50\" cy=\"50\" r=\"40\" fill=\"none\" stroke=\"#3e3e42\" stroke-width=\"10\"/>\
+<circle cx=\"50\" cy=\"50\" r=\"40\" fill=\"none\" stroke=\"{color}\" stroke-width=\"10\" \
+stroke-dasharray=\"{:.2} {GAUGE_CIRCUMFERENCE}\" transform=\"rotate(-90 50 50)\"/>\
+<text x=\"50\" y=\"57\" text-anchor=\"middle\" font-size=\"22\" fill=\"#d4d4d4\">{text}</text>\
+</svg><div class=\"gauge-label\">{}</div></div>\n",
+        filled / 100.0 * GAUGE_CIRCUMFERENCE,
+        esc(label)
+    )
+}
+
 pub fn export_json(results: &AuditResults, path: &str) -> Result<()> {
     let json = serde_json::to_string_pretty(results)?;
     fs::write(path, json)?;
     Ok(())
 }
 
+/// The dry-run diff, one entry per pending fix.
+pub fn export_plan_json(changes: &[PlannedChange], path: &str) -> Result<()> {
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "generated": chrono::Local::now().to_rfc3339(),
+        "reboot_required": changes.iter().any(|c| c.changes && c.reboot_required),
+        "changes": changes,
+    }))?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// SARIF level of a failing check: the status sets the ceiling and checks
+/// below severity 5 (of 10) drop one level.
+fn sarif_level(check: &Check) -> &'static str {
+    match (&check.status, check.severity >= 5) {
+        (CheckStatus::Issue, true) => "error",
+        (CheckStatus::Issue, false) | (CheckStatus::Warning, true) => "warning",
+        _ => "note",
+    }
+}
+
+/// Logical locations for what the check reads: registry values and keys,
+/// WMI classes, service keys. Checks outside the catalog, such as custom
+/// rules, fall back to the key their fix writes.
+fn sarif_locations(check: &Check) -> Vec<serde_json::Value> {
+    let location = |name: &str, qualified: String| {
+        serde_json::json!({
+            "logicalLocations": [{
+                "name": name,
+                "fullyQualifiedName": qualified,
+                "kind": "resource",
+            }]
+        })
+    };
+    let sources = find_check(&check.id).map_or(&[][..], |meta| meta.sources);
+    let mut locations: Vec<serde_json::Value> = sources
+        .iter()
+        .map(|source| match source {
+            DataSource::Registry { hkey, path, value } => {
+                let key = format!("{}\\{}", hkey.full_name(), path);
+                match value {
+                    Some(value) => location(value, format!("{}\\{}", key, value)),
+                    None => location(path.rsplit('\\').next().unwrap_or(path), key),
+                }
+            }
+            DataSource::Wmi { class, property } => match property {
+                Some(property) => location(property, format!("WMI {}.{}", class, property)),
+                None => location(class, format!("WMI {}", class)),
+            },
+            DataSource::ServiceManager { service } => location(
+                service,
+                format!("HKEY_LOCAL_MACHINE\\{}\\{}", SERVICES_KEY, service),
+            ),
+            DataSource::Dxgi => location("DXGI", "DXGI adapter enumeration".to_string()),
+        })
+        .collect();
+    if locations.is_empty() {
+        if let Some((hive, path)) = check.remediation.as_ref().and_then(|r| r.registry_key()) {
+            let name = path.rsplit('\\').next().unwrap_or(&path).to_string();
+            locations.push(location(&name, format!("{}\\{}", hive.full_name(), path)));
+        }
+    }
+    locations
+}
+
+/// One rule per failing check; its help carries the fix and what it risks.
+fn sarif_rule(check: &Check, category: &str) -> serde_json::Value {
+    let description = if check.description.is_empty() {
+        &check.name
+    } else {
+        &check.description
+    };
+    let mut help = description.clone();
+    let mut markdown = help.clone();
+    if let Some(fix) = &check.remediation {
+        let risk = fix.risk().label();
+        help.push_str(&format!("\nFix: {}\nRisk: {}", fix.describe(), risk));
+        markdown.push_str(&format!(
+            "\n\n**Fix:** `{}`\n\n**Risk:** {}",
+            fix.describe(),
+            risk
+        ));
+    }
+    serde_json::json!({
+        "id": check.id,
+        "shortDescription": { "text": check.name },
+        "fullDescription": { "text": description },
+        "help": { "text": help, "markdown": markdown },
+        "defaultConfiguration": { "level": sarif_level(check) },
+        "properties": {
+            "category": category,
+            "security-severity": format!("{:.1}", f64::from(check.severity)),
+            "fixable": check.remediation.is_some(),
+        },
+    })
+}
+
+/// SARIF 2.1.0 for code-scanning and compliance dashboards: one result per
+/// Warning or Issue.
+pub fn export_sarif(results: &AuditResults, path: &str) -> Result<()> {
+    let mut names: Vec<&String> = results.categories.keys().collect();
+    names.sort();
+    let failing: Vec<(&str, &Check)> = names
+        .into_iter()
+        .map(|name| &results.categories[name])
+        .flat_map(|category| {
+            category
+                .checks
+                .iter()
+                .filter(|check| matches!(check.status, CheckStatus::Warning | CheckStatus::Issue))
+                .map(move |check| (category.name.as_str(), check))
+        })
+        .collect();
+
+    let rules: Vec<serde_json::Value> = failing
+        .iter()
+        .map(|(category, check)| sarif_rule(check, category))
+        .collect();
+    let sarif_results: Vec<serde_json::Value> = failing
+        .iter()
+        .enumerate()
+        .map(|(index, (_, check))| {
+            let mut message = format!("{} is {}", check.name, check.value);
+            if let Some(expected) = &check.expected {
+                message.push_str(&format!("; expected {}", expected));
+            }
+            serde_json::json!({
+                "ruleId": check.id,
+                "ruleIndex": index,
+                "level": sarif_level(check),
+                "message": { "text": message },
+                "locations": sarif_locations(check),
+            })
+        })
+        .collect();
+
+    // SARIF wants UTC; reports store local time.
+    let finished = chrono::DateTime::parse_from_rfc3339(&results.timestamp)
+        .map(|t| {
+            t.to_utc()
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        })
+        .unwrap_or_else(|_| results.timestamp.clone());
+    let mut run = serde_json::json!({
+        "tool": {
+            "driver": {
+                "name": "Windows-Optimizer",
+                "version": env!("CARGO_PKG_VERSION"),
+                "semanticVersion": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "invocations": [{
+            "executionSuccessful": true,
+            "endTimeUtc": finished,
+        }],
+        "results": sarif_results,
+    });
+    if let Some(profile) = &results.profile {
+        run["properties"] = serde_json::json!({ "profile": profile });
+    }
+    let sarif = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [run],
+    });
+    fs::write(path, serde_json::to_string_pretty(&sarif)?)?;
+    Ok(())
+}
+
 pub fn export_csv(results: &AuditResults, path: &str) -> Result<()> {
-    let mut csv = String::from("Category,Check,Value,Status,Description\n");
+    let mut csv = String::from("Category,ID,Check,Value,Status,Source,Description\n");
 
     for category in results.categories.values() {
         for check in &category.checks {
-            let status_str = match check.status {
-                CheckStatus::Optimal => "Optimal",
-                CheckStatus::Warning => "Warning",
-                CheckStatus::Issue => "Issue",
-                CheckStatus::Info => "Info",
-            };
             csv.push_str(&format!(
-                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
                 esc_csv(&category.name),
+                esc_csv(&check.id),
                 esc_csv(&check.name),
                 esc_csv(&check.value),
-                status_str,
+                esc_csv(&check.status.to_string()),
+                check.source.label(),
                 esc_csv(&check.description)
             ));
         }
@@ -51,15 +276,218 @@ pub fn export_csv(results: &AuditResults, path: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
-    let mut html = String::from(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <title>Windows Optimizer Report</title>
-    <style>
-        body {
+/// How JUnit reports a Warning: as a failure, so it gates the build, or
+/// skipped, so it shows up without failing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JunitWarnings {
+    Failure,
+    Skip,
+}
+
+impl std::str::FromStr for JunitWarnings {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "failure" | "fail" => Ok(JunitWarnings::Failure),
+            "skip" | "skipped" => Ok(JunitWarnings::Skip),
+            _ => Err(format!("expected failure or skip, got '{}'", s)),
+        }
+    }
+}
+
+/// Seconds, as JUnit's `time` attribute wants them.
+fn junit_time(ms: f64) -> String {
+    format!("{:.3}", ms / 1000.0)
+}
+
+/// Value, expected value and fix, for a failure's body.
+fn junit_details(check: &Check) -> String {
+    let mut details = format!("Value: {}", check.value);
+    if let Some(expected) = &check.expected {
+        details.push_str(&format!("\nExpected: {}", expected));
+    }
+    if !check.description.is_empty() {
+        details.push_str(&format!("\n{}", check.description));
+    }
+    if let Some(fix) = &check.remediation {
+        details.push_str(&format!(
+            "\nFix: {}\nRisk: {}",
+            fix.describe(),
+            fix.risk().label()
+        ));
+    }
+    details
+}
+
+/// What a check counts as in JUnit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JunitOutcome<'a> {
+    Pass,
+    Failure,
+    Error(&'a CheckError),
+    Skipped,
+}
+
+impl<'a> JunitOutcome<'a> {
+    fn of(check: &'a Check, warnings: JunitWarnings) -> Self {
+        match (&check.status, warnings) {
+            (CheckStatus::Issue, _) | (CheckStatus::Warning, JunitWarnings::Failure) => {
+                JunitOutcome::Failure
+            }
+            (CheckStatus::Warning, JunitWarnings::Skip) | (CheckStatus::Unknown, _) => {
+                JunitOutcome::Skipped
+            }
+            (CheckStatus::Error(err), _) => JunitOutcome::Error(err),
+            (CheckStatus::Optimal | CheckStatus::Info, _) => JunitOutcome::Pass,
+        }
+    }
+}
+
+fn junit_testcase(check: &Check, category: &str, warnings: JunitWarnings) -> String {
+    let name = if check.id.is_empty() {
+        &check.name
+    } else {
+        &check.id
+    };
+    let mut message = format!("{} is {}", check.name, check.value);
+    if let Some(expected) = &check.expected {
+        message.push_str(&format!("; expected {}", expected));
+    }
+    let body = match (JunitOutcome::of(check, warnings), &check.status) {
+        (JunitOutcome::Failure, status) => format!(
+            "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+            esc(&message),
+            status.label(),
+            esc(&junit_details(check))
+        ),
+        (JunitOutcome::Error(err), _) => {
+            let kind = format!("{:?}", err);
+            format!(
+                "      <error message=\"{}\" type=\"{}\"/>\n",
+                esc(&err.to_string()),
+                kind.split('(').next().unwrap_or(&kind)
+            )
+        }
+        (JunitOutcome::Skipped, CheckStatus::Unknown) => format!(
+            "      <skipped message=\"{} was not measured\"/>\n",
+            esc(&check.name)
+        ),
+        (JunitOutcome::Skipped, _) => {
+            format!("      <skipped message=\"{}\"/>\n", esc(&message))
+        }
+        (JunitOutcome::Pass, CheckStatus::Info) => {
+            let mut properties = vec![("name", check.name.as_str()), ("value", &check.value)];
+            if let Some(expected) = &check.expected {
+                properties.push(("expected", expected));
+            }
+            properties.push(("source", check.source.label()));
+            let mut body = String::from("      <properties>\n");
+            for (name, value) in properties {
+                body.push_str(&format!(
+                    "        <property name=\"{}\" value=\"{}\"/>\n",
+                    name,
+                    esc(value)
+                ));
+            }
+            body.push_str("      </properties>\n");
+            body
+        }
+        (JunitOutcome::Pass, _) => String::new(),
+    };
+
+    let mut case = format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+        esc(name),
+        esc(category),
+        junit_time(check.elapsed_ms.unwrap_or(0.0))
+    );
+    if body.is_empty() {
+        case.push_str("/>\n");
+    } else {
+        case.push_str(&format!(">\n{}    </testcase>\n", body));
+    }
+    case
+}
+
+/// JUnit XML for CI: a testsuite per category and a testcase per check,
+/// timed with `Check::elapsed_ms`. Issues fail, Warnings fail or skip per
+/// `warnings`, unreadable sources are errors and everything else passes.
+pub fn export_junit(results: &AuditResults, path: &str, warnings: JunitWarnings) -> Result<()> {
+    let mut names: Vec<&String> = results.categories.keys().collect();
+    names.sort();
+
+    let mut suites = String::new();
+    let mut totals = JunitCounts::default();
+    for category in names.into_iter().map(|name| &results.categories[name]) {
+        let mut counts = JunitCounts::default();
+        let mut cases = String::new();
+        for check in &category.checks {
+            counts.add(check, warnings);
+            cases.push_str(&junit_testcase(check, &category.name, warnings));
+        }
+        suites.push_str(&format!(
+            "  <testsuite name=\"{}\" {} timestamp=\"{}\">\n{}  </testsuite>\n",
+            esc(&category.name),
+            counts.attributes(),
+            esc(&results.timestamp),
+            cases
+        ));
+        totals.merge(&counts);
+    }
+
+    let xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"Windows-Optimizer\" {}>\n{}</testsuites>\n",
+        totals.attributes(),
+        suites
+    );
+    fs::write(path, xml)?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct JunitCounts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    elapsed_ms: f64,
+}
+
+impl JunitCounts {
+    fn add(&mut self, check: &Check, warnings: JunitWarnings) {
+        self.tests += 1;
+        match JunitOutcome::of(check, warnings) {
+            JunitOutcome::Failure => self.failures += 1,
+            JunitOutcome::Error(_) => self.errors += 1,
+            JunitOutcome::Skipped => self.skipped += 1,
+            JunitOutcome::Pass => {}
+        }
+        self.elapsed_ms += check.elapsed_ms.unwrap_or(0.0);
+    }
+
+    fn merge(&mut self, other: &JunitCounts) {
+        self.tests += other.tests;
+        self.failures += other.failures;
+        self.errors += other.errors;
+        self.skipped += other.skipped;
+        self.elapsed_ms += other.elapsed_ms;
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\"",
+            self.tests,
+            self.failures,
+            self.errors,
+            self.skipped,
+            junit_time(self.elapsed_ms)
+        )
+    }
+}
+
+/// Shared by every HTML page: dark theme, header, summary cards and check rows.
+const STYLE: &str = r#"        body {
             font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
             margin: 0;
             padding: 20px;
@@ -91,6 +519,8 @@ pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
         .warning { border-color: #ce9178; }
         .issue { border-color: #f48771; }
         .info { border-color: #4fc1ff; }
+        .unknown { border-color: #858585; }
+        .error { border-color: #c586c0; }
         .category {
             background: #2d2d30;
             margin-bottom: 20px;
@@ -112,19 +542,47 @@ pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
             align-items: center;
         }
         .check:last-child { border-bottom: none; }
+        .gauges {
+            display: flex;
+            flex-wrap: wrap;
+            align-items: center;
+            gap: 20px;
+            margin-bottom: 30px;
+        }
+        .gauge { text-align: center; }
+        .gauge-label { margin-top: 5px; color: #858585; }
         .check-icon { font-size: 1.2em; }
         .check-name { font-weight: 500; }
         .check-value { color: #9cdcfe; }
+        .check-source {
+            margin-left: 8px;
+            font-size: 0.75em;
+            color: #858585;
+            text-transform: uppercase;
+        }
         .check-description { 
             grid-column: 2 / -1; 
             font-size: 0.9em; 
             color: #858585;
             margin-top: 5px;
         }
-    </style>
-</head>
-<body>
-    <div class="header">
+"#;
+
+/// Everything up to and including `<body>`, with `extra_style` appended
+/// to the shared styles.
+fn page_start(title: &str, extra_style: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n    <meta charset=\"UTF-8\">\n    <title>{}</title>\n    <style>\n{}{}    </style>\n</head>\n<body>\n",
+        esc(title),
+        STYLE,
+        extra_style
+    )
+}
+
+pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
+    let mut html = page_start("Windows Optimizer Report", "");
+    html.push_str(
+        r#"    <div class="header">
         <h1>🚀 Windows Optimizer Report</h1>
         <div class="timestamp">"#,
     );
@@ -152,6 +610,26 @@ pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
         "        <div class=\"summary-card info\"><h2>{}</h2><p>Info</p></div>\n",
         results.count_status(CheckStatus::Info)
     ));
+    html.push_str(&format!(
+        "        <div class=\"summary-card unknown\"><h2>{}</h2><p>Not Measured</p></div>\n",
+        results.count_status(CheckStatus::Unknown)
+    ));
+    html.push_str(&format!(
+        "        <div class=\"summary-card error\"><h2>{}</h2><p>Errors</p></div>\n",
+        results.count_errors()
+    ));
+    html.push_str("    </div>\n\n");
+
+    // Health score gauges
+    let health = results
+        .score
+        .clone()
+        .unwrap_or_else(|| score::compute(results, &ScoreWeights::default()));
+    html.push_str("    <div class=\"gauges\">\n");
+    html.push_str(&gauge("Overall", health.overall, true));
+    for (name, category) in &health.categories {
+        html.push_str(&gauge(name, category.score, false));
+    }
     html.push_str("    </div>\n\n");
 
     // Categories
@@ -162,25 +640,21 @@ pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
         ));
 
         for check in &category.checks {
-            let icon = match check.status {
-                CheckStatus::Optimal => "✓",
-                CheckStatus::Warning => "⚠",
-                CheckStatus::Issue => "✗",
-                CheckStatus::Info => "ℹ",
-            };
-
-            let class = match check.status {
-                CheckStatus::Optimal => "optimal",
-                CheckStatus::Warning => "warning",
-                CheckStatus::Issue => "issue",
-                CheckStatus::Info => "info",
-            };
+            let icon = status_icon(&check.status);
+            let class = check.status.label().to_lowercase();
 
             html.push_str(&format!(
-                "        <div class=\"check\">\n            <div class=\"check-icon {}\">{}</div>\n            <div class=\"check-name\">{}</div>\n            <div class=\"check-value\">{}</div>\n",
-                class, icon, esc(&check.name), esc(&check.value)
+                "        <div class=\"check\">\n            <div class=\"check-icon {}\">{}</div>\n            <div class=\"check-name\">{}</div>\n            <div class=\"check-value\">{}<span class=\"check-source\">{}</span></div>\n",
+                class, icon, esc(&check.name), esc(&check.value), check.source.label()
             ));
 
+            if let CheckStatus::Error(err) = &check.status {
+                html.push_str(&format!(
+                    "            <div class=\"check-description\">{}</div>\n",
+                    esc(&err.to_string())
+                ));
+            }
+
             if !check.description.is_empty() {
                 html.push_str(&format!(
                     "            <div class=\"check-description\">{}</div>\n",
@@ -199,3 +673,231 @@ pub fn export_html(results: &AuditResults, path: &str) -> Result<()> {
     fs::write(path, html)?;
     Ok(())
 }
+
+/// Whether the Markdown report lists a check outside its `<details>` block.
+fn needs_attention(status: &CheckStatus) -> bool {
+    !matches!(status, CheckStatus::Optimal | CheckStatus::Info)
+}
+
+fn md_score(score: Option<f64>) -> String {
+    score.map_or_else(|| "n/a".to_string(), |s| format!("{:.1}/100", s))
+}
+
+/// A check as a table row: status, ID, name, value, expected and fix.
+fn md_row(check: &Check) -> String {
+    let mut value = esc_md(&check.value);
+    if let CheckStatus::Error(err) = &check.status {
+        value = format!("{} ({})", value, esc_md(&err.to_string()));
+    }
+    format!(
+        "| {} {} | {} | {} | {} | {} | {} |\n",
+        status_icon(&check.status),
+        check.status.label(),
+        esc_md(&check.id),
+        esc_md(&check.name),
+        value,
+        esc_md(check.expected.as_deref().unwrap_or("")),
+        esc_md(
+            &check
+                .remediation
+                .as_ref()
+                .map_or(String::new(), |r| r.describe())
+        )
+    )
+}
+
+const MD_HEADER: &str =
+    "| Status | ID | Check | Value | Expected | Fix |\n| --- | --- | --- | --- | --- | --- |\n";
+
+/// Markdown for issues, wikis and chat: a summary table, then per category
+/// the checks that need attention and, folded into `<details>`, every
+/// check. With `all`, each section lists every check instead.
+pub fn export_markdown(results: &AuditResults, path: &str, all: bool) -> Result<()> {
+    let health = results
+        .score
+        .clone()
+        .unwrap_or_else(|| score::compute(results, &ScoreWeights::default()));
+
+    let mut md = String::from("# Windows Optimizer Report\n\n");
+    md.push_str(&format!("Generated: {}", esc_md(&results.timestamp)));
+    if let Some(profile) = &results.profile {
+        md.push_str(&format!(" · Profile: {}", esc_md(profile)));
+    }
+    md.push_str(&format!(
+        " · Health score: {}\n\n",
+        md_score(health.overall)
+    ));
+    md.push_str("| Optimal | Warnings | Issues | Info | Not Measured | Errors |\n");
+    md.push_str("| ---: | ---: | ---: | ---: | ---: | ---: |\n");
+    md.push_str(&format!(
+        "| {} | {} | {} | {} | {} | {} |\n",
+        results.count_status(CheckStatus::Optimal),
+        results.count_status(CheckStatus::Warning),
+        results.count_status(CheckStatus::Issue),
+        results.count_status(CheckStatus::Info),
+        results.count_status(CheckStatus::Unknown),
+        results.count_errors()
+    ));
+
+    let mut names: Vec<&String> = results.categories.keys().collect();
+    names.sort();
+    for category in names.into_iter().map(|name| &results.categories[name]) {
+        let score = health.categories.get(&category.name).and_then(|c| c.score);
+        md.push_str(&format!(
+            "\n## {} — {}\n\n",
+            esc_md(&category.name),
+            md_score(score)
+        ));
+
+        if all {
+            md.push_str(MD_HEADER);
+            category
+                .checks
+                .iter()
+                .for_each(|check| md.push_str(&md_row(check)));
+            continue;
+        }
+
+        let attention: Vec<&Check> = category
+            .checks
+            .iter()
+            .filter(|check| needs_attention(&check.status))
+            .collect();
+        if attention.is_empty() {
+            md.push_str("Nothing needs attention.\n");
+        } else {
+            md.push_str(MD_HEADER);
+            attention
+                .into_iter()
+                .for_each(|check| md.push_str(&md_row(check)));
+        }
+        // The blank line after the summary lets GitHub render the table.
+        md.push_str(&format!(
+            "\n<details>\n<summary>All {} checks</summary>\n\n{}",
+            category.checks.len(),
+            MD_HEADER
+        ));
+        category
+            .checks
+            .iter()
+            .for_each(|check| md.push_str(&md_row(check)));
+        md.push_str("\n</details>\n");
+    }
+
+    fs::write(path, md)?;
+    Ok(())
+}
+
+/// Column widths for the side-by-side rows of the diff page.
+const DIFF_STYLE: &str = r#"        .check.side { grid-template-columns: 30px 2fr 1fr 1fr; }
+        .column-header { color: #858585; font-size: 0.85em; text-transform: uppercase; }
+        .check-id { display: block; font-size: 0.8em; color: #858585; }
+        .check-status { display: block; font-size: 0.85em; color: #858585; }
+"#;
+
+fn diff_cell(state: &CheckState) -> String {
+    format!(
+        "<div class=\"check-value\">{}<span class=\"check-status\">{}</span></div>",
+        esc(&state.value),
+        esc(&state.status)
+    )
+}
+
+/// Old and new report side by side, one section per kind of change.
+pub fn export_diff_html(diff: &ReportDiff, path: &str) -> Result<()> {
+    let mut html = page_start("Windows Optimizer Diff", DIFF_STYLE);
+    html.push_str(&format!(
+        "    <div class=\"header\">\n        <h1>Windows Optimizer Diff</h1>\n        <div class=\"timestamp\">{} ({}) → {} ({})</div>\n    </div>\n",
+        esc(&diff.old.path),
+        esc(&diff.old.timestamp),
+        esc(&diff.new.path),
+        esc(&diff.new.timestamp)
+    ));
+
+    html.push_str("    <div class=\"summary\">\n");
+    for (class, count, label) in [
+        ("issue", diff.regressions.len(), "Regressions"),
+        ("optimal", diff.improvements.len(), "Improvements"),
+        ("warning", diff.changed.len(), "Changed"),
+        ("info", diff.added.len(), "Added"),
+        ("unknown", diff.removed.len(), "Removed"),
+        ("unknown", diff.unchanged, "Unchanged"),
+    ] {
+        html.push_str(&format!(
+            "        <div class=\"summary-card {}\"><h2>{}</h2><p>{}</p></div>\n",
+            class, count, label
+        ));
+    }
+    html.push_str("    </div>\n\n");
+
+    let sections = [
+        ("Regressions", "issue", "✗", &diff.regressions),
+        ("Improvements", "optimal", "✓", &diff.improvements),
+        ("Changed", "warning", "~", &diff.changed),
+    ];
+    for (title, class, icon, changes) in sections {
+        if changes.is_empty() {
+            continue;
+        }
+        html.push_str(&format!(
+            "    <div class=\"category\">\n        <div class=\"category-header\">{} ({})</div>\n",
+            title,
+            changes.len()
+        ));
+        html.push_str(&format!(
+            "        <div class=\"check side column-header\"><div></div><div>Check</div><div>{}</div><div>{}</div></div>\n",
+            esc(&diff.old.path),
+            esc(&diff.new.path)
+        ));
+        for change in changes {
+            html.push_str(&format!(
+                "        <div class=\"check side\">\n            <div class=\"check-icon {}\">{}</div>\n            <div class=\"check-name\">{}<span class=\"check-id\">{} · {}</span></div>\n            {}\n            {}\n        </div>\n",
+                class,
+                icon,
+                esc(&change.name),
+                esc(&change.check_id),
+                esc(&change.category),
+                diff_cell(&change.old),
+                diff_cell(&change.new)
+            ));
+        }
+        html.push_str("    </div>\n");
+    }
+
+    for (title, class, icon, entries, new_side) in [
+        ("Added", "info", "+", &diff.added, true),
+        ("Removed", "unknown", "-", &diff.removed, false),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        html.push_str(&format!(
+            "    <div class=\"category\">\n        <div class=\"category-header\">{} ({})</div>\n",
+            title,
+            entries.len()
+        ));
+        for entry in entries {
+            let cell = diff_cell(&entry.state);
+            let (old, new) = if new_side {
+                ("<div></div>".to_string(), cell)
+            } else {
+                (cell, "<div></div>".to_string())
+            };
+            html.push_str(&format!(
+                "        <div class=\"check side\">\n            <div class=\"check-icon {}\">{}</div>\n            <div class=\"check-name\">{}<span class=\"check-id\">{} · {}</span></div>\n            {}\n            {}\n        </div>\n",
+                class,
+                icon,
+                esc(&entry.name),
+                esc(&entry.check_id),
+                esc(&entry.category),
+                old,
+                new
+            ));
+        }
+        html.push_str("    </div>\n");
+    }
+
+    html.push_str("</body>\n</html>");
+    fs::write(path, html)?;
+    Ok(())
+}
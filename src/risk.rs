@@ -0,0 +1,366 @@
+//! How much a remediation can hurt, so dangerous fixes need an opt-in.
+//!
+//! Every change is graded safe, moderate or dangerous and tagged with what
+//! it gives up. Most of the catalog is safe; the grades come from a table
+//! of targets known to weaken protection or stability (Defender, the
+//! firewall, VBS/HVCI, GPU timeout recovery, ...), matched on the value a
+//! change writes, so a fix that turns protection back on stays safe.
+
+use crate::checks::{canonical_key_path, RegValue, HKEY_LOCAL_MACHINE};
+use crate::remediation::{Remediation, StartType};
+use crate::types::Check;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    Safe,
+    Moderate,
+    Dangerous,
+}
+
+impl RiskLevel {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "safe" => Some(RiskLevel::Safe),
+            "moderate" => Some(RiskLevel::Moderate),
+            "dangerous" => Some(RiskLevel::Dangerous),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskLevel::Safe => "safe",
+            RiskLevel::Moderate => "moderate",
+            RiskLevel::Dangerous => "dangerous",
+        }
+    }
+}
+
+impl std::str::FromStr for RiskLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s)
+            .ok_or_else(|| format!("expected safe, moderate or dangerous, got '{}'", s))
+    }
+}
+
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RiskCategory {
+    /// Turns off or weakens a protection.
+    SecurityReducing,
+    /// Makes hangs, crashes or data loss more likely.
+    StabilityReducing,
+    /// Read at boot, so neither the change nor its undo takes effect
+    /// before a restart.
+    RebootToRevert,
+}
+
+impl RiskCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskCategory::SecurityReducing => "security-reducing",
+            RiskCategory::StabilityReducing => "stability-reducing",
+            RiskCategory::RebootToRevert => "reversible-only-by-reboot",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Risk {
+    pub level: RiskLevel,
+    pub categories: Vec<RiskCategory>,
+    /// What the change gives up, in words for the person approving it.
+    pub tradeoffs: Vec<String>,
+}
+
+impl Risk {
+    fn safe() -> Self {
+        Self {
+            level: RiskLevel::Safe,
+            categories: Vec::new(),
+            tradeoffs: Vec::new(),
+        }
+    }
+
+    fn raise(&mut self, level: RiskLevel, category: RiskCategory, tradeoff: &str) {
+        self.level = self.level.max(level);
+        self.add(category);
+        if !self.tradeoffs.iter().any(|t| t == tradeoff) {
+            self.tradeoffs.push(tradeoff.to_string());
+        }
+    }
+
+    fn add(&mut self, category: RiskCategory) {
+        if !self.categories.contains(&category) {
+            self.categories.push(category);
+            self.categories.sort();
+        }
+    }
+
+    fn merge(&mut self, other: Risk) {
+        self.level = self.level.max(other.level);
+        for category in other.categories {
+            self.add(category);
+        }
+        for tradeoff in other.tradeoffs {
+            if !self.tradeoffs.contains(&tradeoff) {
+                self.tradeoffs.push(tradeoff);
+            }
+        }
+    }
+
+    pub fn is_security_reducing(&self) -> bool {
+        self.categories.contains(&RiskCategory::SecurityReducing)
+    }
+
+    /// "dangerous (security-reducing, reversible-only-by-reboot)"
+    pub fn label(&self) -> String {
+        if self.categories.is_empty() {
+            return self.level.to_string();
+        }
+        let categories: Vec<&str> = self.categories.iter().map(RiskCategory::as_str).collect();
+        format!("{} ({})", self.level, categories.join(", "))
+    }
+}
+
+/// A machine-wide value that is risky when set to a particular state.
+struct RegistryRule {
+    path: &'static str,
+    name: &'static str,
+    /// Whether the value being written (`None` for a delete) is the risky one.
+    risky: fn(Option<&RegValue>) -> bool,
+    level: RiskLevel,
+    category: RiskCategory,
+    tradeoff: &'static str,
+}
+
+fn is_dword(value: Option<&RegValue>, expected: u32) -> bool {
+    matches!(value, Some(RegValue::Dword(v)) if *v == expected)
+}
+
+const GRAPHICS_DRIVERS: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+const DEFENDER_POLICY: &str = r"SOFTWARE\Policies\Microsoft\Windows Defender";
+
+const REGISTRY_RULES: &[RegistryRule] = &[
+    RegistryRule {
+        path: GRAPHICS_DRIVERS,
+        name: "TdrLevel",
+        risky: |v| is_dword(v, 0),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::StabilityReducing,
+        tradeoff: "A hung GPU freezes the whole machine instead of resetting the driver",
+    },
+    RegistryRule {
+        path: GRAPHICS_DRIVERS,
+        name: "TdrDelay",
+        risky: |v| v.is_some(),
+        level: RiskLevel::Moderate,
+        category: RiskCategory::StabilityReducing,
+        tradeoff: "Changes how long Windows waits before resetting a hung GPU",
+    },
+    RegistryRule {
+        path: r"SYSTEM\CurrentControlSet\Control\DeviceGuard",
+        name: "EnableVirtualizationBasedSecurity",
+        risky: |v| !is_dword(v, 1),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off virtualization-based security, which isolates credentials and kernel code integrity",
+    },
+    RegistryRule {
+        path: r"SYSTEM\CurrentControlSet\Control\DeviceGuard\Scenarios\HypervisorEnforcedCodeIntegrity",
+        name: "Enabled",
+        risky: |v| !is_dword(v, 1),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off memory integrity (HVCI); unsigned or vulnerable drivers can load into the kernel",
+    },
+    RegistryRule {
+        path: DEFENDER_POLICY,
+        name: "DisableAntiSpyware",
+        risky: |v| is_dword(v, 1),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off Microsoft Defender Antivirus",
+    },
+    RegistryRule {
+        path: r"SOFTWARE\Policies\Microsoft\Windows Defender\Real-Time Protection",
+        name: "DisableRealtimeMonitoring",
+        risky: |v| is_dword(v, 1),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off Defender real-time protection",
+    },
+    RegistryRule {
+        path: r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+        name: "FeatureSettingsOverride",
+        risky: |v| matches!(v, Some(RegValue::Dword(v)) if *v & 3 != 0),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off Spectre/Meltdown mitigations",
+    },
+    RegistryRule {
+        path: r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management",
+        name: "PagingFiles",
+        risky: |_| true,
+        level: RiskLevel::Moderate,
+        category: RiskCategory::StabilityReducing,
+        tradeoff: "Changes the page file; too small a page file crashes programs under memory pressure",
+    },
+    RegistryRule {
+        path: r"SOFTWARE\Microsoft\Windows\CurrentVersion\Policies\System",
+        name: "EnableLUA",
+        risky: |v| is_dword(v, 0),
+        level: RiskLevel::Dangerous,
+        category: RiskCategory::SecurityReducing,
+        tradeoff: "Turns off User Account Control; every program runs with full administrator rights",
+    },
+];
+
+/// Services whose absence leaves the machine unprotected or unpatched.
+const SECURITY_SERVICES: &[(&str, &str)] = &[
+    ("WinDefend", "Microsoft Defender Antivirus"),
+    ("WdNisSvc", "Defender network inspection"),
+    ("Sense", "Defender for Endpoint"),
+    ("SecurityHealthService", "Windows Security health reporting"),
+    ("wscsvc", "Security Center"),
+    ("MpsSvc", "Windows Firewall"),
+    ("BFE", "the Base Filtering Engine, and with it the firewall"),
+    ("wuauserv", "Windows Update"),
+];
+
+/// `bcdedit /set` options that weaken boot-time protection.
+const BCD_RULES: &[(&str, &str, &str)] = &[
+    (
+        "hypervisorlaunchtype",
+        "off",
+        "Stops the hypervisor, and with it VBS, HVCI and Credential Guard",
+    ),
+    ("nx", "alwaysoff", "Turns off Data Execution Prevention"),
+    (
+        "testsigning",
+        "on",
+        "Lets test-signed drivers load into the kernel",
+    ),
+    (
+        "nointegritychecks",
+        "on",
+        "Lets unsigned drivers load into the kernel",
+    ),
+];
+
+/// Grades one remediation; a batch is as risky as its riskiest step.
+pub fn assess(remediation: &Remediation) -> Risk {
+    let mut risk = Risk::safe();
+    for step in remediation.steps() {
+        risk.merge(assess_step(step));
+    }
+    risk
+}
+
+fn assess_step(step: &Remediation) -> Risk {
+    let mut risk = Risk::safe();
+    if step.needs_reboot() {
+        risk.add(RiskCategory::RebootToRevert);
+    }
+
+    match step {
+        Remediation::SetRegistryValue {
+            hive, path, name, ..
+        }
+        | Remediation::DeleteRegistryValue { hive, path, name } => {
+            let written = match step {
+                Remediation::SetRegistryValue { value, .. } => Some(value),
+                _ => None,
+            };
+            let path = canonical_key_path(path);
+            let rules = REGISTRY_RULES.iter().filter(|rule| {
+                *hive == HKEY_LOCAL_MACHINE
+                    && canonical_key_path(rule.path) == path
+                    && rule.name.eq_ignore_ascii_case(name)
+                    && (rule.risky)(written)
+            });
+            for rule in rules {
+                risk.raise(rule.level, rule.category, rule.tradeoff);
+            }
+        }
+        Remediation::SetServiceStartType {
+            service,
+            start_type,
+        } => {
+            let protection = SECURITY_SERVICES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(service));
+            match (protection, start_type) {
+                (Some((_, what)), StartType::Disabled) => risk.raise(
+                    RiskLevel::Dangerous,
+                    RiskCategory::SecurityReducing,
+                    &format!("Disables {}", what),
+                ),
+                (Some((_, what)), StartType::Manual) => risk.raise(
+                    RiskLevel::Moderate,
+                    RiskCategory::SecurityReducing,
+                    &format!("{} no longer starts with Windows", what),
+                ),
+                (None, StartType::Disabled) => risk.raise(
+                    RiskLevel::Moderate,
+                    RiskCategory::StabilityReducing,
+                    &format!("Features that depend on {} stop working", service),
+                ),
+                _ => {}
+            }
+        }
+        Remediation::RunBcdEdit { args } => {
+            // bcdedit changes cannot be read back or journaled.
+            risk.raise(
+                RiskLevel::Moderate,
+                RiskCategory::RebootToRevert,
+                "Boot configuration changes are not journaled and cannot be undone automatically",
+            );
+            let args: Vec<String> = args.iter().map(|a| a.to_ascii_lowercase()).collect();
+            for (option, value, tradeoff) in BCD_RULES {
+                if args.windows(2).any(|w| w[0] == *option && w[1] == *value) {
+                    risk.raise(
+                        RiskLevel::Dangerous,
+                        RiskCategory::SecurityReducing,
+                        tradeoff,
+                    );
+                }
+            }
+        }
+        Remediation::SetPowerSetting { .. } | Remediation::Batch { .. } => {}
+    }
+    risk
+}
+
+/// Splits fixes into those within `allowed` and those refused, with the
+/// risk that refused them.
+pub fn gate<'a>(
+    checks: &[&'a Check],
+    allowed: RiskLevel,
+) -> (Vec<&'a Check>, Vec<(&'a Check, Risk)>) {
+    let mut accepted = Vec::new();
+    let mut refused = Vec::new();
+    for check in checks {
+        let risk = check
+            .remediation
+            .as_ref()
+            .map(assess)
+            .unwrap_or_else(Risk::safe);
+        if risk.level <= allowed {
+            accepted.push(*check);
+        } else {
+            refused.push((*check, risk));
+        }
+    }
+    (accepted, refused)
+}
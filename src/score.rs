@@ -0,0 +1,135 @@
+//! Weighted 0-100 health score per category and overall.
+//!
+//! Each graded check weighs `severity x check weight`; Optimal earns full
+//! marks, Warning half and Issue none. Info, Unknown and Error results say
+//! nothing about health and are left out. The overall score averages the
+//! category scores by category weight so categories with many checks do
+//! not drown out small ones.
+
+use crate::types::{AuditResults, CheckStatus};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Multipliers loaded from a TOML file:
+///
+/// ```toml
+/// [categories]
+/// latency = 2.0
+/// thermal = 0.5
+///
+/// [checks]
+/// "gpu.hags" = 3.0
+/// ```
+///
+/// Anything not listed weighs 1.0; a weight of 0 excludes it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoreWeights {
+    #[serde(default)]
+    pub categories: HashMap<String, f64>,
+    #[serde(default)]
+    pub checks: HashMap<String, f64>,
+}
+
+impl ScoreWeights {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let weights: ScoreWeights = toml::from_str(&text)
+            .with_context(|| format!("Invalid score weights in {}", path.display()))?;
+        if let Some((name, w)) = weights
+            .categories
+            .iter()
+            .chain(&weights.checks)
+            .find(|(_, w)| !w.is_finite() || **w < 0.0)
+        {
+            anyhow::bail!(
+                "Weight for {} must be a non-negative number, got {}",
+                name,
+                w
+            );
+        }
+        Ok(weights)
+    }
+
+    /// Category names match case-insensitively ("gpu" or "GPU").
+    pub fn category(&self, name: &str) -> f64 {
+        self.categories
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map_or(1.0, |(_, w)| *w)
+    }
+
+    pub fn check(&self, id: &str) -> f64 {
+        self.checks.get(id).copied().unwrap_or(1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryScore {
+    /// 0-100, or `None` when nothing in the category was graded.
+    pub score: Option<f64>,
+    /// Checks that counted towards the score.
+    pub graded: usize,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct HealthScore {
+    pub overall: Option<f64>,
+    pub categories: BTreeMap<String, CategoryScore>,
+}
+
+/// Share of a check's weight it earns, or `None` if it is not graded.
+fn credit(status: &CheckStatus) -> Option<f64> {
+    match status {
+        CheckStatus::Optimal => Some(1.0),
+        CheckStatus::Warning => Some(0.5),
+        CheckStatus::Issue => Some(0.0),
+        CheckStatus::Info | CheckStatus::Unknown | CheckStatus::Error(_) => None,
+    }
+}
+
+fn round1(x: f64) -> f64 {
+    (x * 10.0).round() / 10.0
+}
+
+pub fn compute(results: &AuditResults, weights: &ScoreWeights) -> HealthScore {
+    let mut score = HealthScore::default();
+    let (mut overall_sum, mut overall_weight) = (0.0, 0.0);
+
+    for category in results.categories.values() {
+        let (mut earned, mut possible, mut graded) = (0.0, 0.0, 0);
+        for check in &category.checks {
+            let Some(credit) = credit(&check.status) else {
+                continue;
+            };
+            let weight = check.severity as f64 * weights.check(&check.id);
+            if weight > 0.0 {
+                earned += weight * credit;
+                possible += weight;
+                graded += 1;
+            }
+        }
+
+        let category_weight = weights.category(&category.name);
+        let value = (possible > 0.0).then(|| 100.0 * earned / possible);
+        if let Some(value) = value {
+            overall_sum += value * category_weight;
+            overall_weight += category_weight;
+        }
+        score.categories.insert(
+            category.name.clone(),
+            CategoryScore {
+                score: value.map(round1),
+                graded,
+                weight: category_weight,
+            },
+        );
+    }
+
+    score.overall = (overall_weight > 0.0).then(|| round1(overall_sum / overall_weight));
+    score
+}
@@ -1,8 +1,11 @@
+use crate::checks::Mistyped;
+use crate::remediation::Remediation;
+use crate::score::HealthScore;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[allow(dead_code)]
+#[serde(rename_all = "lowercase")]
 pub enum Category {
     Latency,
     Cpu,
@@ -21,7 +24,29 @@ pub enum Category {
 }
 
 impl Category {
-    #[allow(dead_code)]
+    pub const ALL: [Category; 14] = [
+        Category::Latency,
+        Category::Cpu,
+        Category::Gpu,
+        Category::Memory,
+        Category::Storage,
+        Category::Network,
+        Category::Audio,
+        Category::Input,
+        Category::Stability,
+        Category::Services,
+        Category::Security,
+        Category::Platform,
+        Category::Thermal,
+        Category::Power,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Category> {
+        Category::ALL
+            .into_iter()
+            .find(|c| c.as_str().eq_ignore_ascii_case(name))
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Category::Latency => "latency",
@@ -41,7 +66,6 @@ impl Category {
         }
     }
 
-    #[allow(dead_code)]
     pub fn display_name(&self) -> &'static str {
         match self {
             Category::Latency => "Latency",
@@ -62,73 +86,189 @@ impl Category {
     }
 }
 
-#[derive(thiserror::Error, Debug)]
-#[allow(dead_code)]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckError {
     #[error("Registry: {0}")]
     Registry(String),
     #[error("WMI: {0}")]
     Wmi(String),
+    #[error("Service: {0}")]
+    Service(String),
     #[error("Privilege: {0}")]
     Privilege(String),
     #[error("Timeout")]
     Timeout,
     #[error("IO: {0}")]
-    Io(#[from] std::io::Error),
+    Io(String),
+}
+
+impl From<std::io::Error> for CheckError {
+    fn from(err: std::io::Error) -> Self {
+        CheckError::Io(err.to_string())
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckStatus {
+    #[serde(alias = "optimal")]
     Optimal,
+    #[serde(alias = "warning")]
     Warning,
+    #[serde(alias = "issue")]
     Issue,
+    #[serde(alias = "info")]
     Info,
+    /// Nothing was measured, e.g. a placeholder value.
+    #[serde(alias = "unknown")]
+    Unknown,
+    /// The data source could not be read.
+    Error(CheckError),
+}
+
+impl CheckStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Optimal => "Optimal",
+            CheckStatus::Warning => "Warning",
+            CheckStatus::Issue => "Issue",
+            CheckStatus::Info => "Info",
+            CheckStatus::Unknown => "Unknown",
+            CheckStatus::Error(_) => "Error",
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, CheckStatus::Error(_))
+    }
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Error(err) => write!(f, "Error ({})", err),
+            other => f.write_str(other.label()),
+        }
+    }
+}
+
+/// Where a check's value came from. `Static` marks hardcoded placeholders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CheckSource {
+    Registry,
+    #[serde(rename = "WMI")]
+    Wmi,
+    #[serde(rename = "DXGI")]
+    Dxgi,
+    ServiceManager,
+    #[default]
+    Static,
+}
+
+impl CheckSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckSource::Registry => "Registry",
+            CheckSource::Wmi => "WMI",
+            CheckSource::Dxgi => "DXGI",
+            CheckSource::ServiceManager => "ServiceManager",
+            CheckSource::Static => "Static",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Check {
+    /// Stable dotted ID such as `gpu.hags`; see `checks::catalog`.
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub value: String,
     pub expected: Option<String>,
     pub status: CheckStatus,
+    #[serde(default)]
+    pub source: CheckSource,
     pub description: String,
     pub severity: u8,
-    pub fix_cmd: Option<String>,
+    /// How to fix the check when it fails; see `remediation::apply_failed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+    /// How long evaluating the check took; see `Check::timed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<f64>,
 }
 
 impl Check {
     pub fn new(name: &str, value: &str, status: CheckStatus) -> Self {
         Self {
+            id: String::new(),
             name: name.to_string(),
             value: value.to_string(),
             expected: None,
             status,
+            source: CheckSource::Static,
             description: String::new(),
             severity: 5,
-            fix_cmd: None,
+            remediation: None,
+            elapsed_ms: None,
         }
     }
 
+    /// Evaluates a check and records how long it took.
+    pub fn timed(check: impl FnOnce() -> Check) -> Check {
+        let start = std::time::Instant::now();
+        let mut check = check();
+        check.elapsed_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+        check
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
     pub fn with_description(mut self, desc: &str) -> Self {
         self.description = desc.to_string();
         self
     }
 
-    #[allow(dead_code)]
+    pub fn with_source(mut self, source: CheckSource) -> Self {
+        self.source = source;
+        self
+    }
+
     pub fn with_expected(mut self, expected: &str) -> Self {
         self.expected = Some(expected.to_string());
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_severity(mut self, severity: u8) -> Self {
         self.severity = severity.min(10);
         self
     }
 
-    #[allow(dead_code)]
-    pub fn with_fix_cmd(mut self, cmd: &str) -> Self {
-        self.fix_cmd = Some(cmd.to_string());
+    pub fn with_remediation(mut self, remediation: Remediation) -> Self {
+        self.remediation = Some(remediation);
+        self
+    }
+
+    /// Marks a check whose data source could not be read, so the value it
+    /// fell back to never passes for a measurement.
+    pub fn with_error(mut self, error: Option<CheckError>) -> Self {
+        if let Some(error) = error {
+            self.value = "Unavailable".to_string();
+            self.status = CheckStatus::Error(error);
+        }
+        self
+    }
+
+    /// Reports a value stored with the wrong type in place of whatever the
+    /// check concluded from it being unreadable. Windows ignores such values,
+    /// so the check never passes.
+    pub fn with_mistyped(mut self, mistyped: Option<Mistyped>) -> Self {
+        if let Some(mistyped) = mistyped {
+            self.value = mistyped.to_string();
+            self.status = CheckStatus::Warning;
+        }
         self
     }
 }
@@ -156,6 +296,12 @@ impl CategoryResults {
 pub struct AuditResults {
     pub categories: HashMap<String, CategoryResults>,
     pub timestamp: String,
+    /// Filled in once every category has run; see `score::compute`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<HealthScore>,
+    /// The profile the audit was judged against, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 impl Default for AuditResults {
@@ -169,6 +315,8 @@ impl AuditResults {
         Self {
             categories: HashMap::new(),
             timestamp: chrono::Local::now().to_rfc3339(),
+            score: None,
+            profile: None,
         }
     }
 
@@ -180,11 +328,22 @@ impl AuditResults {
         self.categories.values().map(|c| c.checks.len()).sum()
     }
 
+    /// Counts checks with the given status. `Error` statuses match whatever
+    /// error they carry.
     pub fn count_status(&self, status: CheckStatus) -> usize {
+        let wanted = std::mem::discriminant(&status);
+        self.categories
+            .values()
+            .flat_map(|c| &c.checks)
+            .filter(|check| std::mem::discriminant(&check.status) == wanted)
+            .count()
+    }
+
+    pub fn count_errors(&self) -> usize {
         self.categories
             .values()
             .flat_map(|c| &c.checks)
-            .filter(|check| check.status == status)
+            .filter(|check| check.status.is_error())
             .count()
     }
 }
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_export_walks_roots_and_touched_keys() {
+        use windows_optimizer::backup::export_reg;
+        use windows_optimizer::checks::{
+            MemoryRegistry, RegValue, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+        };
+
+        let power = r"SYSTEM\CurrentControlSet\Control\Power";
+        let reg = MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, power, "HibernateEnabled", 0)
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                &format!(r"{}\PowerSettings\Sub", power),
+                "Attributes",
+                RegValue::Qword(2),
+            )
+            .with_string(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed", "0")
+            .with_string(
+                HKEY_CURRENT_USER,
+                r"Control Panel\Mouse\Nested",
+                "Skipped",
+                "x",
+            );
+
+        let roots = vec![(HKEY_LOCAL_MACHINE, power.to_string())];
+        let touched = vec![
+            (HKEY_LOCAL_MACHINE, format!(r"{}\PowerSettings", power)),
+            (HKEY_CURRENT_USER, r"Control Panel\Mouse".to_string()),
+            (HKEY_CURRENT_USER, r"Software\Missing".to_string()),
+        ];
+        let (file, stats) = export_reg(&reg, &roots, &touched);
+        let text = file.to_text();
+
+        assert!(text.starts_with("Windows Registry Editor Version 5.00\r\n"));
+        assert!(text.contains(&format!(
+            "[HKEY_LOCAL_MACHINE\\{}]\r\n\"HibernateEnabled\"=dword:00000000\r\n",
+            power
+        )));
+        assert!(text.contains(&format!(
+            "[HKEY_LOCAL_MACHINE\\{}\\PowerSettings\\Sub]\r\n\"Attributes\"=hex(b):02,00,00,00,00,00,00,00\r\n",
+            power
+        )));
+        assert!(
+            text.contains("[HKEY_CURRENT_USER\\Control Panel\\Mouse]\r\n\"MouseSpeed\"=\"0\"\r\n")
+        );
+        assert!(!text.contains("Skipped"));
+        // PowerSettings is inside the Power root and is written only once
+        assert_eq!(text.matches("\\PowerSettings]").count(), 1);
+        assert_eq!(
+            stats,
+            windows_optimizer::backup::BackupStats {
+                keys: 4,
+                values: 3,
+                missing: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_touched_keys_come_from_checks() {
+        use std::sync::Arc;
+        use windows_optimizer::backup::keys_touched_by_checks;
+        use windows_optimizer::checks::{CheckContext, MemoryRegistry, HKEY_LOCAL_MACHINE};
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new()));
+        let touched = keys_touched_by_checks(&ctx);
+
+        assert!(touched.contains(&(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile".to_string()
+        )));
+        let mut sorted = touched.clone();
+        sorted.dedup_by(|a, b| a.0 == b.0 && a.1.eq_ignore_ascii_case(&b.1));
+        assert_eq!(sorted.len(), touched.len());
+    }
+
+    #[test]
+    fn test_backup_records_its_origin() {
+        use windows_optimizer::backup::{export_reg, BackupOrigin};
+        use windows_optimizer::checks::{MemoryRegistry, HKEY_LOCAL_MACHINE};
+        use windows_optimizer::regfile::RegFile;
+
+        let machine = |name: &str, build: &str, ubr: u32| {
+            MemoryRegistry::new()
+                .with_string(
+                    HKEY_LOCAL_MACHINE,
+                    r"SYSTEM\CurrentControlSet\Control\ComputerName\ComputerName",
+                    "ComputerName",
+                    name,
+                )
+                .with_string(
+                    HKEY_LOCAL_MACHINE,
+                    r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+                    "CurrentBuild",
+                    build,
+                )
+                .with_dword(
+                    HKEY_LOCAL_MACHINE,
+                    r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+                    "UBR",
+                    ubr,
+                )
+        };
+        let (file, _) = export_reg(&machine("STUDIO-01", "22631", 4317), &[], &[]);
+        let text = file.to_text();
+        assert!(text.contains("; Machine: STUDIO-01\r\n"));
+        assert!(text.contains("; Windows build: 22631.4317\r\n"));
+
+        let origin = BackupOrigin::from_comments(&RegFile::parse(&text).unwrap().comments);
+        assert_eq!(origin.build.as_deref(), Some("22631.4317"));
+
+        // A later cumulative update of the same build is fine.
+        let live = |name, build, ubr| BackupOrigin::read(&machine(name, build, ubr));
+        assert!(origin
+            .mismatches(&live("studio-01", "22631", 4460))
+            .is_empty());
+        let problems = origin.mismatches(&live("GAMING-02", "26100", 2033));
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("STUDIO-01"), "{:?}", problems);
+        assert!(problems[1].contains("26100"), "{:?}", problems);
+        // Backups from before origins were recorded cannot be verified.
+        assert_eq!(
+            BackupOrigin::default()
+                .mismatches(&live("STUDIO-01", "22631", 4317))
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_restore_diff_and_filters() {
+        use windows_optimizer::backup::{diff_backup, restore_file, RestoreFilter};
+        use windows_optimizer::checks::{MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE};
+        use windows_optimizer::regfile::RegFile;
+
+        let profile = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile";
+        let graphics = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+        let backup = RegFile::parse(&format!(
+            "Windows Registry Editor Version 5.00\r\n\r\n\
+             [HKEY_LOCAL_MACHINE\\{}]\r\n\
+             \"SystemResponsiveness\"=dword:00000014\r\n\
+             \"NetworkThrottlingIndex\"=dword:0000000a\r\n\r\n\
+             [HKEY_LOCAL_MACHINE\\{}]\r\n\
+             \"HwSchMode\"=dword:00000001\r\n\r\n\
+             [-HKEY_LOCAL_MACHINE\\SOFTWARE\\Tweaks]\r\n",
+            profile, graphics
+        ))
+        .unwrap();
+        let live = MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, profile, "SystemResponsiveness", 10)
+            .with_dword(HKEY_LOCAL_MACHINE, profile, "NetworkThrottlingIndex", 10)
+            .with_dword(HKEY_LOCAL_MACHINE, profile, "Added", 1)
+            .with_dword(HKEY_LOCAL_MACHINE, graphics, "HwSchMode", 2)
+            .with_dword(HKEY_LOCAL_MACHINE, r"SOFTWARE\Tweaks", "Boost", 1);
+
+        let changes = diff_backup(&backup, &live);
+        let summary: Vec<(Option<&str>, Option<&RegValue>, Option<&RegValue>)> = changes
+            .iter()
+            .map(|c| (c.name.as_deref(), c.current.as_ref(), c.restored.as_ref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Some("SystemResponsiveness"),
+                    Some(&RegValue::Dword(10)),
+                    Some(&RegValue::Dword(20))
+                ),
+                // Added after the backup, so restoring deletes it.
+                (Some("Added"), Some(&RegValue::Dword(1)), None),
+                (
+                    Some("HwSchMode"),
+                    Some(&RegValue::Dword(2)),
+                    Some(&RegValue::Dword(1))
+                ),
+                (None, None, None),
+            ]
+        );
+        assert_eq!(changes[0].checks, ["latency.system_responsiveness"]);
+        assert_eq!(changes[2].checks, ["gpu.hags"]);
+
+        let select = |only: &[&str], exclude: &[&str]| {
+            let strings = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            let filter = RestoreFilter::new(&strings(only), &strings(exclude));
+            changes
+                .iter()
+                .filter(|c| filter.selects(c))
+                .map(|c| c.name.clone().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(select(&[], &[]).len(), 4);
+        assert_eq!(select(&["gpu.*"], &[]), ["HwSchMode"]);
+        assert_eq!(
+            select(&[r"hklm\software\microsoft\*\SystemProfile"], &[]),
+            ["SystemResponsiveness", "Added"]
+        );
+        assert_eq!(
+            select(
+                &[r"HKLM\SOFTWARE\*"],
+                &["latency.system_responsiveness", "*\\Tweaks"]
+            ),
+            ["Added"]
+        );
+        assert_eq!(select(&[r"*\HwSchMode"], &[]), ["HwSchMode"]);
+
+        let selected: Vec<_> = changes.iter().collect();
+        let text = restore_file(&selected).to_text();
+        assert!(text.contains(&format!(
+            "[HKEY_LOCAL_MACHINE\\{}]\r\n\"SystemResponsiveness\"=dword:00000014\r\n\"Added\"=-\r\n",
+            profile
+        )));
+        assert!(text.contains("[-HKEY_LOCAL_MACHINE\\SOFTWARE\\Tweaks]"));
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_catalog_ids_are_unique_and_prefixed() {
+        use std::collections::HashSet;
+        use windows_optimizer::checks::catalog;
+
+        let mut seen = HashSet::new();
+        for meta in catalog() {
+            assert!(seen.insert(meta.id), "duplicate id {}", meta.id);
+            let (prefix, rest) = meta.id.split_once('.').unwrap();
+            assert_eq!(prefix, meta.category.as_str(), "{}", meta.id);
+            assert!(
+                !rest.is_empty()
+                    && rest
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+                "malformed id {}",
+                meta.id
+            );
+            assert!(meta.severity <= 10);
+            // Only placeholders that read nothing go without docs.
+            match meta.docs {
+                Some(docs) => assert!(!docs.is_empty(), "empty docs on {}", meta.id),
+                None => assert!(meta.sources.is_empty(), "{} has no docs", meta.id),
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_check_result_is_in_the_catalog() {
+        use std::collections::HashSet;
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            catalog, find_check, run_category, CheckContext, MemoryRegistry,
+        };
+        use windows_optimizer::types::Category;
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new()));
+        let mut produced = HashSet::new();
+        for category in Category::ALL {
+            for check in run_category(&ctx, category).checks {
+                let meta = find_check(&check.id).unwrap_or_else(|| {
+                    panic!("'{}' ({}) is not in the catalog", check.name, check.id)
+                });
+                assert_eq!(meta.name, check.name);
+                assert_eq!(meta.category, category);
+                assert_eq!(meta.severity, check.severity);
+                assert!(
+                    produced.insert(check.id.clone()),
+                    "{} reported twice",
+                    check.id
+                );
+            }
+        }
+
+        let stale: Vec<&str> = catalog()
+            .iter()
+            .map(|meta| meta.id)
+            .filter(|id| !produced.contains(*id))
+            .collect();
+        assert!(
+            stale.is_empty(),
+            "catalog entries with no check: {:?}",
+            stale
+        );
+    }
+
+    #[test]
+    fn test_repeated_names_get_distinct_ids() {
+        use windows_optimizer::checks::{find_check, DataSource, HKEY_LOCAL_MACHINE};
+
+        let input = find_check("input.usb_selective_suspend").unwrap();
+        let power = find_check("power.usb_selective_suspend").unwrap();
+        assert_eq!(input.name, power.name);
+        assert_ne!(input.category, power.category);
+
+        let hags = find_check("gpu.hags").unwrap();
+        assert_eq!(
+            hags.sources,
+            &[DataSource::Registry {
+                hkey: HKEY_LOCAL_MACHINE,
+                path: r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+                value: Some("HwSchMode"),
+            }]
+        );
+        assert!(find_check("latency.dynamic_tick").is_some());
+    }
+
+    #[test]
+    fn test_placeholders_are_never_optimal() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_category, CheckContext, MemoryRegistry, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::{
+            AuditResults, Category, CheckError, CheckSource, CheckStatus,
+        };
+
+        let registry = MemoryRegistry::new().with_dword(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "HwSchMode",
+            2,
+        );
+        let ctx = CheckContext::live().with_registry(Arc::new(registry));
+        let gpu = run_category(&ctx, Category::Gpu);
+        let find = |id: &str| gpu.checks.iter().find(|c| c.id == id).unwrap();
+
+        let hags = find("gpu.hags");
+        assert_eq!(hags.status, CheckStatus::Optimal);
+        assert_eq!(hags.source, CheckSource::Registry);
+
+        let link = find("gpu.pcie_link_speed");
+        assert_eq!(link.source, CheckSource::Static);
+        assert_eq!(link.status, CheckStatus::Unknown);
+        assert!(gpu
+            .checks
+            .iter()
+            .all(|c| c.source != CheckSource::Static || c.status != CheckStatus::Optimal));
+
+        let mut results = AuditResults::new();
+        let mut failed = gpu.clone();
+        failed.checks[0].status = CheckStatus::Error(CheckError::Wmi("access denied".into()));
+        results.add_category(failed);
+        assert_eq!(
+            results.count_status(CheckStatus::Error(CheckError::Timeout)),
+            1
+        );
+        assert_eq!(results.count_errors(), 1);
+
+        let json = serde_json::to_string(&results).unwrap();
+        let back: AuditResults = serde_json::from_str(&json).unwrap();
+        let check = &back.categories["GPU"].checks[0];
+        assert_eq!(
+            check.status,
+            CheckStatus::Error(CheckError::Wmi("access denied".into()))
+        );
+        assert_eq!(check.status.to_string(), "Error (WMI: access denied)");
+    }
+}
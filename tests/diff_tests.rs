@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::types::{AuditResults, CategoryResults, Check, CheckStatus};
+
+    fn report(checks: &[(&str, &str, CheckStatus)]) -> AuditResults {
+        let mut results = AuditResults::new();
+        let mut gpu = CategoryResults::new("GPU");
+        for (id, value, status) in checks {
+            gpu.add_check(Check::new(id, value, status.clone()).with_id(id));
+        }
+        results.add_category(gpu);
+        results
+    }
+
+    #[test]
+    fn test_diff_matches_checks_by_id() {
+        use std::path::Path;
+        use windows_optimizer::diff;
+
+        let old = report(&[
+            ("gpu.hags", "Enabled", CheckStatus::Optimal),
+            ("gpu.tdr_delay", "2", CheckStatus::Issue),
+            ("gpu.driver_version", "31.0.15.5222", CheckStatus::Info),
+            ("gpu.mpo", "Disabled", CheckStatus::Optimal),
+            ("gpu.game_dvr", "0", CheckStatus::Optimal),
+        ]);
+        let new = report(&[
+            ("gpu.hags", "Disabled", CheckStatus::Warning),
+            ("gpu.tdr_delay", "8", CheckStatus::Optimal),
+            ("gpu.driver_version", "32.0.15.6094", CheckStatus::Info),
+            ("gpu.mpo", "Disabled", CheckStatus::Optimal),
+            ("gpu.game_bar", "1", CheckStatus::Warning),
+        ]);
+
+        let diff = diff::compare(&old, &new, (Path::new("old.json"), Path::new("new.json")));
+        let ids = |changes: &[diff::CheckChange]| -> Vec<String> {
+            changes.iter().map(|c| c.check_id.clone()).collect()
+        };
+        assert_eq!(ids(&diff.regressions), ["gpu.hags"]);
+        assert_eq!(diff.regressions[0].old.status, "Optimal");
+        assert_eq!(diff.regressions[0].new.value, "Disabled");
+        assert_eq!(ids(&diff.improvements), ["gpu.tdr_delay"]);
+        // The driver update is informational, but still a change.
+        assert_eq!(ids(&diff.changed), ["gpu.driver_version"]);
+        assert_eq!(diff.added[0].check_id, "gpu.game_bar");
+        assert_eq!(diff.removed[0].check_id, "gpu.game_dvr");
+        assert_eq!(diff.unchanged, 1);
+        assert!(!diff.is_empty());
+        assert!(diff::compare(&new, &new, (Path::new("a"), Path::new("b"))).is_empty());
+    }
+
+    #[test]
+    fn test_diff_loads_saved_reports_and_exports_html() {
+        use windows_optimizer::diff;
+        use windows_optimizer::report::{export_diff_html, export_json};
+
+        let dir = std::env::temp_dir().join(format!("wo_diff_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old_path = dir.join("old.json");
+        let new_path = dir.join("new.json");
+        let html_path = dir.join("diff.html");
+        export_json(
+            &report(&[("gpu.hags", "Enabled", CheckStatus::Optimal)]),
+            old_path.to_str().unwrap(),
+        )
+        .unwrap();
+        export_json(
+            &report(&[("gpu.hags", "<Disabled>", CheckStatus::Issue)]),
+            new_path.to_str().unwrap(),
+        )
+        .unwrap();
+
+        let old = diff::load(&old_path).unwrap();
+        let new = diff::load(&new_path).unwrap();
+        let diff = diff::compare(&old, &new, (&old_path, &new_path));
+        assert_eq!(diff.regressions.len(), 1);
+
+        export_diff_html(&diff, html_path.to_str().unwrap()).unwrap();
+        let html = std::fs::read_to_string(&html_path).unwrap();
+        assert!(html.contains(".summary-card"), "reuses the report styles");
+        assert!(html.contains("Regressions (1)"));
+        assert!(html.contains("&lt;Disabled&gt;"));
+        assert!(diff::load(&html_path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
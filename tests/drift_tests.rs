@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::remediation::{MemorySystem, Remediation};
+    use windows_optimizer::types::{Check, CheckStatus};
+
+    const GRAPHICS: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+    const SYSTEM_PROFILE: &str =
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile";
+
+    fn fix(id: &str, remediation: Remediation) -> Check {
+        Check::new(id, "", CheckStatus::Issue)
+            .with_id(id)
+            .with_remediation(remediation)
+    }
+
+    #[test]
+    fn test_drift_against_a_session() {
+        use chrono::TimeZone;
+        use windows_optimizer::drift;
+        use windows_optimizer::history::History;
+        use windows_optimizer::journal::{self, Journal};
+        use windows_optimizer::remediation::SystemWriter;
+
+        let dir = std::env::temp_dir().join(format!("wo_drift_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let history = History::open(&dir);
+        let journal_path = dir.join("apply-journal.json");
+        let updated = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 14, 3, 12, 0)
+            .unwrap();
+        let system = MemorySystem::new(
+            MemoryRegistry::new()
+                .with_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "HwSchMode", 1)
+                .with_last_write(HKEY_LOCAL_MACHINE, GRAPHICS, updated),
+        )
+        .with_power_setting("SCHEME_CURRENT", "SUB_USB", "USBSELECTIVE", 1, 1);
+
+        let run = |checks: Vec<Check>| {
+            let plan: Vec<&Check> = checks.iter().collect();
+            let report =
+                journal::apply_session(Journal::new(), &plan, &system, &journal_path).unwrap();
+            history.record(report.journal).unwrap()
+        };
+        let gaming = run(vec![
+            fix(
+                "gpu.hags",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "HwSchMode", 2),
+            ),
+            fix(
+                "latency.system_responsiveness",
+                Remediation::set_dword(
+                    HKEY_LOCAL_MACHINE,
+                    SYSTEM_PROFILE,
+                    "SystemResponsiveness",
+                    10,
+                ),
+            ),
+            fix(
+                "power.usb_selective_suspend",
+                Remediation::power_setting("SUB_USB", "USBSELECTIVE", 0, 0),
+            ),
+        ]);
+        // A later session deliberately changes one of the same values.
+        run(vec![fix(
+            "latency.system_responsiveness",
+            Remediation::set_dword(
+                HKEY_LOCAL_MACHINE,
+                SYSTEM_PROFILE,
+                "SystemResponsiveness",
+                20,
+            ),
+        )]);
+        // A driver update resets HAGS.
+        system
+            .set_registry_value(
+                HKEY_LOCAL_MACHINE,
+                GRAPHICS,
+                "HwSchMode",
+                &RegValue::Dword(1),
+            )
+            .unwrap();
+
+        let (session, expected) = drift::session_baseline(&history, Some(&gaming)).unwrap();
+        assert_eq!(session.session, gaming);
+        assert_eq!(expected.len(), 2, "the superseded value is not expected");
+        let report = drift::detect("gaming", &expected, &system);
+        assert_eq!(report.drifted.len(), 1);
+        let hags = &report.drifted[0];
+        assert_eq!(hags.check_id, "gpu.hags");
+        assert_eq!(hags.current, "1 (0x00000001)");
+        assert_eq!(hags.expected, "2 (0x00000002)");
+        assert_eq!(
+            hags.last_write.as_deref(),
+            Some(updated.to_rfc3339().as_str())
+        );
+        assert!(report.unverified.is_empty());
+
+        // The latest session has not drifted.
+        let (_, latest) = drift::session_baseline(&history, None).unwrap();
+        assert!(drift::detect("latest", &latest, &system).drifted.is_empty());
+
+        // Re-applying only the drifted items brings the session back.
+        let fixes = report.fixes();
+        assert_eq!(fixes.len(), 1);
+        run(fixes);
+        assert!(drift::detect("gaming", &expected, &system)
+            .drifted
+            .is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_drift_against_a_profile() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{run_category, CheckContext};
+        use windows_optimizer::drift;
+        use windows_optimizer::profile::ProfileSet;
+        use windows_optimizer::types::{AuditResults, Category};
+
+        let registry = MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "HwSchMode", 2)
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                SYSTEM_PROFILE,
+                "SystemResponsiveness",
+                20,
+            );
+        let ctx = CheckContext::live().with_registry(Arc::new(registry));
+        let mut results = AuditResults::new();
+        for category in [Category::Gpu, Category::Latency, Category::Power] {
+            results.add_category(run_category(&ctx, category));
+        }
+        let gaming = ProfileSet::builtin().resolve("gaming").unwrap();
+        gaming.judge(&mut results, &ctx);
+
+        let targets: Vec<&String> = gaming.targets.keys().collect();
+        let expected = drift::profile_baseline(&results, &targets);
+        let report = drift::detect("gaming profile", &expected, &ctx);
+
+        let drifted: Vec<&str> = report.drifted.iter().map(|d| d.check_id.as_str()).collect();
+        assert!(drifted.contains(&"latency.system_responsiveness"));
+        assert!(!drifted.contains(&"gpu.hags"));
+        // Offline, the power scheme cannot be read back.
+        assert!(report
+            .unverified
+            .iter()
+            .any(|target| target.starts_with("power ")));
+    }
+}
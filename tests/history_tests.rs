@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::history::History;
+    use windows_optimizer::journal::{self, Journal};
+    use windows_optimizer::remediation::{MemorySystem, Remediation, SystemReader, SystemWriter};
+    use windows_optimizer::types::{Check, CheckStatus};
+
+    const KEY: &str = r"SOFTWARE\Windows-Optimizer\Test";
+
+    fn scratch(name: &str) -> (History, std::path::PathBuf, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("wo_history_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        (History::open(&dir), dir.join("apply-journal.json"), dir)
+    }
+
+    fn system() -> MemorySystem {
+        MemorySystem::new(
+            MemoryRegistry::new()
+                .with_dword(HKEY_LOCAL_MACHINE, KEY, "First", 7)
+                .with_dword(HKEY_LOCAL_MACHINE, KEY, "Second", 7),
+        )
+    }
+
+    /// Applies one fix per `(value name, data)` and archives the session.
+    fn session(
+        history: &History,
+        system: &MemorySystem,
+        journal_path: &std::path::Path,
+        values: &[(&str, u32)],
+    ) -> String {
+        let checks: Vec<Check> = values
+            .iter()
+            .map(|(name, data)| {
+                Check::new(name, "", CheckStatus::Issue)
+                    .with_id(&format!("test.{}", name.to_lowercase()))
+                    .with_remediation(Remediation::set_dword(HKEY_LOCAL_MACHINE, KEY, name, *data))
+            })
+            .collect();
+        let plan: Vec<&Check> = checks.iter().collect();
+        let report = journal::apply_session(Journal::new(), &plan, system, journal_path).unwrap();
+        assert!(report.committed());
+        history.record(report.journal).unwrap()
+    }
+
+    fn read(system: &MemorySystem, name: &str) -> Option<RegValue> {
+        system.read_registry_value(HKEY_LOCAL_MACHINE, KEY, name)
+    }
+
+    #[test]
+    fn test_undo_any_session() {
+        use windows_optimizer::history::UndoOutcome;
+        use windows_optimizer::journal::Outcome;
+
+        let (history, journal_path, dir) = scratch("undo");
+        let system = system();
+        let first = session(&history, &system, &journal_path, &[("First", 1)]);
+        let second = session(
+            &history,
+            &system,
+            &journal_path,
+            &[("Second", 1), ("Third", 1)],
+        );
+        assert_ne!(first, second, "sessions in the same second get a suffix");
+
+        let sessions = history.sessions().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session, first);
+        assert_eq!(sessions[1].outcome, Some(Outcome::Committed));
+
+        // Undoing the older session leaves the newer one alone.
+        let UndoOutcome::Done(report) = history.undo(&first, &system, &journal_path).unwrap()
+        else {
+            panic!("unexpected conflict");
+        };
+        assert!(report.committed());
+        assert_eq!(read(&system, "First"), Some(RegValue::Dword(7)));
+        assert_eq!(read(&system, "Second"), Some(RegValue::Dword(1)));
+
+        let undone = history.get(&first).unwrap();
+        assert_eq!(undone.undone_by.as_deref(), Some(report.session.as_str()));
+        assert_eq!(
+            history.get(&report.session).unwrap().undoes.as_deref(),
+            Some(first.as_str())
+        );
+        let err = history.undo(&first, &system, &journal_path).unwrap_err();
+        assert!(err.to_string().contains("already undone"), "{}", err);
+
+        // A value the session created is deleted again.
+        history.undo(&second, &system, &journal_path).unwrap();
+        assert_eq!(read(&system, "Second"), Some(RegValue::Dword(7)));
+        assert_eq!(read(&system, "Third"), None);
+        assert_eq!(history.sessions().unwrap().len(), 4);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sessions_order_by_instant() {
+        let (history, journal_path, dir) = scratch("dst");
+        let system = system();
+        let first = session(&history, &system, &journal_path, &[("First", 1)]);
+        let second = session(&history, &system, &journal_path, &[("First", 2)]);
+
+        // The clocks went back between the two: the later session's local
+        // timestamp sorts before the earlier one's as text.
+        for (id, started) in [
+            (&first, "2026-10-25T02:30:00+02:00"),
+            (&second, "2026-10-25T02:10:00+01:00"),
+        ] {
+            let mut journal = history.get(id).unwrap();
+            journal.started = started.to_string();
+            journal.save(&dir.join(format!("{}.json", id))).unwrap();
+        }
+
+        let sessions = history.sessions().unwrap();
+        assert_eq!(sessions[0].session, first);
+        assert_eq!(sessions[1].session, second);
+        let later = history
+            .later_sessions(&history.get(&first).unwrap())
+            .unwrap();
+        assert_eq!(later.len(), 1);
+        assert_eq!(later[0].session, second);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_undo_refuses_later_session() {
+        use windows_optimizer::history::UndoOutcome;
+
+        let (history, journal_path, dir) = scratch("later");
+        let system = system();
+        let first = session(
+            &history,
+            &system,
+            &journal_path,
+            &[("First", 1), ("Second", 1)],
+        );
+        let second = session(&history, &system, &journal_path, &[("First", 2)]);
+
+        let UndoOutcome::Conflicts(conflicts) =
+            history.undo(&first, &system, &journal_path).unwrap()
+        else {
+            panic!("undo should have refused");
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].check_id, "test.first");
+        assert_eq!(conflicts[0].session.as_deref(), Some(second.as_str()));
+        // Nothing changed, not even the value without a conflict.
+        assert_eq!(read(&system, "First"), Some(RegValue::Dword(2)));
+        assert_eq!(read(&system, "Second"), Some(RegValue::Dword(1)));
+        assert!(history.get(&first).unwrap().undone_by.is_none());
+
+        // Once the later session is undone, the earlier one can be too.
+        history.undo(&second, &system, &journal_path).unwrap();
+        assert!(matches!(
+            history.undo(&first, &system, &journal_path).unwrap(),
+            UndoOutcome::Done(_)
+        ));
+        assert_eq!(read(&system, "First"), Some(RegValue::Dword(7)));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_undo_refuses_outside_change() {
+        use windows_optimizer::history::UndoOutcome;
+
+        let (history, journal_path, dir) = scratch("outside");
+        let system = system();
+        let first = session(&history, &system, &journal_path, &[("First", 1)]);
+        system
+            .set_registry_value(HKEY_LOCAL_MACHINE, KEY, "First", &RegValue::Dword(5))
+            .unwrap();
+
+        let UndoOutcome::Conflicts(conflicts) =
+            history.undo(&first, &system, &journal_path).unwrap()
+        else {
+            panic!("undo should have refused");
+        };
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].session, None);
+        assert_eq!(read(&system, "First"), Some(RegValue::Dword(5)));
+
+        let err = history
+            .undo("19700101-000000", &system, &journal_path)
+            .unwrap_err();
+        assert!(err.to_string().contains("No session"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -22,4 +22,461 @@ mod tests {
         let check = Check::new("Test", "Val", CheckStatus::Info).with_description("This is a test");
         assert_eq!(check.description, "This is a test");
     }
+
+    #[test]
+    fn test_memory_registry_drives_checks() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_gpu_checks, CheckContext, MemoryRegistry, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let reg = MemoryRegistry::new().with_dword(
+            HKEY_LOCAL_MACHINE,
+            r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+            "HwSchMode",
+            2,
+        );
+        let ctx = CheckContext::live().with_registry(Arc::new(reg));
+
+        let results = run_gpu_checks(&ctx);
+        let hags = results
+            .checks
+            .iter()
+            .find(|c| c.name.starts_with("HAGS"))
+            .unwrap();
+        assert_eq!(hags.value, "Enabled");
+        assert_eq!(hags.status, CheckStatus::Optimal);
+    }
+
+    #[test]
+    fn test_memory_registry_lookups_are_case_insensitive() {
+        use windows_optimizer::checks::{
+            MemoryRegistry, RegistrySource, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+        };
+
+        let reg = MemoryRegistry::new()
+            .with_string(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed", "0")
+            .with_dword(HKEY_LOCAL_MACHINE, r"SYSTEM\Select", "Current", 1);
+
+        assert_eq!(
+            reg.read_string(HKEY_CURRENT_USER, r"control panel\mouse", "mousespeed"),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            reg.read_dword(HKEY_LOCAL_MACHINE, r"SYSTEM\SELECT", "current"),
+            Some(1)
+        );
+        assert_eq!(
+            reg.read_string(HKEY_LOCAL_MACHINE, r"SYSTEM\Select", "Current"),
+            None
+        );
+        assert!(reg.key_exists(HKEY_CURRENT_USER, "Control Panel"));
+        assert!(!reg.key_exists(HKEY_LOCAL_MACHINE, "Control Panel"));
+    }
+
+    #[test]
+    fn test_latency_checks_from_captured_state() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_latency_checks, CheckContext, MemoryRegistry, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let profile = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile";
+        let reg = MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, profile, "SystemResponsiveness", 40)
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                profile,
+                "NetworkThrottlingIndex",
+                0xFFFFFFFF,
+            )
+            .with_string(
+                HKEY_LOCAL_MACHINE,
+                &format!(r"{}\Tasks\Games", profile),
+                "Scheduling Category",
+                "High",
+            );
+        let ctx = CheckContext::live().with_registry(Arc::new(reg));
+        let results = run_latency_checks(&ctx);
+        let status_of = |name: &str| {
+            results
+                .checks
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.status.clone())
+                .unwrap()
+        };
+
+        assert_eq!(
+            status_of("System Responsiveness (MMCSS)"),
+            CheckStatus::Issue
+        );
+        assert_eq!(status_of("Network Throttling Index"), CheckStatus::Optimal);
+        assert_eq!(
+            status_of("Scheduling Category (Games)"),
+            CheckStatus::Optimal
+        );
+        assert_eq!(status_of("System Profile Tasks"), CheckStatus::Info);
+    }
+
+    #[test]
+    fn test_every_category_runs_offline() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{run_category, CheckContext, MemoryRegistry};
+        use windows_optimizer::types::Category;
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new()));
+        for category in Category::ALL {
+            let results = run_category(&ctx, category);
+            assert_eq!(results.name, category.display_name());
+            assert!(
+                !results.checks.is_empty(),
+                "{} produced no checks",
+                results.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_wmi_replay_drives_checks() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_cpu_checks, run_memory_checks, CheckContext, ReplayWmi, WmiFixture,
+        };
+        use windows_optimizer::types::{CheckError, CheckStatus};
+
+        let fixture: WmiFixture = serde_json::from_str(
+            r#"{
+                "queries": {
+                    "SELECT Speed FROM Win32_PhysicalMemory": [
+                        { "Speed": 3600 }, { "Speed": 3600 }
+                    ],
+                    "SELECT * FROM Win32_PhysicalMemory": [
+                        { "Speed": 3600 }, { "Speed": 3600 }
+                    ],
+                    "SELECT TotalPhysicalMemory FROM Win32_ComputerSystem": [
+                        { "TotalPhysicalMemory": "34359738368" }
+                    ],
+                    "SELECT Name,NumberOfCores,NumberOfLogicalProcessors,MaxClockSpeed,CurrentClockSpeed,L2CacheSize,L3CacheSize,Architecture FROM Win32_Processor": [
+                        {
+                            "Name": "AMD Ryzen 7 5800X3D",
+                            "NumberOfCores": 8,
+                            "NumberOfLogicalProcessors": 16,
+                            "MaxClockSpeed": 3400,
+                            "CurrentClockSpeed": 3400,
+                            "L2CacheSize": 4096,
+                            "L3CacheSize": 98304,
+                            "Architecture": 9
+                        }
+                    ],
+                    "SELECT FreePhysicalMemory FROM Win32_OperatingSystem": null
+                }
+            }"#,
+        )
+        .unwrap();
+        let ctx = CheckContext::live().with_wmi(Arc::new(ReplayWmi::new(fixture)));
+
+        let memory = run_memory_checks(&ctx);
+        let value_of = |checks: &[windows_optimizer::types::Check], name: &str| {
+            checks
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.value.clone())
+                .unwrap()
+        };
+        assert_eq!(value_of(&memory.checks, "RAM Speed"), "3600 MHz");
+        assert_eq!(
+            value_of(&memory.checks, "RAM Channel Configuration"),
+            "Dual Channel"
+        );
+        assert_eq!(value_of(&memory.checks, "Total RAM"), "32.00 GB");
+
+        let cpu = run_cpu_checks(&ctx);
+        assert_eq!(value_of(&cpu.checks, "CPU Name"), "AMD Ryzen 7 5800X3D");
+        assert_eq!(value_of(&cpu.checks, "CPU Architecture"), "x64");
+        assert_eq!(value_of(&cpu.checks, "CPU Cores"), "8 cores");
+
+        // A failed query is an error, not a measured "Unknown".
+        let available = memory
+            .checks
+            .iter()
+            .find(|c| c.id == "memory.available_ram")
+            .unwrap();
+        assert_eq!(available.value, "Unavailable");
+        assert_eq!(
+            available.status,
+            CheckStatus::Error(CheckError::Wmi("Win32_OperatingSystem query failed".into()))
+        );
+        let channel = memory
+            .checks
+            .iter()
+            .find(|c| c.id == "memory.ram_channel")
+            .unwrap();
+        assert_eq!(channel.status, CheckStatus::Info);
+    }
+
+    #[test]
+    fn test_wmi_record_then_replay_round_trip() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            RecordingWmi, ReplayWmi, WmiFixture, WmiRow, WmiSource, WmiValue,
+        };
+
+        struct Canned;
+        impl WmiSource for Canned {
+            fn query(&self, wql: &str) -> Option<Vec<WmiRow>> {
+                if wql.contains("Win32_TemperatureProbe") {
+                    return None;
+                }
+                let mut row = WmiRow::new();
+                row.insert("Caption".into(), WmiValue::String("Windows 11 Pro".into()));
+                row.insert("BuildNumber".into(), WmiValue::String("22631".into()));
+                Some(vec![row])
+            }
+        }
+
+        let recorder = RecordingWmi::new(Arc::new(Canned));
+        let live_os = recorder.query("SELECT Caption FROM Win32_OperatingSystem");
+        let live_probe = recorder.query("SELECT * FROM Win32_TemperatureProbe");
+
+        let path = std::env::temp_dir().join(format!("wmi_fixture_{}.json", std::process::id()));
+        recorder.fixture().save(&path).unwrap();
+        let replay = ReplayWmi::new(WmiFixture::load(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            replay.query("SELECT Caption FROM Win32_OperatingSystem"),
+            live_os
+        );
+        assert_eq!(
+            replay.query("SELECT * FROM Win32_TemperatureProbe"),
+            live_probe
+        );
+        assert_eq!(replay.query("SELECT * FROM Win32_BIOS"), None);
+    }
+
+    #[test]
+    fn test_wmi_value_coercions() {
+        use windows_optimizer::checks::WmiValue;
+
+        let v: WmiValue = serde_json::from_str("\"17179869184\"").unwrap();
+        assert_eq!(v.as_u64(), Some(17179869184));
+        assert_eq!(v.as_u32(), None);
+        assert_eq!(WmiValue::Int(-1).as_u64(), None);
+        assert_eq!(WmiValue::UInt(2400).as_u32(), Some(2400));
+        assert_eq!(WmiValue::Null.as_str(), None);
+    }
+
+    #[test]
+    fn test_nagle_reads_each_interface() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_network_checks, CheckContext, MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let root = r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces";
+        let ethernet = format!(r"{}\{{4D36E972-0001}}", root);
+        let wifi = format!(r"{}\{{4D36E972-0002}}", root);
+        let nagle = |reg: MemoryRegistry| {
+            let ctx = CheckContext::live().with_registry(Arc::new(reg));
+            run_network_checks(&ctx)
+                .checks
+                .into_iter()
+                .find(|c| c.name == "Nagle Algorithm")
+                .unwrap()
+        };
+        let base = MemoryRegistry::new()
+            .with_string(HKEY_LOCAL_MACHINE, &ethernet, "DhcpIPAddress", "10.0.0.5")
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                &wifi,
+                "IPAddress",
+                RegValue::MultiString(vec!["192.168.1.20".into()]),
+            )
+            // Disconnected adapters do not count
+            .with_string(
+                HKEY_LOCAL_MACHINE,
+                &format!(r"{}\{{4D36E972-0003}}", root),
+                "DhcpIPAddress",
+                "0.0.0.0",
+            )
+            .with_dword(HKEY_LOCAL_MACHINE, &ethernet, "TcpAckFrequency", 1);
+
+        let partial = nagle(base.clone());
+        assert_eq!(partial.value, "Disabled on 1/2 interfaces");
+        assert_eq!(partial.status, CheckStatus::Warning);
+
+        let tuned = nagle(
+            base.clone()
+                .with_dword(HKEY_LOCAL_MACHINE, &wifi, "TcpAckFrequency", 1),
+        );
+        assert_eq!(tuned.value, "Disabled");
+        assert_eq!(tuned.status, CheckStatus::Optimal);
+
+        let mismatched = nagle(base.with_string(HKEY_LOCAL_MACHINE, &wifi, "TcpAckFrequency", "1"));
+        assert_eq!(
+            mismatched.value,
+            "TcpAckFrequency stored as REG_SZ on 1 interface(s)"
+        );
+        assert_eq!(mismatched.status, CheckStatus::Warning);
+    }
+
+    #[test]
+    fn test_page_file_reads_multi_string() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_memory_checks, CheckContext, MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE,
+        };
+
+        let mm = r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management";
+        let page_file = |value: RegValue| {
+            let reg =
+                MemoryRegistry::new().with_value(HKEY_LOCAL_MACHINE, mm, "PagingFiles", value);
+            let ctx = CheckContext::live().with_registry(Arc::new(reg));
+            run_memory_checks(&ctx)
+                .checks
+                .into_iter()
+                .find(|c| c.name == "Page File")
+                .map(|c| c.value)
+                .unwrap()
+        };
+
+        assert_eq!(
+            page_file(RegValue::MultiString(vec![
+                r"C:\pagefile.sys 0 0".into(),
+                r"D:\pagefile.sys 4096 8192".into(),
+            ])),
+            r"C:\pagefile.sys (system managed), D:\pagefile.sys 4096-8192 MB"
+        );
+        assert_eq!(
+            page_file(RegValue::MultiString(vec![r"?:\pagefile.sys".into()])),
+            "all drives (system managed)"
+        );
+        assert_eq!(page_file(RegValue::MultiString(vec![])), "Disabled");
+        assert_eq!(
+            page_file(RegValue::String(r"C:\pagefile.sys".into())),
+            "Stored as REG_SZ (expected REG_MULTI_SZ)"
+        );
+    }
+
+    #[test]
+    fn test_mistyped_values_are_reported() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_gpu_checks, run_input_checks, run_platform_checks, CheckContext, MemoryRegistry,
+            RegValue, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let graphics = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+        let find = |checks: Vec<windows_optimizer::types::Check>, id: &str| {
+            checks.into_iter().find(|c| c.id == id).unwrap()
+        };
+        let hags = |reg: MemoryRegistry| {
+            let ctx = CheckContext::live().with_registry(Arc::new(reg));
+            find(run_gpu_checks(&ctx).checks, "gpu.hags")
+        };
+
+        let mistyped =
+            hags(MemoryRegistry::new().with_string(HKEY_LOCAL_MACHINE, graphics, "HwSchMode", "2"));
+        assert_eq!(mistyped.value, "Stored as REG_SZ (expected REG_DWORD)");
+        assert_eq!(mistyped.status, CheckStatus::Warning);
+        assert!(mistyped.remediation.is_some(), "the fix rewrites the type");
+        let missing = hags(MemoryRegistry::new());
+        assert_eq!(missing.value, "Disabled");
+        let enabled =
+            hags(MemoryRegistry::new().with_dword(HKEY_LOCAL_MACHINE, graphics, "HwSchMode", 2));
+        assert_eq!(enabled.status, CheckStatus::Optimal);
+
+        // QWORD and binary values are read through their own typed lookups.
+        let reg = MemoryRegistry::new()
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+                "InstallTime",
+                RegValue::Qword(133_850_000_000_000_000),
+            )
+            .with_value(
+                HKEY_CURRENT_USER,
+                r"Control Panel\Desktop",
+                "UserPreferencesMask",
+                RegValue::Dword(0x2000),
+            );
+        let ctx = CheckContext::live().with_registry(Arc::new(reg));
+        let installed = find(run_platform_checks(&ctx).checks, "platform.install_date");
+        assert_eq!(installed.value, "2025-02-25");
+        let shadow = find(run_input_checks(&ctx).checks, "input.pointer_shadow");
+        assert_eq!(shadow.value, "Stored as REG_DWORD (expected REG_BINARY)");
+    }
+
+    #[test]
+    fn test_memory_registry_enumeration_and_timestamps() {
+        use chrono::{TimeZone, Utc};
+        use windows_optimizer::checks::{
+            MemoryRegistry, RegValue, RegistrySource, HKEY_CURRENT_USER,
+        };
+
+        let stamp = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let reg = MemoryRegistry::new()
+            .with_dword(HKEY_CURRENT_USER, r"Software\Vendor\App", "Level", 3)
+            .with_value(
+                HKEY_CURRENT_USER,
+                r"Software\Vendor\App",
+                "Blob",
+                RegValue::Binary(vec![1, 2]),
+            )
+            .with_key(HKEY_CURRENT_USER, r"Software\Vendor\Tool\Sub")
+            .with_last_write(HKEY_CURRENT_USER, r"Software\Vendor\App", stamp);
+
+        assert_eq!(
+            reg.subkeys(HKEY_CURRENT_USER, r"software\vendor"),
+            Some(vec!["App".to_string(), "Tool".to_string()])
+        );
+        assert_eq!(
+            reg.values(HKEY_CURRENT_USER, r"Software\Vendor\App"),
+            Some(vec![
+                ("Level".to_string(), RegValue::Dword(3)),
+                ("Blob".to_string(), RegValue::Binary(vec![1, 2])),
+            ])
+        );
+        assert_eq!(
+            reg.values(HKEY_CURRENT_USER, r"Software\Vendor\Tool"),
+            Some(vec![])
+        );
+        assert_eq!(reg.subkeys(HKEY_CURRENT_USER, r"Software\Missing"), None);
+        assert_eq!(
+            reg.last_write_time(HKEY_CURRENT_USER, r"Software\Vendor\App"),
+            Some(stamp)
+        );
+        assert_eq!(
+            reg.last_write_time(HKEY_CURRENT_USER, r"Software\Vendor"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reg_value_raw_round_trip() {
+        use windows_optimizer::checks::RegValue;
+
+        let values = [
+            RegValue::String("text".into()),
+            RegValue::ExpandString("%TEMP%".into()),
+            RegValue::MultiString(vec!["a".into(), "bc".into()]),
+            RegValue::Dword(7),
+            RegValue::Qword(1 << 40),
+            RegValue::Binary(vec![0, 255]),
+            RegValue::Other(8, vec![1]),
+        ];
+        for value in values {
+            assert_eq!(RegValue::from_raw(value.kind(), &value.to_raw()), value);
+        }
+        // A DWORD with the wrong length is kept verbatim
+        assert_eq!(
+            RegValue::from_raw(4, &[1, 2]),
+            RegValue::Other(4, vec![1, 2])
+        );
+    }
 }
@@ -0,0 +1,310 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{Hkey, MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::remediation::{
+        ApplyError, MemorySystem, Remediation, StartType, SystemReader, SystemWriter,
+    };
+    use windows_optimizer::types::{Check, CheckStatus};
+
+    const KEY: &str = r"SOFTWARE\Windows-Optimizer\Test";
+
+    fn fix(id: &str, remediation: Remediation) -> Check {
+        Check::new(id, "value", CheckStatus::Issue)
+            .with_id(id)
+            .with_remediation(remediation)
+    }
+
+    fn journal_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("wo_journal_{}_{}.json", name, std::process::id()))
+    }
+
+    fn starting_registry() -> MemoryRegistry {
+        MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, KEY, "Existing", 7)
+            .with_string(HKEY_LOCAL_MACHINE, KEY, "Removed", "keep me")
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Services\DiagTrack",
+                "Start",
+                2,
+            )
+    }
+
+    /// Passes everything through to a `MemorySystem` except the writes a
+    /// test wants to break.
+    struct Faulty {
+        inner: MemorySystem,
+        fail_on: &'static str,
+        /// Report success without writing, so only read-back catches it.
+        silent: bool,
+    }
+
+    impl SystemWriter for Faulty {
+        fn set_registry_value(
+            &self,
+            hive: Hkey,
+            path: &str,
+            name: &str,
+            value: &RegValue,
+        ) -> Result<(), ApplyError> {
+            if name == self.fail_on {
+                if self.silent {
+                    return Ok(());
+                }
+                return Err(ApplyError::Registry {
+                    path: name.to_string(),
+                    message: "Access is denied.".to_string(),
+                });
+            }
+            self.inner.set_registry_value(hive, path, name, value)
+        }
+
+        fn delete_registry_value(
+            &self,
+            hive: Hkey,
+            path: &str,
+            name: &str,
+        ) -> Result<(), ApplyError> {
+            self.inner.delete_registry_value(hive, path, name)
+        }
+
+        fn set_service_start_type(
+            &self,
+            service: &str,
+            start_type: StartType,
+        ) -> Result<(), ApplyError> {
+            self.inner.set_service_start_type(service, start_type)
+        }
+
+        fn set_power_setting(
+            &self,
+            scheme: &str,
+            subgroup: &str,
+            setting: &str,
+            ac: u32,
+            dc: u32,
+        ) -> Result<(), ApplyError> {
+            self.inner
+                .set_power_setting(scheme, subgroup, setting, ac, dc)
+        }
+
+        fn run_bcdedit(&self, args: &[String]) -> Result<(), ApplyError> {
+            self.inner.run_bcdedit(args)
+        }
+    }
+
+    impl SystemReader for Faulty {
+        fn read_registry_value(&self, hive: Hkey, path: &str, name: &str) -> Option<RegValue> {
+            self.inner.read_registry_value(hive, path, name)
+        }
+
+        fn power_setting(&self, scheme: &str, subgroup: &str, setting: &str) -> Option<(u32, u32)> {
+            self.inner.power_setting(scheme, subgroup, setting)
+        }
+    }
+
+    fn fixes() -> Vec<Check> {
+        vec![
+            fix(
+                "test.existing",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, KEY, "Existing", 1),
+            ),
+            fix(
+                "test.created",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, KEY, "Created", 1),
+            ),
+            fix(
+                "test.removed",
+                Remediation::delete_value(HKEY_LOCAL_MACHINE, KEY, "Removed"),
+            ),
+            fix(
+                "test.service",
+                Remediation::service_start("DiagTrack", StartType::Disabled),
+            ),
+            fix(
+                "test.last",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, KEY, "Last", 1),
+            ),
+        ]
+    }
+
+    fn assert_untouched(system: &dyn SystemReader) {
+        assert_eq!(
+            system.read_registry_value(HKEY_LOCAL_MACHINE, KEY, "Existing"),
+            Some(RegValue::Dword(7))
+        );
+        assert_eq!(
+            system.read_registry_value(HKEY_LOCAL_MACHINE, KEY, "Created"),
+            None
+        );
+        assert_eq!(
+            system.read_registry_value(HKEY_LOCAL_MACHINE, KEY, "Removed"),
+            Some(RegValue::String("keep me".to_string()))
+        );
+        assert_eq!(
+            system.service_start_type("DiagTrack"),
+            Some(StartType::Automatic)
+        );
+    }
+
+    #[test]
+    fn test_transaction_commits() {
+        use windows_optimizer::journal::{self, Journal};
+
+        let path = journal_path("commit");
+        let system = MemorySystem::new(starting_registry());
+        let checks = fixes();
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let report = journal::apply_session(Journal::new(), &plan, &system, &path).unwrap();
+        assert!(report.committed());
+        assert_eq!(report.applied.len(), 5);
+        assert!(report.rolled_back.is_empty());
+        // Plain values and a service change apply without a restart.
+        assert!(!report.needs_reboot());
+        assert!(!path.exists());
+        assert_eq!(
+            system.read_registry_value(HKEY_LOCAL_MACHINE, KEY, "Removed"),
+            None
+        );
+        assert_eq!(
+            system.service_start_type("DiagTrack"),
+            Some(StartType::Disabled)
+        );
+    }
+
+    #[test]
+    fn test_failed_step_rolls_back_in_reverse() {
+        use windows_optimizer::journal::{self, Journal};
+
+        let path = journal_path("rollback");
+        let system = Faulty {
+            inner: MemorySystem::new(starting_registry()),
+            fail_on: "Last",
+            silent: false,
+        };
+        let checks = fixes();
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let report = journal::apply_session(Journal::new(), &plan, &system, &path).unwrap();
+        let (id, err) = report.failed.as_ref().unwrap();
+        assert_eq!(id, "test.last");
+        assert!(matches!(err, ApplyError::Registry { .. }));
+        assert_eq!(report.applied.len(), 4);
+        // The failed step is undone too, in case it wrote part of its change.
+        assert_eq!(
+            report.rolled_back,
+            [
+                "test.last",
+                "test.service",
+                "test.removed",
+                "test.created",
+                "test.existing"
+            ]
+            .map(String::from)
+        );
+        assert!(report.not_rolled_back.is_empty());
+        assert!(!path.exists());
+        assert_untouched(&system);
+    }
+
+    #[test]
+    fn test_writes_are_read_back() {
+        use windows_optimizer::journal::{self, Journal};
+
+        let path = journal_path("verify");
+        let system = Faulty {
+            inner: MemorySystem::new(starting_registry()),
+            fail_on: "Created",
+            silent: true,
+        };
+        let checks = fixes();
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let report = journal::apply_session(Journal::new(), &plan, &system, &path).unwrap();
+        let (id, err) = report.failed.as_ref().unwrap();
+        assert_eq!(id, "test.created");
+        assert!(
+            matches!(err, ApplyError::Verification { ref found, .. } if found == "nothing"),
+            "{}",
+            err
+        );
+        assert_eq!(report.applied, vec!["test.existing".to_string()]);
+        assert_untouched(&system);
+    }
+
+    #[test]
+    fn test_interrupted_apply_is_recovered() {
+        use windows_optimizer::journal::{self, Journal, JournalEntry, StepState};
+
+        let path = journal_path("crash");
+        let system = MemorySystem::new(starting_registry());
+
+        // Simulate a crash: two steps made it to the journal and the
+        // system, the second before it could be marked applied.
+        let mut journal = Journal::new();
+        for (check, state) in fixes()
+            .into_iter()
+            .zip([StepState::Applied, StepState::Pending])
+        {
+            let change = check.remediation.unwrap();
+            journal.entries.push(JournalEntry {
+                check_id: check.id,
+                prior: change.capture(&system),
+                change: change.clone(),
+                state,
+            });
+            change.apply(&system).unwrap();
+        }
+        journal.save(&path).unwrap();
+
+        let checks = fixes();
+        let plan: Vec<&Check> = checks.iter().collect();
+        assert!(journal::apply_session(Journal::new(), &plan, &system, &path).is_err());
+
+        let report = journal::recover(&path, &system).unwrap().unwrap();
+        assert_eq!(report.session, journal.session);
+        assert_eq!(report.rolled_back, vec!["test.created", "test.existing"]);
+        assert!(!path.exists());
+        assert_untouched(&system);
+
+        assert!(journal::recover(&path, &system).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unknown_prior_state_is_reported() {
+        use windows_optimizer::journal::{self, Journal};
+
+        let path = journal_path("unknown");
+        let system = Faulty {
+            inner: MemorySystem::new(starting_registry()),
+            fail_on: "Last",
+            silent: false,
+        };
+        let checks = [
+            fix(
+                "test.bcd",
+                Remediation::RunBcdEdit {
+                    args: vec!["/set".into(), "disabledynamictick".into(), "yes".into()],
+                },
+            ),
+            fixes().pop().unwrap(),
+        ];
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let report = journal::apply_session(Journal::new(), &plan, &system, &path).unwrap();
+        assert!(!report.committed());
+        assert_eq!(report.not_rolled_back.len(), 1);
+        assert_eq!(report.not_rolled_back[0].0, "test.bcd");
+
+        // The journal stays behind for `recover`, which reports the step
+        // again rather than losing track of it.
+        assert_eq!(report.kept_journal.as_deref(), Some(path.as_path()));
+        assert!(path.exists());
+        let retry = journal::recover(&path, &system).unwrap().unwrap();
+        assert_eq!(retry.not_rolled_back[0].0, "test.bcd");
+        assert!(retry.rolled_back.is_empty());
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{run_category, CheckContext};
+    use windows_optimizer::types::{AuditResults, Category, Check};
+
+    const GRAPHICS: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+    const SYSTEM_PROFILE: &str =
+        r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile";
+    const INTERFACE: &str =
+        r"SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces\{0a1b2c3d}";
+
+    fn audit(ctx: &CheckContext) -> AuditResults {
+        let mut results = AuditResults::new();
+        for category in Category::ALL {
+            results.add_category(run_category(ctx, category));
+        }
+        results
+    }
+
+    fn find<'a>(results: &'a AuditResults, id: &str) -> &'a Check {
+        results
+            .categories
+            .values()
+            .flat_map(|c| &c.checks)
+            .find(|c| c.id == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builtin_profiles_and_inheritance() {
+        use windows_optimizer::checks::find_check;
+        use windows_optimizer::profile::{ProfileSet, Target};
+
+        let profiles = ProfileSet::builtin();
+        assert_eq!(
+            profiles.names(),
+            vec!["audio-production", "battery", "gaming", "workstation"]
+        );
+
+        for name in profiles.names() {
+            let profile = profiles.resolve(name).unwrap();
+            for id in profile.targets.keys() {
+                assert!(
+                    find_check(id).is_some(),
+                    "{} targets unknown check {}",
+                    name,
+                    id
+                );
+            }
+        }
+
+        let gaming = profiles.resolve("Gaming").unwrap();
+        assert_eq!(gaming.chain, vec!["gaming", "workstation"]);
+        assert_eq!(gaming.targets["storage.trim_status"], Target::Number(0));
+        assert_eq!(gaming.targets["gpu.hags"], Target::Number(2));
+        // No built-in profile trades away security features.
+        for name in profiles.names() {
+            let profile = profiles.resolve(name).unwrap();
+            assert_eq!(
+                profile.targets["cpu.vbs_status"],
+                Target::Number(1),
+                "{}",
+                name
+            );
+            assert_eq!(
+                profile.targets["cpu.hvci_status"],
+                Target::Number(1),
+                "{}",
+                name
+            );
+        }
+        assert!(matches!(
+            profiles.resolve("battery").unwrap().targets["cpu.processor_throttle"],
+            Target::Fix(_)
+        ));
+    }
+
+    #[test]
+    fn test_profile_judges_compliance() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{MemoryRegistry, HKEY_LOCAL_MACHINE};
+        use windows_optimizer::journal::{self, Journal};
+        use windows_optimizer::profile::ProfileSet;
+        use windows_optimizer::remediation::{plan, MemorySystem};
+        use windows_optimizer::types::CheckStatus;
+
+        let registry = MemoryRegistry::new()
+            .with_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "HwSchMode", 2)
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                SYSTEM_PROFILE,
+                "SystemResponsiveness",
+                20,
+            )
+            .with_string(HKEY_LOCAL_MACHINE, INTERFACE, "DhcpIPAddress", "10.0.0.5")
+            .with_dword(HKEY_LOCAL_MACHINE, INTERFACE, "TcpAckFrequency", 1);
+        let ctx = CheckContext::live().with_registry(Arc::new(registry.clone()));
+        let gaming = ProfileSet::builtin().resolve("gaming").unwrap();
+
+        let mut results = audit(&ctx);
+        let report = gaming.judge(&mut results, &ctx);
+        assert!(report.invalid.is_empty(), "{:?}", report.invalid);
+
+        assert_eq!(find(&results, "gpu.hags").status, CheckStatus::Optimal);
+        let responsiveness = find(&results, "latency.system_responsiveness");
+        assert_eq!(responsiveness.status, CheckStatus::Issue);
+        assert_eq!(
+            responsiveness.expected.as_deref(),
+            Some("10 (0x0000000a) (gaming profile)")
+        );
+        // TcpAckFrequency alone does not turn Nagle off; TCPNoDelay is missing.
+        assert_eq!(
+            find(&results, "network.nagle_algorithm").status,
+            CheckStatus::Issue
+        );
+        // Offline, nothing can read the power scheme.
+        assert_eq!(
+            find(&results, "power.usb_selective_suspend").status,
+            CheckStatus::Unknown
+        );
+
+        // Applying the profile's fixes makes the same profile pass.
+        let system = MemorySystem::new(registry);
+        let path = std::env::temp_dir().join(format!("wo_profile_{}.json", std::process::id()));
+        let fixes: Vec<&Check> = plan(&results)
+            .into_iter()
+            .filter(|c| gaming.targets.contains_key(&c.id))
+            .collect();
+        assert!(
+            journal::apply_session(Journal::new(), &fixes, &system, &path)
+                .unwrap()
+                .committed()
+        );
+
+        let ctx = CheckContext::live().with_registry(Arc::new(system.registry()));
+        let mut results = audit(&ctx);
+        gaming.judge(&mut results, &ctx);
+        for id in [
+            "latency.system_responsiveness",
+            "network.nagle_algorithm",
+            "cpu.vbs_status",
+            "storage.trim_status",
+        ] {
+            assert_eq!(find(&results, id).status, CheckStatus::Optimal, "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_profiles_disagree_on_optimal() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{MemoryRegistry, HKEY_LOCAL_MACHINE};
+        use windows_optimizer::profile::ProfileSet;
+        use windows_optimizer::types::CheckStatus;
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new().with_dword(
+            HKEY_LOCAL_MACHINE,
+            SYSTEM_PROFILE,
+            "SystemResponsiveness",
+            20,
+        )));
+        let profiles = ProfileSet::builtin();
+        let responsiveness = |profile: &str| {
+            let mut results = audit(&ctx);
+            profiles.resolve(profile).unwrap().judge(&mut results, &ctx);
+            find(&results, "latency.system_responsiveness")
+                .status
+                .clone()
+        };
+
+        assert_eq!(responsiveness("battery"), CheckStatus::Optimal);
+        assert_eq!(responsiveness("gaming"), CheckStatus::Issue);
+    }
+
+    #[test]
+    fn test_user_profiles() {
+        use windows_optimizer::profile::{ProfileSet, Target};
+
+        let dir = std::env::temp_dir().join(format!("wo_profiles_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("streaming.toml"),
+            "inherits = \"gaming\"\n[targets]\n\"latency.system_responsiveness\" = 20\n\"gpu.hags\" = \"on\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("loop-a.toml"), "inherits = \"loop-b\"\n").unwrap();
+        std::fs::write(dir.join("loop-b.toml"), "inherits = \"loop-a\"\n").unwrap();
+
+        let mut profiles = ProfileSet::builtin();
+        profiles.load_dir(&dir).unwrap();
+        let streaming = profiles.resolve("streaming").unwrap();
+        assert_eq!(streaming.chain, vec!["streaming", "gaming", "workstation"]);
+        assert_eq!(
+            streaming.targets["latency.system_responsiveness"],
+            Target::Number(20)
+        );
+
+        // "on" is not a DWORD, so HAGS is left to its built-in verdict.
+        let mut results = AuditResults::new();
+        results.add_category(run_category(&CheckContext::live(), Category::Gpu));
+        let report = streaming.judge(&mut results, &CheckContext::live());
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].0, "gpu.hags");
+        assert!(report
+            .unmatched
+            .contains(&"latency.system_responsiveness".to_string()));
+
+        let err = profiles.resolve("loop-a").unwrap_err();
+        assert!(err.to_string().contains("inherits from itself"), "{}", err);
+        let err = profiles.resolve("nope").unwrap_err();
+        assert!(err.to_string().contains("audio-production"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,383 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::Hive;
+
+    /// 2024-01-02T03:04:05Z as a FILETIME, stamped on every key.
+    const KEY_TIMESTAMP: u64 = 133_486_382_450_000_000;
+
+    /// Minimal regf writer used to build hive fixtures in memory.
+    struct HiveBuilder {
+        bins: Vec<u8>,
+        minor_version: u32,
+    }
+
+    enum Data<'a> {
+        Dword(u32),
+        Sz(&'a str),
+        Raw(u32, Vec<u8>),
+    }
+
+    struct Key<'a> {
+        name: &'a str,
+        values: Vec<(&'a str, Data<'a>)>,
+        subkeys: Vec<Key<'a>>,
+    }
+
+    fn key<'a>(name: &'a str, values: Vec<(&'a str, Data<'a>)>, subkeys: Vec<Key<'a>>) -> Key<'a> {
+        Key {
+            name,
+            values,
+            subkeys,
+        }
+    }
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(|u| u.to_le_bytes())
+            .collect()
+    }
+
+    impl HiveBuilder {
+        fn new() -> Self {
+            // Room for the 32-byte hbin header; cells start right after it.
+            Self {
+                bins: vec![0; 32],
+                minor_version: 5,
+            }
+        }
+
+        fn cell(&mut self, payload: &[u8]) -> u32 {
+            let offset = self.bins.len() as u32;
+            let size = (payload.len() + 4 + 7) & !7;
+            self.bins.extend_from_slice(&(-(size as i32)).to_le_bytes());
+            self.bins.extend_from_slice(payload);
+            self.bins.resize(offset as usize + size, 0);
+            offset
+        }
+
+        fn data_cell(&mut self, data: &[u8]) -> u32 {
+            if data.len() > 16344 {
+                let segments: Vec<u32> = data.chunks(16344).map(|c| self.cell(c)).collect();
+                let list: Vec<u8> = segments.iter().flat_map(|o| o.to_le_bytes()).collect();
+                let list = self.cell(&list);
+                let mut db = b"db".to_vec();
+                db.extend_from_slice(&(segments.len() as u16).to_le_bytes());
+                db.extend_from_slice(&list.to_le_bytes());
+                self.cell(&db)
+            } else {
+                self.cell(data)
+            }
+        }
+
+        fn value(&mut self, name: &str, data: &Data) -> u32 {
+            let (kind, bytes) = match data {
+                Data::Dword(v) => (4u32, v.to_le_bytes().to_vec()),
+                Data::Sz(s) => (1, utf16(s)),
+                Data::Raw(kind, bytes) => (*kind, bytes.clone()),
+            };
+            let (size, offset) = if bytes.len() <= 4 {
+                let mut inline = [0u8; 4];
+                inline[..bytes.len()].copy_from_slice(&bytes);
+                (bytes.len() as u32 | 0x8000_0000, u32::from_le_bytes(inline))
+            } else {
+                (bytes.len() as u32, self.data_cell(&bytes))
+            };
+            let mut vk = b"vk".to_vec();
+            vk.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            vk.extend_from_slice(&size.to_le_bytes());
+            vk.extend_from_slice(&offset.to_le_bytes());
+            vk.extend_from_slice(&kind.to_le_bytes());
+            vk.extend_from_slice(&1u16.to_le_bytes());
+            vk.extend_from_slice(&0u16.to_le_bytes());
+            vk.extend_from_slice(name.as_bytes());
+            self.cell(&vk)
+        }
+
+        /// Writes a key; keys with more than two children use an ri of li lists.
+        fn key(&mut self, key: &Key, utf16_name: bool) -> u32 {
+            let children: Vec<u32> = key.subkeys.iter().map(|k| self.key(k, false)).collect();
+            let values: Vec<u32> = key
+                .values
+                .iter()
+                .map(|(name, data)| self.value(name, data))
+                .collect();
+
+            let subkey_list = if children.is_empty() {
+                u32::MAX
+            } else if children.len() > 2 {
+                let lists: Vec<u32> = children
+                    .chunks(2)
+                    .map(|chunk| {
+                        let mut li = b"li".to_vec();
+                        li.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+                        chunk
+                            .iter()
+                            .for_each(|o| li.extend_from_slice(&o.to_le_bytes()));
+                        self.cell(&li)
+                    })
+                    .collect();
+                let mut ri = b"ri".to_vec();
+                ri.extend_from_slice(&(lists.len() as u16).to_le_bytes());
+                lists
+                    .iter()
+                    .for_each(|o| ri.extend_from_slice(&o.to_le_bytes()));
+                self.cell(&ri)
+            } else {
+                let mut lf = b"lf".to_vec();
+                lf.extend_from_slice(&(children.len() as u16).to_le_bytes());
+                for o in &children {
+                    lf.extend_from_slice(&o.to_le_bytes());
+                    lf.extend_from_slice(&[0; 4]);
+                }
+                self.cell(&lf)
+            };
+
+            let value_list = if values.is_empty() {
+                u32::MAX
+            } else {
+                let list: Vec<u8> = values.iter().flat_map(|o| o.to_le_bytes()).collect();
+                self.cell(&list)
+            };
+
+            let name: Vec<u8> = if utf16_name {
+                key.name
+                    .encode_utf16()
+                    .flat_map(|u| u.to_le_bytes())
+                    .collect()
+            } else {
+                key.name.as_bytes().to_vec()
+            };
+            let flags: u16 = if utf16_name { 0 } else { 0x20 };
+
+            let mut nk = vec![0u8; 76];
+            nk[0..2].copy_from_slice(b"nk");
+            nk[2..4].copy_from_slice(&flags.to_le_bytes());
+            nk[4..12].copy_from_slice(&KEY_TIMESTAMP.to_le_bytes());
+            nk[20..24].copy_from_slice(&(children.len() as u32).to_le_bytes());
+            nk[28..32].copy_from_slice(&subkey_list.to_le_bytes());
+            nk[36..40].copy_from_slice(&(values.len() as u32).to_le_bytes());
+            nk[40..44].copy_from_slice(&value_list.to_le_bytes());
+            nk[72..74].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            nk.extend_from_slice(&name);
+            self.cell(&nk)
+        }
+
+        fn build(mut self, root: &Key) -> Vec<u8> {
+            let root = self.key(root, true);
+            let bins_size = self.bins.len().div_ceil(4096) * 4096;
+            self.bins.resize(bins_size, 0);
+            self.bins[0..4].copy_from_slice(b"hbin");
+            self.bins[8..12].copy_from_slice(&(bins_size as u32).to_le_bytes());
+
+            let mut base = vec![0u8; 4096];
+            base[0..4].copy_from_slice(b"regf");
+            base[20..24].copy_from_slice(&1u32.to_le_bytes());
+            base[24..28].copy_from_slice(&self.minor_version.to_le_bytes());
+            base[36..40].copy_from_slice(&root.to_le_bytes());
+            base[40..44].copy_from_slice(&(bins_size as u32).to_le_bytes());
+            base.extend_from_slice(&self.bins);
+            base
+        }
+    }
+
+    fn system_hive() -> Vec<u8> {
+        let graphics = key(
+            "GraphicsDrivers",
+            vec![("HwSchMode", Data::Dword(2)), ("TdrDelay", Data::Dword(8))],
+            vec![],
+        );
+        let kernel = key(
+            "kernel",
+            vec![("DisableDynamicTick", Data::Dword(1))],
+            vec![],
+        );
+        let session = key("Session Manager", vec![], vec![kernel]);
+        let control = key("Control", vec![], vec![graphics, session]);
+        let stale = key(
+            "Control",
+            vec![],
+            vec![key(
+                "GraphicsDrivers",
+                vec![("HwSchMode", Data::Dword(1))],
+                vec![],
+            )],
+        );
+        let select = key(
+            "Select",
+            vec![("Current", Data::Dword(2)), ("Default", Data::Dword(2))],
+            vec![],
+        );
+        let root = key(
+            "ROOT",
+            vec![],
+            vec![
+                key("ControlSet001", vec![], vec![stale]),
+                key("ControlSet002", vec![], vec![control]),
+                select,
+                key("MountedDevices", vec![], vec![]),
+            ],
+        );
+        HiveBuilder::new().build(&root)
+    }
+
+    #[test]
+    fn test_rejects_non_hive_data() {
+        assert!(Hive::from_bytes(b"not a hive".to_vec()).is_err());
+        let mut bytes = system_hive();
+        bytes[4096..4100].copy_from_slice(b"junk");
+        assert!(Hive::from_bytes(bytes).is_err());
+    }
+
+    #[test]
+    fn test_walks_keys_and_values() {
+        let hive = Hive::from_bytes(system_hive()).unwrap();
+        let names: Vec<String> = hive.root().subkeys().iter().map(|k| k.name()).collect();
+        assert_eq!(
+            names,
+            vec!["ControlSet001", "ControlSet002", "Select", "MountedDevices"]
+        );
+
+        let graphics = hive
+            .open_key(r"controlset002\CONTROL\GraphicsDrivers")
+            .unwrap();
+        let value = graphics.value("hwschmode").unwrap();
+        assert_eq!(value.name(), "HwSchMode");
+        assert_eq!(value.data_type(), 4);
+        assert_eq!(value.data(), Some(2u32.to_le_bytes().to_vec()));
+        assert!(hive.open_key(r"ControlSet002\Missing").is_none());
+    }
+
+    #[test]
+    fn test_reads_big_data_values() {
+        let blob: Vec<u8> = (0..40_000u32).map(|i| (i % 251) as u8).collect();
+        let root = key(
+            "ROOT",
+            vec![
+                ("Blob", Data::Raw(3, blob.clone())),
+                ("Small", Data::Raw(3, vec![1, 2, 3])),
+                ("Name", Data::Sz("Windows 11 Pro")),
+            ],
+            vec![],
+        );
+        let hive = Hive::from_bytes(HiveBuilder::new().build(&root)).unwrap();
+        let root = hive.root();
+        assert_eq!(root.value("Blob").unwrap().data(), Some(blob));
+        assert_eq!(root.value("Small").unwrap().data(), Some(vec![1, 2, 3]));
+        assert_eq!(root.values().len(), 3);
+    }
+
+    #[test]
+    fn test_offline_registry_resolves_current_control_set() {
+        use windows_optimizer::checks::{OfflineRegistry, RegistrySource, HKEY_LOCAL_MACHINE};
+
+        let reg = OfflineRegistry::new().with_system(Hive::from_bytes(system_hive()).unwrap());
+        assert_eq!(
+            reg.read_dword(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers",
+                "HwSchMode"
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            reg.read_dword(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\ControlSet001\Control\GraphicsDrivers",
+                "HwSchMode"
+            ),
+            Some(1)
+        );
+        assert!(reg.key_exists(HKEY_LOCAL_MACHINE, r"SYSTEM\MountedDevices"));
+        assert!(!reg.key_exists(HKEY_LOCAL_MACHINE, r"SOFTWARE\Microsoft"));
+    }
+
+    #[test]
+    fn test_offline_registry_enumerates_keys_and_values() {
+        use windows_optimizer::checks::{
+            OfflineRegistry, RegValue, RegistrySource, HKEY_LOCAL_MACHINE,
+        };
+
+        let reg = OfflineRegistry::new().with_system(Hive::from_bytes(system_hive()).unwrap());
+        assert_eq!(
+            reg.subkeys(HKEY_LOCAL_MACHINE, r"SYSTEM\CurrentControlSet\Control"),
+            Some(vec![
+                "GraphicsDrivers".to_string(),
+                "Session Manager".to_string()
+            ])
+        );
+        assert_eq!(
+            reg.values(
+                HKEY_LOCAL_MACHINE,
+                r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers"
+            ),
+            Some(vec![
+                ("HwSchMode".to_string(), RegValue::Dword(2)),
+                ("TdrDelay".to_string(), RegValue::Dword(8)),
+            ])
+        );
+        assert_eq!(reg.values(HKEY_LOCAL_MACHINE, r"SYSTEM\Nope"), None);
+        assert_eq!(
+            reg.last_write_time(HKEY_LOCAL_MACHINE, r"SYSTEM\Select")
+                .map(|t| t.to_rfc3339()),
+            Some("2024-01-02T03:04:05+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_offline_audit_matches_hive_contents() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_gpu_checks, run_latency_checks, CheckContext, OfflineRegistry,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let software = key(
+            "CMI-CreateHive{199DAFC2-6F16-4946-BF90-5A3FC3A60902}",
+            vec![],
+            vec![key(
+                "Microsoft",
+                vec![],
+                vec![key(
+                    "Windows NT",
+                    vec![],
+                    vec![key(
+                        "CurrentVersion",
+                        vec![],
+                        vec![key(
+                            "Multimedia",
+                            vec![],
+                            vec![key(
+                                "SystemProfile",
+                                vec![("SystemResponsiveness", Data::Dword(10))],
+                                vec![],
+                            )],
+                        )],
+                    )],
+                )],
+            )],
+        );
+        let reg = OfflineRegistry::new()
+            .with_system(Hive::from_bytes(system_hive()).unwrap())
+            .with_software(Hive::from_bytes(HiveBuilder::new().build(&software)).unwrap());
+        let ctx = CheckContext::live().with_registry(Arc::new(reg));
+
+        let gpu = run_gpu_checks(&ctx);
+        let find = |checks: &[windows_optimizer::types::Check], prefix: &str| {
+            checks
+                .iter()
+                .find(|c| c.name.starts_with(prefix))
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(find(&gpu.checks, "HAGS").status, CheckStatus::Optimal);
+        assert_eq!(find(&gpu.checks, "TDR Delay").value, "8s");
+
+        let latency = run_latency_checks(&ctx);
+        assert_eq!(find(&latency.checks, "Dynamic Tick").value, "Disabled");
+        assert_eq!(
+            find(&latency.checks, "System Responsiveness").status,
+            CheckStatus::Optimal
+        );
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{RegValue, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::regfile::{RegFile, RegKeyBlock, RegVersion, ValueOp};
+
+    fn single_line(name: &str, value: RegValue) -> String {
+        let mut file = RegFile::new();
+        file.keys
+            .push(RegKeyBlock::new(HKEY_CURRENT_USER, "Test").set(name, value));
+        let text = file.to_text();
+        let body = text.split("[HKEY_CURRENT_USER\\Test]\r\n").nth(1).unwrap();
+        body.trim_end().to_string()
+    }
+
+    #[test]
+    fn test_writes_every_value_type() {
+        assert_eq!(
+            single_line("Path", RegValue::String(r#"C:\Program Files\"x""#.into())),
+            r#""Path"="C:\\Program Files\\\"x\"""#
+        );
+        assert_eq!(single_line("", RegValue::Dword(0x26)), "@=dword:00000026");
+        assert_eq!(
+            single_line("Q", RegValue::Qword(0x0102030405060708)),
+            r#""Q"=hex(b):08,07,06,05,04,03,02,01"#
+        );
+        assert_eq!(
+            single_line("E", RegValue::ExpandString("%A%".into())),
+            r#""E"=hex(2):25,00,41,00,25,00,00,00"#
+        );
+        assert_eq!(
+            single_line("M", RegValue::MultiString(vec!["a".into(), "b".into()])),
+            r#""M"=hex(7):61,00,00,00,62,00,00,00,00,00"#
+        );
+        assert_eq!(
+            single_line("B", RegValue::Binary(vec![0xde, 0xad])),
+            r#""B"=hex:de,ad"#
+        );
+        assert_eq!(
+            single_line("N", RegValue::Other(0, vec![])),
+            r#""N"=hex(0):"#
+        );
+        // Strings with line breaks fall back to hex(1)
+        assert_eq!(
+            single_line("S", RegValue::String("a\nb".into())),
+            r#""S"=hex(1):61,00,0a,00,62,00,00,00"#
+        );
+    }
+
+    #[test]
+    fn test_long_hex_wraps_like_regedit() {
+        let text = single_line("Blob", RegValue::Binary(vec![0xab; 64]));
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert!(lines.len() > 1);
+        for l in &lines[..lines.len() - 1] {
+            assert!(l.len() <= 80, "line too long: {}", l);
+            assert!(l.ends_with(",\\"));
+        }
+        for l in &lines[1..] {
+            assert!(l.starts_with("  "));
+        }
+        assert_eq!(text.matches("ab").count(), 64);
+    }
+
+    #[test]
+    fn test_parses_full_grammar() {
+        let text = "\u{feff}Windows Registry Editor Version 5.00\r\n\
+            \r\n\
+            ; exported by hand\r\n\
+            \r\n\
+            [HKEY_LOCAL_MACHINE\\SOFTWARE\\Test]\r\n\
+            @=\"default\"\r\n\
+            \"Quoted \\\"name\\\"\"=\"C:\\\\dir\"\r\n\
+            \"Dw\"=dword:0000ffff\r\n\
+            \"Qw\"=hex(b):01,00,00,00,\\\r\n\
+            \x20 00,00,00,00\r\n\
+            \"Multi\"=hex(7):61,00,00,00,\\\r\n\
+            \x20 62,00,00,00,00,00\r\n\
+            \"Gone\"=-\r\n\
+            \r\n\
+            [-HKCU\\Software\\Old]\r\n\
+            \r\n\
+            [HKEY_CLASSES_ROOT\\.txt]\r\n\
+            \"Bin\"=hex:\r\n";
+        let file = RegFile::parse(text.trim_start_matches('\u{feff}')).unwrap();
+
+        assert_eq!(file.version, RegVersion::V5);
+        assert_eq!(file.comments, vec!["exported by hand".to_string()]);
+        assert_eq!(file.keys.len(), 3);
+
+        let test = &file.keys[0];
+        assert_eq!(test.hkey, HKEY_LOCAL_MACHINE);
+        assert_eq!(test.path, r"SOFTWARE\Test");
+        let ops: Vec<(&str, &ValueOp)> = test
+            .entries
+            .iter()
+            .map(|e| (e.name.as_str(), &e.op))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                ("", &ValueOp::Set(RegValue::String("default".into()))),
+                (
+                    "Quoted \"name\"",
+                    &ValueOp::Set(RegValue::String(r"C:\dir".into()))
+                ),
+                ("Dw", &ValueOp::Set(RegValue::Dword(0xffff))),
+                ("Qw", &ValueOp::Set(RegValue::Qword(1))),
+                (
+                    "Multi",
+                    &ValueOp::Set(RegValue::MultiString(vec!["a".into(), "b".into()]))
+                ),
+                ("Gone", &ValueOp::Delete),
+            ]
+        );
+
+        assert!(file.keys[1].delete);
+        assert_eq!(file.keys[1].hkey, HKEY_CURRENT_USER);
+        assert_eq!(file.keys[1].full_path(), r"HKEY_CURRENT_USER\Software\Old");
+        assert_eq!(
+            file.keys[2].entries[0].op,
+            ValueOp::Set(RegValue::Binary(vec![]))
+        );
+    }
+
+    #[test]
+    fn test_regedit4_strings_are_ansi() {
+        let text = "REGEDIT4\r\n\r\n[HKEY_CURRENT_USER\\Env]\r\n\"Path\"=hex(2):25,41,25,00\r\n";
+        let file = RegFile::parse(text).unwrap();
+        assert_eq!(file.version, RegVersion::Regedit4);
+        assert_eq!(
+            file.keys[0].entries[0].op,
+            ValueOp::Set(RegValue::ExpandString("%A%".into()))
+        );
+        assert_eq!(RegFile::parse(&file.to_text()).unwrap(), file);
+    }
+
+    #[test]
+    fn test_round_trips_through_utf16_file() {
+        let mut file = RegFile::new();
+        file.comments.push("Ünïcode backup".into());
+        file.keys.push(
+            RegKeyBlock::new(HKEY_LOCAL_MACHINE, r"SOFTWARE\Ünïcode")
+                .set("Blob", RegValue::Binary((0..=255).collect()))
+                .set("Multi", RegValue::MultiString(vec!["x".into(), "ÿ".into()]))
+                .set("Q", RegValue::Qword(u64::MAX))
+                .set("", RegValue::ExpandString("%SystemRoot%".into()))
+                .delete_value("Old"),
+        );
+        file.keys
+            .push(RegKeyBlock::deletion(HKEY_CURRENT_USER, r"Software\Stale"));
+
+        let path = std::env::temp_dir().join(format!("regfile_test_{}.reg", std::process::id()));
+        file.save(&path).unwrap();
+        let raw = std::fs::read(&path).unwrap();
+        let loaded = RegFile::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(&raw[..4], &[0xFF, 0xFE, b'W', 0]);
+        assert_eq!(loaded, file);
+    }
+
+    #[test]
+    fn test_reports_errors_with_line_numbers() {
+        use windows_optimizer::regfile::RegFileError;
+
+        assert!(matches!(
+            RegFile::parse("[HKEY_CURRENT_USER\\X]\r\n"),
+            Err(RegFileError::Header)
+        ));
+
+        let err = RegFile::parse(
+            "REGEDIT4\r\n\r\n[HKEY_CURRENT_USER\\X]\r\n\"A\"=dword:1\r\n\"B\"=hex:zz\r\n",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, RegFileError::Syntax { line: 5, .. }),
+            "{}",
+            err
+        );
+
+        let err = RegFile::parse("REGEDIT4\r\n\"A\"=\"x\"\r\n").unwrap_err();
+        assert!(matches!(err, RegFileError::Syntax { line: 2, .. }));
+
+        let err = RegFile::parse("REGEDIT4\r\n[HKEY_NOWHERE\\X]\r\n").unwrap_err();
+        assert!(err.to_string().contains("unknown registry root"));
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{run_category, CheckContext};
+    use windows_optimizer::types::{AuditResults, Category};
+
+    fn audit(ctx: &CheckContext) -> AuditResults {
+        let mut results = AuditResults::new();
+        for category in Category::ALL {
+            results.add_category(run_category(ctx, category));
+        }
+        results
+    }
+
+    #[test]
+    fn test_plan_only_includes_failed_checks() {
+        use windows_optimizer::remediation::{plan, Remediation};
+        use windows_optimizer::types::{CategoryResults, Check, CheckStatus};
+
+        let fix = Remediation::set_dword(
+            windows_optimizer::checks::HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Test",
+            "Value",
+            1,
+        );
+        let mut category = CategoryResults::new("GPU");
+        for (id, status) in [
+            ("gpu.a", CheckStatus::Optimal),
+            ("gpu.b", CheckStatus::Issue),
+            ("gpu.c", CheckStatus::Warning),
+            ("gpu.d", CheckStatus::Unknown),
+            ("gpu.e", CheckStatus::Info),
+        ] {
+            category.add_check(
+                Check::new(id, "value", status)
+                    .with_id(id)
+                    .with_remediation(fix.clone()),
+            );
+        }
+        category.add_check(Check::new("gpu.f", "value", CheckStatus::Issue).with_id("gpu.f"));
+        let mut results = AuditResults::new();
+        results.add_category(category);
+
+        let ids: Vec<&str> = plan(&results).iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["gpu.b", "gpu.c"]);
+    }
+
+    #[test]
+    fn test_applied_fixes_make_checks_optimal() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::MemoryRegistry;
+        use windows_optimizer::journal::{self, Journal};
+        use windows_optimizer::remediation::{plan, MemorySystem, Remediation};
+        use windows_optimizer::types::CheckStatus;
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new()));
+        let before = audit(&ctx);
+        let registry_fixes: Vec<String> = plan(&before)
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c.remediation,
+                    Some(Remediation::SetRegistryValue { .. })
+                        | Some(Remediation::DeleteRegistryValue { .. })
+                )
+            })
+            .map(|c| c.id.clone())
+            .collect();
+        assert!(registry_fixes.contains(&"gpu.hags".to_string()));
+
+        // Power settings go through powercfg; seed them so they read back.
+        let system = MemorySystem::new(MemoryRegistry::new())
+            .with_power_setting("SCHEME_CURRENT", "SUB_PROCESSOR", "CPMINCORES", 5, 5)
+            .with_power_setting("SCHEME_CURRENT", "SUB_PROCESSOR", "PERFBOOSTMODE", 0, 0)
+            .with_power_setting("SCHEME_CURRENT", "SUB_PROCESSOR", "PROCTHROTTLEMIN", 5, 5);
+        let path = std::env::temp_dir().join(format!("wo_fix_journal_{}.json", std::process::id()));
+        let report =
+            journal::apply_session(Journal::new(), &plan(&before), &system, &path).unwrap();
+        assert!(report.committed(), "{:?}", report.failed);
+        assert!(!path.exists());
+
+        let after = audit(&CheckContext::live().with_registry(Arc::new(system.registry())));
+        for id in &registry_fixes {
+            let check = after
+                .categories
+                .values()
+                .flat_map(|c| &c.checks)
+                .find(|c| &c.id == id)
+                .unwrap();
+            assert_eq!(check.status, CheckStatus::Optimal, "{} after its fix", id);
+        }
+
+        assert!(system.actions().iter().any(
+            |a| matches!(a, Remediation::SetPowerSetting { setting, .. } if setting == "CPMINCORES")
+        ));
+    }
+
+    #[test]
+    fn test_non_registry_actions_are_recorded() {
+        use windows_optimizer::checks::MemoryRegistry;
+        use windows_optimizer::remediation::{MemorySystem, Remediation, StartType};
+
+        let system = MemorySystem::new(MemoryRegistry::new());
+        let fixes = [
+            Remediation::service_start("DiagTrack", StartType::Disabled),
+            Remediation::power_setting("SUB_PROCESSOR", "PERFBOOSTMODE", 1, 1),
+            Remediation::RunBcdEdit {
+                args: vec!["/deletevalue".into(), "useplatformclock".into()],
+            },
+        ];
+        for fix in &fixes {
+            fix.apply(&system).unwrap();
+        }
+        assert_eq!(system.actions(), fixes.to_vec());
+        assert_eq!(
+            StartType::from_raw(StartType::Disabled.raw()),
+            Some(StartType::Disabled)
+        );
+        assert_eq!(fixes[2].describe(), "bcdedit /deletevalue useplatformclock");
+    }
+
+    #[test]
+    fn test_remediation_json_round_trip() {
+        use windows_optimizer::checks::{RegValue, HKEY_CURRENT_USER};
+        use windows_optimizer::remediation::Remediation;
+
+        let fix = Remediation::set_value(
+            HKEY_CURRENT_USER,
+            r"Control Panel\Mouse",
+            "MouseSpeed",
+            RegValue::String("0".to_string()),
+        );
+        let json = serde_json::to_value(&fix).unwrap();
+        assert_eq!(json["action"], "set_registry_value");
+        assert_eq!(json["hive"], "HKCU");
+        assert_eq!(json["value"]["type"], "string");
+        assert_eq!(json["value"]["data"], "0");
+        let back: Remediation = serde_json::from_value(json).unwrap();
+        assert_eq!(back, fix);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_live_system_refuses_off_windows() {
+        use windows_optimizer::remediation::{ApplyError, LiveSystem, Remediation, StartType};
+
+        let err = Remediation::service_start("DiagTrack", StartType::Disabled)
+            .apply(&LiveSystem)
+            .unwrap_err();
+        assert!(matches!(err, ApplyError::Command { ref program, .. } if program == "sc"));
+    }
+
+    #[test]
+    fn test_backup_covers_keys_fixes_write() {
+        use std::sync::Arc;
+        use windows_optimizer::backup::keys_touched_by_checks;
+        use windows_optimizer::checks::{MemoryRegistry, HKEY_CURRENT_USER};
+
+        let ctx = CheckContext::live().with_registry(Arc::new(MemoryRegistry::new()));
+        let keys = keys_touched_by_checks(&ctx);
+        assert!(keys
+            .iter()
+            .any(|(hive, path)| *hive == HKEY_CURRENT_USER && path == r"Control Panel\Mouse"));
+        assert!(keys.iter().any(|(_, path)| path.ends_with(r"Tasks\Games")));
+    }
+
+    #[test]
+    fn test_dry_run_preview() {
+        use windows_optimizer::checks::{MemoryRegistry, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+        use windows_optimizer::remediation::{preview, MemorySystem, Remediation, StartType};
+        use windows_optimizer::report::export_plan_json;
+        use windows_optimizer::types::{Check, CheckStatus};
+
+        let graphics = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+        let system = MemorySystem::new(
+            MemoryRegistry::new()
+                .with_dword(HKEY_LOCAL_MACHINE, graphics, "HwSchMode", 1)
+                .with_string(HKEY_CURRENT_USER, r"Control Panel\Mouse", "MouseSpeed", "0"),
+        );
+        let fix = |id: &str, remediation| {
+            Check::new(id, "value", CheckStatus::Issue)
+                .with_id(id)
+                .with_remediation(remediation)
+        };
+        let checks = [
+            fix(
+                "gpu.hags",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, graphics, "HwSchMode", 2),
+            ),
+            fix(
+                "gpu.shader_cache",
+                Remediation::delete_value(HKEY_LOCAL_MACHINE, graphics, "DisableShaderCache"),
+            ),
+            fix(
+                "input.mouse_acceleration",
+                Remediation::set_string(
+                    HKEY_CURRENT_USER,
+                    r"Control Panel\Mouse",
+                    "MouseSpeed",
+                    "0",
+                ),
+            ),
+            fix(
+                "services.diagtrack",
+                Remediation::service_start("DiagTrack", StartType::Disabled),
+            ),
+        ];
+        let plan: Vec<&Check> = checks.iter().collect();
+        let changes = preview(&plan, &system);
+
+        assert_eq!(changes.len(), 4);
+        let hags = &changes[0];
+        assert_eq!(hags.key, format!(r"HKEY_LOCAL_MACHINE\{}", graphics));
+        assert_eq!(hags.value, "HwSchMode");
+        assert_eq!(hags.current, "1 (0x00000001)");
+        assert_eq!(hags.proposed, "2 (0x00000002)");
+        assert!(hags.changes && hags.reboot_required);
+
+        // Deleting a value that is not there changes nothing.
+        assert_eq!(changes[1].current, "(not set)");
+        assert!(!changes[1].changes);
+
+        assert!(!changes[2].changes);
+        assert!(!changes[2].reboot_required);
+
+        assert_eq!(changes[3].current, "(unknown)");
+        assert_eq!(changes[3].proposed, "Disabled");
+
+        // Previewing never writes.
+        assert!(system.actions().is_empty());
+
+        let path = std::env::temp_dir().join(format!("wo_plan_{}.json", std::process::id()));
+        export_plan_json(&changes, path.to_str().unwrap()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["reboot_required"], true);
+        assert_eq!(json["changes"][0]["check_id"], "gpu.hags");
+        assert_eq!(
+            json["changes"][0]["remediation"]["action"],
+            "set_registry_value"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::HKEY_LOCAL_MACHINE;
+    use windows_optimizer::remediation::Remediation;
+    use windows_optimizer::types::{AuditResults, CategoryResults, Check, CheckStatus};
+
+    const GRAPHICS: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+
+    fn results() -> AuditResults {
+        let mut results = AuditResults::new();
+        let mut gpu = CategoryResults::new("GPU");
+        gpu.add_check(
+            Check::new("HAGS", "Disabled", CheckStatus::Issue)
+                .with_id("gpu.hags")
+                .with_severity(7)
+                .with_expected("2")
+                .with_description("Hardware-accelerated GPU scheduling.")
+                .with_remediation(Remediation::set_dword(
+                    HKEY_LOCAL_MACHINE,
+                    GRAPHICS,
+                    "HwSchMode",
+                    2,
+                )),
+        );
+        gpu.add_check(
+            Check::new("Game DVR", "0", CheckStatus::Optimal)
+                .with_id("gpu.game_dvr")
+                .with_severity(2),
+        );
+        results.add_category(gpu);
+        let mut studio = CategoryResults::new("studio");
+        studio.add_check(
+            Check::new("Pro Audio Priority", "2", CheckStatus::Warning)
+                .with_id("studio.pro_audio_priority")
+                .with_severity(2)
+                .with_remediation(Remediation::set_dword(
+                    HKEY_LOCAL_MACHINE,
+                    r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Pro Audio",
+                    "Priority",
+                    1,
+                )),
+        );
+        results.add_category(studio);
+        results
+    }
+
+    #[test]
+    fn test_sarif_export() {
+        use windows_optimizer::report::export_sarif;
+
+        let path = std::env::temp_dir().join(format!("wo_report_{}.sarif", std::process::id()));
+        export_sarif(&results(), path.to_str().unwrap()).unwrap();
+        let sarif: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "Windows-Optimizer");
+        assert_eq!(run["tool"]["driver"]["version"], env!("CARGO_PKG_VERSION"));
+
+        // Only failing checks, in category order.
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        let hags = &results[0];
+        assert_eq!(hags["ruleId"], "gpu.hags");
+        assert_eq!(hags["level"], "error");
+        assert_eq!(hags["message"]["text"], "HAGS is Disabled; expected 2");
+        assert_eq!(
+            hags["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            format!(r"HKEY_LOCAL_MACHINE\{}\HwSchMode", GRAPHICS)
+        );
+        // A low-severity warning is only a note; a custom rule is located
+        // by the key its fix writes.
+        let studio = &results[1];
+        assert_eq!(studio["level"], "note");
+        assert_eq!(
+            studio["locations"][0]["logicalLocations"][0]["name"],
+            "Pro Audio"
+        );
+
+        let rule = &run["tool"]["driver"]["rules"][hags["ruleIndex"].as_u64().unwrap() as usize];
+        assert_eq!(rule["id"], "gpu.hags");
+        assert_eq!(rule["properties"]["security-severity"], "7.0");
+        let help = rule["help"]["text"].as_str().unwrap();
+        assert!(
+            help.contains(r"Fix: Set HKEY_LOCAL_MACHINE\SYSTEM"),
+            "{}",
+            help
+        );
+        assert!(help.contains("Risk: safe"), "{}", help);
+    }
+
+    #[test]
+    fn test_junit_export() {
+        use windows_optimizer::report::{export_junit, JunitWarnings};
+        use windows_optimizer::types::CheckError;
+
+        let mut results = results();
+        let mut platform = CategoryResults::new("Platform");
+        platform.add_check(
+            Check::timed(|| Check::new("Windows Build", "26100", CheckStatus::Info))
+                .with_id("platform.windows_build"),
+        );
+        platform.add_check(
+            Check::new(
+                "CPU Temperature",
+                "Unavailable",
+                CheckStatus::Error(CheckError::Wmi("access denied".into())),
+            )
+            .with_id("thermal.cpu_temp"),
+        );
+        results.add_category(platform);
+        // Every built-in check is timed as it runs.
+        let ctx = windows_optimizer::checks::CheckContext::live().with_registry(
+            std::sync::Arc::new(windows_optimizer::checks::MemoryRegistry::new()),
+        );
+        let gpu =
+            windows_optimizer::checks::run_category(&ctx, windows_optimizer::types::Category::Gpu);
+        assert!(gpu.checks.iter().all(|c| c.elapsed_ms.is_some()));
+        assert!(results.categories["Platform"].checks[0]
+            .elapsed_ms
+            .is_some());
+
+        let path = std::env::temp_dir().join(format!("wo_report_{}.xml", std::process::id()));
+        export_junit(&results, path.to_str().unwrap(), JunitWarnings::Failure).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(xml.contains(
+            r#"<testsuites name="Windows-Optimizer" tests="5" failures="2" errors="1" skipped="0""#
+        ));
+        assert!(
+            xml.contains(r#"<testsuite name="GPU" tests="2" failures="1" errors="0" skipped="0""#)
+        );
+        assert!(xml.contains(r#"<testcase name="gpu.game_dvr" classname="GPU" time="0.000"/>"#));
+        assert!(xml.contains(
+            r#"<failure message="HAGS is Disabled; expected 2" type="Issue">Value: Disabled"#
+        ));
+        assert!(xml.contains(r#"<property name="value" value="26100"/>"#));
+        assert!(xml.contains(r#"<error message="WMI: access denied" type="Wmi"/>"#));
+
+        // Warnings can be reported without failing the build.
+        export_junit(&results, path.to_str().unwrap(), JunitWarnings::Skip).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(xml.contains(r#"tests="5" failures="1" errors="1" skipped="1""#));
+        assert!(xml.contains(r#"<skipped message="Pro Audio Priority is 2"/>"#));
+        assert_eq!("skip".parse(), Ok(JunitWarnings::Skip));
+        assert!("ignore".parse::<JunitWarnings>().is_err());
+    }
+
+    #[test]
+    fn test_markdown_export() {
+        use windows_optimizer::report::export_markdown;
+
+        let mut results = results();
+        let mut platform = CategoryResults::new("Platform");
+        platform.add_check(
+            Check::new("Windows Edition", "Pro | N <beta>", CheckStatus::Info)
+                .with_id("platform.windows_edition"),
+        );
+        results.add_category(platform);
+        results.profile = Some("gaming".into());
+
+        let path = std::env::temp_dir().join(format!("wo_report_{}.md", std::process::id()));
+        export_markdown(&results, path.to_str().unwrap(), false).unwrap();
+        let md = std::fs::read_to_string(&path).unwrap();
+        assert!(md.starts_with("# Windows Optimizer Report\n"));
+        assert!(md.contains("· Profile: gaming"));
+        assert!(md.contains("| 1 | 1 | 1 | 1 | 0 | 0 |"), "{}", md);
+
+        // Only what needs attention is listed outside the details block.
+        let gpu = &md[md.find("## GPU").unwrap()..md.find("## Platform").unwrap()];
+        let (listed, details) = gpu.split_once("<details>").unwrap();
+        assert!(listed
+            .contains(r"| ✗ Issue | gpu.hags | HAGS | Disabled | 2 | Set HKEY\_LOCAL\_MACHINE"));
+        assert!(!listed.contains("Game DVR"));
+        assert!(details.contains("<summary>All 2 checks</summary>\n\n| Status |"));
+        assert!(details.contains("| ✓ Optimal | gpu.game\\_dvr | Game DVR | 0 |"));
+        let platform = &md[md.find("## Platform").unwrap()..];
+        assert!(platform.contains("Nothing needs attention."));
+        assert!(platform.contains(r"Pro \| N &lt;beta&gt;"));
+
+        export_markdown(&results, path.to_str().unwrap(), true).unwrap();
+        let md = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!md.contains("<details>"));
+        assert!(md.contains("| ✓ Optimal | gpu.game\\_dvr |"));
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::remediation::{Remediation, StartType};
+    use windows_optimizer::risk::{RiskCategory, RiskLevel};
+
+    const GRAPHICS: &str = r"SYSTEM\CurrentControlSet\Control\GraphicsDrivers";
+    const DEVICE_GUARD: &str = r"SYSTEM\CurrentControlSet\Control\DeviceGuard";
+
+    #[test]
+    fn test_risk_follows_the_value_written() {
+        let tdr_off = Remediation::set_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "TdrLevel", 0).risk();
+        assert_eq!(tdr_off.level, RiskLevel::Dangerous);
+        assert_eq!(
+            tdr_off.categories,
+            [
+                RiskCategory::StabilityReducing,
+                RiskCategory::RebootToRevert
+            ]
+        );
+        assert_eq!(tdr_off.tradeoffs.len(), 1);
+
+        // The same value reached through a numbered control set, or a
+        // sloppily written path, is graded the same.
+        for path in [
+            r"SYSTEM\ControlSet001\Control\GraphicsDrivers",
+            r"system\\currentcontrolset\control\GraphicsDrivers\",
+            r" \SYSTEM\ControlSet002\Control\GraphicsDrivers ",
+        ] {
+            let tdr_off = Remediation::set_dword(HKEY_LOCAL_MACHINE, path, "TdrLevel", 0);
+            assert_eq!(tdr_off.risk().level, RiskLevel::Dangerous, "{}", path);
+            assert!(tdr_off.needs_reboot(), "{}", path);
+        }
+
+        // Full recovery is the default; going back to it gives nothing up.
+        let tdr_on = Remediation::set_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "TdrLevel", 3).risk();
+        assert_eq!(tdr_on.level, RiskLevel::Safe);
+        assert_eq!(tdr_on.categories, [RiskCategory::RebootToRevert]);
+
+        // Ordinary machine-wide tweaks and service changes revert live.
+        let throttling = Remediation::set_dword(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile",
+            "NetworkThrottlingIndex",
+            0xFFFF_FFFF,
+        );
+        assert!(!throttling.needs_reboot());
+        assert!(throttling.risk().categories.is_empty());
+        assert!(!Remediation::service_start("SysMain", StartType::Disabled).needs_reboot());
+        assert!(Remediation::service_start("nvlddmkm", StartType::System).needs_reboot());
+
+        let vbs_off = Remediation::set_dword(
+            HKEY_LOCAL_MACHINE,
+            DEVICE_GUARD,
+            "EnableVirtualizationBasedSecurity",
+            0,
+        )
+        .risk();
+        assert_eq!(vbs_off.level, RiskLevel::Dangerous);
+        assert!(vbs_off.is_security_reducing());
+        assert!(Remediation::delete_value(
+            HKEY_LOCAL_MACHINE,
+            DEVICE_GUARD,
+            "EnableVirtualizationBasedSecurity"
+        )
+        .risk()
+        .is_security_reducing());
+
+        let game_dvr = Remediation::set_dword(
+            HKEY_CURRENT_USER,
+            r"System\GameConfigStore",
+            "GameDVR_Enabled",
+            0,
+        )
+        .risk();
+        assert_eq!(game_dvr.level, RiskLevel::Safe);
+        assert!(game_dvr.categories.is_empty());
+    }
+
+    #[test]
+    fn test_services_and_bcdedit() {
+        let defender = Remediation::service_start("windefend", StartType::Disabled).risk();
+        assert_eq!(defender.level, RiskLevel::Dangerous);
+        assert!(defender.is_security_reducing());
+        assert!(defender.tradeoffs[0].contains("Defender"));
+        assert_eq!(
+            Remediation::service_start("MpsSvc", StartType::Automatic)
+                .risk()
+                .level,
+            RiskLevel::Safe
+        );
+        let sysmain = Remediation::service_start("SysMain", StartType::Disabled).risk();
+        assert_eq!(sysmain.level, RiskLevel::Moderate);
+        assert!(!sysmain.is_security_reducing());
+
+        assert_eq!(
+            Remediation::RunBcdEdit {
+                args: vec!["/set".into(), "disabledynamictick".into(), "yes".into()],
+            }
+            .risk()
+            .level,
+            RiskLevel::Moderate
+        );
+        let hypervisor = Remediation::RunBcdEdit {
+            args: vec!["/set".into(), "hypervisorlaunchtype".into(), "Off".into()],
+        }
+        .risk();
+        assert_eq!(hypervisor.level, RiskLevel::Dangerous);
+        assert!(hypervisor.is_security_reducing());
+
+        // A batch is as risky as its riskiest step.
+        let batch = Remediation::Batch {
+            steps: vec![
+                Remediation::service_start("SysMain", StartType::Disabled),
+                Remediation::service_start("WinDefend", StartType::Disabled),
+            ],
+        }
+        .risk();
+        assert_eq!(batch.level, RiskLevel::Dangerous);
+        assert_eq!(batch.tradeoffs.len(), 2);
+    }
+
+    #[test]
+    fn test_gate_refuses_above_allowed_level() {
+        use windows_optimizer::risk::gate;
+        use windows_optimizer::types::{Check, CheckStatus};
+
+        let fix = |id: &str, remediation: Remediation| {
+            Check::new(id, "", CheckStatus::Issue)
+                .with_id(id)
+                .with_remediation(remediation)
+        };
+        let checks = [
+            fix(
+                "gpu.hags",
+                Remediation::set_dword(HKEY_LOCAL_MACHINE, GRAPHICS, "HwSchMode", 2),
+            ),
+            fix(
+                "services.sysmain",
+                Remediation::service_start("SysMain", StartType::Disabled),
+            ),
+            fix(
+                "services.windefend",
+                Remediation::service_start("WinDefend", StartType::Disabled),
+            ),
+        ];
+        let plan: Vec<&Check> = checks.iter().collect();
+
+        let default: RiskLevel = "moderate".parse().unwrap();
+        let (accepted, refused) = gate(&plan, default);
+        assert_eq!(accepted.len(), 2);
+        assert_eq!(refused.len(), 1);
+        assert_eq!(refused[0].0.id, "services.windefend");
+        // The service can be turned back on without a restart.
+        assert_eq!(refused[0].1.label(), "dangerous (security-reducing)");
+
+        assert_eq!(gate(&plan, RiskLevel::Safe).0.len(), 1);
+        assert_eq!(gate(&plan, RiskLevel::Dangerous).1.len(), 0);
+        assert!("reckless".parse::<RiskLevel>().is_err());
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    const STUDIO_RULES: &str = r#"
+[[rule]]
+id = "studio.pro_audio_priority"
+name = "Pro Audio Task Priority"
+category = "studio"
+hive = "HKLM"
+path = 'SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Pro Audio'
+value = "Priority"
+operator = "ge"
+expected = 6
+on_mismatch = "issue"
+severity = 8
+
+[rule.remediation]
+action = "set_registry_value"
+hive = "HKLM"
+path = 'SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks\Pro Audio'
+name = "Priority"
+value = { type = "dword", data = 6 }
+
+[[rule]]
+id = "studio.responsiveness"
+name = "System Responsiveness"
+category = "Studio"
+hive = "HKEY_LOCAL_MACHINE"
+path = 'SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile'
+value = "SystemResponsiveness"
+operator = "between"
+min = 0
+max = 10
+
+[[rule]]
+id = "studio.asio_driver"
+name = "ASIO Driver Registered"
+category = "audio"
+hive = "HKLM"
+path = 'SOFTWARE\ASIO\Studio Interface'
+value = "Description"
+operator = "contains"
+expected = "studio"
+on_missing = "info"
+"#;
+
+    #[test]
+    fn test_rules_grade_registry_values() {
+        use std::path::Path;
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            CheckContext, MemoryRegistry, RegValue, RuleSet, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::remediation::Remediation;
+        use windows_optimizer::types::CheckStatus;
+
+        let mut rules = RuleSet::new();
+        rules
+            .add_file(Path::new("studio.toml"), STUDIO_RULES)
+            .unwrap();
+        assert_eq!(rules.rules().len(), 3);
+        assert_eq!(rules.custom_categories(), vec!["studio".to_string()]);
+
+        let profile = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile";
+        let registry = MemoryRegistry::new()
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                &format!(r"{}\Tasks\Pro Audio", profile),
+                "Priority",
+                2,
+            )
+            .with_string(HKEY_LOCAL_MACHINE, profile, "SystemResponsiveness", "10");
+        let ctx = CheckContext::live()
+            .with_registry(Arc::new(registry))
+            .with_rules(Arc::new(rules));
+
+        let studio = ctx.rules().run_custom(&ctx, "studio");
+        assert_eq!(studio.checks.len(), 2);
+
+        let priority = &studio.checks[0];
+        assert_eq!(priority.id, "studio.pro_audio_priority");
+        assert_eq!(priority.value, "2");
+        assert_eq!(priority.expected.as_deref(), Some(">= 6"));
+        assert_eq!(priority.status, CheckStatus::Issue);
+        assert_eq!(priority.severity, 8);
+        assert!(matches!(
+            priority.remediation,
+            Some(Remediation::SetRegistryValue { ref name, value: RegValue::Dword(6), .. })
+                if name == "Priority"
+        ));
+
+        // A DWORD range rule never matches a value stored as text.
+        let responsiveness = &studio.checks[1];
+        assert_eq!(responsiveness.value, "Stored as REG_SZ");
+        assert_eq!(responsiveness.status, CheckStatus::Warning);
+    }
+
+    #[test]
+    fn test_rules_extend_builtin_categories() {
+        use std::path::Path;
+        use std::sync::Arc;
+        use windows_optimizer::checks::{
+            run_category_named, CheckContext, MemoryRegistry, RuleSet, HKEY_LOCAL_MACHINE,
+        };
+        use windows_optimizer::types::CheckStatus;
+
+        let mut rules = RuleSet::new();
+        rules
+            .add_file(Path::new("studio.toml"), STUDIO_RULES)
+            .unwrap();
+        let rules = Arc::new(rules);
+
+        let empty = CheckContext::live()
+            .with_registry(Arc::new(MemoryRegistry::new()))
+            .with_rules(rules.clone());
+        let audio = run_category_named(&empty, "audio");
+        let asio = audio
+            .checks
+            .iter()
+            .find(|c| c.id == "studio.asio_driver")
+            .unwrap();
+        assert_eq!(asio.value, "Not Set");
+        assert_eq!(asio.status, CheckStatus::Info);
+
+        let registry = MemoryRegistry::new().with_string(
+            HKEY_LOCAL_MACHINE,
+            r"SOFTWARE\ASIO\Studio Interface",
+            "Description",
+            "Studio Interface ASIO 2.0",
+        );
+        let ctx = CheckContext::live()
+            .with_registry(Arc::new(registry))
+            .with_rules(rules);
+        let audio = run_category_named(&ctx, "Audio");
+        let asio = audio
+            .checks
+            .iter()
+            .find(|c| c.id == "studio.asio_driver")
+            .unwrap();
+        assert_eq!(asio.status, CheckStatus::Optimal);
+
+        assert_eq!(run_category_named(&ctx, "STUDIO").checks.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        use std::path::Path;
+        use windows_optimizer::checks::{RuleError, RuleSet};
+
+        let rule = |body: &str| {
+            format!(
+                "[[rule]]\nname = \"Test\"\nhive = \"HKLM\"\npath = 'SOFTWARE\\Test'\nvalue = \"V\"\n{}\n",
+                body
+            )
+        };
+        let load = |text: String| RuleSet::new().add_file(Path::new("bad.toml"), &text);
+
+        let err = load(rule("id = \"custom.a\"\noperator = \"between\"\nmin = 3")).unwrap_err();
+        assert!(matches!(err, RuleError::Invalid { ref id, .. } if id == "custom.a"));
+        assert!(err.to_string().starts_with("bad.toml: rule 'custom.a'"));
+
+        let err = load(rule("id = \"gpu.hags\"\nexpected = 2")).unwrap_err();
+        assert!(err.to_string().contains("built-in"), "{}", err);
+
+        let err = load(rule(
+            "id = \"custom.a\"\noperator = \"gt\"\nexpected = \"x\"",
+        ))
+        .unwrap_err();
+        assert!(matches!(err, RuleError::Invalid { .. }));
+
+        let err = load(rule("id = \"custom.a\"\nexpected = 1\nhive_typo = 1")).unwrap_err();
+        assert!(matches!(err, RuleError::Parse { .. }));
+
+        let err =
+            load(rule("id = \"custom.a\"\nexpected = 1").replace("HKLM", "HKXX")).unwrap_err();
+        assert!(err.to_string().contains("unknown hive"), "{}", err);
+
+        let twice = format!(
+            "{}{}",
+            rule("id = \"custom.a\"\nexpected = 1"),
+            rule("id = \"custom.a\"\nexpected = 2")
+        );
+        assert!(load(twice).is_err());
+    }
+
+    #[test]
+    fn test_load_rules_directory() {
+        use windows_optimizer::checks::RuleSet;
+
+        let dir = std::env::temp_dir().join(format!("wo_rules_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.toml"), STUDIO_RULES).unwrap();
+        std::fs::write(
+            dir.join("a.toml"),
+            "[[rule]]\nid = \"custom.first\"\nname = \"First\"\nhive = \"HKCU\"\npath = 'Software\\Test'\nvalue = \"V\"\noperator = \"exists\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a rule").unwrap();
+
+        let rules = RuleSet::load_dir(&dir).unwrap();
+        let ids: Vec<&str> = rules.rules().iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids[0], "custom.first");
+        assert_eq!(ids.len(), 4);
+        assert_eq!(
+            rules.custom_categories(),
+            vec!["custom".to_string(), "studio".to_string()]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::types::{AuditResults, CategoryResults, Check, CheckStatus};
+
+    fn category(name: &str, checks: &[(&str, CheckStatus, u8)]) -> CategoryResults {
+        let mut results = CategoryResults::new(name);
+        for (id, status, severity) in checks {
+            results.add_check(
+                Check::new(id, "value", status.clone())
+                    .with_id(id)
+                    .with_severity(*severity),
+            );
+        }
+        results
+    }
+
+    fn sample() -> AuditResults {
+        let mut results = AuditResults::new();
+        results.add_category(category(
+            "GPU",
+            &[
+                ("gpu.a", CheckStatus::Optimal, 7),
+                ("gpu.b", CheckStatus::Issue, 7),
+                ("gpu.c", CheckStatus::Warning, 2),
+                ("gpu.d", CheckStatus::Info, 2),
+                ("gpu.e", CheckStatus::Unknown, 7),
+            ],
+        ));
+        results.add_category(category(
+            "Latency",
+            &[("latency.a", CheckStatus::Optimal, 5)],
+        ));
+        results.add_category(category("Thermal", &[("thermal.a", CheckStatus::Info, 2)]));
+        results
+    }
+
+    #[test]
+    fn test_scores_weigh_severity_and_skip_ungraded_checks() {
+        use windows_optimizer::score::{compute, ScoreWeights};
+
+        let score = compute(&sample(), &ScoreWeights::default());
+
+        // (7 * 1 + 7 * 0 + 2 * 0.5) / (7 + 7 + 2)
+        let gpu = &score.categories["GPU"];
+        assert_eq!(gpu.score, Some(50.0));
+        assert_eq!(gpu.graded, 3);
+        assert_eq!(score.categories["Latency"].score, Some(100.0));
+        assert_eq!(score.categories["Thermal"].score, None);
+        assert_eq!(score.overall, Some(75.0));
+    }
+
+    #[test]
+    fn test_configured_weights() {
+        use windows_optimizer::score::{compute, ScoreWeights};
+
+        let weights: ScoreWeights = toml::from_str(
+            r#"
+[categories]
+latency = 3.0
+
+[checks]
+"gpu.b" = 0.0
+"#,
+        )
+        .unwrap();
+        let score = compute(&sample(), &weights);
+
+        // gpu.b no longer counts: (7 + 1) / (7 + 2)
+        assert_eq!(score.categories["GPU"].score, Some(88.9));
+        assert_eq!(score.categories["Latency"].weight, 3.0);
+        // (88.89 * 1 + 100 * 3) / 4
+        assert_eq!(score.overall, Some(97.2));
+    }
+
+    #[test]
+    fn test_score_in_exports() {
+        use windows_optimizer::report::{export_html, export_json};
+        use windows_optimizer::score::{compute, ScoreWeights};
+
+        let dir = std::env::temp_dir();
+        let json_path = dir.join(format!("wo_score_{}.json", std::process::id()));
+        let html_path = dir.join(format!("wo_score_{}.html", std::process::id()));
+
+        let mut results = sample();
+        results.score = Some(compute(&results, &ScoreWeights::default()));
+        export_json(&results, json_path.to_str().unwrap()).unwrap();
+        export_html(&results, html_path.to_str().unwrap()).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["score"]["overall"], 75.0);
+        assert_eq!(json["score"]["categories"]["GPU"]["score"], 50.0);
+
+        let html = std::fs::read_to_string(&html_path).unwrap();
+        assert!(html.contains("class=\"gauges\""));
+        assert!(html.contains(">75</text>"));
+        assert!(html.contains(">n/a</text>"));
+
+        std::fs::remove_file(json_path).unwrap();
+        std::fs::remove_file(html_path).unwrap();
+    }
+
+    #[test]
+    fn test_negative_weights_are_rejected() {
+        use windows_optimizer::score::ScoreWeights;
+
+        let path = std::env::temp_dir().join(format!("wo_weights_{}.toml", std::process::id()));
+        std::fs::write(&path, "[checks]\n\"gpu.hags\" = -1.0\n").unwrap();
+        let err = ScoreWeights::load(&path).unwrap_err();
+        assert!(err.to_string().contains("gpu.hags"), "{}", err);
+        std::fs::remove_file(path).unwrap();
+    }
+}
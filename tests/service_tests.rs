@@ -0,0 +1,313 @@
+#[cfg(test)]
+mod tests {
+    use windows_optimizer::checks::{MemoryRegistry, RegValue, HKEY_LOCAL_MACHINE};
+    use windows_optimizer::remediation::StartType;
+
+    const SERVICES: &str = r"SYSTEM\CurrentControlSet\Services";
+
+    fn service(registry: MemoryRegistry, name: &str, start: u32) -> MemoryRegistry {
+        registry.with_dword(
+            HKEY_LOCAL_MACHINE,
+            &format!(r"{}\{}", SERVICES, name),
+            "Start",
+            start,
+        )
+    }
+
+    #[test]
+    fn test_service_config_from_registry() {
+        use windows_optimizer::checks::read_service_config;
+        use windows_optimizer::remediation::{MemorySystem, SystemReader, SystemWriter};
+
+        let key = format!(r"{}\WSearch", SERVICES);
+        let registry = service(MemoryRegistry::new(), "WSearch", 2)
+            .with_dword(HKEY_LOCAL_MACHINE, &key, "DelayedAutostart", 1)
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "ImagePath",
+                RegValue::ExpandString(
+                    r"%systemroot%\system32\SearchIndexer.exe /Embedding".into(),
+                ),
+            )
+            .with_string(HKEY_LOCAL_MACHINE, &key, "ObjectName", "LocalSystem")
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                &key,
+                "DependOnService",
+                RegValue::MultiString(vec!["RPCSS".into(), "BrokerInfrastructure".into()]),
+            );
+
+        let config = read_service_config(&registry, "WSearch").unwrap();
+        assert_eq!(config.start_type, Some(StartType::Delayed));
+        assert_eq!(
+            config.binary_path.as_deref(),
+            Some(r"%systemroot%\system32\SearchIndexer.exe /Embedding")
+        );
+        assert_eq!(config.account.as_deref(), Some("LocalSystem"));
+        assert_eq!(config.dependencies, ["RPCSS", "BrokerInfrastructure"]);
+        assert!(config.group_dependencies.is_empty());
+        assert!(read_service_config(&registry, "Fax").is_none());
+
+        // Delayed start goes through the apply engine like any start type.
+        let system = MemorySystem::new(registry);
+        assert_eq!(
+            system.service_start_type("WSearch"),
+            Some(StartType::Delayed)
+        );
+        system
+            .set_service_start_type("WSearch", StartType::Automatic)
+            .unwrap();
+        assert_eq!(
+            system.service_start_type("WSearch"),
+            Some(StartType::Automatic)
+        );
+        system
+            .set_service_start_type("WSearch", StartType::Delayed)
+            .unwrap();
+        assert_eq!(
+            system.service_start_type("WSearch"),
+            Some(StartType::Delayed)
+        );
+        assert_eq!(StartType::parse("delayed-auto"), Some(StartType::Delayed));
+        assert_eq!(StartType::Delayed.raw(), 2);
+    }
+
+    #[test]
+    fn test_services_graded_on_actual_start_type() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{run_category, CheckContext};
+        use windows_optimizer::profile::ProfileSet;
+        use windows_optimizer::remediation::Remediation;
+        use windows_optimizer::types::{AuditResults, Category, CheckStatus};
+
+        let registry = [
+            ("DiagTrack", 4),
+            ("WinDefend", 4),
+            ("MpsSvc", 2),
+            ("XblAuthManager", 4),
+        ]
+        .into_iter()
+        .fold(MemoryRegistry::new(), |registry, (name, start)| {
+            service(registry, name, start)
+        });
+        // An offline image reports only the configured start type, never
+        // the host's running state.
+        let ctx = CheckContext::live()
+            .with_registry(Arc::new(registry))
+            .with_offline(true);
+        let mut results = AuditResults::new();
+        results.add_category(run_category(&ctx, Category::Services));
+        let status = |results: &AuditResults, id: &str| {
+            results.categories["Services"]
+                .checks
+                .iter()
+                .find(|c| c.id == id)
+                .unwrap()
+                .status
+                .clone()
+        };
+
+        // Already disabled, so telemetry is no longer a warning.
+        assert_eq!(status(&results, "services.diagtrack"), CheckStatus::Optimal);
+        assert_eq!(status(&results, "services.mpssvc"), CheckStatus::Optimal);
+        let value = |id: &str| {
+            let services = &results.categories["Services"].checks;
+            services.iter().find(|c| c.id == id).unwrap().value.clone()
+        };
+        assert_eq!(value("services.diagtrack"), "Disabled");
+        assert_eq!(value("services.mpssvc"), "Automatic");
+        assert_eq!(
+            status(&results, "services.xblauthmanager"),
+            CheckStatus::Info
+        );
+        assert_eq!(status(&results, "services.fax"), CheckStatus::Info);
+        let defender = results.categories["Services"]
+            .checks
+            .iter()
+            .find(|c| c.id == "services.windefend")
+            .unwrap();
+        assert_eq!(defender.status, CheckStatus::Issue);
+        assert_eq!(
+            defender.remediation,
+            Some(Remediation::service_start(
+                "WinDefend",
+                StartType::Automatic
+            ))
+        );
+
+        // Gaming wants the Xbox services available on demand.
+        let gaming = ProfileSet::builtin().resolve("gaming").unwrap();
+        gaming.judge(&mut results, &ctx);
+        assert_eq!(
+            status(&results, "services.xblauthmanager"),
+            CheckStatus::Issue
+        );
+        assert_eq!(status(&results, "services.diagtrack"), CheckStatus::Optimal);
+    }
+
+    #[test]
+    fn test_dependency_graph_and_analysis() {
+        use windows_optimizer::checks::ServiceGraph;
+        use windows_optimizer::risk::RiskLevel;
+
+        let key = |name: &str| format!(r"{}\{}", SERVICES, name);
+        let needs = |registry: MemoryRegistry, name: &str, on: &[&str]| {
+            registry.with_value(
+                HKEY_LOCAL_MACHINE,
+                &key(name),
+                "DependOnService",
+                RegValue::MultiString(on.iter().map(|s| s.to_string()).collect()),
+            )
+        };
+        let mut registry = MemoryRegistry::new();
+        for (name, start) in [
+            ("LanmanServer", 2),
+            ("Browser", 3),
+            ("SessionEnv", 3),
+            ("Srv2", 3),
+            ("Ndis", 0),
+            ("NetBT", 1),
+        ] {
+            registry = service(registry, name, start);
+        }
+        registry = needs(registry, "LanmanServer", &["SamSS", "Srv2"]);
+        registry = needs(registry, "Browser", &["LanmanWorkstation", "LANMANSERVER"]);
+        registry = needs(registry, "SessionEnv", &["Browser"]);
+        let registry = registry
+            .with_string(HKEY_LOCAL_MACHINE, &key("Ndis"), "Group", "NDIS Wrapper")
+            .with_value(
+                HKEY_LOCAL_MACHINE,
+                &key("NetBT"),
+                "DependOnGroup",
+                RegValue::MultiString(vec!["ndis wrapper".into()]),
+            );
+
+        let graph = ServiceGraph::build(&registry);
+        assert_eq!(graph.dependencies("lanmanserver"), ["samss", "Srv2"]);
+
+        let running = |name: &str| (name == "Browser").then(|| "Running".to_string());
+        let analysis = graph.analyze("lanmanserver", &running).unwrap();
+        assert_eq!(analysis.service.name, "LanmanServer");
+        let dependents: Vec<(&str, usize, &str)> = analysis
+            .dependents
+            .iter()
+            .map(|d| (d.name.as_str(), d.depth, d.via.as_str()))
+            .collect();
+        assert_eq!(
+            dependents,
+            [("Browser", 1, "LanmanServer"), ("SessionEnv", 2, "Browser")]
+        );
+        assert_eq!(analysis.running_dependents().len(), 1);
+        assert!(analysis
+            .checks
+            .iter()
+            .any(|c| c.check_id == "services.lanmanserver"));
+        assert_eq!(analysis.risk.level, RiskLevel::Moderate);
+        assert!(graph.analyze("Spooler", &running).is_none());
+
+        let ndis = graph.analyze("Ndis", &running).unwrap();
+        assert_eq!(ndis.dependents[0].name, "NetBT");
+        assert_eq!(ndis.dependents[0].group.as_deref(), Some("ndis wrapper"));
+
+        let dot = graph.to_dot(Some("Browser"));
+        assert!(dot.starts_with("digraph services {"));
+        assert!(dot.contains(r#""SessionEnv" -> "Browser";"#));
+        assert!(dot.contains(r#""Browser" -> "LanmanServer";"#));
+        assert!(dot.contains(r#""LanmanServer" -> "Srv2";"#));
+        assert!(!dot.contains("NetBT"), "unrelated services are left out");
+        let full = graph.to_dot(None);
+        assert!(full.contains(r#""NetBT" -> "Ndis" [style=dashed, label="ndis wrapper"];"#));
+        assert!(full.contains(r#"label="NDIS Wrapper";"#));
+    }
+
+    #[test]
+    fn test_per_user_template_and_instances() {
+        use std::sync::Arc;
+        use windows_optimizer::checks::{run_category, user_service_instances, CheckContext};
+        use windows_optimizer::journal::{self, Journal};
+        use windows_optimizer::profile::ProfileSet;
+        use windows_optimizer::remediation::{plan, MemorySystem, Remediation, SystemReader};
+        use windows_optimizer::types::{AuditResults, Category, Check, CheckStatus};
+
+        let key = |name: &str| format!(r"{}\{}", SERVICES, name);
+        let registry = service(MemoryRegistry::new(), "CDPUserSvc", 2)
+            .with_dword(HKEY_LOCAL_MACHINE, &key("CDPUserSvc"), "Type", 0x60)
+            .with_dword(
+                HKEY_LOCAL_MACHINE,
+                &key("CDPUserSvc"),
+                "UserServiceFlags",
+                3,
+            );
+        let registry = service(registry, "CDPUserSvc_4f2a1", 2).with_dword(
+            HKEY_LOCAL_MACHINE,
+            &key("CDPUserSvc_4f2a1"),
+            "Type",
+            0xE0,
+        );
+        let registry = service(registry, "CDPSvc", 2).with_dword(
+            HKEY_LOCAL_MACHINE,
+            &key("CDPSvc"),
+            "Type",
+            0x20,
+        );
+
+        let instances = user_service_instances(&registry, "cdpusersvc");
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].name, "CDPUserSvc_4f2a1");
+
+        let audit = |registry: MemoryRegistry| {
+            let ctx = CheckContext::live().with_registry(Arc::new(registry));
+            let mut results = AuditResults::new();
+            results.add_category(run_category(&ctx, Category::Services));
+            (results, ctx)
+        };
+        let find = |results: &AuditResults| -> Check {
+            results.categories["Services"]
+                .checks
+                .iter()
+                .find(|c| c.id == "services.cdpusersvc")
+                .unwrap()
+                .clone()
+        };
+        let (mut results, ctx) = audit(registry.clone());
+        let check = find(&results);
+        assert_eq!(check.status, CheckStatus::Info);
+        assert_eq!(check.value, "Automatic, instances: CDPUserSvc_4f2a1");
+        assert_eq!(
+            check.remediation,
+            Some(Remediation::user_service_start(
+                "CDPUserSvc",
+                StartType::Automatic
+            ))
+        );
+
+        // A profile that turns the template off goes through UserServiceFlags.
+        let mut profiles = ProfileSet::builtin();
+        profiles
+            .add(
+                "quiet",
+                "[targets]\n\"services.cdpusersvc\" = \"disabled\"\n",
+            )
+            .unwrap();
+        let quiet = profiles.resolve("quiet").unwrap();
+        quiet.judge(&mut results, &ctx);
+        let check = find(&results);
+        assert_eq!(check.status, CheckStatus::Issue);
+        let fix = check.remediation.clone().unwrap();
+        assert_eq!(fix.user_service_template(), Some("CDPUserSvc"));
+
+        let system = MemorySystem::new(registry);
+        let path = std::env::temp_dir().join(format!("wo_user_svc_{}.json", std::process::id()));
+        let fixes = plan(&results);
+        journal::apply_session(Journal::new(), &fixes, &system, &path).unwrap();
+        assert_eq!(
+            system.read_registry_value(HKEY_LOCAL_MACHINE, &key("CDPUserSvc"), "UserServiceFlags"),
+            Some(RegValue::Dword(0))
+        );
+        let (mut results, ctx) = audit(system.registry());
+        quiet.judge(&mut results, &ctx);
+        assert_eq!(find(&results).status, CheckStatus::Optimal);
+        let _ = std::fs::remove_file(&path);
+    }
+}